@@ -0,0 +1,313 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! `pchain-sdk-build` builds the deployable WASM module of a contract in the same way on every machine, instead of
+//! ad-hoc scripts around `cargo build`. [build] performs these steps:
+//! 1. compile the contract crate for `wasm32-unknown-unknown` in release mode, with source paths remapped so that
+//!    they do not depend on the location of the crate,
+//! 2. optimize the module by `wasm-opt` with fixed settings ([WASM_OPT_ARGS]), if enabled,
+//! 3. remove custom sections except those written by the SDK ([DEFAULT_KEEP_SECTIONS]), e.g. the SDK version stamp,
+//! 4. check that the module only imports functions of the Contract Binary Interface and exports the entrypoints
+//!    required by the runtime ([lint]), if enabled,
+//! 5. report the size and the imports of the module in [BuildReport].
+//! 
+//! It can be used as a library, e.g. from `build.rs` of a packaging crate or an xtask, or as the binary
+//! `pchain-sdk-build`, e.g. with a cargo alias in `.cargo/config.toml`:
+//! 
+//! ```toml
+//! [alias]
+//! build-contract = "run --manifest-path ../pchain-sdk/build/Cargo.toml -- --manifest-path Cargo.toml --out artifacts"
+//! ```
+//! 
+//! ### Example
+//! ```no_run
+//! use pchain_sdk_build::BuildOptions;
+//! 
+//! let report = pchain_sdk_build::build(&BuildOptions::new("my-contract/Cargo.toml").out_dir("artifacts"))?;
+//! println!("{}", report);
+//! ```
+
+use std::{path::{Path, PathBuf}, process::Command};
+
+mod wasm;
+pub use wasm::{Export, Import, ImportKind, custom_sections, exports, imports, strip_custom_sections};
+
+mod lint;
+pub use lint::*;
+
+/// Target of contracts.
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Custom sections written by the SDK, which are kept in the module by default.
+pub const DEFAULT_KEEP_SECTIONS: [&str; 5] = ["pchain_sdk", "pchain_metadata", "pchain_config", "pchain_events", "pchain_abi"];
+
+/// Arguments of `wasm-opt`, besides the input and output. The result is deterministic for the same version of
+/// `wasm-opt`, which is recorded in [BuildReport::wasm_opt_version].
+pub const WASM_OPT_ARGS: [&str; 3] = ["-Oz", "--strip-debug", "--strip-producers"];
+
+/// Options of [build].
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    manifest_path: PathBuf,
+    target_dir: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
+    keep_sections: Vec<String>,
+    wasm_opt: Option<PathBuf>,
+    lint: bool,
+}
+
+impl BuildOptions {
+    /// Options to build the contract crate of the manifest, with `wasm-opt` from `PATH`.
+    pub fn new(manifest_path: impl Into<PathBuf>) -> Self {
+        Self {
+            manifest_path: manifest_path.into(),
+            target_dir: None,
+            out_dir: None,
+            keep_sections: DEFAULT_KEEP_SECTIONS.iter().map(|s| s.to_string()).collect(),
+            wasm_opt: Some(PathBuf::from("wasm-opt")),
+            lint: true,
+        }
+    }
+
+    /// Target directory of cargo. By default, it is `CARGO_TARGET_DIR`, or `target` next to the manifest.
+    pub fn target_dir(mut self, target_dir: impl Into<PathBuf>) -> Self {
+        self.target_dir = Some(target_dir.into());
+        self
+    }
+
+    /// Directory to which the module is written. By default, it is the release directory of the WASM target.
+    pub fn out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Keep the custom section in addition to [DEFAULT_KEEP_SECTIONS].
+    pub fn keep_section(mut self, name: &str) -> Self {
+        self.keep_sections.push(name.to_string());
+        self
+    }
+
+    /// Path of `wasm-opt`, or None to skip the optimization.
+    pub fn wasm_opt(mut self, wasm_opt: Option<PathBuf>) -> Self {
+        self.wasm_opt = wasm_opt;
+        self
+    }
+
+    /// Whether to check the imports and exports of the module by [lint]. It is enabled by default.
+    pub fn lint(mut self, lint: bool) -> Self {
+        self.lint = lint;
+        self
+    }
+}
+
+/// Result of [build].
+#[derive(Debug, Clone)]
+pub struct BuildReport {
+    /// Path of the deployable module.
+    pub artifact: PathBuf,
+    /// Size of the module in bytes.
+    pub size: u64,
+    /// Size of the module compiled by cargo, before optimization and stripping.
+    pub unoptimized_size: u64,
+    /// Output of `wasm-opt --version`, if the module is optimized.
+    pub wasm_opt_version: Option<String>,
+    /// The imports of the module, which must be provided by the runtime.
+    pub imports: Vec<Import>,
+    /// Names of the custom sections kept in the module.
+    pub custom_sections: Vec<String>,
+    /// Warnings of [lint], i.e. imports of optional host features.
+    pub lint_warnings: Vec<LintIssue>,
+}
+
+impl std::fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "artifact: {}", self.artifact.display())?;
+        writeln!(f, "size: {} bytes (before optimization: {} bytes)", self.size, self.unoptimized_size)?;
+        writeln!(f, "wasm-opt: {}", self.wasm_opt_version.as_deref().unwrap_or("skipped"))?;
+        writeln!(f, "custom sections: {}", self.custom_sections.join(", "))?;
+        writeln!(f, "imports:")?;
+        for import in self.imports.iter() {
+            writeln!(f, "  {}::{} ({:?})", import.module, import.name, import.kind)?;
+        }
+        for warning in self.lint_warnings.iter() {
+            writeln!(f, "warning: {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error of [build].
+#[derive(Debug)]
+pub enum BuildError {
+    /// The manifest cannot be read, or has no package name.
+    InvalidManifest(String),
+    /// `cargo build` failed.
+    Cargo(String),
+    /// `wasm-opt` cannot be run or failed.
+    WasmOpt(String),
+    /// The compiled module cannot be parsed.
+    InvalidWasm(String),
+    /// The module cannot be deployed because of the issues found by [lint].
+    Lint(Vec<LintIssue>),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::InvalidManifest(e) => write!(f, "invalid manifest: {}", e),
+            BuildError::Cargo(e) => write!(f, "cargo build failed: {}", e),
+            BuildError::WasmOpt(e) => write!(f, "wasm-opt failed: {}", e),
+            BuildError::InvalidWasm(e) => write!(f, "invalid WASM module: {}", e),
+            BuildError::Lint(issues) => {
+                write!(f, "module cannot be deployed:")?;
+                issues.iter().try_for_each(|issue| write!(f, "\n  {}", issue))
+            },
+            BuildError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl From<std::io::Error> for BuildError {
+    fn from(e: std::io::Error) -> Self {
+        BuildError::Io(e)
+    }
+}
+
+/// Build the deployable module of the contract crate.
+pub fn build(options: &BuildOptions) -> Result<BuildReport, BuildError> {
+    let manifest_path = options.manifest_path.canonicalize()?;
+    let crate_dir = manifest_path.parent().unwrap().to_path_buf();
+    let package = package_name(&manifest_path)?;
+    let target_dir = options.target_dir.clone()
+        .or_else(|| std::env::var_os("CARGO_TARGET_DIR").map(PathBuf::from))
+        .unwrap_or_else(|| crate_dir.join("target"));
+
+    // 1. compile
+    let mut rustflags = format!("--remap-path-prefix={}=.", crate_dir.display());
+    if let Some(cargo_home) = cargo_home() {
+        rustflags.push_str(&format!("\x1f--remap-path-prefix={}=/cargo", cargo_home.display()));
+    }
+    let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["build", "--release", "--target", WASM_TARGET, "--manifest-path"])
+        .arg(&manifest_path)
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("CARGO_ENCODED_RUSTFLAGS", rustflags)
+        .env_remove("RUSTFLAGS")
+        .output()?;
+    if !output.status.success() {
+        return Err(BuildError::Cargo(String::from_utf8_lossy(&output.stderr).to_string()))
+    }
+    let compiled = target_dir.join(WASM_TARGET).join("release").join(format!("{}.wasm", package.replace('-', "_")));
+    let unoptimized_size = std::fs::metadata(&compiled)?.len();
+
+    let out_dir = options.out_dir.clone().unwrap_or_else(|| compiled.parent().unwrap().to_path_buf());
+    std::fs::create_dir_all(&out_dir)?;
+    let artifact = out_dir.join(format!("{}.deploy.wasm", package.replace('-', "_")));
+
+    // 2. optimize
+    let wasm_opt_version = match &options.wasm_opt {
+        Some(wasm_opt) => Some(run_wasm_opt(wasm_opt, &compiled, &artifact)?),
+        None => {
+            std::fs::copy(&compiled, &artifact)?;
+            None
+        }
+    };
+
+    // 3. strip
+    let module = std::fs::read(&artifact)?;
+    let module = strip_custom_sections(&module, &options.keep_sections).map_err(BuildError::InvalidWasm)?;
+    std::fs::write(&artifact, &module)?;
+
+    // 4. lint
+    let lint_warnings = if options.lint {
+        let (errors, warnings) = lint(&module).map_err(BuildError::InvalidWasm)?
+            .into_iter()
+            .partition::<Vec<_>, _>(LintIssue::is_error);
+        if !errors.is_empty() {
+            return Err(BuildError::Lint(errors))
+        }
+        warnings
+    } else {
+        vec![]
+    };
+
+    // 5. report
+    Ok(BuildReport {
+        artifact,
+        size: module.len() as u64,
+        unoptimized_size,
+        wasm_opt_version,
+        imports: imports(&module).map_err(BuildError::InvalidWasm)?,
+        custom_sections: custom_sections(&module).map_err(BuildError::InvalidWasm)?,
+        lint_warnings,
+    })
+}
+
+/// Run `wasm-opt` with [WASM_OPT_ARGS]. Returns its version.
+fn run_wasm_opt(wasm_opt: &Path, input: &Path, output: &Path) -> Result<String, BuildError> {
+    let version = Command::new(wasm_opt).arg("--version").output()
+        .map_err(|e| BuildError::WasmOpt(format!("cannot run {}: {}", wasm_opt.display(), e)))?;
+    let result = Command::new(wasm_opt).args(WASM_OPT_ARGS).arg(input).arg("-o").arg(output).output()
+        .map_err(|e| BuildError::WasmOpt(format!("cannot run {}: {}", wasm_opt.display(), e)))?;
+    if !result.status.success() {
+        return Err(BuildError::WasmOpt(String::from_utf8_lossy(&result.stderr).to_string()))
+    }
+    Ok(String::from_utf8_lossy(&version.stdout).trim().to_string())
+}
+
+/// Name of the package in the manifest, i.e. `name` in the section `[package]`.
+fn package_name(manifest_path: &Path) -> Result<String, BuildError> {
+    let manifest = std::fs::read_to_string(manifest_path)?;
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let (true, Some(value)) = (in_package, line.strip_prefix("name")) {
+            // the name is a basic or literal string, which may be followed by a comment
+            let name = value.trim_start().strip_prefix('=').map(str::trim_start)
+                .and_then(|name| ['"', '\''].into_iter().find_map(|quote| name.strip_prefix(quote)?.split_once(quote)));
+            if let Some((name, _)) = name {
+                return Ok(name.to_string())
+            }
+        }
+    }
+    Err(BuildError::InvalidManifest(format!("no package name in {}", manifest_path.display())))
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME").map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package_name_of(manifest: &str) -> Result<String, BuildError> {
+        let path = std::env::temp_dir().join(format!("pchain-sdk-build-{}-{}.toml", std::process::id(), manifest.len()));
+        std::fs::write(&path, manifest).unwrap();
+        let name = package_name(&path);
+        std::fs::remove_file(&path).unwrap();
+        name
+    }
+
+    #[test]
+    fn package_name_is_read_from_the_package_table() {
+        let manifest = "[workspace]\nname = \"workspace\"\n\n[package]\nversion = \"0.1.0\"\nname = \"my-contract\"\n";
+        assert_eq!(package_name_of(manifest).unwrap(), "my-contract");
+        assert_eq!(package_name_of("[package]\n  name='literal'  # comment\n").unwrap(), "literal");
+        assert_eq!(package_name_of("[package]\nname = \"commented\" # the \"name\"\n").unwrap(), "commented");
+    }
+
+    #[test]
+    fn manifest_without_package_name_is_invalid() {
+        assert!(matches!(package_name_of("[package]\nnamespace = \"x\"\n[dependencies]\nname = \"y\"\n"), Err(BuildError::InvalidManifest(_))));
+        assert!(matches!(package_name_of("[package]\nname = x\n"), Err(BuildError::InvalidManifest(_))));
+    }
+}
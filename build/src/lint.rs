@@ -0,0 +1,91 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [lint], which checks that a module only imports functions of the Contract Binary Interface (CBI) and
+//! exports the entrypoints called by the runtime. A module that fails the check, e.g. because a dependency pulled
+//! in `wasi_snapshot_preview1` or `__wbindgen_*` imports, is rejected by the runtime when it is deployed, with an
+//! error that does not tell which import is at fault.
+
+use crate::wasm::{exports, imports, Import, ImportKind};
+
+/// Module from which the CBI functions are imported. It is the default module of `extern "C"` blocks.
+pub const CBI_MODULE: &str = "env";
+
+/// Functions of the CBI that every runtime provides.
+pub const CBI_IMPORTS: [&str; 28] = [
+    "set", "get", "get_network_storage", "balance",
+    "block_height", "block_timestamp", "prev_block_hash",
+    "calling_account", "current_account", "method", "arguments", "amount", "is_internal_call", "transaction_hash",
+    "call", "return_value", "transfer",
+    "defer_create_deposit", "defer_set_deposit_settings", "defer_topup_deposit", "defer_withdraw_deposit",
+    "defer_stake_deposit", "defer_unstake_deposit",
+    "_log",
+    "sha256", "keccak256", "ripemd", "verify_ed25519_signature",
+];
+
+/// Functions of the CBI that are only provided by runtimes supporting the corresponding host features of the SDK
+/// (`log-batch`, `fee-context`, `gas-remaining`, `random-bytes` and `secp256k1`).
+pub const OPTIONAL_CBI_IMPORTS: [&str; 6] = ["_log_batch", "priority_fee_per_gas", "gas_price", "gas_remaining", "random_bytes", "verify_secp256k1_signature"];
+
+/// Exports that the runtime requires: the entrypoint of calls, the allocator used to pass data into the module,
+/// and the linear memory.
+pub const REQUIRED_EXPORTS: [(&str, ImportKind); 3] = [
+    ("entrypoint", ImportKind::Function),
+    ("alloc", ImportKind::Function),
+    ("memory", ImportKind::Memory),
+];
+
+/// A problem found by [lint].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintIssue {
+    /// The import is not a function of the CBI.
+    UnknownImport(Import),
+    /// The import is a function of the CBI that is not provided by every runtime.
+    OptionalImport(Import),
+    /// The export required by the runtime is missing.
+    MissingExport(String),
+}
+
+impl LintIssue {
+    /// Whether the module is rejected by runtimes because of the issue. [LintIssue::OptionalImport] is only a
+    /// warning, since the module is accepted by runtimes that support the host feature.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, LintIssue::OptionalImport(_))
+    }
+}
+
+impl std::fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintIssue::UnknownImport(import) => write!(f, "import {}::{} is not in the Contract Binary Interface", import.module, import.name),
+            LintIssue::OptionalImport(import) => write!(f, "import {}::{} requires a runtime supporting the host feature", import.module, import.name),
+            LintIssue::MissingExport(name) => write!(f, "export {} is required by the runtime", name),
+        }
+    }
+}
+
+/// Check the imports and exports of the module. Returns the issues found, which is empty if the module can be
+/// deployed to every runtime.
+pub fn lint(module: &[u8]) -> Result<Vec<LintIssue>, String> {
+    let mut issues = vec![];
+    for import in imports(module)? {
+        let is_function = import.kind == ImportKind::Function && import.module == CBI_MODULE;
+        if is_function && CBI_IMPORTS.contains(&import.name.as_str()) {
+            continue
+        }
+        if is_function && OPTIONAL_CBI_IMPORTS.contains(&import.name.as_str()) {
+            issues.push(LintIssue::OptionalImport(import));
+        } else {
+            issues.push(LintIssue::UnknownImport(import));
+        }
+    }
+    let exports = exports(module)?;
+    for (name, kind) in REQUIRED_EXPORTS {
+        if !exports.iter().any(|export| export.name == name && export.kind == kind) {
+            issues.push(LintIssue::MissingExport(name.to_string()));
+        }
+    }
+    Ok(issues)
+}
@@ -0,0 +1,77 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Command line of [pchain_sdk_build::build].
+//! 
+//! ```text
+//! pchain-sdk-build [--manifest-path <Cargo.toml>] [--out <dir>] [--target-dir <dir>] [--keep-section <name>]... [--no-wasm-opt] [--no-lint]
+//! pchain-sdk-build lint <module.wasm>
+//! ```
+
+use pchain_sdk_build::{build, lint, BuildOptions, LintIssue};
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("lint") {
+        args.next();
+        let path = args.next().unwrap_or_else(|| exit_with("missing path of module"));
+        return lint_file(&path)
+    }
+    let mut manifest_path = "Cargo.toml".to_string();
+    let mut out_dir = None;
+    let mut target_dir = None;
+    let mut keep_sections = vec![];
+    let mut wasm_opt = true;
+    let mut lint = true;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| exit_with(&format!("missing value of {}", arg)));
+        match arg.as_str() {
+            "--manifest-path" => manifest_path = value(),
+            "--out" => out_dir = Some(value()),
+            "--target-dir" => target_dir = Some(value()),
+            "--keep-section" => keep_sections.push(value()),
+            "--no-wasm-opt" => wasm_opt = false,
+            "--no-lint" => lint = false,
+            _ => exit_with(&format!("unknown argument {}", arg))
+        }
+    }
+
+    let mut options = BuildOptions::new(manifest_path);
+    if let Some(out_dir) = out_dir {
+        options = options.out_dir(out_dir);
+    }
+    if let Some(target_dir) = target_dir {
+        options = options.target_dir(target_dir);
+    }
+    for name in keep_sections {
+        options = options.keep_section(&name);
+    }
+    if !wasm_opt {
+        options = options.wasm_opt(None);
+    }
+    options = options.lint(lint);
+
+    match build(&options) {
+        Ok(report) => print!("{}", report),
+        Err(e) => exit_with(&e.to_string())
+    }
+}
+
+/// Check a module built by other tools, e.g. `pchain-compile`.
+fn lint_file(path: &str) {
+    let module = std::fs::read(path).unwrap_or_else(|e| exit_with(&e.to_string()));
+    let issues = lint(&module).unwrap_or_else(|e| exit_with(&e));
+    for issue in issues.iter() {
+        eprintln!("{}: {}", if issue.is_error() { "error" } else { "warning" }, issue);
+    }
+    if issues.iter().any(LintIssue::is_error) {
+        std::process::exit(1)
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(1)
+}
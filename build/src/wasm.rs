@@ -0,0 +1,183 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines a minimal reader of WASM modules, which reads the imports and exports and removes custom sections.
+
+/// Magic number and version of a WASM module.
+const HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+const CUSTOM_SECTION_ID: u8 = 0;
+const IMPORT_SECTION_ID: u8 = 2;
+const EXPORT_SECTION_ID: u8 = 7;
+
+/// A function, table, memory, global or tag imported by a WASM module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+    pub kind: ImportKind,
+}
+
+/// A function, table, memory or global exported by a WASM module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub name: String,
+    pub kind: ImportKind,
+}
+
+/// Kind of an import or export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Function,
+    Table,
+    Memory,
+    Global,
+    Tag,
+}
+
+/// A section of a WASM module. `range` is the range of the whole section in the module, including its ID and size.
+struct Section<'a> {
+    id: u8,
+    content: &'a [u8],
+    range: std::ops::Range<usize>,
+}
+
+/// Reader of LEB128 integers, names and bytes from a slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        let byte = *self.bytes.get(self.offset).ok_or("unexpected end of module")?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    fn leb128(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value)
+            }
+        }
+        Err("invalid LEB128 integer".to_string())
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.offset.checked_add(len).filter(|end| *end <= self.bytes.len()).ok_or("unexpected end of module")?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let len = self.leb128()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec()).map_err(|_| "invalid UTF-8 name".to_string())
+    }
+
+    fn limits(&mut self) -> Result<(), String> {
+        let flags = self.u8()?;
+        self.leb128()?;
+        if flags & 0x01 != 0 {
+            self.leb128()?;
+        }
+        Ok(())
+    }
+}
+
+fn sections(module: &[u8]) -> Result<Vec<Section<'_>>, String> {
+    if module.len() < HEADER.len() || module[..HEADER.len()] != HEADER {
+        return Err("not a WASM module of version 1".to_string())
+    }
+    let mut reader = Reader::new(&module[HEADER.len()..]);
+    let mut sections = vec![];
+    while !reader.is_empty() {
+        let start = HEADER.len() + reader.offset;
+        let id = reader.u8()?;
+        let len = reader.leb128()? as usize;
+        let content = reader.bytes(len)?;
+        sections.push(Section { id, content, range: start..HEADER.len() + reader.offset });
+    }
+    Ok(sections)
+}
+
+/// Names of the custom sections of the module, in order.
+pub fn custom_sections(module: &[u8]) -> Result<Vec<String>, String> {
+    sections(module)?.iter()
+        .filter(|section| section.id == CUSTOM_SECTION_ID)
+        .map(|section| Reader::new(section.content).name())
+        .collect()
+}
+
+/// The imports of the module, in order.
+pub fn imports(module: &[u8]) -> Result<Vec<Import>, String> {
+    let mut imports = vec![];
+    for section in sections(module)?.iter().filter(|section| section.id == IMPORT_SECTION_ID) {
+        let mut reader = Reader::new(section.content);
+        for _ in 0..reader.leb128()? {
+            let module = reader.name()?;
+            let name = reader.name()?;
+            let kind = match reader.u8()? {
+                0x00 => { reader.leb128()?; ImportKind::Function },
+                0x01 => { reader.u8()?; reader.limits()?; ImportKind::Table },
+                0x02 => { reader.limits()?; ImportKind::Memory },
+                0x03 => { reader.u8()?; reader.u8()?; ImportKind::Global },
+                0x04 => { reader.u8()?; reader.leb128()?; ImportKind::Tag },
+                kind => return Err(format!("unknown import kind {}", kind))
+            };
+            imports.push(Import { module, name, kind });
+        }
+    }
+    Ok(imports)
+}
+
+/// The exports of the module, in order.
+pub fn exports(module: &[u8]) -> Result<Vec<Export>, String> {
+    let mut exports = vec![];
+    for section in sections(module)?.iter().filter(|section| section.id == EXPORT_SECTION_ID) {
+        let mut reader = Reader::new(section.content);
+        for _ in 0..reader.leb128()? {
+            let name = reader.name()?;
+            let kind = match reader.u8()? {
+                0x00 => ImportKind::Function,
+                0x01 => ImportKind::Table,
+                0x02 => ImportKind::Memory,
+                0x03 => ImportKind::Global,
+                0x04 => ImportKind::Tag,
+                kind => return Err(format!("unknown export kind {}", kind))
+            };
+            reader.leb128()?;
+            exports.push(Export { name, kind });
+        }
+    }
+    Ok(exports)
+}
+
+/// Remove the custom sections whose names are not in `keep`. Other sections are copied unchanged.
+pub fn strip_custom_sections(module: &[u8], keep: &[String]) -> Result<Vec<u8>, String> {
+    let mut stripped = HEADER.to_vec();
+    for section in sections(module)? {
+        if section.id == CUSTOM_SECTION_ID {
+            let name = Reader::new(section.content).name()?;
+            if !keep.contains(&name) {
+                continue
+            }
+        }
+        stripped.extend_from_slice(&module[section.range]);
+    }
+    Ok(stripped)
+}
@@ -0,0 +1,61 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Builders of small WASM modules for the tests.
+
+#![allow(dead_code)]
+
+/// Magic number and version of a WASM module.
+pub const HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+pub fn leb128(mut value: u64) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+pub fn name(name: &str) -> Vec<u8> {
+    [leb128(name.len() as u64), name.as_bytes().to_vec()].concat()
+}
+
+pub fn section(id: u8, content: &[u8]) -> Vec<u8> {
+    [vec![id], leb128(content.len() as u64), content.to_vec()].concat()
+}
+
+pub fn custom_section(section_name: &str, data: &[u8]) -> Vec<u8> {
+    section(0, &[name(section_name), data.to_vec()].concat())
+}
+
+/// Import section of functions of type 0, given as pairs of module and name.
+pub fn import_section(functions: &[(&str, &str)]) -> Vec<u8> {
+    let mut content = leb128(functions.len() as u64);
+    for (module, function) in functions {
+        content.extend(name(module));
+        content.extend(name(function));
+        content.extend([0x00, 0x00]);
+    }
+    section(2, &content)
+}
+
+/// Export section of functions and memories, given as pairs of name and kind, which are exported at index 0.
+pub fn export_section(exports: &[(&str, u8)]) -> Vec<u8> {
+    let mut content = leb128(exports.len() as u64);
+    for (export, kind) in exports {
+        content.extend(name(export));
+        content.extend([*kind, 0x00]);
+    }
+    section(7, &content)
+}
+
+pub fn module(sections: &[Vec<u8>]) -> Vec<u8> {
+    [HEADER.to_vec(), sections.concat()].concat()
+}
@@ -0,0 +1,62 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [lint](pchain_sdk_build::lint) with small hand-built modules.
+
+mod common;
+
+use common::*;
+use pchain_sdk_build::{lint, Import, ImportKind, LintIssue};
+
+fn exports_of_contract() -> Vec<u8> {
+    export_section(&[("entrypoint", 0x00), ("alloc", 0x00), ("memory", 0x02)])
+}
+
+fn function(module: &str, name: &str) -> Import {
+    Import { module: module.to_string(), name: name.to_string(), kind: ImportKind::Function }
+}
+
+#[test]
+fn contract_module_has_no_issues() {
+    let module = module(&[import_section(&[("env", "get"), ("env", "_log")]), exports_of_contract()]);
+    assert_eq!(lint(&module), Ok(vec![]));
+}
+
+#[test]
+fn unknown_import_is_an_error() {
+    let module = module(&[
+        import_section(&[("env", "get"), ("wasi_snapshot_preview1", "fd_write"), ("env", "__wbindgen_throw")]),
+        exports_of_contract(),
+    ]);
+    let issues = lint(&module).unwrap();
+    assert_eq!(issues, vec![
+        LintIssue::UnknownImport(function("wasi_snapshot_preview1", "fd_write")),
+        LintIssue::UnknownImport(function("env", "__wbindgen_throw")),
+    ]);
+    assert!(issues.iter().all(LintIssue::is_error));
+}
+
+#[test]
+fn optional_import_is_a_warning() {
+    let module = module(&[import_section(&[("env", "verify_secp256k1_signature")]), exports_of_contract()]);
+    let issues = lint(&module).unwrap();
+    assert_eq!(issues, vec![LintIssue::OptionalImport(function("env", "verify_secp256k1_signature"))]);
+    assert!(!issues[0].is_error());
+}
+
+#[test]
+fn missing_export_is_an_error() {
+    // the entrypoint is exported as a memory rather than a function
+    let module = module(&[import_section(&[("env", "get")]), export_section(&[("entrypoint", 0x02), ("memory", 0x02)])]);
+    let issues = lint(&module).unwrap();
+    assert_eq!(issues, vec![LintIssue::MissingExport("entrypoint".to_string()), LintIssue::MissingExport("alloc".to_string())]);
+    assert!(issues.iter().all(LintIssue::is_error));
+    assert_eq!(issues[0].to_string(), "export entrypoint is required by the runtime");
+}
+
+#[test]
+fn invalid_module_is_not_linted() {
+    assert!(lint(b"not a module").is_err());
+}
@@ -0,0 +1,114 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the reader of WASM modules with small hand-built modules.
+
+mod common;
+
+use common::*;
+use pchain_sdk_build::{custom_sections, exports, imports, strip_custom_sections, Export, Import, ImportKind};
+
+/// Type section with the type `() -> ()`, which is copied unchanged.
+fn type_section() -> Vec<u8> {
+    section(1, &[0x01, 0x60, 0x00, 0x00])
+}
+
+#[test]
+fn custom_sections_are_kept_or_stripped_by_name() {
+    let module = module(&[
+        custom_section("pchain_sdk", b"0.4.2"),
+        type_section(),
+        custom_section("name", b"names"),
+        custom_section("producers", b""),
+    ]);
+    assert_eq!(custom_sections(&module), Ok(vec!["pchain_sdk".to_string(), "name".to_string(), "producers".to_string()]));
+
+    let stripped = strip_custom_sections(&module, &["pchain_sdk".to_string()]).unwrap();
+    assert_eq!(stripped, self::module(&[custom_section("pchain_sdk", b"0.4.2"), type_section()]));
+    assert_eq!(custom_sections(&stripped), Ok(vec!["pchain_sdk".to_string()]));
+}
+
+#[test]
+fn module_without_custom_sections_to_strip_is_unchanged() {
+    let module = module(&[custom_section("pchain_sdk", b"0.4.2"), type_section()]);
+    assert_eq!(strip_custom_sections(&module, &["pchain_sdk".to_string()]), Ok(module.clone()));
+
+    let empty = self::module(&[]);
+    assert_eq!(strip_custom_sections(&empty, &[]), Ok(empty.clone()));
+}
+
+#[test]
+fn section_sizes_are_read_as_multi_byte_leb128() {
+    // a section of 300 bytes has the two-byte size 0xac 0x02
+    let data = vec![7u8; 300 - name("big").len()];
+    let big = custom_section("big", &data);
+    assert_eq!(big[1..3], [0xac, 0x02]);
+
+    let module = module(&[big.clone(), type_section(), custom_section("small", b"")]);
+    assert_eq!(custom_sections(&module), Ok(vec!["big".to_string(), "small".to_string()]));
+    let stripped = strip_custom_sections(&module, &["big".to_string()]).unwrap();
+    assert_eq!(stripped, self::module(&[big, type_section()]));
+
+    // a size may be encoded with more bytes than needed
+    let padded = module_with_raw_section(&[0x00, 0x85, 0x80, 0x80, 0x00], &name("pad")[..], b"x");
+    assert_eq!(custom_sections(&padded), Ok(vec!["pad".to_string()]));
+}
+
+/// Module with one section of the given ID and size bytes, followed by the content.
+fn module_with_raw_section(id_and_size: &[u8], name: &[u8], data: &[u8]) -> Vec<u8> {
+    [HEADER.as_slice(), id_and_size, name, data].concat()
+}
+
+#[test]
+fn imports_and_exports_are_read() {
+    let module = module(&[
+        type_section(),
+        import_section(&[("env", "get"), ("wasi_snapshot_preview1", "fd_write")]),
+        export_section(&[("entrypoint", 0x00), ("memory", 0x02)]),
+    ]);
+    assert_eq!(imports(&module), Ok(vec![
+        Import { module: "env".to_string(), name: "get".to_string(), kind: ImportKind::Function },
+        Import { module: "wasi_snapshot_preview1".to_string(), name: "fd_write".to_string(), kind: ImportKind::Function },
+    ]));
+    assert_eq!(exports(&module), Ok(vec![
+        Export { name: "entrypoint".to_string(), kind: ImportKind::Function },
+        Export { name: "memory".to_string(), kind: ImportKind::Memory },
+    ]));
+}
+
+#[test]
+fn invalid_modules_are_rejected() {
+    let sections = [
+        custom_section("pchain_sdk", b"0.4.2"),
+        import_section(&[("env", "get")]),
+        export_section(&[("entrypoint", 0x00)]),
+    ];
+    let valid = module(&sections);
+    // a module truncated between sections is valid
+    let boundaries: Vec<usize> = (0..=sections.len()).map(|count| HEADER.len() + sections[..count].concat().len()).collect();
+    // every other truncation of the module is an error rather than a panic
+    for len in (0..valid.len()).filter(|len| !boundaries.contains(len)) {
+        let truncated = &valid[..len];
+        assert!(custom_sections(truncated).is_err(), "{}", len);
+        assert!(imports(truncated).is_err(), "{}", len);
+        assert!(exports(truncated).is_err(), "{}", len);
+        assert!(strip_custom_sections(truncated, &[]).is_err(), "{}", len);
+    }
+
+    let not_wasm = [b"\0asm".as_slice(), &[0x02, 0x00, 0x00, 0x00]].concat();
+    assert!(custom_sections(&not_wasm).is_err());
+    // a size longer than 10 bytes is not a LEB128 integer of 64 bits
+    let overlong = [HEADER.as_slice(), &[0x00], &[0x80; 11]].concat();
+    assert!(custom_sections(&overlong).is_err());
+    // a size beyond the end of the module
+    let oversized = [HEADER.as_slice(), &[0x00, 0xff, 0xff, 0xff, 0xff, 0x0f]].concat();
+    assert!(strip_custom_sections(&oversized, &[]).is_err());
+    // a custom section without a name, and a name which is not UTF-8
+    assert!(custom_sections(&module(&[section(0, &[])])).is_err());
+    assert!(custom_sections(&module(&[section(0, &[0x01, 0xff])])).is_err());
+    // an unknown kind of import or export
+    assert!(imports(&module(&[section(2, &[[0x01].as_slice(), &name("env"), &name("get"), &[0x09]].concat())])).is_err());
+    assert!(exports(&module(&[section(7, &[[0x01].as_slice(), &name("entrypoint"), &[0x09, 0x00]].concat())])).is_err());
+}
@@ -0,0 +1,37 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Fields, ItemStruct, Index};
+
+/// `generate_call_args` implements `pchain_sdk::method::CallArgs` for a struct, which adds the fields to a 
+/// `ContractMethodInputBuilder` in order of declaration, i.e. one argument per field.
+pub(crate) fn generate_call_args(item: &ItemStruct) -> TokenStream {
+    let struct_name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let code_add_fields: Vec<proc_macro2::TokenStream> = match &item.fields {
+        Fields::Named(fields) => fields.named.iter().map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            quote!{ builder.add(self.#ident); }
+        }).collect(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len()).map(|i| {
+            let index = Index::from(i);
+            quote!{ builder.add(self.#index); }
+        }).collect(),
+        Fields::Unit => vec![]
+    };
+
+    TokenStream::from(quote!{
+        impl #impl_generics pchain_sdk::method::CallArgs for #struct_name #ty_generics #where_clause {
+            fn into_call_arguments(self) -> Vec<u8> {
+                #[allow(unused_mut)]
+                let mut builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+                #(#code_add_fields)*
+                builder.to_call_arguments()
+            }
+        }
+    })
+}
@@ -5,21 +5,40 @@
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{ItemStruct, ItemImpl, punctuated::Punctuated, FnArg, token::Comma, ImplItemMethod, Ident};
+use syn::{ItemStruct, ItemImpl, punctuated::Punctuated, FnArg, token::Comma, ImplItemMethod, Ident, spanned::Spanned, parse_str, Expr};
+use pchain_types::cryptography::PublicAddress;
 
-use super::generate_compilation_error;
+use super::{generate_compilation_error, emit_storage_layout, check_layout_lock, STORAGE_LAYOUT_LOCK_ENV, standard_methods, STANDARDS};
 
 /// `generate_contract_struct` performs the following items:
 /// 1. imports crates from sdk
 /// 2. generate implementation of Storage for contract
 /// 3. generate implementation of Accesser for contract
-pub(crate) fn generate_contract_struct(ist: &mut ItemStruct) -> TokenStream {
+/// 4. generate getters of ReadOnly for contract
+/// 5. check the storage layout against `storage_layout.lock`, if `layout_version` is set
+pub(crate) fn generate_contract_struct(ist: &mut ItemStruct, options: &ContractStructOptions) -> TokenStream {
     let contract_struct = ist.clone();
 
+    // include the lock file, and read the variable of its path, so that the crate is rebuilt, and the layout checked 
+    // again, when either changes
+    let code_layout_lock = match options.layout_version.map(|version| check_layout_lock(ist, version)) {
+        Some(Ok(path)) => {
+            let path = path.display().to_string();
+            quote!{ 
+                const _: &[u8] = include_bytes!(#path);
+                const _: Option<&str> = option_env!(#STORAGE_LAYOUT_LOCK_ENV);
+            }
+        },
+        Some(Err(e)) => return generate_compilation_error(e),
+        None => quote!{}
+    };
+
     let code_impl_storage :proc_macro2::TokenStream = generate_storage_impl(ist).into();
 
     let code_impl_accesser :proc_macro2::TokenStream = generate_accesser_impl(ist).into();
 
+    let code_impl_read_only :proc_macro2::TokenStream = generate_read_only_impl(ist).into();
+
     // All Code after struct
     TokenStream::from(
         quote!{
@@ -30,11 +49,39 @@ pub(crate) fn generate_contract_struct(ist: &mut ItemStruct) -> TokenStream {
             #code_impl_storage
 
             #code_impl_accesser
+
+            #code_impl_read_only
+
+            #code_layout_lock
         }
     )
 }
 
-/// `generate_storage_impl` generates implementation of Storable for contract (load_storage and save_storage). 
+/// Options of `#[contract]`.
+#[derive(Default)]
+pub(crate) struct ContractStructOptions {
+    /// Version of the storage layout, which is locked in `storage_layout.lock` (`layout_version = N`).
+    pub layout_version: Option<u32>,
+}
+
+/// `parse_contract_struct_options` parses the arguments of `#[contract]`, which is `layout_version = N`, optional.
+pub(crate) fn parse_contract_struct_options(attr_args: &[syn::NestedMeta]) -> syn::Result<ContractStructOptions> {
+    let mut options = ContractStructOptions::default();
+    for arg in attr_args {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { path, lit: syn::Lit::Int(version), .. })) if path.is_ident("layout_version") => {
+                options.layout_version = Some(version.base10_parse()?);
+            },
+            _ => return Err(syn::Error::new(arg.span(), "Expect arguments of contract to be layout_version = N, where N is an integer"))
+        }
+    }
+    Ok(options)
+}
+
+/// `generate_storage_impl` generates implementation of Storable for contract (load_storage and save_storage), and the
+/// hidden functions `__load_fields` and `__save_fields`, which load and save only the fields of the given names, so
+/// that the entrypoint generated by `#[contract_methods]` does not read the fields that a method does not access. The
+/// other fields are their default values, or are loaded if their types do not implement Default.
 pub(crate) fn generate_storage_impl(ist: &mut ItemStruct) -> TokenStream {
     let struct_name = &ist.ident;
     let fields = if let syn::Fields::Named(syn::FieldsNamed {ref named, ..})
@@ -44,6 +91,11 @@ pub(crate) fn generate_storage_impl(ist: &mut ItemStruct) -> TokenStream {
         return generate_compilation_error("Cannot find named fields in the struct".to_string())
     };
 
+    // write the storage layout report, if it is enabled
+    if let Err(e) = emit_storage_layout(ist) {
+        return generate_compilation_error(e)
+    }
+
     // get the values from world state
     let code_get_each_fields = fields.iter().enumerate().map(|(i, f)| {
         let f_name = f.ident.clone().unwrap();
@@ -62,6 +114,31 @@ pub(crate) fn generate_storage_impl(ist: &mut ItemStruct) -> TokenStream {
         }
     });
 
+    // get the values of the given fields from world state
+    let code_get_named_fields = fields.iter().enumerate().map(|(i, f)| {
+        let f_name = f.ident.clone().unwrap();
+        let f_ty = &f.ty;
+        let name = f_name.to_string();
+        quote!{
+            #f_name: if names.contains(&#name) {
+                pchain_sdk::Storable::__load_storage(&field.add(#i as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<#f_ty>(std::marker::PhantomData)).__unloaded(&field.add(#i as u8))
+            }
+        }
+    });
+
+    // set the values of the given fields to world state
+    let code_set_named_fields = fields.iter().enumerate().map(|(i, f)| {
+        let f_name = f.ident.clone().unwrap();
+        let name = f_name.to_string();
+        quote!{
+            if names.contains(&#name) {
+                self.#f_name.__save_storage(&field.add(#i as u8));
+            }
+        }
+    });
+
     TokenStream::from(
         quote!{
             impl pchain_sdk::Storable for #struct_name {
@@ -75,6 +152,22 @@ pub(crate) fn generate_storage_impl(ist: &mut ItemStruct) -> TokenStream {
                     #(#code_set_each_fields)*
                 }
             }
+
+            impl #struct_name {
+                #[doc(hidden)]
+                pub fn __load_fields(field :&pchain_sdk::StoragePath, names: &[&str]) -> Self {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+                    #struct_name {
+                        #(#code_get_named_fields,)*
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn __save_fields(&mut self, field :&pchain_sdk::StoragePath, names: &[&str]) {
+                    #(#code_set_named_fields)*
+                }
+            }
         }
     )
 }
@@ -159,8 +252,67 @@ pub(crate) fn generate_accesser_impl(ist: &mut ItemStruct) -> TokenStream {
     )
 }
 
+/// `generate_read_only_impl` creates trait ReadOnly of the contract and implements it for `pchain_sdk::ReadOnly`, 
+/// which is passed to methods with attribute `view`. Each getter returns a shared reference to a field.
+/// 
+/// Example:
+///```no_run
+/// 
+/// trait MyContractReadOnly {
+///     fn data(&self) -> &i32;
+/// }
+/// impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+///     fn data(&self) -> &i32 {
+///         &self.__inner().data
+///     }
+/// }
+/// ```
+pub(crate) fn generate_read_only_impl(ist: &mut ItemStruct) -> TokenStream {
+    let struct_name = &ist.ident;
+    let fields = if let syn::Fields::Named(syn::FieldsNamed {ref named, ..})
+    = &ist.fields {
+        named
+    } else {
+        return generate_compilation_error("Cannot find named fields in the struct".to_string())
+    };
+
+    let read_only_trait = format_ident!("{}ReadOnly", struct_name.to_string());
+
+    let code_trait_methods_each_fields = fields.iter().map(|f| {
+        let f_name = f.ident.clone().unwrap();
+        let f_ty = f.ty.clone();
+        quote!{
+            fn #f_name(&self) -> &#f_ty;
+        }
+    });
+
+    let code_impl_methods_each_fields = fields.iter().map(|f| {
+        let f_name = f.ident.clone().unwrap();
+        let f_ty = f.ty.clone();
+        quote!{
+            fn #f_name(&self) -> &#f_ty {
+                &self.__inner().#f_name
+            }
+        }
+    });
+
+    TokenStream::from(
+        quote!{
+            // getters of the fields that no view reads are not used
+            #[allow(dead_code)]
+            trait #read_only_trait {
+                #(#code_trait_methods_each_fields)*
+            }
+
+            impl<'a> #read_only_trait for pchain_sdk::ReadOnly<'a, #struct_name> {
+                #(#code_impl_methods_each_fields)*
+            }
+        }
+    )
+}
+
 /// `generate_contract_impl` generate code skeleton for Contract Methods
-pub(crate) fn generate_contract_impl(ipl: &ItemImpl) -> TokenStream {
+pub(crate) fn generate_contract_impl(ipl: &ItemImpl, options: &ContractOptions) -> TokenStream {
     let original_code = ipl.clone();
     let impl_name = match &*ipl.self_ty {
         syn::Type::Path(tp) => tp.path.segments.first().unwrap().ident.clone(),
@@ -170,7 +322,13 @@ pub(crate) fn generate_contract_impl(ipl: &ItemImpl) -> TokenStream {
     };
 
     // Create Contract Method Skeleton
-    let contract_skeleton = generate_contract_methods(&impl_name, ipl);
+    let contract_skeleton = generate_contract_methods(&impl_name, ipl, options);
+
+    // Create Contract Metadata
+    let contract_metadata = generate_contract_metadata(&options.meta, &gas_estimates(ipl));
+
+    // Create SDK Version Stamp
+    let sdk_version_stamp = generate_sdk_version_stamp(options.return_envelope);
 
     // All Code after impl
     TokenStream::from(
@@ -178,10 +336,181 @@ pub(crate) fn generate_contract_impl(ipl: &ItemImpl) -> TokenStream {
             #original_code
 
             #contract_skeleton
+
+            #contract_metadata
+
+            #sdk_version_stamp
         }
     )
 }
 
+/// Keys accepted in `meta(..)` of `#[contract_methods]`.
+const CONTRACT_META_KEYS: [&str; 4] = ["version", "author", "license", "repo"];
+
+/// Options of `#[contract_methods]`.
+#[derive(Default)]
+pub(crate) struct ContractOptions {
+    /// Fields of `meta(key = "value", ..)`.
+    pub meta: Vec<(String, String)>,
+    /// Return values are encoded in `pchain_sdk::method::OutputEnvelope`.
+    pub return_envelope: bool,
+    /// The interface reference is written to `OUT_DIR` as a markdown file (`interface_doc`).
+    pub interface_doc: bool,
+    /// Calls are rejected after `pchain_sdk::lifecycle::decommission` (`decommissionable`).
+    pub decommissionable: bool,
+    /// The method `multicall` is generated (`multicall`).
+    pub multicall: bool,
+    /// The ABI is written to `OUT_DIR` as a JSON file (`abi_file`).
+    pub abi_file: bool,
+    /// A `pchain_sdk::metrics::CallMetrics` is logged for every call (`metrics`).
+    pub metrics: bool,
+    /// The standard derived by the contract struct, whose methods are described in the ABI, the interface reference 
+    /// and the metrics (`fungible_token` or `nft`).
+    pub standard: Option<&'static str>,
+}
+
+/// `parse_contract_options` parses the arguments of `#[contract_methods]`, which are `meta(key = "value", ..)`, 
+/// `return_envelope`, `interface_doc`, `decommissionable`, `multicall`, `abi_file`, `metrics`, and one of the standards
+/// `fungible_token` and `nft`, all optional.
+pub(crate) fn parse_contract_options(attr_args: &[syn::NestedMeta]) -> syn::Result<ContractOptions> {
+    let mut options = ContractOptions::default();
+    let meta = &mut options.meta;
+    for arg in attr_args {
+        let list = match arg {
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("meta") => list,
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("return_envelope") => {
+                options.return_envelope = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("interface_doc") => {
+                options.interface_doc = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("decommissionable") => {
+                options.decommissionable = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("multicall") => {
+                options.multicall = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("abi_file") => {
+                options.abi_file = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("metrics") => {
+                options.metrics = true;
+                continue
+            },
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if STANDARDS.iter().any(|standard| path.is_ident(standard)) => {
+                if options.standard.is_some() {
+                    return Err(syn::Error::new(arg.span(), "Only one standard can be declared in contract_methods"))
+                }
+                options.standard = STANDARDS.iter().copied().find(|standard| path.is_ident(standard));
+                continue
+            },
+            _ => return Err(syn::Error::new(arg.span(), "Expect arguments of contract_methods to be meta(version = \"..\", ..), return_envelope, interface_doc, decommissionable, multicall, abi_file, metrics, fungible_token or nft"))
+        };
+        for nested in list.nested.iter() {
+            let (key, value) = match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { path, lit: syn::Lit::Str(s), .. })) => {
+                    (path.get_ident().map(|ident| ident.to_string()).unwrap_or_default(), s.value())
+                },
+                _ => return Err(syn::Error::new(nested.span(), "Expect fields of meta to be key = \"value\""))
+            };
+            if !CONTRACT_META_KEYS.contains(&key.as_str()) {
+                return Err(syn::Error::new(nested.span(), format!("Unknown field of meta. Expect one of {:?}", CONTRACT_META_KEYS)))
+            }
+            if meta.iter().any(|(k, _)| *k == key) {
+                return Err(syn::Error::new(nested.span(), format!("Duplicated field of meta: {}", key)))
+            }
+            meta.push((key, value));
+        }
+    }
+    Ok(options)
+}
+
+/// `generate_contract_metadata` generates the metadata of the contract as a JSON object, e.g. `{"version":"1.2.0"}`. 
+/// It is available as the constant `CONTRACT_METADATA`, and embedded in the custom section `pchain_metadata` of 
+/// the WASM module so that it can be read from the deployed code. The gas estimates of the methods, if any, are 
+/// under the key `gas_estimates`, e.g. `{"version":"1.2.0","gas_estimates":{"deposit":120000}}`.
+fn generate_contract_metadata(meta: &[(String, String)], gas_estimates: &[(String, u64)]) -> proc_macro2::TokenStream {
+    let mut fields: Vec<String> = meta.iter()
+        .map(|(key, value)| format!("\"{}\":\"{}\"", key, escape_json(value)))
+        .collect();
+    if !gas_estimates.is_empty() {
+        let estimates: Vec<String> = gas_estimates.iter()
+            .map(|(method, gas)| format!("\"{}\":{}", method, gas))
+            .collect();
+        fields.push(format!("\"gas_estimates\":{{{}}}", estimates.join(",")));
+    }
+    let json = format!("{{{}}}", fields.join(","));
+    let json_len = json.len();
+    let json_bytes = proc_macro2::Literal::byte_string(json.as_bytes());
+
+    quote!{
+        /// Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`.
+        pub const CONTRACT_METADATA: &str = #json;
+
+        #[cfg(target_arch = "wasm32")]
+        #[link_section = "pchain_metadata"]
+        #[used]
+        static __PCHAIN_CONTRACT_METADATA: [u8; #json_len] = *#json_bytes;
+    }
+}
+
+/// `gas_estimates` collects the `gas_estimate` of the methods with attribute `call`, in order of declaration. Invalid 
+/// options are skipped here, since they are reported when generating the entrypoint.
+fn gas_estimates(ipl: &ItemImpl) -> Vec<(String, u64)> {
+    ipl.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(e) if e.is_contract_method() => e.call_options().ok()
+            .and_then(|options| options.gas_estimate)
+            .map(|gas| (e.sig.ident.to_string(), gas)),
+        _ => None
+    }).collect()
+}
+
+/// Version of the encoding of method calls, i.e. method name and Borsh-serialized arguments, which the generated 
+/// entrypoint accepts. It changes only if the generated dispatcher becomes incompatible with existing callers.
+const CALLDATA_VERSION: u32 = 1;
+
+/// Version of `pchain_sdk::method::OutputEnvelope` returned by the generated entrypoint with `return_envelope`. 
+/// It is the same as `pchain_sdk::method::OUTPUT_ENVELOPE_VERSION`.
+const OUTPUT_ENVELOPE_VERSION: u32 = 1;
+
+/// `generate_sdk_version_stamp` embeds the SDK version, the calldata version and the version of the return value 
+/// encoding in the custom section `pchain_sdk` of the WASM module as JSON, e.g. `{"sdk":"0.4.2","calldata":1,"return":0}`, 
+/// so that the SDK generation a deployed contract was built with, and how to decode its return values, can be 
+/// identified from its code. The version of the macros is always the same as the SDK's. Return version 0 means 
+/// return values are not enveloped.
+fn generate_sdk_version_stamp(return_envelope: bool) -> proc_macro2::TokenStream {
+    let return_version = if return_envelope { OUTPUT_ENVELOPE_VERSION } else { 0 };
+    let json = format!("{{\"sdk\":\"{}\",\"calldata\":{},\"return\":{}}}", env!("CARGO_PKG_VERSION"), CALLDATA_VERSION, return_version);
+    let json_len = json.len();
+    let json_bytes = proc_macro2::Literal::byte_string(json.as_bytes());
+
+    quote!{
+        #[cfg(target_arch = "wasm32")]
+        #[link_section = "pchain_sdk"]
+        #[used]
+        static __PCHAIN_SDK_VERSION: [u8; #json_len] = *#json_bytes;
+    }
+}
+
+/// Escape a string as the content of a JSON string.
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// generate code segmenet from function arguments. e.g.
 /// 
 /// ===> transform from fn func (a: i32, b: String)
@@ -223,7 +552,123 @@ fn generate_let_arguments(pass_args :&mut Vec<proc_macro2::TokenStream>, fn_args
 /// `generate_contract_methods` performs the following items:
 /// 1. generate contract method function entrypoint() with macro #[contract_init]
 /// 2. generate skeleton of code inside entrypoint().
-fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl) -> Option<proc_macro2::TokenStream> {
+fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl, options: &ContractOptions) -> Option<proc_macro2::TokenStream> {
+    let return_envelope = options.return_envelope;
+
+    // parse the arguments of attribute `call` of each contract method
+    let mut call_options = vec![];
+    for item in ipl.items.iter() {
+        if let syn::ImplItem::Method(e) = item {
+            if e.is_contract_method() {
+                match e.call_options() {
+                    Ok(options) if options.max_len.is_some() && collection_arguments(e).is_empty() => {
+                        return Some(syn::Error::new(e.sig.span(), "Method with max_len should take an argument of a collection type, e.g. Vec<T>").to_compile_error())
+                    },
+                    Ok(options) => call_options.push(options),
+                    Err(err) => return Some(err.to_compile_error())
+                }
+            }
+        }
+    }
+
+    // create code segment for the method `config` of `#[contract_config]`, unless the contract defines its own
+    let has_config = !ipl.items.iter().any(|item| matches!(item, syn::ImplItem::Method(e) if e.sig.ident == "config"));
+    let code_config = if has_config { generate_config(impl_name) } else { quote!{} };
+
+    // the generated method multicall cannot be overridden
+    if options.multicall {
+        if let Some(e) = ipl.items.iter().find_map(|item| match item {
+            syn::ImplItem::Method(e) if e.sig.ident == "multicall" => Some(e),
+            _ => None
+        }) {
+            return Some(syn::Error::new(e.sig.ident.span(), "Method multicall is generated by the option multicall of contract_methods.").to_compile_error())
+        }
+    }
+
+    // create code segment for the hidden method which lists the callable methods
+    let code_method_list = generate_method_list(impl_name, ipl, &call_options, has_config, options.multicall);
+
+    // create the interface reference, as the doc comment of the entrypoint and optionally a markdown file
+    let interface_doc = generate_interface_doc(impl_name, ipl, &call_options, options.multicall, options.standard);
+    if options.interface_doc {
+        if let Err(err) = write_out_file(impl_name, "interface_doc", &format!("{}.interface.md", impl_name), &interface_doc) {
+            return Some(err.to_compile_error())
+        }
+    }
+
+    // create the ABI, which is embedded in the contract and optionally written to a JSON file
+    let abi = generate_contract_abi(impl_name, ipl, &call_options, options.multicall, options.standard);
+    if options.abi_file {
+        if let Err(err) = write_out_file(impl_name, "abi_file", &format!("{}.abi.json", impl_name), &abi) {
+            return Some(err.to_compile_error())
+        }
+    }
+    let abi_len = abi.len();
+    let abi_bytes = proc_macro2::Literal::byte_string(abi.as_bytes());
+    let doc_lines = interface_doc.lines();
+
+    let mut call_options = call_options.into_iter();
+
+    // methods with attribute `invariant`, which are checked after each call of a method with receiver `&mut self`
+    let invariants = match invariant_methods(ipl) {
+        Ok(invariants) => invariants,
+        Err(err) => return Some(err.to_compile_error())
+    };
+
+    // create code segment for plain transfers, i.e. calls with attached value and empty method name
+    let code_on_receive = match generate_on_receive(impl_name, ipl, &invariants) {
+        Ok(code) => code,
+        Err(err) => return Some(err.to_compile_error())
+    };
+
+    // create code segment for methods with attribute `view`
+    let code_views = match generate_views(impl_name, ipl) {
+        Ok(code) => code,
+        Err(err) => return Some(err.to_compile_error())
+    };
+
+    // create code segment for calls of undefined or malformed method names
+    let code_fallback = match generate_fallback(impl_name, ipl, &invariants) {
+        Ok(code) => code,
+        Err(err) => return Some(err.to_compile_error())
+    };
+
+    // create code segments for the hooks invoked around each call
+    let (code_before_call, code_after_call) = match generate_call_hooks(impl_name, ipl) {
+        Ok(code) => code,
+        Err(err) => return Some(err.to_compile_error())
+    };
+
+
+    // fields accessed by the invariants, which are loaded for every method checked by them
+    let mut invariant_fields = Some(vec![]);
+    for item in ipl.items.iter() {
+        let e = match item {
+            syn::ImplItem::Method(e) => e,
+            _ => continue
+        };
+        if let Some(attr) = e.attrs.iter().find(|attr| attr.path.is_ident("fields")) {
+            if !e.is_contract_method() || e.is_associate() {
+                return Some(syn::Error::new(attr.span(), "Attribute fields can only be used on methods with call and receiver &self or &mut self").to_compile_error())
+            }
+            if let Err(err) = loaded_fields(e, &[], None) {
+                return Some(err.to_compile_error())
+            }
+        }
+        if e.is_invariant() {
+            invariant_fields = invariant_fields.zip(accessed_fields(&e.block)).map(|(mut fields, accessed)| {
+                fields.extend(accessed);
+                fields
+            });
+        }
+    }
+
+    // names of the methods with attribute `call`, which can be called by multicall
+    let call_method_names: Vec<String> = ipl.items.iter().filter_map(|f| match f {
+        syn::ImplItem::Method(e) if e.is_contract_method() => Some(e.sig.ident.to_string()),
+        _ => None
+    }).collect();
+
     // create code segment for function selection
     let code_function_selection = ipl.items.iter().filter_map(|f| {
         match &f {
@@ -234,14 +679,93 @@ fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl) -> Option<proc_m
                     return None;
                 }
 
+                // define guard for internal-only method
+                let options = call_options.next().unwrap();
+                let code_guard = if options.internal_only {
+                    let code_helper_check = match options.helper {
+                        Some(helper) => {
+                            let helper_args = parse_str::<Expr>(format!("{:?}", helper).as_str()).unwrap();
+                            quote!{ && pchain_sdk::transaction::calling_account() != #helper_args }
+                        },
+                        None => quote!{}
+                    };
+                    quote!{
+                        if !pchain_sdk::transaction::is_internal_call() #code_helper_check {
+                            panic!("Method {} can only be called by an internal call.", stringify!(#fn_name));
+                        }
+                    }
+                } else {
+                    quote!{}
+                };
+
+                // define guard for the range of attached amount
+                let code_min_value = options.min_value.map(|min_value| quote!{
+                    if pchain_sdk::transaction::amount() < #min_value {
+                        panic!("Method {} requires an attached amount of at least {}.", stringify!(#fn_name), #min_value);
+                    }
+                });
+                let code_max_value = options.max_value.map(|max_value| quote!{
+                    if pchain_sdk::transaction::amount() > #max_value {
+                        panic!("Method {} accepts an attached amount of at most {}.", stringify!(#fn_name), #max_value);
+                    }
+                });
+
+                // define guards for the size of arguments, which are checked before the contract is loaded and the arguments 
+                // are deserialized
+                let code_max_args_bytes = options.max_args_bytes.map(|max_args_bytes| quote!{
+                    if ctx.arguments.len() as u64 > #max_args_bytes {
+                        panic!("Method {} accepts arguments of at most {} bytes.", stringify!(#fn_name), #max_args_bytes);
+                    }
+                });
+                let code_max_len = match options.max_len {
+                    Some(max_len) => {
+                        let collection_args = collection_arguments(e);
+                        quote!{
+                            #(
+                                if let Some(len) = pchain_sdk::ContractMethodInput::argument_len(&multi_args, #collection_args) {
+                                    if len > #max_len {
+                                        panic!("Argument {} of method {} has {} items, but at most {} are allowed.", #collection_args, stringify!(#fn_name), len, #max_len);
+                                    }
+                                }
+                            )*
+                        }
+                    },
+                    None => quote!{}
+                };
+
+                // fields loaded for the method, or None to load the whole contract
+                let loaded_fields = match loaded_fields(e, &invariants, invariant_fields.as_deref()) {
+                    Ok(fields) => fields,
+                    Err(err) => return Some(err.to_compile_error())
+                };
+
                 // define load storage
+                let field_names: Vec<String> = loaded_fields.iter().flatten().map(|field| field.to_string()).collect();
+                let code_load_contract = match &loaded_fields {
+                    // the fields are accessed in a closure that is never called, so that unknown names do not compile
+                    Some(fields) => quote!{{
+                        #[allow(unused_imports)]
+                        use pchain_sdk::storage::__AllFields;
+                        let _ = |contract: &#impl_name| { #(let _ = &contract.#fields;)* };
+                        #impl_name::__load_fields(&pchain_sdk::StoragePath::new(), &[#(#field_names),*])
+                    }},
+                    None => quote!{ #impl_name::__load_storage(&pchain_sdk::StoragePath::new()) }
+                };
                 let code_load_storage = if e.is_mutable() {
-                    quote!{let mut contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new());}
+                    quote!{let mut contract = #code_load_contract;}
                 } else if e.is_immutable() {
-                    quote!{let contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new());}
+                    quote!{let contract = #code_load_contract;}
                 } else {
                     quote!{}
                 };
+                let code_save_contract = match &loaded_fields {
+                    Some(_) => quote!{{
+                        #[allow(unused_imports)]
+                        use pchain_sdk::storage::__AllFields;
+                        contract.__save_fields(&pchain_sdk::StoragePath::new(), &[#(#field_names),*]);
+                    }},
+                    None => quote!{ contract.__save_storage(&pchain_sdk::StoragePath::new()); }
+                };
 
                 // create method body based input arguments
                 let has_typed_args = e.sig.inputs.iter().any(|f| matches!(f, syn::FnArg::Typed(_)));
@@ -265,16 +789,31 @@ fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl) -> Option<proc_m
                     quote!{contract.#fn_name(#(#pass_args,)*);}
                 };
 
-                // define save storage
-                let code_save_storage = if e.is_mutable() {
-                    quote!{contract.__save_storage(&pchain_sdk::StoragePath::new());}
+                // define save storage after checking the invariants, which is skipped if the method returns an error, 
+                // e.g. by `?` after changing some fields
+                let returns_result = matches!(&e.sig.output, syn::ReturnType::Type(_, ty) if is_result_type(ty));
+                let code_check_invariants = generate_invariant_checks(&invariants, quote!{ stringify!(#fn_name) });
+                let code_save_storage = if e.is_mutable() && returns_result {
+                    quote!{
+                        if ret_cb.is_ok() {
+                            #code_check_invariants
+                            #code_save_contract
+                        }
+                    }
+                } else if e.is_mutable() {
+                    quote!{
+                        #code_check_invariants
+                        #code_save_contract
+                    }
                 } else {
                     quote!{}
                 };
 
                 // define return method
                 let code_return_cb = 
-                if has_return_value {
+                if returns_result {
+                    quote!{pchain_sdk::ContractMethodOutput::set_result(stringify!(#fn_name), &ret_cb)}
+                } else if has_return_value {
                     quote!{pchain_sdk::ContractMethodOutput::set(&ret_cb)}
                 } else {
                     quote!{pchain_sdk::ContractMethodOutput::default()}
@@ -282,8 +821,13 @@ fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl) -> Option<proc_m
 
                 Some(quote!{
                     stringify!(#fn_name) => {
-                        #code_load_storage
+                        #code_guard
+                        #code_min_value
+                        #code_max_value
+                        #code_max_args_bytes
                         #code_init_multiple_args
+                        #code_max_len
+                        #code_load_storage
                         #code_parse_args
                         #code_return_handle
                         #code_call_function
@@ -296,31 +840,927 @@ fn generate_contract_methods(impl_name :&Ident, ipl: &ItemImpl) -> Option<proc_m
         }
     });
 
-    // Skeleton - contract entrypoint
-    Some(quote!{
-        #[no_mangle]
-        pub extern "C" fn entrypoint() {
-            // Parse contract input
-            let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
-            // Enter function selector
-            let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
-                #(#code_function_selection)*
-                _=>{ unimplemented!() }
+    // With multicall, the methods with attribute `call` are selected in a function that is shared by the entrypoint 
+    // and multicall. A panic or an error returned by any call of multicall aborts the whole transaction.
+    let (code_function_selection, code_dispatch, code_multicall) = if options.multicall {
+        let code_dispatch_arm = (!call_method_names.is_empty()).then(|| quote!{
+            #(#call_method_names)|* => dispatch(&ctx),
+        });
+        let code_dispatch = quote!{
+            fn dispatch(ctx: &pchain_sdk::ContractMethodInput) -> pchain_sdk::ContractMethodOutput {
+                match ctx.method_name.as_str() {
+                    #(#code_function_selection)*
+                    _=>{ panic!("Method {:?} cannot be called by multicall.", ctx.method_name) }
+                }
+            }
+        };
+        let code_multicall = quote!{
+            "multicall" => {
+                // every call would see the attached amount
+                if pchain_sdk::transaction::amount() > 0 {
+                    panic!("Method multicall does not accept an attached amount.");
+                }
+                let multi_args = ctx.get_multiple_arguments();
+                let calls: Vec<pchain_sdk::method::EncodedCall> = pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0);
+                let results: Vec<pchain_sdk::method::EncodedResult> = calls.into_iter().enumerate().map(|(index, call)| {
+                    let ctx = pchain_sdk::ContractMethodInput { method_name: call.method_name, arguments: call.arguments };
+                    #code_before_call
+                    let result = dispatch(&ctx);
+                    if result.is_error() {
+                        panic!("Call {} of multicall to method {} returned an error.", index, ctx.method_name);
+                    }
+                    #code_after_call
+                    pchain_sdk::method::EncodedResult { return_value: result.get() }
+                }).collect();
+                pchain_sdk::ContractMethodOutput::set(&results)
+            }
+        };
+        (quote!{ #code_dispatch_arm }, code_dispatch, code_multicall)
+    } else {
+        (quote!{ #(#code_function_selection)* }, quote!{}, quote!{})
+    };
+
+    // Return value, which is not enveloped for the method list so that it can be read without knowing the contract
+    let code_return = if return_envelope {
+        quote!{
+            let return_value = if ctx.method_name.as_str() == pchain_sdk::method::METHODS_METHOD {
+                callresult.get()
+            } else {
+                callresult.to_envelope()
             };
-            // Return
+            if let Some(return_value) = return_value {
+                pchain_sdk::return_value(return_value);
+            }
+        }
+    } else {
+        quote!{
             if let Some(return_value) = callresult.get() {
                 pchain_sdk::return_value(return_value);
             } 
         }
+    };
+
+    // With metrics, a log of the call is emitted after the method returns. The ids of the methods of the contract and 
+    // of its standard are computed here, and other method names, e.g. of the fallback, are hashed at runtime.
+    let (code_metrics_enter, code_metrics_emit) = if options.metrics {
+        let mut method_names = defined_method_names(ipl);
+        for method in standard_methods(options.standard.unwrap_or_default()) {
+            if !method_names.iter().any(|name| name == method.name) {
+                method_names.push(method.name.to_string());
+            }
+        }
+        let method_ids = method_names.iter().map(|name| {
+            let hash = pchain_types::cryptography::sha256(name.as_bytes());
+            u32::from_be_bytes(hash[..4].try_into().unwrap())
+        });
+        (
+            quote!{ let gas_at_entry = pchain_sdk::metrics::__enter(); },
+            quote!{
+                let method_id: u32 = match ctx.method_name.as_str() {
+                    #(#method_names => #method_ids,)*
+                    method_name => pchain_sdk::metrics::method_id(method_name)
+                };
+                pchain_sdk::metrics::__emit(method_id, gas_at_entry);
+            }
+        )
+    } else {
+        (quote!{}, quote!{})
+    };
+
+    // The methods of the standards save the contract field themselves, so the invariants are checked on the contract
+    // loaded again after a method that is not a view
+    let code_standard_invariants = if invariants.is_empty() {
+        quote!{}
+    } else {
+        let code_load_contract = match &invariant_fields {
+            Some(fields) => {
+                let field_names = fields.iter().map(|field| field.to_string());
+                quote!{{
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    #impl_name::__load_fields(&pchain_sdk::StoragePath::new(), &[#(#field_names),*])
+                }}
+            },
+            None => quote!{ #impl_name::__load_storage(&pchain_sdk::StoragePath::new()) }
+        };
+        let code_check_invariants = generate_invariant_checks(&invariants, quote!{ ctx.method_name });
+        quote!{
+            if #impl_name::__fungible_token_methods().into_iter().chain(#impl_name::__nft_methods()).any(|info| info.name == ctx.method_name && !info.view) {
+                let contract = #code_load_contract;
+                #code_check_invariants
+            }
+        }
+    };
+
+    // Calls are rejected before they are parsed once the contract is decommissioned
+    let code_require_active = options.decommissionable.then(|| quote!{
+        pchain_sdk::lifecycle::__require_active();
+    });
+
+    // Skeleton - contract entrypoint
+    let code_entrypoint = quote!{
+        pchain_sdk::panic_handler::__install_default();
+        #code_require_active
+        #code_dispatch
+        // Parse contract input
+        let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+        #code_metrics_enter
+        #code_before_call
+        // Enter function selector
+        let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+            #code_method_list
+            #code_on_receive
+            #code_multicall
+            #code_function_selection
+            #code_views
+            #code_config
+            // methods of the standards derived by the contract struct, which the methods of the contract override
+            _ => {
+                #[allow(unused_imports)]
+                use pchain_sdk::standards::__NoStandards;
+                match #impl_name::__fungible_token(&ctx).or_else(|| #impl_name::__nft(&ctx)) {
+                    Some(callresult) => {
+                        #code_standard_invariants
+                        callresult
+                    },
+                    None => match ctx.method_name.as_str() {
+                        #code_fallback
+                        _=>{ unimplemented!("Method {:?} is not defined.", ctx.method_name) }
+                    }
+                }
+            }
+        };
+        #code_after_call
+        #code_metrics_emit
+        // Return
+        #code_return
+    };
+
+    // The standard derived by the contract struct must be declared, so that its methods are described above
+    let declared = options.standard.unwrap_or_default();
+    let message = match options.standard {
+        Some(standard) => format!("{} must derive the standard declared by the option {} of contract_methods.", impl_name, standard),
+        None => format!("The standard derived by {} must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)].", impl_name),
+    };
+    let code_check_standard = quote!{
+        const _: () = {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            assert!(pchain_sdk::standards::__is_declared(#impl_name::__STANDARD, #declared), #message);
+        };
+    };
+
+    // On native targets, the entrypoint delegates to a Rust function so that a panic unwinds to the caller, e.g.
+    // `pchain_sdk::testing::TestEnv`, instead of aborting at the boundary of `extern "C"`.
+    Some(quote!{
+        #(#[doc = #doc_lines])*
+        #[cfg_attr(target_arch = "wasm32", no_mangle)]
+        pub extern "C" fn entrypoint() {
+            #[cfg(target_arch = "wasm32")]
+            {
+                #code_entrypoint
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            __entrypoint();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        #[doc(hidden)]
+        pub fn __entrypoint() {
+            #code_entrypoint
+        }
+
+        #code_check_standard
+
+        /// ABI of this contract in JSON, generated by `#[contract_methods]`.
+        #[allow(non_upper_case_globals)]
+        pub static __contract_abi__: &str = #abi;
+
+        #[cfg(target_arch = "wasm32")]
+        #[link_section = "pchain_abi"]
+        #[used]
+        static __PCHAIN_CONTRACT_ABI: [u8; #abi_len] = *#abi_bytes;
     })
 }
 
+/// `invariant_methods` returns the names of the methods with attribute `invariant`, in order of declaration. An 
+/// invariant takes `&self` and returns bool, e.g. `fn check(&self) -> bool`, and is not callable.
+fn invariant_methods(ipl: &ItemImpl) -> syn::Result<Vec<Ident>> {
+    let mut invariants = vec![];
+    for item in ipl.items.iter() {
+        let e = match item {
+            syn::ImplItem::Method(e) if e.is_invariant() => e,
+            _ => continue
+        };
+        let returns_bool = matches!(&e.sig.output, syn::ReturnType::Type(_, ty) if token_string(ty) == "bool");
+        if !e.is_immutable() || e.sig.inputs.len() != 1 || !returns_bool {
+            return Err(syn::Error::new(e.sig.span(), "Method with invariant should take &self and return bool, e.g. fn check(&self) -> bool"));
+        }
+        if e.is_contract_method() || e.is_view() || e.is_on_receive() || e.is_fallback() {
+            return Err(syn::Error::new(e.sig.span(), "Method with invariant cannot have call, view, on_receive or fallback"));
+        }
+        invariants.push(e.sig.ident.clone());
+    }
+    Ok(invariants)
+}
+
+/// `generate_invariant_checks` generates the checks of the invariants on the loaded `contract`, which panic with the 
+/// name of the violated invariant and of the method, given by the expression `method_name` of type `&str`.
+fn generate_invariant_checks(invariants: &[Ident], method_name: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote!{
+        #(
+            if !contract.#invariants() {
+                panic!("Invariant {} is violated by {}.", stringify!(#invariants), #method_name);
+            }
+        )*
+    }
+}
+
+/// `loaded_fields` returns the names of the fields of the contract that are loaded for a method with attribute `call` and
+/// a receiver, or None if the whole contract is loaded. The fields are those listed by the attribute `fields`, e.g.
+/// `#[fields(balances, owner)]`, or otherwise those accessed by the method as found by [accessed_fields]. A method
+/// with receiver `&mut self` also loads the fields accessed by the invariants. It is an error if the attribute does
+/// not list a field which [accessed_fields] finds in the method, or if the method uses `self` in a way which
+/// [accessed_fields] cannot follow.
+fn loaded_fields(e: &ImplItemMethod, invariants: &[Ident], invariant_fields: Option<&[Ident]>) -> syn::Result<Option<Vec<Ident>>> {
+    let fields = match e.attrs.iter().find(|attr| attr.path.is_ident("fields")) {
+        Some(attr) => {
+            let error = || syn::Error::new(attr.span(), "Expect fields to be a list of the names of the fields, e.g. #[fields(balances, owner)]");
+            let nested = match attr.parse_meta() {
+                Ok(syn::Meta::List(list)) => list.nested,
+                _ => return Err(error())
+            };
+            let mut fields = vec![];
+            for nested in nested.iter() {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.get_ident().is_some() => fields.push(path.get_ident().unwrap().clone()),
+                    _ => return Err(error())
+                }
+            }
+            // the fields reached through `self` in any other way cannot be checked against the list
+            let accessed = match accessed_fields(&e.block) {
+                Some(accessed) => accessed,
+                None => return Err(syn::Error::new(attr.span(), "Attribute fields cannot be used on a method that uses self other than as self.field, e.g. calls another method of the contract"))
+            };
+            // fields accessed directly in the body are not loaded unless listed
+            if let Some(field) = accessed.into_iter().find(|field| !fields.contains(field)) {
+                return Err(syn::Error::new(field.span(), format!("Field {} is accessed by the method but not listed in attribute fields", field)));
+            }
+            Some(fields)
+        },
+        None => accessed_fields(&e.block)
+    };
+    if !e.is_mutable() || invariants.is_empty() {
+        return Ok(fields)
+    }
+    Ok(fields.zip(invariant_fields).map(|(mut fields, invariant_fields)| {
+        for field in invariant_fields {
+            if !fields.contains(field) {
+                fields.push(field.clone());
+            }
+        }
+        fields
+    }))
+}
+
+/// `accessed_fields` returns the names of the fields accessed by the block as `self.<field>`, including in the 
+/// arguments of macros, or None if it uses `self` in any other way, e.g. calls a method of the contract or passes 
+/// `self` to a function, which may access any field.
+fn accessed_fields(block: &syn::Block) -> Option<Vec<Ident>> {
+    fn scan(tokens: proc_macro2::TokenStream, fields: &mut Vec<Ident>) -> bool {
+        use proc_macro2::{Delimiter, TokenTree};
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => if !scan(group.stream(), fields) {
+                    return false
+                },
+                TokenTree::Ident(ident) if ident == "self" => match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) {
+                    // a method call is followed by its arguments or a turbofish
+                    (_, _, Some(TokenTree::Group(group))) if group.delimiter() == Delimiter::Parenthesis => return false,
+                    (_, _, Some(TokenTree::Punct(punct))) if punct.as_char() == ':' => return false,
+                    (Some(TokenTree::Punct(dot)), Some(TokenTree::Ident(field)), _) if dot.as_char() == '.' => {
+                        if !fields.contains(field) {
+                            fields.push(field.clone());
+                        }
+                    },
+                    _ => return false
+                },
+                _ => {}
+            }
+        }
+        true
+    }
+    let mut fields = vec![];
+    scan(quote!{ #block }, &mut fields).then_some(fields)
+}
+
+/// Names of the hooks which the entrypoint invokes before and after each call, if the contract defines them.
+const CALL_HOOKS: [&str; 2] = ["__before_call", "__after_call"];
+
+/// `generate_call_hooks` generates the invocations of the hooks `__before_call` and `__after_call`, which are 
+/// associated functions of the contract taking the method name, e.g. `fn __before_call(method: &str)`. They are 
+/// invoked around every call, including views, plain transfers (with an empty method name) and each call of multicall, 
+/// except the method list `__methods`. A panic in a hook aborts the call.
+fn generate_call_hooks(impl_name :&Ident, ipl: &ItemImpl) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let mut code_hooks = vec![];
+    for hook in CALL_HOOKS {
+        let e = ipl.items.iter().find_map(|item| match item {
+            syn::ImplItem::Method(e) if e.sig.ident == hook => Some(e),
+            _ => None
+        });
+        let e = match e {
+            Some(e) => e,
+            None => {
+                code_hooks.push(quote!{});
+                continue
+            }
+        };
+        let is_associated_fn = e.is_associate()
+            && e.sig.inputs.len() == 1
+            && matches!(&e.sig.output, syn::ReturnType::Default);
+        if !is_associated_fn || e.is_contract_method() || e.is_view() || e.is_on_receive() || e.is_fallback() {
+            return Err(syn::Error::new(e.sig.span(), format!("Hook {} should be an associated function that takes the method name, i.e. fn {}(method: &str)", hook, hook)));
+        }
+        let fn_name = &e.sig.ident;
+        code_hooks.push(quote!{
+            if ctx.method_name.as_str() != pchain_sdk::method::METHODS_METHOD {
+                #impl_name::#fn_name(ctx.method_name.as_str());
+            }
+        });
+    }
+    let code_after_call = code_hooks.pop().unwrap();
+    let code_before_call = code_hooks.pop().unwrap();
+    Ok((code_before_call, code_after_call))
+}
+
+/// `generate_method_list` generates the arm of the function selector for the hidden method `__methods`, which returns 
+/// the callable methods as `Vec<pchain_sdk::method::MethodInfo>`, in order of declaration. `call_options` are the 
+/// options of the methods with attribute `call`, in the same order. The method `multicall` is listed after them if 
+/// `multicall` is true, the method `config` if the contract has `#[contract_config]` and `has_config` is true, and 
+/// the methods of the standards derived by the contract struct last.
+fn generate_method_list(impl_name :&Ident, ipl: &ItemImpl, call_options: &[CallOptions], has_config: bool, multicall: bool) -> proc_macro2::TokenStream {
+    let mut call_options = call_options.iter();
+    let code_method_infos = ipl.items.iter().filter_map(|item| {
+        let e = match item {
+            syn::ImplItem::Method(e) => e,
+            _ => return None
+        };
+        let typed_args = e.sig.inputs.iter().filter(|f| matches!(f, syn::FnArg::Typed(_))).count() as u32;
+        let (name, arity, payable, internal_only, view) = if e.is_contract_method() {
+            let options = call_options.next().unwrap();
+            (e.sig.ident.to_string(), typed_args, options.max_value != Some(0), options.internal_only, !e.is_mutable())
+        } else if e.is_view() {
+            (e.sig.ident.to_string(), typed_args.saturating_sub(1), true, false, true)
+        } else if e.is_on_receive() {
+            (String::new(), 0, true, false, !e.is_mutable())
+        } else {
+            return None
+        };
+        Some(quote!{
+            pchain_sdk::method::MethodInfo {
+                name: #name.to_string(),
+                arity: #arity,
+                payable: #payable,
+                internal_only: #internal_only,
+                view: #view,
+            }
+        })
+    });
+
+    let code_multicall_info = multicall.then(|| quote!{
+        methods.push(pchain_sdk::method::MethodInfo {
+            name: pchain_sdk::method::MULTICALL_METHOD.to_string(),
+            arity: 1,
+            payable: false,
+            internal_only: false,
+            view: false,
+        });
+    });
+
+    let code_config_info = if has_config {
+        quote!{
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if #impl_name::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+        }
+    } else {
+        quote!{}
+    };
+
+    // methods of the standards derived by the contract struct, except those overridden by the contract
+    let code_standard_infos = quote!{
+        #[allow(unused_imports)]
+        use pchain_sdk::standards::__NoStandards;
+        for info in #impl_name::__fungible_token_methods().into_iter().chain(#impl_name::__nft_methods()) {
+            if !methods.iter().any(|method| method.name == info.name) {
+                methods.push(info);
+            }
+        }
+    };
+
+    quote!{
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> = vec![#(#code_method_infos,)*];
+            #code_multicall_info
+            #code_config_info
+            #code_standard_infos
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+    }
+}
+
+/// `generate_interface_doc` generates the interface reference of the contract in markdown, which lists the callable 
+/// methods in order of declaration with their arguments, return types, payability and kind, and the first line of 
+/// their doc comments. `call_options` are the options of the methods with attribute `call`, in the same order. The 
+/// method `multicall` is listed after them if `multicall` is true, and the methods of the derived `standard` that the
+/// contract does not override last. A column of gas estimates is added if any method has one.
+fn generate_interface_doc(impl_name :&Ident, ipl: &ItemImpl, call_options: &[CallOptions], multicall: bool, standard: Option<&str>) -> String {
+    let has_gas_estimates = call_options.iter().any(|options| options.gas_estimate.is_some());
+    let mut call_options = call_options.iter();
+    let mut rows = vec![];
+    for item in ipl.items.iter() {
+        let e = match item {
+            syn::ImplItem::Method(e) => e,
+            _ => continue
+        };
+        let typed_args = |skip: usize| -> String {
+            e.sig.inputs.iter().filter_map(|arg| match arg {
+                syn::FnArg::Typed(pat_type) => Some(format!("{}: {}", token_string(&pat_type.pat), token_string(&pat_type.ty))),
+                syn::FnArg::Receiver(_) => None
+            }).skip(skip).collect::<Vec<String>>().join(", ")
+        };
+        let mut gas_estimate = None;
+        let (name, args, payable, kind) = if e.is_contract_method() {
+            let options = call_options.next().unwrap();
+            gas_estimate = options.gas_estimate;
+            let payable = match (options.min_value, options.max_value) {
+                (_, Some(0)) => "no".to_string(),
+                (None, None) => "yes".to_string(),
+                (min_value, max_value) => format!(
+                    "{} to {}",
+                    min_value.unwrap_or(0),
+                    max_value.map_or("any".to_string(), |max_value| max_value.to_string())
+                ),
+            };
+            let mut kind = if e.is_mutable() { "call".to_string() } else { "call (read-only)".to_string() };
+            if options.internal_only {
+                kind.push_str(", internal only");
+            }
+            (format!("`{}`", e.sig.ident), typed_args(0), payable, kind)
+        } else if e.is_view() {
+            (format!("`{}`", e.sig.ident), typed_args(1), "yes".to_string(), "view".to_string())
+        } else if e.is_on_receive() {
+            ("(plain transfer)".to_string(), String::new(), "yes".to_string(), "on_receive".to_string())
+        } else if e.is_fallback() {
+            ("(other methods)".to_string(), typed_args(0), "yes".to_string(), "fallback".to_string())
+        } else {
+            continue
+        };
+        let returns = match &e.sig.output {
+            syn::ReturnType::Default => "()".to_string(),
+            syn::ReturnType::Type(_, ty) => token_string(ty),
+        };
+        let description = e.attrs.iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .find_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. })) => Some(s.value().trim().to_string()),
+                _ => None
+            })
+            .unwrap_or_default();
+        let args = if args.is_empty() { args } else { format!("`{}`", args) };
+        let gas = if has_gas_estimates {
+            format!("{}|", gas_estimate.map_or(String::new(), |gas| gas.to_string()))
+        } else {
+            String::new()
+        };
+        rows.push(format!("|{}|{}|`{}`|{}|{}|{}{}|", name, args, returns, payable, kind, gas, description.replace('|', "\\|")));
+    }
+    if multicall {
+        let gas = if has_gas_estimates { "|" } else { "" };
+        rows.push(format!("|`multicall`|`calls: Vec<EncodedCall>`|`Vec<EncodedResult>`|no|call|{}Call methods of this contract in order, all or nothing.|", gas));
+    }
+    let defined_names = defined_method_names(ipl);
+    for method in standard_methods(standard.unwrap_or_default()) {
+        if defined_names.iter().any(|name| name == method.name) {
+            continue
+        }
+        let args = method.args.iter().map(|(name, ty)| format!("{}: {}", name, ty)).collect::<Vec<String>>().join(", ");
+        let args = if args.is_empty() { args } else { format!("`{}`", args) };
+        let kind = if method.mutable { "call" } else { "view" };
+        let gas = if has_gas_estimates { "|" } else { "" };
+        rows.push(format!(
+            "|`{}`|{}|`{}`|no|{} ({}){}|{}|",
+            method.name, args, method.returns.unwrap_or("()"), kind, standard.unwrap(), gas, method.description
+        ));
+    }
+
+    let mut doc = format!("Entrypoint of the contract `{}`. Callable methods:\n\n", impl_name);
+    if has_gas_estimates {
+        doc.push_str("|Method|Arguments|Return|Payable|Kind|Gas (estimate)|Description|\n|:---|:---|:---|:---|:---|:---|:---|\n");
+    } else {
+        doc.push_str("|Method|Arguments|Return|Payable|Kind|Description|\n|:---|:---|:---|:---|:---|:---|\n");
+    }
+    for row in rows {
+        doc.push_str(&row);
+        doc.push('\n');
+    }
+    doc
+}
+
+/// `generate_contract_abi` generates the ABI of the contract in JSON, which lists the methods that can be called by 
+/// name in order of declaration, with their arguments, return types (null if none), kind (`call`, `view` or 
+/// `multicall`), mutability and payability, e.g.
+/// 
+/// ```json
+/// {"contract":"MyContract","methods":[{"name":"deposit","kind":"call","mutable":true,"payable":true,"internal_only":false,
+/// "args":[{"name":"memo","type":"String"}],"returns":"u64"}],"on_receive":false,"fallback":false}
+/// ```
+/// 
+/// `call_options` are the options of the methods with attribute `call`, in the same order. The method `multicall` is 
+/// listed after them if `multicall` is true, and the methods of the derived `standard` that the contract does not 
+/// override last.
+fn generate_contract_abi(impl_name :&Ident, ipl: &ItemImpl, call_options: &[CallOptions], multicall: bool, standard: Option<&str>) -> String {
+    let mut call_options = call_options.iter();
+    let mut methods = vec![];
+    let mut has_on_receive = false;
+    let mut has_fallback = false;
+    for item in ipl.items.iter() {
+        let e = match item {
+            syn::ImplItem::Method(e) => e,
+            _ => continue
+        };
+        let args = |skip: usize| -> String {
+            e.sig.inputs.iter().filter_map(|arg| match arg {
+                syn::FnArg::Typed(pat_type) => {
+                    let name = match pat_type.pat.as_ref() {
+                        syn::Pat::Ident(pat) => pat.ident.to_string(),
+                        pat => token_string(pat),
+                    };
+                    Some(format!("{{\"name\":\"{}\",\"type\":\"{}\"}}", escape_json(&name), escape_json(&token_string(&pat_type.ty))))
+                },
+                syn::FnArg::Receiver(_) => None
+            }).skip(skip).collect::<Vec<String>>().join(",")
+        };
+        let (kind, mutable, payable, internal_only, args, gas_estimate) = if e.is_contract_method() {
+            let options = call_options.next().unwrap();
+            ("call", e.is_mutable(), options.max_value != Some(0), options.internal_only, args(0), options.gas_estimate)
+        } else if e.is_view() {
+            ("view", false, true, false, args(1), None)
+        } else {
+            has_on_receive |= e.is_on_receive();
+            has_fallback |= e.is_fallback();
+            continue
+        };
+        let returns = match &e.sig.output {
+            syn::ReturnType::Default => "null".to_string(),
+            syn::ReturnType::Type(_, ty) => format!("\"{}\"", escape_json(&token_string(ty))),
+        };
+        let gas_estimate = gas_estimate.map_or(String::new(), |gas| format!(",\"gas_estimate\":{}", gas));
+        methods.push(format!(
+            "{{\"name\":\"{}\",\"kind\":\"{}\",\"mutable\":{},\"payable\":{},\"internal_only\":{},\"args\":[{}],\"returns\":{}{}}}",
+            e.sig.ident, kind, mutable, payable, internal_only, args, returns, gas_estimate
+        ));
+    }
+    if multicall {
+        methods.push("{\"name\":\"multicall\",\"kind\":\"multicall\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"calls\",\"type\":\"Vec<EncodedCall>\"}],\"returns\":\"Vec<EncodedResult>\"}".to_string());
+    }
+    let defined_names = defined_method_names(ipl);
+    for method in standard_methods(standard.unwrap_or_default()) {
+        if defined_names.iter().any(|name| name == method.name) {
+            continue
+        }
+        let args = method.args.iter().map(|(name, ty)| format!("{{\"name\":\"{}\",\"type\":\"{}\"}}", name, ty)).collect::<Vec<String>>().join(",");
+        let returns = method.returns.map_or("null".to_string(), |returns| format!("\"{}\"", returns));
+        methods.push(format!(
+            "{{\"name\":\"{}\",\"kind\":\"{}\",\"mutable\":{},\"payable\":false,\"internal_only\":false,\"args\":[{}],\"returns\":{}}}",
+            method.name, if method.mutable { "call" } else { "view" }, method.mutable, args, returns
+        ));
+    }
+    format!(
+        "{{\"contract\":\"{}\",\"methods\":[{}],\"on_receive\":{},\"fallback\":{}}}",
+        impl_name, methods.join(","), has_on_receive, has_fallback
+    )
+}
+
+/// `defined_method_names` returns the names of the methods with attribute `call` or `view`, which override the methods 
+/// of a derived standard of the same names.
+fn defined_method_names(ipl: &ItemImpl) -> Vec<String> {
+    ipl.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(e) if e.is_contract_method() || e.is_view() => Some(e.sig.ident.to_string()),
+        _ => None
+    }).collect()
+}
+
+/// `write_out_file` writes the file generated for the option of `#[contract_methods]` to `<OUT_DIR>/<file_name>`, 
+/// e.g. the interface reference for `interface_doc`. `OUT_DIR` is set by cargo if the crate has a build script.
+fn write_out_file(impl_name :&Ident, option: &str, file_name: &str, contents: &str) -> syn::Result<()> {
+    let out_dir = std::env::var("OUT_DIR").map_err(|_| syn::Error::new(
+        impl_name.span(),
+        format!("{} requires OUT_DIR, which is set by cargo if the crate has a build script (build.rs).", option)
+    ))?;
+    let path = std::path::Path::new(&out_dir).join(file_name);
+    std::fs::write(&path, contents).map_err(|e| syn::Error::new(
+        impl_name.span(),
+        format!("Cannot write {} to {}: {}", file_name, path.display(), e)
+    ))
+}
+
+/// Check if the type is declared as `Result<T, E>`. Aliases of `Result` with fewer type arguments, e.g. 
+/// `std::io::Result<T>`, are not recognized.
+fn is_result_type(ty: &syn::Type) -> bool {
+    let segment = match ty {
+        syn::Type::Path(tp) => tp.path.segments.last(),
+        _ => None
+    };
+    match segment {
+        Some(segment) if segment.ident == "Result" => matches!(
+            &segment.arguments,
+            syn::PathArguments::AngleBracketed(args) if args.args.len() == 2
+        ),
+        _ => false
+    }
+}
+
+/// Indices of the arguments of the method whose types are collections by [is_collection_type], which are limited by 
+/// `max_len` of attribute `call`.
+fn collection_arguments(e: &ImplItemMethod) -> Vec<usize> {
+    e.sig.inputs.iter()
+        .filter_map(|f| match f {
+            syn::FnArg::Typed(arg) => Some(&arg.ty),
+            _ => None
+        })
+        .enumerate()
+        .filter_map(|(idx, ty)| is_collection_type(ty).then_some(idx))
+        .collect()
+}
+
+/// Check if the type is a collection that borsh serializes with a u32 length prefix, e.g. `Vec<T>` or `String`, by the 
+/// last segment of its path. Aliases of the collections are not recognized.
+fn is_collection_type(ty: &syn::Type) -> bool {
+    const COLLECTIONS: [&str; 7] = ["Vec", "VecDeque", "String", "BTreeMap", "BTreeSet", "HashMap", "HashSet"];
+    match ty {
+        syn::Type::Path(tp) => tp.path.segments.last().map_or(false, |segment| COLLECTIONS.iter().any(|c| segment.ident == c)),
+        _ => false
+    }
+}
+
+/// The source code of the tokens, without the spaces inserted by `to_string` around punctuation.
+fn token_string<T: quote::ToTokens>(tokens: &T) -> String {
+    let mut s = tokens.to_token_stream().to_string();
+    for (from, to) in [(" <", "<"), ("< ", "<"), (" >", ">"), (" ,", ","), ("& ", "&"), (" :: ", "::"), ("[ ", "["), (" ]", "]"), (" ;", ";"), ("( ", "("), (" )", ")")] {
+        s = s.replace(from, to);
+    }
+    s
+}
+
+/// `generate_config` generates the arm of the function selector for the method `config`, which returns the 
+/// configuration constants of `#[contract_config]` as `Vec<(String, String)>`. Without `#[contract_config]`, 
+/// the fallback `pchain_sdk::method::__NoContractConfig` returns None, and the guard of the arm lets the call fall 
+/// through to the fallback method or the error of an undefined method.
+fn generate_config(impl_name :&Ident) -> proc_macro2::TokenStream {
+    quote!{
+        "config" if {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            #impl_name::__config().is_some()
+        } => {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&#impl_name::__config().unwrap())
+        }
+    }
+}
+
+/// `generate_on_receive` generates the arm of the function selector for the method with attribute `on_receive`, 
+/// if any. The method is invoked when the contract is called with attached value and an empty method name. It takes 
+/// the attached amount as its only argument, and rejects the transfer by panicking. The invariants are checked after 
+/// it if it takes `&mut self`.
+fn generate_on_receive(impl_name :&Ident, ipl: &ItemImpl, invariants: &[Ident]) -> syn::Result<proc_macro2::TokenStream> {
+    let mut methods = ipl.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(e) if e.is_on_receive() => Some(e),
+        _ => None
+    });
+    let e = match methods.next() {
+        Some(e) => e,
+        None => return Ok(quote!{})
+    };
+    if let Some(other) = methods.next() {
+        return Err(syn::Error::new(other.sig.span(), "Only one method can be defined with on_receive"));
+    }
+    if e.is_contract_method() {
+        return Err(syn::Error::new(e.sig.span(), "Method with on_receive cannot be a contract method with call"));
+    }
+    if e.is_view() {
+        return Err(syn::Error::new(e.sig.span(), "Method with on_receive cannot be a view"));
+    }
+    let typed_args = e.sig.inputs.iter().filter(|f| matches!(f, syn::FnArg::Typed(_))).count();
+    if typed_args != 1 || !matches!(e.sig.output, syn::ReturnType::Default) {
+        return Err(syn::Error::new(e.sig.span(), "Method with on_receive should take the attached amount as the only argument and return nothing, e.g. fn receive(&mut self, amount: u64)"));
+    }
+
+    let fn_name = &e.sig.ident;
+    let code_check_invariants = generate_invariant_checks(invariants, quote!{ stringify!(#fn_name) });
+    let code_call_function = if e.is_mutable() {
+        quote!{
+            let mut contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new());
+            contract.#fn_name(pchain_sdk::transaction::amount());
+            #code_check_invariants
+            contract.__save_storage(&pchain_sdk::StoragePath::new());
+        }
+    } else if e.is_immutable() {
+        quote!{
+            let contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new());
+            contract.#fn_name(pchain_sdk::transaction::amount());
+        }
+    } else {
+        quote!{ #impl_name::#fn_name(pchain_sdk::transaction::amount()); }
+    };
+
+    Ok(quote!{
+        "" if pchain_sdk::transaction::amount() > 0 => {
+            #code_call_function
+            pchain_sdk::ContractMethodOutput::default()
+        }
+    })
+}
+
+/// `generate_fallback` generates the arm of the function selector for the method with attribute `fallback`, if any. 
+/// The method is invoked when the method name of the call is not the name of a method, including names that are not 
+/// valid UTF-8 or contain NUL, which are sanitized by `pchain_sdk::transaction::method` so that they never match. 
+/// It takes the raw bytes of the method name and the arguments of the call, and may return a value. Plain transfers, 
+/// i.e. calls with an empty method name, are not passed to it. The invariants are checked after it if it takes 
+/// `&mut self`.
+fn generate_fallback(impl_name :&Ident, ipl: &ItemImpl, invariants: &[Ident]) -> syn::Result<proc_macro2::TokenStream> {
+    let mut methods = ipl.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(e) if e.is_fallback() => Some(e),
+        _ => None
+    });
+    let e = match methods.next() {
+        Some(e) => e,
+        None => return Ok(quote!{})
+    };
+    if let Some(other) = methods.next() {
+        return Err(syn::Error::new(other.sig.span(), "Only one method can be defined with fallback"));
+    }
+    if e.is_contract_method() || e.is_view() || e.is_on_receive() {
+        return Err(syn::Error::new(e.sig.span(), "Method with fallback cannot have call, view or on_receive"));
+    }
+    let typed_args = e.sig.inputs.iter().filter(|f| matches!(f, syn::FnArg::Typed(_))).count();
+    if typed_args != 2 {
+        return Err(syn::Error::new(e.sig.span(), "Method with fallback should take the method name and the arguments as raw bytes, e.g. fn fallback(&mut self, method: Vec<u8>, arguments: Vec<u8>)"));
+    }
+
+    let fn_name = &e.sig.ident;
+    let pass_args = quote!{ pchain_sdk::transaction::method_bytes(), ctx.arguments.clone() };
+    let code_check_invariants = generate_invariant_checks(invariants, quote!{ stringify!(#fn_name) });
+    let (code_load_storage, code_call_function, code_save_storage) = if e.is_mutable() {
+        (
+            quote!{ let mut contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new()); },
+            quote!{ contract.#fn_name(#pass_args) },
+            quote!{
+                #code_check_invariants
+                contract.__save_storage(&pchain_sdk::StoragePath::new());
+            },
+        )
+    } else if e.is_immutable() {
+        (
+            quote!{ let contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new()); },
+            quote!{ contract.#fn_name(#pass_args) },
+            quote!{},
+        )
+    } else {
+        (quote!{}, quote!{ #impl_name::#fn_name(#pass_args) }, quote!{})
+    };
+    let code_return_cb = if matches!(&e.sig.output, syn::ReturnType::Default) {
+        quote!{
+            #code_call_function;
+            #code_save_storage
+            pchain_sdk::ContractMethodOutput::default()
+        }
+    } else {
+        quote!{
+            let ret_cb = #code_call_function;
+            #code_save_storage
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+    };
+
+    Ok(quote!{
+        _ if !ctx.method_name.is_empty() => {
+            #code_load_storage
+            #code_return_cb
+        }
+    })
+}
+
+/// `generate_views` generates the arms of the function selector for the methods with attribute `view`. A view takes 
+/// `pchain_sdk::ReadOnly<Self>` as its first argument instead of a receiver, followed by the arguments of the call. 
+/// The contract is loaded but never saved.
+fn generate_views(impl_name :&Ident, ipl: &ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    let mut code_views = vec![];
+    for item in ipl.items.iter() {
+        let e = match item {
+            syn::ImplItem::Method(e) if e.is_view() => e,
+            _ => continue
+        };
+        if e.is_contract_method() {
+            return Err(syn::Error::new(e.sig.span(), "Method with view cannot be a contract method with call"));
+        }
+        let takes_read_only = match e.sig.inputs.first() {
+            Some(syn::FnArg::Typed(arg)) => match &*arg.ty {
+                syn::Type::Path(tp) => tp.path.segments.last().map_or(false, |segment| segment.ident == "ReadOnly"),
+                _ => false
+            },
+            _ => false
+        };
+        if !takes_read_only {
+            return Err(syn::Error::new(e.sig.span(), "Method with view should take the contract as ReadOnly in the first argument instead of a receiver, e.g. fn balance(contract: ReadOnly<'_, Self>) -> u64"));
+        }
+
+        let fn_name = &e.sig.ident;
+        let call_args: Punctuated<FnArg, Comma> = e.sig.inputs.iter().skip(1).cloned().collect();
+        let code_init_multiple_args = if call_args.is_empty() {
+            quote!{}
+        } else {
+            quote!{ let multi_args = ctx.get_multiple_arguments(); }
+        };
+        let mut pass_args :Vec<proc_macro2::TokenStream> = vec![];
+        let code_parse_args = generate_let_arguments(&mut pass_args, &call_args);
+
+        let code_call_function = quote!{
+            #impl_name::#fn_name(pchain_sdk::ReadOnly::__new(&contract), #(#pass_args,)*)
+        };
+        let code_return_cb = if matches!(&e.sig.output, syn::ReturnType::Default) {
+            quote!{
+                #code_call_function;
+                pchain_sdk::ContractMethodOutput::default()
+            }
+        } else {
+            quote!{
+                let ret_cb = #code_call_function;
+                pchain_sdk::ContractMethodOutput::set(&ret_cb)
+            }
+        };
+
+        code_views.push(quote!{
+            stringify!(#fn_name) => {
+                let contract = #impl_name::__load_storage(&pchain_sdk::StoragePath::new());
+                #code_init_multiple_args
+                #code_parse_args
+                #code_return_cb
+            }
+        });
+    }
+    Ok(quote!{ #(#code_views)* })
+}
+
+/// Options of a contract method specified as arguments of attribute `call`. E.g.
+/// 
+/// ```no_run
+/// #[call(internal_only, helper = "Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+/// #[call(min_value = 1_000, max_value = 1_000_000)]
+/// #[call(gas_estimate = 120_000)]
+/// #[call(max_args_bytes = 64_000, max_len = 100)]
+/// ```
+#[derive(Default)]
+struct CallOptions {
+    /// The method can only be called by an internal call (`internal_only`).
+    internal_only: bool,
+    /// The account which can also call an internal-only method (`helper = "<base64url address>"`).
+    helper: Option<PublicAddress>,
+    /// The minimum amount attached to the call (`min_value = <u64>`).
+    min_value: Option<u64>,
+    /// The maximum amount attached to the call (`max_value = <u64>`).
+    max_value: Option<u64>,
+    /// The gas used by the call as measured by benchmarks, exported as an estimate (`gas_estimate = <u64>`).
+    gas_estimate: Option<u64>,
+    /// The maximum size of the serialized arguments of the call (`max_args_bytes = <u64>`).
+    max_args_bytes: Option<u64>,
+    /// The maximum number of items of each argument of a collection type (`max_len = <u32>`).
+    max_len: Option<u32>,
+}
+
 /// Trait for adding helper functions to method for checking information of a contract
 trait ContractMethodAnalysis {
     fn is_mutable(&self) -> bool;
     fn is_immutable(&self) -> bool;
     fn is_associate(&self) -> bool;
     fn is_contract_method(&self) -> bool;
+    fn is_on_receive(&self) -> bool;
+    fn is_view(&self) -> bool;
+    fn is_fallback(&self) -> bool;
+    fn is_invariant(&self) -> bool;
+    fn call_options(&self) -> syn::Result<CallOptions>;
 }
 
 /// Impl for EntrypointAnalysis explicitly to see if the methods match with design of a contract 
@@ -366,4 +1806,99 @@ impl ContractMethodAnalysis for ImplItemMethod {
         })
     }
 
+    fn is_on_receive(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("on_receive"))
+    }
+
+    fn is_view(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("view"))
+    }
+
+    fn is_fallback(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("fallback"))
+    }
+
+    fn is_invariant(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("invariant"))
+    }
+
+    fn call_options(&self) -> syn::Result<CallOptions> {
+        let mut options = CallOptions::default();
+        let attr = match self.attrs.iter().find(|attr| attr.path.is_ident("call")) {
+            Some(attr) => attr,
+            None => return Ok(options)
+        };
+        let nested = match attr.parse_meta()? {
+            syn::Meta::Path(_) => return Ok(options),
+            syn::Meta::List(list) => list.nested,
+            meta => return Err(syn::Error::new(meta.span(), "Expect arguments of call to be a list, e.g. #[call(internal_only)]"))
+        };
+        for arg in nested.iter() {
+            match arg {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("internal_only") => {
+                    options.internal_only = true;
+                },
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("helper") => {
+                    let address = match &nv.lit {
+                        syn::Lit::Str(s) => s.value(),
+                        _ => return Err(syn::Error::new(nv.lit.span(), "Only &str are allowed as helper address"))
+                    };
+                    let address: PublicAddress = match base64url::decode(&address).ok().and_then(|a| a.try_into().ok()) {
+                        Some(address) => address,
+                        None => return Err(syn::Error::new(nv.lit.span(), "Helper address cannot be decoded. Please ensure this address is base64 format with urlencoding."))
+                    };
+                    options.helper = Some(address);
+                },
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("min_value") || nv.path.is_ident("max_value") => {
+                    let value = match &nv.lit {
+                        syn::Lit::Int(i) => i.base10_parse::<u64>()?,
+                        _ => return Err(syn::Error::new(nv.lit.span(), "Only u64 are allowed as min_value and max_value"))
+                    };
+                    if nv.path.is_ident("min_value") {
+                        options.min_value = Some(value);
+                    } else {
+                        options.max_value = Some(value);
+                    }
+                },
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("gas_estimate") => {
+                    let gas = match &nv.lit {
+                        syn::Lit::Int(i) => i.base10_parse::<u64>()?,
+                        _ => return Err(syn::Error::new(nv.lit.span(), "Only u64 are allowed as gas_estimate"))
+                    };
+                    if gas == 0 {
+                        return Err(syn::Error::new(nv.lit.span(), "gas_estimate must be greater than 0"));
+                    }
+                    options.gas_estimate = Some(gas);
+                },
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("max_args_bytes") => {
+                    let max_args_bytes = match &nv.lit {
+                        syn::Lit::Int(i) => i.base10_parse::<u64>()?,
+                        _ => return Err(syn::Error::new(nv.lit.span(), "Only u64 are allowed as max_args_bytes"))
+                    };
+                    if max_args_bytes == 0 {
+                        return Err(syn::Error::new(nv.lit.span(), "max_args_bytes must be greater than 0"));
+                    }
+                    options.max_args_bytes = Some(max_args_bytes);
+                },
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("max_len") => {
+                    let max_len = match &nv.lit {
+                        syn::Lit::Int(i) => i.base10_parse::<u32>()?,
+                        _ => return Err(syn::Error::new(nv.lit.span(), "Only u32 are allowed as max_len"))
+                    };
+                    options.max_len = Some(max_len);
+                },
+                _ => return Err(syn::Error::new(arg.span(), "Unknown argument of call. Expect `internal_only`, `helper = \"<address>\"`, `min_value = <u64>`, `max_value = <u64>`, `gas_estimate = <u64>`, `max_args_bytes = <u64>` or `max_len = <u32>`."))
+            }
+        }
+        if options.helper.is_some() && !options.internal_only {
+            return Err(syn::Error::new_spanned(attr, "helper can only be specified for internal_only method"));
+        }
+        if let (Some(min_value), Some(max_value)) = (options.min_value, options.max_value) {
+            if min_value > max_value {
+                return Err(syn::Error::new_spanned(attr, "min_value cannot be greater than max_value"));
+            }
+        }
+        Ok(options)
+    }
+
 }
\ No newline at end of file
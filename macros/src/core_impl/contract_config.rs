@@ -0,0 +1,78 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Expr, ImplItem, ItemImpl, Lit};
+
+use super::escape_json;
+
+/// `generate_contract_config` generates code for an impl block of configuration constants with `#[contract_config]`:
+/// 1. the constant `CONTRACT_CONFIG` of the contract, which is the constants as a JSON object, e.g. `{"FEE":100}`, 
+/// 2. the custom section `pchain_config` of the WASM module, which contains the same JSON,
+/// 3. the hidden function `__config`, which is returned by the method `config` generated by `#[contract_methods]`.
+/// 
+/// The value of each constant must be a literal, so that it can be embedded at compile time.
+pub(crate) fn generate_contract_config(ipl: &ItemImpl) -> syn::Result<TokenStream> {
+    let impl_name = &ipl.self_ty;
+    let mut json_fields = vec![];
+    let mut entries = vec![];
+    for item in ipl.items.iter() {
+        let constant = match item {
+            ImplItem::Const(constant) => constant,
+            _ => return Err(syn::Error::new(item.span(), "#[contract_config] can only contain constants"))
+        };
+        let (value, json_value) = literal_value(&constant.expr)
+            .ok_or_else(|| syn::Error::new(constant.expr.span(), "Value of a configuration constant should be a literal"))?;
+        let name = constant.ident.to_string();
+        json_fields.push(format!("\"{}\":{}", name, json_value));
+        entries.push(quote!{ (#name.to_string(), #value.to_string()) });
+    }
+
+    let json = format!("{{{}}}", json_fields.join(","));
+    let json_len = json.len();
+    let json_bytes = proc_macro2::Literal::byte_string(json.as_bytes());
+
+    Ok(TokenStream::from(quote!{
+        #ipl
+
+        impl #impl_name {
+            /// Configuration constants of this contract in JSON, specified by `#[contract_config]`.
+            pub const CONTRACT_CONFIG: &'static str = #json;
+
+            #[doc(hidden)]
+            pub fn __config() -> Option<Vec<(String, String)>> {
+                Some(vec![#(#entries,)*])
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[link_section = "pchain_config"]
+        #[used]
+        static __PCHAIN_CONTRACT_CONFIG: [u8; #json_len] = *#json_bytes;
+    }))
+}
+
+/// The value of a literal as returned by the method `config`, and as a JSON value. Negative numbers are accepted.
+fn literal_value(expr: &Expr) -> Option<(String, String)> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => Some((s.value(), format!("\"{}\"", escape_json(&s.value())))),
+            Lit::Char(c) => Some((c.value().to_string(), format!("\"{}\"", escape_json(&c.value().to_string())))),
+            Lit::Int(i) => Some((i.base10_digits().to_string(), i.base10_digits().to_string())),
+            Lit::Float(f) => Some((f.base10_digits().to_string(), f.base10_digits().to_string())),
+            Lit::Bool(b) => Some((b.value.to_string(), b.value.to_string())),
+            _ => None
+        },
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => match literal_value(expr)? {
+            (value, json) if matches!(&**expr, Expr::Lit(syn::ExprLit { lit: Lit::Int(_) | Lit::Float(_), .. })) => {
+                Some((format!("-{}", value), format!("-{}", json)))
+            },
+            _ => None
+        },
+        Expr::Group(group) => literal_value(&group.expr),
+        _ => None
+    }
+}
@@ -0,0 +1,175 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use quote::quote;
+use snakecase::unicode::to_snakecase;
+use syn::{Attribute, Fields, Item};
+
+use super::type_to_string;
+
+/// Environment variable of the directory to which the types crate is written at build time. No crate is written
+/// if it is not set.
+const CONTRACT_TYPES_DIR_ENV: &str = "PCHAIN_CONTRACT_TYPES_DIR";
+
+/// Version of borsh which the types crate depends on. It is the same as the SDK's, so that the encoding is identical.
+const BORSH_VERSION: &str = "0.10.2";
+
+/// First line of the files written to the types crate, by which the files of removed items are recognized.
+const GENERATED_HEADER: &str = "// Generated by #[contract_types]";
+
+/// Whether the generated files in the directory were cleared by this compilation. Items of a crate are expanded in 
+/// the same compiler process, so only the first item clears the files written by the previous build.
+static CLEARED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Derives that are copied from the item to the types crate, since they only need the standard library.
+const STD_DERIVES: [&str; 9] = ["Clone", "Copy", "Debug", "Default", "PartialEq", "Eq", "PartialOrd", "Ord", "Hash"];
+
+/// `emit_contract_types` writes a struct or enum with `#[contract_types]` to the types crate of the contract in
+/// `<PCHAIN_CONTRACT_TYPES_DIR>`, if the environment variable is set. E.g.
+/// 
+/// ```text
+/// PCHAIN_CONTRACT_TYPES_DIR=../my-contract-types cargo build --target wasm32-unknown-unknown --release
+/// ```
+/// 
+/// The crate is named `<package>-types` after the package of the contract, and only depends on borsh. Each item
+/// is written to `src/<item>.rs` with its doc comments, the standard derives of the item, and `BorshSerialize` and
+/// `BorshDeserialize`. The item and its fields are public in the crate. `src/lib.rs` re-exports all items in the 
+/// directory, and defines `PublicAddress` as in the SDK. The files written by the previous build are removed first, 
+/// so that an item that is removed or renamed does not stay in the crate. Other files in `src` are kept.
+pub(crate) fn emit_contract_types(item: &Item) -> Result<(), String> {
+    let dir = match std::env::var(CONTRACT_TYPES_DIR_ENV) {
+        Ok(dir) if !dir.is_empty() => std::path::PathBuf::from(dir),
+        _ => return Ok(())
+    };
+
+    let (name, source) = match item {
+        Item::Struct(item) => (item.ident.to_string(), struct_to_string(item)),
+        Item::Enum(item) => (item.ident.to_string(), enum_to_string(item)),
+        _ => return Err("#[contract_types] can only be applied to struct or enum definitions.".to_string())
+    };
+
+    let package = std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "contract".to_string());
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.1.0".to_string());
+    let manifest = format!(
+        "[package]\nname = \"{}-types\"\nversion = \"{}\"\nedition = \"2021\"\n\n[dependencies]\nborsh = \"{}\"\n",
+        package, version, BORSH_VERSION
+    );
+    let module = to_snakecase(name.as_str());
+    let file = format!(
+        "{} from {}. Do not edit.\n\n#[allow(unused_imports)]\nuse crate::*;\nuse borsh::{{BorshSerialize, BorshDeserialize}};\n\n{}",
+        GENERATED_HEADER, package, source
+    );
+
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir)
+        .and_then(|_| clear_generated(&src_dir))
+        .and_then(|_| std::fs::write(dir.join("Cargo.toml"), manifest))
+        .and_then(|_| std::fs::write(src_dir.join(format!("{}.rs", module)), file))
+        .and_then(|_| write_lib(&src_dir, &package))
+        .map_err(|e| format!("Cannot write contract types of {} to {}: {}", name, dir.display(), e))
+}
+
+/// Remove the files written by the previous build, once per compilation.
+fn clear_generated(src_dir: &std::path::Path) -> std::io::Result<()> {
+    if CLEARED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return Ok(())
+    }
+    for entry in std::fs::read_dir(src_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "rs") && std::fs::read_to_string(&path)?.starts_with(GENERATED_HEADER) {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `lib.rs`, which re-exports the items of all modules in the directory. It is rewritten for each item, since
+/// each item is written by a separate expansion of the macro.
+fn write_lib(src_dir: &std::path::Path, package: &str) -> std::io::Result<()> {
+    let mut modules: Vec<String> = std::fs::read_dir(src_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().and_then(|f| f.strip_suffix(".rs")).map(|f| f.to_string()))
+        .filter(|module| module != "lib")
+        .collect();
+    modules.sort();
+
+    let mut lib = format!("{}. Do not edit.\n//! Types of the arguments, return values and events of {}.\n\n", GENERATED_HEADER, package);
+    lib.push_str("/// Address of an account, as defined in the SDK.\npub type PublicAddress = [u8; 32];\n");
+    for module in modules {
+        lib.push_str(&format!("\nmod {};\npub use {}::*;\n", module, module));
+    }
+    std::fs::write(src_dir.join("lib.rs"), lib)
+}
+
+fn struct_to_string(item: &syn::ItemStruct) -> String {
+    let generics = &item.generics;
+    let header = format!("{}pub struct {}{}", attrs_to_string(&item.attrs, ""), item.ident, type_to_string(&quote!(#generics).to_string()));
+    match &item.fields {
+        Fields::Named(_) => format!("{} {{\n{}}}\n", header, fields_to_string(&item.fields, "    ", "pub ")),
+        Fields::Unnamed(_) => format!("{}({});\n", header, fields_to_string(&item.fields, "", "pub ")),
+        Fields::Unit => format!("{};\n", header),
+    }
+}
+
+fn enum_to_string(item: &syn::ItemEnum) -> String {
+    let generics = &item.generics;
+    let mut source = format!("{}pub enum {}{} {{\n", attrs_to_string(&item.attrs, ""), item.ident, type_to_string(&quote!(#generics).to_string()));
+    for variant in item.variants.iter() {
+        source.push_str(&docs_to_string(&variant.attrs, "    "));
+        match &variant.fields {
+            Fields::Named(_) => source.push_str(&format!("    {} {{\n{}    }},\n", variant.ident, fields_to_string(&variant.fields, "        ", ""))),
+            Fields::Unnamed(_) => source.push_str(&format!("    {}({}),\n", variant.ident, fields_to_string(&variant.fields, "", ""))),
+            Fields::Unit => source.push_str(&format!("    {},\n", variant.ident)),
+        }
+    }
+    source.push_str("}\n");
+    source
+}
+
+/// Named fields on separate lines with the indent, or unnamed fields separated by commas if the indent is empty. 
+/// Fields of a struct are public, and fields of an enum variant are public by definition.
+fn fields_to_string(fields: &Fields, indent: &str, vis: &str) -> String {
+    let fields = fields.iter().map(|f| {
+        let ty = &f.ty;
+        let ty = type_to_string(&quote!(#ty).to_string());
+        match &f.ident {
+            Some(ident) => format!("{}{}{}{}: {},\n", docs_to_string(&f.attrs, indent), indent, vis, ident, ty),
+            None => format!("{}{}", vis, ty),
+        }
+    });
+    if indent.is_empty() {
+        fields.collect::<Vec<String>>().join(", ")
+    } else {
+        fields.collect()
+    }
+}
+
+/// Doc comments and derives of the item.
+fn attrs_to_string(attrs: &[Attribute], indent: &str) -> String {
+    let mut derives = vec!["BorshSerialize".to_string(), "BorshDeserialize".to_string()];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("derive")) {
+        if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested.iter() {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = nested {
+                    let derive = path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+                    if STD_DERIVES.contains(&derive.as_str()) && !derives.contains(&derive) {
+                        derives.push(derive);
+                    }
+                }
+            }
+        }
+    }
+    format!("{}{}#[derive({})]\n", docs_to_string(attrs, indent), indent, derives.join(", "))
+}
+
+fn docs_to_string(attrs: &[Attribute], indent: &str) -> String {
+    attrs.iter().filter_map(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::NameValue(nv)) if nv.path.is_ident("doc") => match nv.lit {
+            syn::Lit::Str(s) => Some(format!("{}///{}\n", indent, s.value())),
+            _ => None
+        },
+        _ => None
+    }).collect()
+}
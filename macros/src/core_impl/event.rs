@@ -0,0 +1,214 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Ident, ItemStruct, NestedMeta, Path};
+
+use super::escape_json;
+
+/// Options of the macro `event`, e.g. `#[event(name = "Transfer", version = 2, previous(TransferV1))]`.
+pub(crate) struct EventOptions {
+    name: Option<String>,
+    version: u32,
+    previous: Option<Path>,
+}
+
+pub(crate) fn parse_event_options(attr_args: &[NestedMeta]) -> syn::Result<EventOptions> {
+    let mut options = EventOptions { name: None, version: 1, previous: None };
+    for arg in attr_args {
+        match arg {
+            NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { path, lit: syn::Lit::Str(s), .. })) if path.is_ident("name") => {
+                options.name = Some(s.value());
+            },
+            NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { path, lit: syn::Lit::Int(i), .. })) if path.is_ident("version") => {
+                options.version = match i.base10_parse::<u32>() {
+                    Ok(version) if version > 0 => version,
+                    _ => return Err(syn::Error::new(i.span(), "Expect version of event to be a positive integer"))
+                };
+            },
+            NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("previous") && list.nested.len() == 1 => {
+                match list.nested.first() {
+                    Some(NestedMeta::Meta(syn::Meta::Path(path))) => options.previous = Some(path.clone()),
+                    _ => return Err(syn::Error::new(list.span(), "Expect previous version of event to be previous(StructName)"))
+                }
+            },
+            _ => return Err(syn::Error::new(arg.span(), "Expect arguments of event to be name = \"..\", version = N or previous(StructName)"))
+        }
+    }
+    if options.previous.is_some() && options.version == 1 {
+        return Err(syn::Error::new(proc_macro2::Span::call_site(), "Event of version 1 cannot have a previous version"))
+    }
+    Ok(options)
+}
+
+/// `generate_event` implements `pchain_sdk::event::Event` and Borsh serialization for a struct with named fields,
+/// and embeds the schema of the event in the custom section `pchain_events` of the WASM module. The previous version,
+/// if specified, must implement `Event` with the same name, and be convertible into the struct by `From`.
+pub(crate) fn generate_event(item: &ItemStruct, options: &EventOptions) -> syn::Result<TokenStream> {
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => return Err(syn::Error::new(item.ident.span(), "Events should be structs with named fields."))
+    };
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new(item.generics.span(), "Events cannot be generic."))
+    }
+    let ident = &item.ident;
+    let field_names: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+
+    let name = options.name.clone().unwrap_or_else(|| ident.to_string());
+    let version = options.version;
+    let topic = match version {
+        1 => name.clone(),
+        _ => format!("{}@{}", name, version),
+    };
+    let schema_fields: Vec<String> = fields.iter()
+        .map(|f| format!("[\"{}\",\"{}\"]", f.ident.as_ref().unwrap(), escape_json(&f.ty.to_token_stream().to_string())))
+        .collect();
+    let schema = format!(
+        "{{\"name\":\"{}\",\"version\":{},\"topic\":\"{}\",\"fields\":[{}]}}",
+        escape_json(&name), version, escape_json(&topic), schema_fields.join(",")
+    );
+    let topic = proc_macro2::Literal::byte_string(topic.as_bytes());
+
+    let code_decode_previous = options.previous.as_ref().map(|previous| quote!{
+        fn decode_previous(log: &pchain_sdk::types::Log) -> Option<Self> {
+            const _: () = assert!(
+                <#previous as pchain_sdk::event::Event>::VERSION < #version,
+                "previous version of event must have a lower version"
+            );
+            <#previous as pchain_sdk::event::Event>::decode(log).map(Self::from)
+        }
+    });
+    let code_borsh = generate_borsh_impls(ident, &field_names);
+    let code_schema_section = generate_schema_section(&schema);
+
+    Ok(TokenStream::from(quote!{
+        #item
+
+        impl pchain_sdk::event::Event for #ident {
+            const NAME: &'static str = #name;
+            const VERSION: u32 = #version;
+            const TOPIC: &'static [u8] = #topic;
+            const SCHEMA: &'static str = #schema;
+            #code_decode_previous
+        }
+
+        #code_borsh
+
+        #code_schema_section
+    }))
+}
+
+/// `generate_event_derive` implements `pchain_sdk::event::Event` and Borsh serialization for a struct with named 
+/// fields by `#[derive(Event)]`. The topic is the SHA256 hash of the name of the event, which is the name of the 
+/// struct unless specified by `#[topic = ".."]`. The fields marked `#[indexed]` are appended to the topic of the log 
+/// in order of declaration, Borsh-serialized, so that indexers can filter the logs by their values.
+pub(crate) fn generate_event_derive(item: &ItemStruct) -> syn::Result<TokenStream> {
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => return Err(syn::Error::new(item.ident.span(), "Events should be structs with named fields."))
+    };
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new(item.generics.span(), "Events cannot be generic."))
+    }
+    let ident = &item.ident;
+    let field_names: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+
+    let mut name = ident.to_string();
+    for attr in item.attrs.iter().filter(|attr| attr.path.is_ident("topic")) {
+        match attr.parse_meta()? {
+            syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. }) => name = s.value(),
+            meta => return Err(syn::Error::new(meta.span(), "Expect topic of event to be #[topic = \"..\"]"))
+        }
+    }
+    let mut indexed = vec![];
+    for f in fields.iter() {
+        if let Some(attr) = f.attrs.iter().find(|attr| attr.path.is_ident("indexed")) {
+            if !attr.tokens.is_empty() {
+                return Err(syn::Error::new(attr.span(), "Expect indexed field to be marked by #[indexed] without arguments"))
+            }
+            indexed.push(f.ident.as_ref().unwrap());
+        }
+    }
+
+    let topic = pchain_types::cryptography::sha256(name.as_bytes());
+    let topic_hex: String = topic.iter().map(|b| format!("{:02x}", b)).collect();
+    let schema_fields: Vec<String> = fields.iter()
+        .map(|f| format!("[\"{}\",\"{}\"]", f.ident.as_ref().unwrap(), escape_json(&f.ty.to_token_stream().to_string())))
+        .collect();
+    let schema_indexed: Vec<String> = indexed.iter().map(|f| format!("\"{}\"", f)).collect();
+    let schema = format!(
+        "{{\"name\":\"{}\",\"version\":1,\"topic_sha256\":\"{}\",\"indexed\":[{}],\"fields\":[{}]}}",
+        escape_json(&name), topic_hex, schema_indexed.join(","), schema_fields.join(",")
+    );
+    let indexed_names: Vec<String> = indexed.iter().map(|f| f.to_string()).collect();
+    let topic = proc_macro2::Literal::byte_string(&topic);
+
+    let code_topic = if indexed.is_empty() {
+        quote!{}
+    } else {
+        quote!{
+            fn topic(&self) -> Vec<u8> {
+                let mut topic = Self::TOPIC.to_vec();
+                #(pchain_sdk::borsh::BorshSerialize::serialize(&self.#indexed, &mut topic).unwrap();)*
+                topic
+            }
+        }
+    };
+    let code_borsh = generate_borsh_impls(ident, &field_names);
+    let code_schema_section = generate_schema_section(&schema);
+
+    Ok(TokenStream::from(quote!{
+        impl pchain_sdk::event::Event for #ident {
+            const NAME: &'static str = #name;
+            const VERSION: u32 = 1;
+            const TOPIC: &'static [u8] = #topic;
+            const SCHEMA: &'static str = #schema;
+            const INDEXED: &'static [&'static str] = &[#(#indexed_names),*];
+            #code_topic
+        }
+
+        #code_borsh
+
+        #code_schema_section
+    }))
+}
+
+/// Borsh serialization of an event, which serializes the fields in order of declaration.
+fn generate_borsh_impls(ident: &Ident, field_names: &[&Ident]) -> proc_macro2::TokenStream {
+    quote!{
+        impl pchain_sdk::borsh::BorshSerialize for #ident {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                #(pchain_sdk::borsh::BorshSerialize::serialize(&self.#field_names, writer)?;)*
+                Ok(())
+            }
+        }
+
+        impl pchain_sdk::borsh::BorshDeserialize for #ident {
+            fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+                Ok(Self {
+                    #(#field_names: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,)*
+                })
+            }
+        }
+    }
+}
+
+/// Embeds the schema of an event in the custom section `pchain_events` of the WASM module.
+fn generate_schema_section(schema: &str) -> proc_macro2::TokenStream {
+    // one object per line in the custom section
+    let schema_line = format!("{}\n", schema);
+    let schema_len = schema_line.len();
+    let schema_bytes = proc_macro2::Literal::byte_string(schema_line.as_bytes());
+    quote!{
+        const _: () = {
+            #[cfg(target_arch = "wasm32")]
+            #[link_section = "pchain_events"]
+            #[used]
+            static __PCHAIN_EVENT_SCHEMA: [u8; #schema_len] = *#schema_bytes;
+        };
+    }
+}
@@ -19,3 +19,27 @@ pub use contract::*;
 mod use_contract;
 #[allow(unused_imports)]
 pub use use_contract::*;
+
+mod storage_layout;
+#[allow(unused_imports)]
+pub use storage_layout::*;
+
+mod contract_types;
+#[allow(unused_imports)]
+pub use contract_types::*;
+
+mod contract_config;
+#[allow(unused_imports)]
+pub use contract_config::*;
+
+mod call_args;
+#[allow(unused_imports)]
+pub use call_args::*;
+
+mod event;
+#[allow(unused_imports)]
+pub use event::*;
+
+mod standards;
+#[allow(unused_imports)]
+pub use standards::*;
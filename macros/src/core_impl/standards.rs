@@ -0,0 +1,118 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Fields, ItemStruct};
+
+/// Modules of the token standards in `pchain_sdk::standards`, which are also the options of `#[contract_methods]` that
+/// declare the standard derived by the contract struct.
+pub(crate) const STANDARDS: [&str; 2] = ["fungible_token", "nft"];
+
+/// Types of the contract fields of the token standards, in the order of [STANDARDS].
+const STANDARD_FIELD_TYPES: [&str; 2] = ["FungibleToken", "NonFungibleToken"];
+
+/// A method of a token standard, as described in the ABI and the interface reference of a contract that adopts it.
+pub(crate) struct StandardMethod {
+    pub name: &'static str,
+    /// Names and types of the arguments.
+    pub args: &'static [(&'static str, &'static str)],
+    pub returns: Option<&'static str>,
+    /// The method changes the token, i.e. it is not a view.
+    pub mutable: bool,
+    pub description: &'static str,
+}
+
+/// Methods of `pchain_sdk::standards::fungible_token`, in the order of the table in its documentation.
+const FUNGIBLE_TOKEN_METHODS: [StandardMethod; 9] = [
+    StandardMethod { name: "name", args: &[], returns: Some("String"), mutable: false, description: "Name of the token." },
+    StandardMethod { name: "symbol", args: &[], returns: Some("String"), mutable: false, description: "Symbol of the token." },
+    StandardMethod { name: "decimals", args: &[], returns: Some("u8"), mutable: false, description: "Number of decimals of the amounts." },
+    StandardMethod { name: "total_supply", args: &[], returns: Some("u64"), mutable: false, description: "Amount of tokens in existence." },
+    StandardMethod { name: "balance_of", args: &[("owner", "PublicAddress")], returns: Some("u64"), mutable: false, description: "Balance of the owner." },
+    StandardMethod { name: "allowance", args: &[("owner", "PublicAddress"), ("spender", "PublicAddress")], returns: Some("u64"), mutable: false, description: "Amount that the spender may transfer on behalf of the owner." },
+    StandardMethod { name: "transfer", args: &[("to", "PublicAddress"), ("amount", "u64")], returns: Some("Result<(), ContractError>"), mutable: true, description: "Transfer tokens of the caller." },
+    StandardMethod { name: "approve", args: &[("spender", "PublicAddress"), ("amount", "u64")], returns: None, mutable: true, description: "Set the allowance of the spender." },
+    StandardMethod { name: "transfer_from", args: &[("from", "PublicAddress"), ("to", "PublicAddress"), ("amount", "u64")], returns: Some("Result<(), ContractError>"), mutable: true, description: "Transfer tokens on behalf of the owner." },
+];
+
+/// Methods of `pchain_sdk::standards::nft`, in the order of the table in its documentation.
+const NFT_METHODS: [StandardMethod; 10] = [
+    StandardMethod { name: "name", args: &[], returns: Some("String"), mutable: false, description: "Name of the collection." },
+    StandardMethod { name: "symbol", args: &[], returns: Some("String"), mutable: false, description: "Symbol of the collection." },
+    StandardMethod { name: "total_supply", args: &[], returns: Some("u64"), mutable: false, description: "Number of tokens in existence." },
+    StandardMethod { name: "balance_of", args: &[("owner", "PublicAddress")], returns: Some("u64"), mutable: false, description: "Number of tokens of the owner." },
+    StandardMethod { name: "owner_of", args: &[("token_id", "u64")], returns: Some("Option<PublicAddress>"), mutable: false, description: "Owner of the token." },
+    StandardMethod { name: "get_approved", args: &[("token_id", "u64")], returns: Some("Option<PublicAddress>"), mutable: false, description: "Spender approved for the token." },
+    StandardMethod { name: "token_uri", args: &[("token_id", "u64")], returns: Some("Option<String>"), mutable: false, description: "URI of the metadata of the token." },
+    StandardMethod { name: "transfer", args: &[("to", "PublicAddress"), ("token_id", "u64")], returns: Some("Result<(), ContractError>"), mutable: true, description: "Transfer a token of the caller." },
+    StandardMethod { name: "approve", args: &[("spender", "PublicAddress"), ("token_id", "u64")], returns: Some("Result<(), ContractError>"), mutable: true, description: "Approve the spender for a token of the caller." },
+    StandardMethod { name: "transfer_from", args: &[("from", "PublicAddress"), ("to", "PublicAddress"), ("token_id", "u64")], returns: Some("Result<(), ContractError>"), mutable: true, description: "Transfer a token on behalf of its owner." },
+];
+
+/// `standard_methods` returns the methods of the standard of the module, e.g. `fungible_token`.
+pub(crate) fn standard_methods(module: &str) -> &'static [StandardMethod] {
+    match module {
+        "fungible_token" => &FUNGIBLE_TOKEN_METHODS,
+        "nft" => &NFT_METHODS,
+        _ => &[]
+    }
+}
+
+/// `generate_standard_derive` implements a token standard of `pchain_sdk::standards` for a contract struct, e.g. 
+/// `#[derive(FungibleToken)]`, whose contract field is the only field of the type `field_type`. It generates the hidden 
+/// functions `__<module>` and `__<module>_methods`, which dispatch and list the methods of the standard in 
+/// `pchain_sdk::standards::<module>` on the field, and take precedence over the fallbacks in 
+/// `pchain_sdk::standards::__NoStandards` that `#[contract_methods]` calls otherwise. The hidden constant 
+/// `__STANDARD` is the module, which `#[contract_methods]` checks against the standard declared in its options.
+/// 
+/// A contract struct adopts at most one standard, since their methods share names, e.g. `transfer`. Because a derive
+/// macro does not see all the derives of the struct, a struct with contract fields of more than one standard cannot
+/// derive any of them.
+pub(crate) fn generate_standard_derive(item: &ItemStruct, field_type: &str, module: &str) -> syn::Result<TokenStream> {
+    let fields = match &item.fields {
+        Fields::Named(fields) => &fields.named,
+        _ => return Err(syn::Error::new(item.span(), format!("{} can only be derived for a contract struct with named fields.", field_type)))
+    };
+    let is_of_type = |f: &syn::Field, field_type: &str| match &f.ty {
+        syn::Type::Path(tp) => tp.path.segments.last().map_or(false, |segment| segment.ident == field_type),
+        _ => false
+    };
+    let standard_field_types: Vec<&str> = STANDARD_FIELD_TYPES.into_iter()
+        .filter(|field_type| fields.iter().any(|f| is_of_type(f, field_type)))
+        .collect();
+    if standard_field_types.len() > 1 {
+        return Err(syn::Error::new(item.ident.span(), format!("{} can only be derived for a contract struct that adopts one standard, but it has fields of types {}.", field_type, standard_field_types.join(" and "))))
+    }
+    // the index of the field is its storage key in the contract, as generated by `#[contract]`
+    let mut indices = fields.iter().enumerate().filter(|(_, f)| is_of_type(f, field_type)).map(|(i, _)| i);
+    let index = match (indices.next(), indices.next()) {
+        (Some(index), None) => index as u8,
+        (None, _) => return Err(syn::Error::new(item.ident.span(), format!("{} can only be derived for a contract struct with a field of type {}.", field_type, field_type))),
+        (Some(_), Some(_)) => return Err(syn::Error::new(item.ident.span(), format!("{} can only be derived for a contract struct with one field of type {}.", field_type, field_type)))
+    };
+
+    let struct_name = &item.ident;
+    let standard = module;
+    let module = format_ident!("{}", module);
+    let fn_dispatch = format_ident!("__{}", module);
+    let fn_methods = format_ident!("__{}_methods", module);
+    Ok(TokenStream::from(quote!{
+        impl #struct_name {
+            #[doc(hidden)]
+            pub const __STANDARD: &'static str = #standard;
+
+            #[doc(hidden)]
+            pub fn #fn_dispatch(ctx: &pchain_sdk::ContractMethodInput) -> Option<pchain_sdk::ContractMethodOutput> {
+                pchain_sdk::standards::#module::__dispatch(&pchain_sdk::StoragePath::new().add(#index), ctx)
+            }
+
+            #[doc(hidden)]
+            pub fn #fn_methods() -> Vec<pchain_sdk::method::MethodInfo> {
+                pchain_sdk::standards::#module::__methods()
+            }
+        }
+    }))
+}
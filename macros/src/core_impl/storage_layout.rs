@@ -0,0 +1,183 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+use quote::quote;
+use syn::ItemStruct;
+
+/// Environment variable of the directory to which the storage layout reports are written at build time. 
+/// No report is written if it is not set.
+const STORAGE_LAYOUT_DIR_ENV: &str = "PCHAIN_STORAGE_LAYOUT_DIR";
+
+/// `emit_storage_layout` writes the storage layout of a struct with `#[contract]` or `#[contract_field]` to 
+/// `<PCHAIN_STORAGE_LAYOUT_DIR>/<Struct>.storage.txt` (human-readable) and `<Struct>.storage.json`, if the environment
+/// variable is set. E.g.
+/// 
+/// ```text
+/// PCHAIN_STORAGE_LAYOUT_DIR=target/storage-layout cargo build --target wasm32-unknown-unknown --release
+/// ```
+/// 
+/// Each field is reported with its index, its key relative to the struct, its type and the layout of the keys under
+/// the field if it is a collection. The key of a contract struct is empty, so the keys of its fields are absolute. 
+/// A field of a `#[contract_field]` struct is reported in the struct's own report, and its keys are prefixed by 
+/// the key of the field.
+pub(crate) fn emit_storage_layout(ist: &ItemStruct) -> Result<(), String> {
+    let dir = match std::env::var(STORAGE_LAYOUT_DIR_ENV) {
+        Ok(dir) if !dir.is_empty() => std::path::PathBuf::from(dir),
+        _ => return Ok(())
+    };
+
+    let struct_name = ist.ident.to_string();
+    let fields: Vec<(usize, String, String)> = ist.fields.iter().enumerate().map(|(i, f)| {
+        let f_name = f.ident.as_ref().map_or(i.to_string(), |ident| ident.to_string());
+        let f_ty = &f.ty;
+        (i, f_name, type_to_string(&quote!(#f_ty).to_string()))
+    }).collect();
+
+    // human-readable report
+    let mut text = format!("Storage layout of {}\n\n", struct_name);
+    text.push_str(&format!("{:<6}{:<8}{:<24}{}\n", "Index", "Key", "Field", "Type"));
+    for (i, f_name, f_ty) in fields.iter() {
+        text.push_str(&format!("{:<6}{:<8}{:<24}{}\n", i, format!("[{}]", i), f_name, f_ty));
+        if let Some(layout) = collection_layout(f_ty) {
+            text.push_str(&format!("{:<38}keys: {}\n", "", layout));
+        }
+    }
+
+    // JSON report
+    let json_fields: Vec<String> = fields.iter().map(|(i, f_name, f_ty)| {
+        format!(
+            "{{\"index\":{},\"key\":[{}],\"name\":\"{}\",\"type\":\"{}\",\"layout\":{}}}",
+            i, i, f_name, f_ty,
+            collection_layout(f_ty).map_or("null".to_string(), |layout| format!("\"{}\"", layout))
+        )
+    }).collect();
+    let json = format!("{{\"struct\":\"{}\",\"fields\":[{}]}}\n", struct_name, json_fields.join(","));
+
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(format!("{}.storage.txt", struct_name)), text))
+        .and_then(|_| std::fs::write(dir.join(format!("{}.storage.json", struct_name)), json))
+        .map_err(|e| format!("Cannot write storage layout of {} to {}: {}", struct_name, dir.display(), e))
+}
+
+/// Remove the spaces inserted by the token printer, e.g. `FastMap < String , u64 >` to `FastMap<String, u64>`.
+pub(crate) fn type_to_string(ty: &str) -> String {
+    ty.replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" :: ", "::")
+        .replace(" ; ", "; ")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace("( ", "(")
+        .replace(" )", ")")
+}
+
+/// The collection types whose keys are reported, with the id of the kind of their storage model and the keys under a
+/// field of the type, relative to the key of the field (P). The keys follow the storage models documented in the SDK.
+/// The id is hashed into the locked layout instead of the description of the keys, so it changes, by its revision,
+/// only when the storage model changes in a way that existing state cannot be read with.
+const COLLECTIONS: [(&str, &str, &str); 11] = [
+    ("Vector", "vector/1", "length at P,0; element at P,1,I (I: u32 LE index)"),
+    ("Deque", "deque/1", "positions of the ends at P,0; element at P,1,I (I: u64 LE position)"),
+    ("FastMap", "fast_map/1", "value at P,E,K (E: u32 LE edition, K: MapKey); edition log at P,X,.. (X: u32::MAX LE)"),
+    ("IterableMap", "iterable_map/1", "map info at P,0; key-index at P,1,L,K; index-key at P,2,L,I; index-value at P,3,L,I (L: u32 LE level, I: u32 LE index)"),
+    ("IterableSet", "iterable_set/1", "length at P,0; members as IterableMap at P,1,.."),
+    ("ChunkedBlob", "chunked_blob/1", "manifest at P,0; chunk at P,1,I (I: u32 LE index)"),
+    ("InternTable", "intern_table/1", "length at P,0; string-id at P,1,S; id-string at P,2,I (S: UTF-8 bytes, I: u32 LE id)"),
+    ("Cacher", "cacher/1", "value at P"),
+    ("LazyOption", "lazy_option/1", "presence flag at P,0; value at P,1"),
+    ("FungibleToken", "fungible_token/1", "metadata at P,0; total supply at P,1; balances as FastMap at P,2,..; allowances at P,3,..; holder registry at P,4,.."),
+    ("NonFungibleToken", "non_fungible_token/1", "metadata at P,0; total supply at P,1; owners at P,2,..; balances at P,3,..; approvals at P,4,..; token URIs at P,5,.. (FastMaps)"),
+];
+
+/// The entry of [COLLECTIONS] of the type, if it is a collection.
+fn collection(ty: &str) -> Option<&'static (&'static str, &'static str, &'static str)> {
+    let name = ty.split('<').next()?.rsplit("::").next()?;
+    COLLECTIONS.iter().find(|(collection, _, _)| *collection == name)
+}
+
+/// The keys under a field of collection type, relative to the key of the field (P).
+fn collection_layout(ty: &str) -> Option<&'static str> {
+    collection(ty).map(|(_, _, layout)| *layout)
+}
+
+/// The id of the kind of storage model of a field of collection type.
+fn collection_kind(ty: &str) -> Option<&'static str> {
+    collection(ty).map(|(_, kind, _)| *kind)
+}
+
+/// Name of the file of locked storage layouts, in the directory of the manifest of the contract crate.
+const STORAGE_LAYOUT_LOCK_FILE: &str = "storage_layout.lock";
+
+/// Environment variable of the path of the lock file, which replaces `storage_layout.lock` in the directory of the 
+/// manifest, e.g. for crates of a workspace that share one lock file.
+pub(crate) const STORAGE_LAYOUT_LOCK_ENV: &str = "PCHAIN_STORAGE_LAYOUT_LOCK";
+
+/// `check_layout_lock` compares the storage layout of a struct with `#[contract(layout_version = N)]` with the entry of
+/// the struct in `storage_layout.lock`, which is a line of `<Struct> <version> <hash>`. The hash covers the index, the
+/// type and the kind of collection of each field, but not the names of the fields, which can be renamed freely.
+/// 
+/// - If the lock has no entry of the struct, it is an error whose message is the entry to add, so that a lock file 
+///   that is missing, e.g. not committed, is not silently replaced by the current layout.
+/// - If the entry has a lower version, the entry is updated with the current layout, so bumping the version accepts
+///   the change of layout.
+/// - If the entry has the same version but another hash, the layout changed without bumping the version, which is an
+///   error.
+/// - If the entry has a higher version, the version was lowered, which is an error.
+/// 
+/// Returns the path of the lock file, which the generated code includes so that the crate is rebuilt when it changes.
+pub(crate) fn check_layout_lock(ist: &ItemStruct, version: u32) -> Result<std::path::PathBuf, String> {
+    let path = match std::env::var(STORAGE_LAYOUT_LOCK_ENV) {
+        Ok(path) if !path.is_empty() => std::path::PathBuf::from(path),
+        _ => {
+            let dir = std::env::var("CARGO_MANIFEST_DIR")
+                .map_err(|_| format!("CARGO_MANIFEST_DIR is not set, so {} cannot be found", STORAGE_LAYOUT_LOCK_FILE))?;
+            std::path::PathBuf::from(dir).join(STORAGE_LAYOUT_LOCK_FILE)
+        }
+    };
+
+    let struct_name = ist.ident.to_string();
+    let hash = layout_hash(ist);
+    let lock = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut lines: Vec<String> = lock.lines().map(|line| line.to_string()).collect();
+
+    let entry = lines.iter().position(|line| line.split_whitespace().next() == Some(struct_name.as_str()))
+        .ok_or_else(|| format!(
+            "{} has no entry in {}. Add the line `{} {} {}` to lock layout_version {}, and commit the file",
+            struct_name, STORAGE_LAYOUT_LOCK_FILE, struct_name, version, hash, version
+        ))?;
+    let mut parts = lines[entry].split_whitespace().skip(1);
+    let locked_version: u32 = parts.next().and_then(|v| v.parse().ok())
+        .ok_or_else(|| format!("Malformed entry of {} in {}: {}", struct_name, STORAGE_LAYOUT_LOCK_FILE, lines[entry]))?;
+    let locked_hash = parts.next().unwrap_or_default();
+    if locked_version > version {
+        return Err(format!("layout_version {} of {} is lower than version {} in {}", version, struct_name, locked_version, STORAGE_LAYOUT_LOCK_FILE))
+    }
+    if locked_version == version {
+        if locked_hash != hash {
+            return Err(format!(
+                "Storage layout of {} changed since layout_version {} was locked in {}. Bump layout_version if the change is intended, e.g. with a migration of the existing state, or revert the change",
+                struct_name, version, STORAGE_LAYOUT_LOCK_FILE
+            ))
+        }
+        return Ok(path)
+    }
+    lines[entry] = format!("{} {} {}", struct_name, version, hash);
+
+    std::fs::write(&path, lines.join("\n") + "\n")
+        .map_err(|e| format!("Cannot write storage layout of {} to {}: {}", struct_name, path.display(), e))?;
+    Ok(path)
+}
+
+/// Hex of the SHA256 hash of the layout of the fields, one line of `<index> <type> <collection kind>` per field.
+fn layout_hash(ist: &ItemStruct) -> String {
+    let layout: String = ist.fields.iter().enumerate().map(|(i, f)| {
+        let f_ty = &f.ty;
+        let f_ty = type_to_string(&quote!(#f_ty).to_string());
+        format!("{} {} {}\n", i, f_ty, collection_kind(&f_ty).unwrap_or_default())
+    }).collect();
+    pchain_types::cryptography::sha256(layout.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
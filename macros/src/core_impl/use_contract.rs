@@ -7,7 +7,8 @@ use syn::{
     Block, FnArg, ItemTrait,
     parse_quote, punctuated::Punctuated,
     TraitItem, TraitItemMethod, Signature,
-    spanned::Spanned, token::Comma, ItemMod,Item, ItemFn, Visibility, ReturnType, Type, TypePath, PatType, parse_str, Expr,    
+    spanned::Spanned, token::Comma, ItemMod,Item, ItemFn, Visibility, ReturnType, Type, TypePath, PatType, parse_str, Expr, LitStr,    
+    ItemStruct, Ident,
 };
 use pchain_types::cryptography::PublicAddress;
 use proc_macro::TokenStream;
@@ -16,6 +17,112 @@ use super::generate_compilation_error;
 
 use snakecase::unicode::to_snakecase;
 
+/// `parse_contract_address` decodes the base64url-encoded contract address given to `use_contract`. If it is not 
+/// valid, the error points at the literal and suggests the likely fix.
+pub(crate) fn parse_contract_address(lit: &LitStr) -> syn::Result<PublicAddress> {
+    let address = lit.value();
+    let error = |message: String| Err(syn::Error::new(lit.span(), message));
+
+    if address.is_empty() {
+        return error("Contract address is empty. Expect a base64url-encoded address of 32 bytes, e.g. \"Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=\".".to_string())
+    }
+
+    // standard base64 uses '+' and '/' instead of '-' and '_'
+    if address.contains(|c| c == '+' || c == '/') {
+        let suggestion = address.replace('+', "-").replace('/', "_");
+        return error(format!("Contract address is in standard base64, but base64url is expected. Try \"{}\".", suggestion))
+    }
+
+    if let Some(c) = address.trim_end_matches('=').chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_')) {
+        return error(format!("Contract address contains the character {:?}, which is not in the base64url alphabet.", c))
+    }
+
+    // 32 bytes are encoded into 43 characters and 1 padding character
+    let unpadded = address.trim_end_matches('=');
+    if unpadded.len() == 43 && address.len() != 44 {
+        return error(format!("Contract address has wrong padding. Try \"{}=\".", unpadded))
+    }
+
+    match base64url::decode(&address) {
+        Ok(bytes) if bytes.len() == 32 => Ok(bytes.try_into().unwrap()),
+        Ok(bytes) => error(format!("Contract address is decoded into {} bytes, but an address has 32 bytes (44 characters in base64url).", bytes.len())),
+        Err(_) => error(format!("Contract address cannot be decoded. Expect 44 characters in base64url, but it has {} characters.", address.len())),
+    }
+}
+
+/// `generate_event_decoder` generates the event struct of the external contract, declared in the trait by 
+/// `event! { struct .. }`, with the topic of its logs and a decoder of its logs. The topic is the name of the struct, 
+/// unless specified by `#[topic = ".."]`. An event declared with `#[version = N]` for N > 1 has the topic `topic@N`, 
+/// the same as events defined by `#[event(version = N)]`. The value of the log is the borsh-serialized struct.
+fn generate_event_decoder(event: &ItemStruct) -> syn::Result<Vec<Item>> {
+    let mut topic = event.ident.to_string();
+    let mut version = 1u32;
+    let mut attrs = vec![];
+    for attr in event.attrs.iter() {
+        if attr.path.is_ident("topic") {
+            match attr.parse_meta()? {
+                syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. }) => topic = s.value(),
+                meta => return Err(syn::Error::new(meta.span(), "Expect topic of event to be #[topic = \"..\"]"))
+            }
+        } else if attr.path.is_ident("version") {
+            match attr.parse_meta()? {
+                syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Int(i), .. }) if i.base10_parse::<u32>().map_or(false, |v| v > 0) => {
+                    version = i.base10_parse()?
+                },
+                meta => return Err(syn::Error::new(meta.span(), "Expect version of event to be #[version = N] for a positive integer N"))
+            }
+        } else {
+            attrs.push(attr.clone());
+        }
+    }
+    if version > 1 {
+        topic = format!("{}@{}", topic, version);
+    }
+
+    let fields = match &event.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => return Err(syn::Error::new(event.ident.span(), "Events should be structs with named fields."))
+    };
+    let field_names: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let ident = &event.ident;
+    let vis = &event.vis;
+    let topic = proc_macro2::Literal::byte_string(topic.as_bytes());
+
+    let event_struct = ItemStruct { attrs, ..event.clone() };
+    let event_impls: Item = parse_quote!{
+        impl #ident {
+            /// Topic of the logs of this event.
+            #vis const TOPIC: &'static [u8] = #topic;
+
+            /// Decode the log, if it is of this event.
+            #vis fn decode(log: &pchain_sdk::types::Log) -> Option<Self> {
+                if log.topic.as_slice() != Self::TOPIC {
+                    return None
+                }
+                <Self as pchain_sdk::borsh::BorshDeserialize>::try_from_slice(&log.value).ok()
+            }
+        }
+    };
+    let borsh_serialize: Item = parse_quote!{
+        impl pchain_sdk::borsh::BorshSerialize for #ident {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                #(pchain_sdk::borsh::BorshSerialize::serialize(&self.#field_names, writer)?;)*
+                Ok(())
+            }
+        }
+    };
+    let borsh_deserialize: Item = parse_quote!{
+        impl pchain_sdk::borsh::BorshDeserialize for #ident {
+            fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+                Ok(Self {
+                    #(#field_names: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,)*
+                })
+            }
+        }
+    };
+    Ok(vec![Item::Struct(event_struct), event_impls, borsh_serialize, borsh_deserialize])
+}
+
 /// `general_external_contract_mod` performs the following items:
 ///  1. Parses the trait items of an external smart contract trait definition written by a 
 ///     smart contract developer.
@@ -28,15 +135,32 @@ use snakecase::unicode::to_snakecase;
 /// 
 /// The rationale for this is illustrated below:
 ///  1. Facilitates the usage of calling other contracts.
-pub(crate) fn generate_external_contract_mod(trait_definition: ItemTrait, contract_address: String) -> TokenStream {
+pub(crate) fn generate_external_contract_mod(trait_definition: ItemTrait, contract_address: &LitStr) -> TokenStream {
+
+    // Parses the contract address before the trait items, so that an invalid address is reported once, at the literal.
+    let contract_address = match parse_contract_address(contract_address) {
+        Ok(address) => address,
+        Err(e) => return e.to_compile_error().into()
+    };
 
     // `transform_to_external_contract_mod` takes the parsed properties in the previous sentence and trasnforms
     // the trait item methods into a list of function definitions embedded in a mod block.
 
     // stores the trait item methods that are converted into function definitions
     let mut item_functions: Vec<Item> = Vec::new();
+
+    // types in the trait, e.g. of return values, are resolved in the scope of the trait
+    item_functions.push(parse_quote!{ #[allow(unused_imports)] use super::*; });
+    let mut events: Vec<ItemStruct> = Vec::new();
     for item in trait_definition.items {
         match item {
+            TraitItem::Macro(m) if m.mac.path.is_ident("event") => {
+                // events of the external contract, declared by `event! { struct .. }`
+                match m.mac.parse_body::<ItemStruct>() {
+                    Ok(event) => events.push(event),
+                    Err(e) => return e.to_compile_error().into()
+                }
+            },
             TraitItem::Method(mut method) => {
                 item_functions.push(
                     // converts trait item methods into a list of function definitions
@@ -54,13 +178,21 @@ pub(crate) fn generate_external_contract_mod(trait_definition: ItemTrait, contra
                 // if the TraitItem is not a method, throw an error and halt compilation of the smart contract
                 let e = syn::Error::new(
                     item.span(),
-                    "Traits that are used to describe external contract should only include methods.",
+                    "Traits that are used to describe external contract should only include methods and events.",
                 );
                 return generate_compilation_error(e.to_string())
             }
         }
     }
 
+    // events of the external contract, with decoders of their logs
+    for event in events.iter() {
+        match generate_event_decoder(event) {
+            Ok(items) => item_functions.extend(items),
+            Err(e) => return e.to_compile_error().into()
+        }
+    }
+
     // Collects the function definitions in step 4 and embed inside a `mod`. The `mod` name will
     // be the `trait` name in snake case.
 
@@ -77,11 +209,30 @@ pub(crate) fn generate_external_contract_mod(trait_definition: ItemTrait, contra
     quote!{ #external_contract_mod }.into()
 } 
 
+/// `contract_result_ok_type` returns `T` if the return type is `Result<T, ContractError>`, or None if it is not a
+/// `Result`. A `Result` with another error type is an error, since the error envelope is decoded as `ContractError`.
+fn contract_result_ok_type(return_type: &TypePath) -> syn::Result<Option<Type>> {
+    let segment = match return_type.path.segments.last() {
+        Some(segment) if segment.ident == "Result" => segment,
+        _ => return Ok(None)
+    };
+    let error = || syn::Error::new(return_type.span(), "Methods of external contract returning Result should return Result<T, ContractError>.");
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => &args.args,
+        _ => return Err(error())
+    };
+    match (args.first(), args.iter().nth(1), args.len()) {
+        (Some(syn::GenericArgument::Type(ok_type)), Some(syn::GenericArgument::Type(Type::Path(error_type))), 2)
+            if error_type.path.segments.last().map_or(false, |ps| ps.ident == "ContractError") => Ok(Some(ok_type.clone())),
+        _ => Err(error())
+    }
+}
+
 // `transform_to_function_defintion` is where the bulk of the `use_macro` logic takes place. It does the following low level operations:
 // a. Takes the trait item methods and appends the `value` arguments to its signature.
 // b. Adds a block to the function defintion in step 1. This function block contains the SDK provided cross contract associated function.
 // c. Inherits the visibility properties and returns the new transformed node as a `Item`
-fn transform_to_function_definition(original_trait_item_method: &mut TraitItemMethod, trait_visibility: &Visibility, contract_address: &String) -> syn::Result<Item> {
+fn transform_to_function_definition(original_trait_item_method: &mut TraitItemMethod, trait_visibility: &Visibility, contract_address: &PublicAddress) -> syn::Result<Item> {
     // no default implementation of a trait is allowed. The SDK cross contract associated function will handle the default implementation.
     if original_trait_item_method.default.is_some() {
         Err(syn::Error::new(
@@ -139,13 +290,14 @@ fn transform_to_function_definition(original_trait_item_method: &mut TraitItemMe
         if let syn::ReturnType::Type(_, box_type) = &original_trait_item_method.sig.output {
             match box_type.as_ref() {
                 syn::Type::Path(e) => {
-                    if let Some(ps) = e.path.segments.first() {
+                    if let Some(ok_type) = contract_result_ok_type(e)? {
+                        // use the call function which decodes the error envelope, i.e. Result<T, ContractError>
+                        use_function = quote!{ try_call_result };
+                        return_type = parse_quote!{ Result<#ok_type, pchain_sdk::CallError> };
+                    } else {
                         // use the call function with known return data type
-                        use_function = quote!{ call };
-
-                        // known return data type
-                        let psident = &ps.ident;
-                        return_type = parse_quote!{ Option<#psident> };
+                        use_function = quote!{ try_call };
+                        return_type = parse_quote!{ Result<#e, pchain_sdk::CallError> };
                     }
                 }
                 _=> return Err(syn::Error::new(
@@ -171,13 +323,6 @@ fn transform_to_function_definition(original_trait_item_method: &mut TraitItemMe
         //////////////////////////////////////////////////////////////////////////////////////////////
         // 3. Parses the decoded contract address from the argument to `use_contract` attribute macro.
         //////////////////////////////////////////////////////////////////////////////////////////////
-        let contract_address: PublicAddress = match base64url::decode(contract_address) {
-            Ok(address) => address.try_into().unwrap(),
-            Err(_) => return Err(syn::Error::new(
-                original_trait_item_method.span(),
-                "Contract address cannot be decoded. Please ensure this contract address is base64 format with urlencoding.",
-            ))
-        };
         let contract_address_args = parse_str::<Expr>(format!("{:?}", contract_address).as_str()).unwrap();
         //////////////////////////////////////////////////////////////////////////////////////////////
         //  4. Generates a function definition from the parsed items in steps 1 - 3. The function body 
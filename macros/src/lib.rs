@@ -6,7 +6,7 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{ItemStruct, ItemImpl, NestedMeta, ItemTrait};
+use syn::{ItemStruct, ItemImpl, NestedMeta, ItemTrait, Item};
 
 
 mod core_impl;
@@ -25,11 +25,37 @@ use self::core_impl::*;
 ///   data :i32
 /// }
 /// ```
+/// 
+/// # Storage layout report
+/// If the environment variable `PCHAIN_STORAGE_LAYOUT_DIR` is set at build time, the storage layout of the struct 
+/// (field indices, keys and types) is written to `<Struct>.storage.txt` and `<Struct>.storage.json` in that directory.
+/// The same applies to `#[contract_field]`.
+/// 
+/// # Storage layout lock
+/// With `layout_version = N`, the storage layout of the struct (the index, type and collection kind of each field) is
+/// hashed at build time and compared with the entry of the struct in `storage_layout.lock`, next to `Cargo.toml` of
+/// the crate. The build fails if the layout changed while `N` is still the locked version, since the existing state
+/// of a deployed contract would be read with the new layout. Bumping `N` accepts the change and updates the entry.
+/// The build also fails if the struct has no entry, with the entry to add in the error message, so that a lock file 
+/// that is not committed with the contract is not silently replaced. The environment variable 
+/// `PCHAIN_STORAGE_LAYOUT_LOCK` sets another path of the lock file, e.g. one shared by the crates of a workspace.
+/// 
+/// ```no_run
+/// #[contract(layout_version = 2)]
+/// struct MyContract {
+///   balances: FastMap<PublicAddress, u64>,
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn contract(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn contract(attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  let attr_args = syn::parse_macro_input!(attr_args as syn::AttributeArgs);
+  let options = match parse_contract_struct_options(&attr_args) {
+    Ok(options) => options,
+    Err(e) => return e.to_compile_error().into()
+  };
 
   if let Ok(mut ist) = syn::parse::<ItemStruct>(input) {
-    generate_contract_struct(&mut ist)
+    generate_contract_struct(&mut ist, &options)
   } else {
     generate_compilation_error("ERROR:  contract macro can only be applied to smart contract Struct to read/write into world state".to_string())
   }
@@ -52,10 +78,169 @@ pub fn contract(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
 ///   }
 /// }
 /// ```
+/// 
+/// # Metadata
+/// Version, author, license and repository of the contract can be specified by `meta`. They are available as the 
+/// constant `CONTRACT_METADATA` in JSON, and embedded in the custom section `pchain_metadata` of the WASM module, 
+/// together with the gas estimates of methods specified by `#[call(gas_estimate = ..)]`.
+/// Regardless of `meta`, the SDK version, calldata version and return value version are embedded in the custom 
+/// section `pchain_sdk`, e.g. `{"sdk":"0.4.2","calldata":1,"return":0}`, to identify which SDK generation the 
+/// contract was built with.
+/// 
+/// ```no_run
+/// #[contract_methods(meta(version = "1.2.0", author = "ParallelChain Lab", license = "Apache-2.0", repo = "https://github.com/..."))]
+/// impl MyContract {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Method list
+/// A hidden method `__methods` is generated, which returns the callable methods with their number of arguments and 
+/// whether they accept an attached amount, as `Vec<pchain_sdk::method::MethodInfo>`. Other contracts can call it by 
+/// `pchain_sdk::method::methods_of`.
+/// 
+/// # Return envelope
+/// With `return_envelope`, return values are encoded in a versioned `pchain_sdk::method::OutputEnvelope` instead of 
+/// plain Borsh, so that the encoding can be extended without breaking callers. Methods without return value still 
+/// return nothing, and `__methods` is never enveloped. The version is recorded as `"return"` in the SDK version stamp.
+/// Callers must read the envelope, e.g. by `pchain_sdk::try_call_envelope`, since `call`, `try_call` and the 
+/// functions generated by `use_contract` read plain Borsh.
+/// 
+/// ```no_run
+/// #[contract_methods(return_envelope)]
+/// impl MyContract {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Interface reference
+/// The doc comment of the generated `entrypoint` is a table of the callable methods with their arguments, return 
+/// types, payability, kind and the first line of their doc comments, so `cargo doc` always shows the interface as 
+/// it is in the code. With `interface_doc`, the table is also written to `<OUT_DIR>/<Contract>.interface.md`, which 
+/// requires a build script (`build.rs`, which may be empty) so that cargo sets `OUT_DIR`.
+/// 
+/// ```no_run
+/// #[contract_methods(interface_doc)]
+/// impl MyContract {
+///   // ...
+/// }
+/// 
+/// // e.g. in a test or in a tool that writes the README
+/// const INTERFACE: &str = include_str!(concat!(env!("OUT_DIR"), "/MyContract.interface.md"));
+/// ```
+/// 
+/// # ABI
+/// The ABI of the contract, i.e. the methods that can be called by name with their argument names and types, return 
+/// types, kind (`call`, `view` or `multicall`), mutability and payability, is available as the static 
+/// `__contract_abi__` in JSON, and embedded in the custom section `pchain_abi` of the WASM module, so that tools can 
+/// render call forms. Whether the contract accepts plain transfers (`on_receive`) or has a `fallback` is also 
+/// recorded. With `abi_file`, it is also written to `<OUT_DIR>/<Contract>.abi.json`, which requires a build script as 
+/// for `interface_doc`.
+/// 
+/// ```no_run
+/// #[contract_methods(abi_file)]
+/// impl MyContract {
+///   // ...
+/// }
+/// 
+/// // {"contract":"MyContract","methods":[{"name":"deposit","kind":"call","mutable":true,"payable":true,
+/// // "internal_only":false,"args":[{"name":"memo","type":"String"}],"returns":"u64"}],"on_receive":false,"fallback":false}
+/// const ABI: &str = include_str!(concat!(env!("OUT_DIR"), "/MyContract.abi.json"));
+/// ```
+/// 
+/// # Decommissioning
+/// With `decommissionable`, the entrypoint rejects all calls, including views, after the contract is decommissioned 
+/// by `pchain_sdk::lifecycle::decommission`. It costs one read from world state per call.
+/// 
+/// ```no_run
+/// #[contract_methods(decommissionable)]
+/// impl MyContract {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Multicall
+/// With `multicall`, a method `multicall(calls: Vec<pchain_sdk::method::EncodedCall>) -> Vec<pchain_sdk::method::EncodedResult>` 
+/// is generated, which calls methods of the contract with attribute `call` in order, in one transaction. If any of 
+/// the calls panics or returns `Err`, none of them take effect. It does not accept an attached amount.
+/// 
+/// ```no_run
+/// #[contract_methods(multicall)]
+/// impl MyContract {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Metrics
+/// With `metrics`, the entrypoint logs a `pchain_sdk::metrics::CallMetrics` with the topic `Metrics` after every call, 
+/// including views and plain transfers. It has the id of the method, the gas remaining when the call entered the 
+/// contract, and whether the method returned `Err`.
+/// 
+/// ```no_run
+/// #[contract_methods(metrics)]
+/// impl MyContract {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Standards
+/// A contract struct that derives a token standard, e.g. `#[derive(FungibleToken)]`, declares it by the option of 
+/// the name of its module, `fungible_token` or `nft`, so that the methods of the standard are described in the 
+/// interface reference, the ABI and the metrics. Deriving a standard without declaring it, or declaring a standard 
+/// that is not derived, is a compile error.
+/// 
+/// ```no_run
+/// #[contract_methods(fungible_token)]
+/// impl MyToken {
+///   // ...
+/// }
+/// ```
+/// 
+/// # Field-selective loading
+/// The entrypoint of a method with attribute `call` only loads and saves the fields of the contract that the method 
+/// accesses as `self.field`, and the fields accessed by the invariants if the method takes `&mut self`. The other 
+/// fields are left as their default values if the type implements `Default`, so a method touching one collection 
+/// does not read the others from storage. If the method uses `self` in any other way, e.g. calls another method of 
+/// the contract, every field is loaded. The fields can also be listed by the attribute `fields`, which is checked 
+/// against the fields that the method accesses.
+/// 
+/// ```no_run
+/// #[call]
+/// #[fields(balances, total_supply)]
+/// fn mint(&mut self, to: PublicAddress, amount: u64) {
+///   let balance = self.balances.get(&to).unwrap_or(0);
+///   self.balances.insert(&to, balance + amount);
+///   self.total_supply += amount;
+/// }
+/// ```
+/// 
+/// # Call hooks
+/// The contract can define the associated functions `__before_call` and `__after_call`, which take the method name. 
+/// The entrypoint invokes them before and after every call, including views, plain transfers (with an empty method 
+/// name) and each call of multicall, but not `__methods`. A panic in a hook aborts the call, so they can check 
+/// access or invariants, or emit logs, for all methods. `__after_call` is invoked after the contract is saved.
+/// 
+/// ```no_run
+/// #[contract_methods]
+/// impl MyContract {
+///   fn __before_call(method: &str) {
+///     pchain_sdk::log(b"Called", method.as_bytes());
+///   }
+///   fn __after_call(method: &str) {
+///     // ...
+///   }
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn contract_methods(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn contract_methods(attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  let attr_args = syn::parse_macro_input!(attr_args as syn::AttributeArgs);
+  let options = match parse_contract_options(&attr_args) {
+    Ok(options) => options,
+    Err(e) => return e.to_compile_error().into()
+  };
+
   if let Ok(ipl) = syn::parse::<ItemImpl>(input) {
-    generate_contract_impl(&ipl)
+    generate_contract_impl(&ipl, &options)
   } else {
     generate_compilation_error("ERROR: contract_methods macro can only be applied to smart contract implStruct/implTrait.".to_string())
   }
@@ -73,7 +258,7 @@ pub fn contract_methods(_attr_args: TokenStream, input: TokenStream) -> TokenStr
 /// // As rust enforces a unique name for each trait defined, it is important that the external 
 /// // contract address is fed into `use_contract`. The trait name can therefore be anything. 
 /// // However it is recommended to use a name similar to the external smart contract to be called.
-/// #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo/Jw=")]
+/// #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
 /// pub trait MyContract {
 ///   fn print_a_value();
 ///   fn get_commodities_price(item: String) -> u64;
@@ -95,12 +280,47 @@ pub fn contract_methods(_attr_args: TokenStream, input: TokenStream) -> TokenStr
 ///   }
 /// }
 /// ```
+/// A method declared to return `T` returns `Result<T, CallError>`, which is `CallError::NoReturnValue` if the external
+/// contract returns no value, or `CallError::ReturnTypeMismatch` if its return value is not of type `T`. A method 
+/// declared to return `Result<T, ContractError>` also returns `Result<T, CallError>`, where the error returned by the
+/// external contract is `CallError::Failed`. A method without return type returns the return value as 
+/// `Option<Vec<u8>>`.
+/// 
+/// Return values are read as plain Borsh, so the methods of an external contract with `return_envelope` cannot be 
+/// declared in the trait. They are called by `pchain_sdk::try_call_envelope` instead.
+/// 
+/// ```no_run
+/// match my_contract::get_commodities_price("sugar".to_string(), 0) {
+///     Ok(price) => {},
+///     Err(CallError::NoReturnValue) => {},
+///     Err(e) => panic!("{}", e),
+/// }
+/// ```
+/// 
+/// The events of the external contract can be declared in the trait by `event! { struct .. }`. The generated module 
+/// contains the structs, with the topic of their logs (`TOPIC`) and a decoder of their logs (`decode`). The topic is 
+/// the name of the struct, unless specified by `#[topic = ".."]`. Events of version N > 1, i.e. defined by 
+/// `#[event(version = N)]` in the external contract, are declared with `#[version = N]`.
+/// 
+/// ```no_run
+/// #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+/// pub trait MyToken {
+///   fn transfer(to: [u8; 32], amount: u64);
+/// 
+///   event! {
+///     pub struct Transfer { pub from: [u8; 32], pub to: [u8; 32], pub amount: u64 }
+///   }
+/// }
+/// 
+/// let transfer: Option<my_token::Transfer> = my_token::Transfer::decode(&log);
+/// ```
+/// 
 /// The available functions can be used anywhere at the crate level by the smart contract developer. As an example
 /// ```no_run
 /// // In external_call.rs
 /// use pchain_sdk::use_contract;
 /// 
-/// #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo/Jw=")]
+/// #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
 /// pub trait MyContract {
 ///   fn print_a_value();
 ///   fn get_commodities_price(item: String) -> u64;
@@ -136,7 +356,7 @@ pub fn use_contract(attr_args: TokenStream, input: TokenStream) -> TokenStream {
       // `attr_args[0]` is the contract address of the external contract to be called.
       let attr_contract_address = &attr_args[0];
       let contract_address = match attr_contract_address {
-            NestedMeta::Lit(syn::Lit::Str(s)) => s.value(),
+            NestedMeta::Lit(syn::Lit::Str(s)) => s,
             NestedMeta::Lit(_) | NestedMeta::Meta(_) => {
               return generate_compilation_error("Only &str are allowed as first argument to use_contract".to_string())
             },
@@ -185,6 +405,209 @@ pub fn contract_field(_attr_args: TokenStream, input: TokenStream) -> TokenStrea
   }
 }
 
+/// `contract_types` marks a struct or enum used as an argument, return value or event of the contract, so that it 
+/// is shared with other contracts and off-chain tools in a lightweight types crate. The item itself is unchanged.
+/// 
+/// If the environment variable `PCHAIN_CONTRACT_TYPES_DIR` is set at build time, the crate `<package>-types` is 
+/// written to that directory. It contains the marked items with Borsh derives, and only depends on borsh, so that 
+/// integrating teams can depend on it without the contract and its entrypoint. Items may refer to each other and to 
+/// `PublicAddress`, but not to other types of the contract.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract_types]
+/// #[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+/// pub struct Order {
+///     pub maker: PublicAddress,
+///     pub amount: u64,
+/// }
+/// ```
+/// 
+/// ```text
+/// PCHAIN_CONTRACT_TYPES_DIR=../my-contract-types cargo build --target wasm32-unknown-unknown --release
+/// ```
+#[proc_macro_attribute]
+pub fn contract_types(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  let item = match syn::parse::<Item>(input.clone()) {
+    Ok(item @ (Item::Struct(_) | Item::Enum(_))) => item,
+    _ => return generate_compilation_error("#[contract_types] can only be applied to struct or enum definitions.".to_string())
+  };
+  match emit_contract_types(&item) {
+    Ok(()) => input,
+    Err(e) => generate_compilation_error(e)
+  }
+}
+
+/// `contract_config` defines configuration constants of the contract, e.g. fees and limits, in an impl block of the 
+/// contract struct, so that operators can read the values of a deployed contract without storage reads or its source. 
+/// The value of each constant must be a literal. The constants are:
+/// - available as the constant `CONTRACT_CONFIG` of the contract in JSON, e.g. `{"FEE":100,"MAX_ORDERS":50}`,
+/// - embedded in the custom section `pchain_config` of the WASM module, 
+/// - returned by the generated method `config` as `Vec<(String, String)>` of names and values, which other contracts 
+/// can call by `pchain_sdk::method::config_of`. The method is not generated if the contract defines its own `config`.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract_config]
+/// impl MyContract {
+///     const FEE: u64 = 100;
+///     const MAX_ORDERS: u32 = 50;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn contract_config(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  match syn::parse::<ItemImpl>(input) {
+    Ok(ipl) => generate_contract_config(&ipl).unwrap_or_else(|e| e.to_compile_error().into()),
+    Err(_) => generate_compilation_error("#[contract_config] can only be applied to an impl block of constants.".to_string())
+  }
+}
+
+/// `CallArgs` derives `pchain_sdk::method::CallArgs` for a struct of the arguments of a contract method, so that the 
+/// arguments of a call are built from a struct literal with named fields, instead of adding them in order by 
+/// `ContractMethodInputBuilder`. The fields are the arguments in order of declaration.
+/// 
+/// ### Example
+/// ```no_run
+/// #[derive(CallArgs)]
+/// struct TransferArgs {
+///     recipient: PublicAddress,
+///     amount: u64,
+/// }
+/// 
+/// let args = TransferArgs { recipient, amount: 100 }.into_call_arguments();
+/// pchain_sdk::call_untyped(token, "transfer", args, 0);
+/// ```
+#[proc_macro_derive(CallArgs)]
+pub fn derive_call_args(input: TokenStream) -> TokenStream {
+  match syn::parse::<ItemStruct>(input) {
+    Ok(item) => generate_call_args(&item),
+    Err(_) => generate_compilation_error("CallArgs can only be derived for struct definitions.".to_string())
+  }
+}
+
+/// `Event` derives `pchain_sdk::event::Event` for a struct with named fields, which emits the struct as a log by 
+/// `emit` and decodes logs by `decode`, and Borsh serialization of the struct, so the struct should not also derive 
+/// Borsh. The topic of the logs is the SHA256 hash of the name of the event, followed by the Borsh-serialized fields 
+/// marked `#[indexed]` in order of declaration, so that indexers can filter the logs by those fields. The name is the 
+/// name of the struct, unless specified by `#[topic = ".."]`.
+/// 
+/// The schema of the event is embedded in the custom section `pchain_events` of the WASM module, with the hash of 
+/// the name as `topic_sha256` and the names of the indexed fields.
+/// 
+/// ### Example
+/// ```no_run
+/// #[derive(Event)]
+/// #[topic = "Transfer"]
+/// pub struct TransferEvent {
+///     #[indexed]
+///     pub from: PublicAddress,
+///     #[indexed]
+///     pub to: PublicAddress,
+///     pub amount: u64,
+/// }
+/// 
+/// TransferEvent { from, to, amount }.emit();
+/// ```
+#[proc_macro_derive(Event, attributes(topic, indexed))]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+  match syn::parse::<ItemStruct>(input) {
+    Ok(item) => generate_event_derive(&item).unwrap_or_else(|e| e.to_compile_error().into()),
+    Err(_) => generate_compilation_error("Event can only be derived for struct definitions.".to_string())
+  }
+}
+
+/// `FungibleToken` adopts the fungible token standard of `pchain_sdk::standards::fungible_token` for a contract struct 
+/// with a field of type `FungibleToken`. The entrypoint generated by `contract_methods` dispatches the methods of the 
+/// standard, e.g. `transfer` and `balance_of`, to the field, unless the contract defines a method of the same name, 
+/// and lists them in the method `__methods`. The invariants of the contract are checked after each method of the 
+/// standard that is not a view. The standard is declared by the option `fungible_token` of `contract_methods`.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// #[derive(FungibleToken)]
+/// pub struct MyToken {
+///     token: FungibleToken,
+/// }
+/// 
+/// #[contract_methods(fungible_token)]
+/// impl MyToken {
+///   // ...
+/// }
+/// ```
+#[proc_macro_derive(FungibleToken)]
+pub fn derive_fungible_token(input: TokenStream) -> TokenStream {
+  match syn::parse::<ItemStruct>(input) {
+    Ok(item) => generate_standard_derive(&item, "FungibleToken", "fungible_token").unwrap_or_else(|e| e.to_compile_error().into()),
+    Err(_) => generate_compilation_error("FungibleToken can only be derived for struct definitions.".to_string())
+  }
+}
+
+/// `NonFungibleToken` adopts the non-fungible token standard of `pchain_sdk::standards::nft` for a contract struct 
+/// with a field of type `NonFungibleToken`. The entrypoint generated by `contract_methods` dispatches the methods of 
+/// the standard, e.g. `owner_of` and `transfer`, to the field, unless the contract defines a method of the same name, 
+/// and lists them in the method `__methods`. The invariants of the contract are checked after each method of the 
+/// standard that is not a view. The standard is declared by the option `nft` of `contract_methods`.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// #[derive(NonFungibleToken)]
+/// pub struct MyCollection {
+///     nft: NonFungibleToken,
+/// }
+/// 
+/// #[contract_methods(nft)]
+/// impl MyCollection {
+///   // ...
+/// }
+/// ```
+#[proc_macro_derive(NonFungibleToken)]
+pub fn derive_non_fungible_token(input: TokenStream) -> TokenStream {
+  match syn::parse::<ItemStruct>(input) {
+    Ok(item) => generate_standard_derive(&item, "NonFungibleToken", "nft").unwrap_or_else(|e| e.to_compile_error().into()),
+    Err(_) => generate_compilation_error("NonFungibleToken can only be derived for struct definitions.".to_string())
+  }
+}
+
+/// `event` defines a versioned event of the contract on a struct with named fields. It implements 
+/// `pchain_sdk::event::Event`, which emits the struct as a log by `emit` and decodes logs by `decode`, and Borsh 
+/// serialization of the struct. The arguments are optional:
+/// - `name = ".."`: name of the event, which is the name of the struct by default,
+/// - `version = N`: version of the layout of the event, which is 1 by default. The topic of the logs is the name for 
+/// version 1, or `name@N` otherwise,
+/// - `previous(StructName)`: the event of the previous version, which converts into the struct by `From`, so that 
+/// `decode` also decodes logs emitted before the layout was changed.
+/// 
+/// The schema of the event is embedded in the custom section `pchain_events` of the WASM module.
+/// 
+/// ### Example
+/// ```no_run
+/// #[event(name = "Transfer")]
+/// pub struct TransferV1 { pub to: PublicAddress, pub amount: u64 }
+/// 
+/// #[event(version = 2, previous(TransferV1))]
+/// pub struct Transfer { pub to: PublicAddress, pub amount: u64, pub memo: String }
+/// 
+/// impl From<TransferV1> for Transfer {
+///     fn from(v1: TransferV1) -> Self { Self { to: v1.to, amount: v1.amount, memo: String::new() } }
+/// }
+/// 
+/// Transfer { to, amount, memo }.emit();
+/// ```
+#[proc_macro_attribute]
+pub fn event(attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  let attr_args = syn::parse_macro_input!(attr_args as syn::AttributeArgs);
+  let options = match parse_event_options(&attr_args) {
+    Ok(options) => options,
+    Err(e) => return e.to_compile_error().into()
+  };
+  match syn::parse::<ItemStruct>(input) {
+    Ok(item) => generate_event(&item, &options).unwrap_or_else(|e| e.to_compile_error().into()),
+    Err(_) => generate_compilation_error("#[event] can only be applied to struct definitions.".to_string())
+  }
+}
+
 /// `call` macro applies to impl methods for contract method call.
 /// 
 /// ### Example
@@ -194,8 +617,164 @@ pub fn contract_field(_attr_args: TokenStream, input: TokenStream) -> TokenStrea
 ///  // ...
 /// }
 /// ```
+/// 
+/// A method with `internal_only` can only be called by another contract. A call from an external account panics, 
+/// unless the account is the `helper` address (base64url-encoded), if specified. E.g. for callbacks of a flash loan:
+/// 
+/// ```no_run
+/// #[call(internal_only)]
+/// fn on_flash_loan(&mut self, amount: u64) { 
+///  // ...
+/// }
+/// ```
+/// 
+/// `min_value` and `max_value` bound the amount attached to the call. A call with an amount out of the range panics, 
+/// e.g. for deposit caps and rejecting dust:
+/// 
+/// ```no_run
+/// #[call(min_value = 1_000, max_value = 1_000_000)]
+/// fn deposit(&mut self) { 
+///  // ...
+/// }
+/// ```
+/// 
+/// A method declared to return `Result<T, E>`, where `E: BorshSerialize`, can fail without panicking. The return value 
+/// is the borsh-serialized `Result`, so the caller receives the error. If it returns `Err`, the changes to the 
+/// contract fields are not saved, and the method name and the error are logged with the topic 
+/// `pchain_sdk::error::METHOD_ERROR_TOPIC`. The transaction is not reverted, so the transfers and internal calls 
+/// made by the method are not undone, and should be made after the last point where it can return `Err`:
+/// 
+/// ```no_run
+/// #[call]
+/// fn withdraw(&mut self, amount: u64) -> Result<u64, WithdrawError> { 
+///  // ...
+/// }
+/// ```
+/// 
+/// `gas_estimate` records the gas used by the call as measured by benchmarks, e.g. with `pchain_sdk::testing`. It does 
+/// not limit the call. The estimates are exported under the key `gas_estimates` of `CONTRACT_METADATA`, e.g. 
+/// `{"gas_estimates":{"deposit":120000}}`, and in the interface reference, so that wallets can suggest a gas limit. 
+/// They are only as accurate as the benchmarks, so a gas limit should include a margin above them.
+/// 
+/// ```no_run
+/// #[call(gas_estimate = 120_000)]
+/// fn deposit(&mut self) { 
+///  // ...
+/// }
+/// ```
+/// 
+/// `max_args_bytes` limits the size of the serialized arguments of a call, and `max_len` limits the number of items of 
+/// each argument of a collection type, i.e. `Vec`, `VecDeque`, `String`, `BTreeMap`, `BTreeSet`, `HashMap` or 
+/// `HashSet`. They are checked by the entrypoint before the contract is loaded and the arguments are deserialized, 
+/// and a call exceeding them panics, so that an oversized input costs the caller little gas and cannot reach the 
+/// method. Collections nested in other types, e.g. `Option<Vec<T>>`, are only limited by `max_args_bytes`.
+/// 
+/// ```no_run
+/// #[call(max_args_bytes = 64_000, max_len = 500)]
+/// fn airdrop(&mut self, recipients: Vec<PublicAddress>, amount: u64) { 
+///  // ...
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn call(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
   // it does nothing. The macro contract will handle this attribure.
   input
+}
+
+/// `on_receive` macro applies to at most one impl method, which is invoked when the contract is called with attached 
+/// value and an empty method name, i.e. a plain transfer. The method takes the attached amount as its only argument. 
+/// It accepts the transfer by returning, and rejects it by panicking. Without `on_receive`, plain transfers are rejected.
+/// 
+/// ### Example
+/// ```no_run
+/// #[on_receive]
+/// fn receive(&mut self, amount: u64) { 
+///   self.deposits += amount;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn on_receive(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  // it does nothing. The macro contract_methods will handle this attribure.
+  input
+}
+
+/// `fallback` macro applies to at most one impl method, which is invoked when the contract is called with a method 
+/// name that is not the name of a method, including names that are not valid UTF-8 or contain NUL. It takes the raw 
+/// bytes of the method name and the Borsh-serialized arguments of the call, and may return a value. Without 
+/// `fallback`, such calls abort. Plain transfers are handled by `on_receive` instead.
+/// 
+/// ### Example
+/// ```no_run
+/// #[fallback]
+/// fn fallback(&mut self, method: Vec<u8>, arguments: Vec<u8>) { 
+///   // e.g. forward the call to the implementation contract
+///   pchain_sdk::call_untyped(self.implementation, &String::from_utf8_lossy(&method), arguments, 0);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fallback(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  // it does nothing. The macro contract_methods will handle this attribure.
+  input
+}
+
+/// `invariant` macro applies to impl methods that check a property of the contract which must always hold, e.g. 
+/// conservation of funds. The method takes `&self` and returns bool. It is checked after every call of a method with 
+/// attribute `call`, `on_receive` or `fallback` and receiver `&mut self`, including calls of multicall, before the 
+/// contract is saved, and after every call of a method of a derived standard that is not a view. If it returns false, 
+/// the call panics, so that none of its changes take effect. A method that returns `Err` is not checked, since its 
+/// changes are not saved. An invariant cannot be called as a contract method.
+/// 
+/// ### Example
+/// ```no_run
+/// #[invariant]
+/// fn total_supply_matches(&self) -> bool { 
+///   self.balances.values().sum::<u64>() == self.total_supply
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn invariant(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  // it does nothing. The macro contract_methods will handle this attribure.
+  input
+}
+
+/// `fields` macro applies to impl methods with attribute `call`, and lists the fields of the contract that the 
+/// entrypoint loads before calling the method and saves afterwards. Without it, the fields are found from the body of 
+/// the method. The other fields are left as their default values if the type implements `Default`, or loaded 
+/// otherwise. Naming a field that does not exist is a compile error, and so is leaving out a field that the method 
+/// accesses as `self.field`. Since the fields that the method reaches through `self` in any other way, e.g. by 
+/// calling another method of the contract or passing `self` to a function, cannot be checked, the attribute cannot be 
+/// used on such a method, which loads every field instead.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// #[fields(balances)]
+/// fn deposit(&mut self) { 
+///   let account = pchain_sdk::transaction::calling_account();
+///   let balance = self.balances.get(&account).unwrap_or(0);
+///   self.balances.insert(&account, balance + pchain_sdk::transaction::amount());
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fields(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  // it does nothing. The macro contract_methods will handle this attribure.
+  input
+}
+
+/// `view` macro applies to impl methods that read the contract without changing it. Instead of a receiver, a view 
+/// takes the contract as `ReadOnly<'_, Self>` in its first argument, which only exposes getters of the fields 
+/// (generated by the macro `contract`) that return shared references. Mutating the contract in a view is therefore 
+/// a compile error, and the contract is never saved after a view is called.
+/// 
+/// ### Example
+/// ```no_run
+/// #[view]
+/// fn balance_of(contract: ReadOnly<'_, Self>, address: PublicAddress) -> u64 { 
+///   contract.balances().get(&address).unwrap_or(0)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn view(_attr_args: TokenStream, input: TokenStream) -> TokenStream {
+  // it does nothing. The macro contract_methods will handle this attribure.
+  input
 }
\ No newline at end of file
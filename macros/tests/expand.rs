@@ -0,0 +1,17 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Snapshot tests of the code generated by the macros. Each case in `expand` is compared with its committed 
+//! `.expanded.rs` file, so that a change of the generated storage keys or dispatcher fails the test and shows up in 
+//! review. A case without `.expanded.rs` file fails too. Run with `MACROTEST=overwrite` to update the files after 
+//! changing the generated code on purpose. It requires `cargo-expand`.
+//! 
+//! The storage layout lock is not covered here, since its generated code embeds the path of the lock file. It is 
+//! covered by the cases in `ui`.
+
+#[test]
+fn expand() {
+    macrotest::expand_without_refresh("tests/expand/*.rs");
+}
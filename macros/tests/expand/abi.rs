@@ -0,0 +1,42 @@
+use pchain_sdk::{contract, contract_methods, call, view, on_receive};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+}
+
+#[contract_methods(multicall)]
+impl MyContract {
+    #[call(gas_estimate = 120_000)]
+    fn deposit(&mut self, mut memo: String) -> u64 {
+        memo.clear();
+        self.balance
+    }
+
+    #[call(internal_only, max_value = 0)]
+    fn reset(&mut self) {
+        self.balance = 0;
+    }
+
+    #[view]
+    fn balance(contract: pchain_sdk::ReadOnly<'_, Self>, of: [u8; 32]) -> Option<u64> {
+        let _ = of;
+        Some(*contract.balance())
+    }
+
+    #[on_receive]
+    fn receive(&mut self, amount: u64) {
+        self.balance += amount;
+    }
+}
+
+fn main() {
+    assert_eq!(__contract_abi__, concat!(
+        "{\"contract\":\"MyContract\",\"methods\":[",
+        "{\"name\":\"deposit\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"memo\",\"type\":\"String\"}],\"returns\":\"u64\",\"gas_estimate\":120000},",
+        "{\"name\":\"reset\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":true,\"args\":[],\"returns\":null},",
+        "{\"name\":\"balance\",\"kind\":\"view\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"of\",\"type\":\"[u8; 32]\"}],\"returns\":\"Option<u64>\"},",
+        "{\"name\":\"multicall\",\"kind\":\"multicall\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"calls\",\"type\":\"Vec<EncodedCall>\"}],\"returns\":\"Vec<EncodedResult>\"}",
+        "],\"on_receive\":true,\"fallback\":false}"
+    ));
+}
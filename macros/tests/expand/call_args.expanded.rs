@@ -0,0 +1,105 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{CallArgs, method::ContractMethodInputBuilder};
+
+struct TransferArgs {
+    recipient: [u8; 32],
+    amount: u64,
+    memo: String,
+}
+impl pchain_sdk::method::CallArgs for TransferArgs {
+    fn into_call_arguments(self) -> Vec<u8> {
+        #[allow(unused_mut)]
+        let mut builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        builder.add(self.recipient);
+        builder.add(self.amount);
+        builder.add(self.memo);
+        builder.to_call_arguments()
+    }
+}
+
+struct PairArgs(u32, bool);
+impl pchain_sdk::method::CallArgs for PairArgs {
+    fn into_call_arguments(self) -> Vec<u8> {
+        #[allow(unused_mut)]
+        let mut builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        builder.add(self.0);
+        builder.add(self.1);
+        builder.to_call_arguments()
+    }
+}
+
+struct NoArgs;
+impl pchain_sdk::method::CallArgs for NoArgs {
+    fn into_call_arguments(self) -> Vec<u8> {
+        #[allow(unused_mut)]
+        let mut builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        builder.to_call_arguments()
+    }
+}
+
+fn main() {
+    let args = TransferArgs {
+        recipient: [1u8; 32],
+        amount: 100,
+        memo: "rent".to_string(),
+    }
+    .into_call_arguments();
+    let expected = ContractMethodInputBuilder::new()
+        .add([1u8; 32])
+        .add(100u64)
+        .add("rent".to_string())
+        .to_call_arguments();
+
+    match (&args, &expected) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let args = PairArgs(7, true).into_call_arguments();
+    match (
+        &args,
+        &ContractMethodInputBuilder::new()
+            .add(7u32)
+            .add(true)
+            .to_call_arguments(),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (
+        &NoArgs.into_call_arguments(),
+        &ContractMethodInputBuilder::new().to_call_arguments(),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
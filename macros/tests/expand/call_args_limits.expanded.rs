@@ -0,0 +1,353 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, ContractMethodInput};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    total: u64,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            total: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.total.__save_storage(&field.add(0usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            total: if names.contains(&"total") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"total") {
+            self.total.__save_storage(&field.add(0usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_total() -> u64;
+    fn set_total(_: u64);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_total() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_total(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn total(&self) -> &u64;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn total(&self) -> &u64 {
+        &self.__inner().total
+    }
+}
+
+impl MyContract {
+    fn airdrop(&mut self, recipients: Vec<[u8; 32]>, amount: u64) {
+        self.total += recipients.len() as u64 * amount;
+    }
+
+    fn rename(&mut self, name: String, tags: std::collections::BTreeSet<String>) {
+        let _ = (name, tags);
+    }
+
+    fn note(&self, note: Option<Vec<u8>>) -> bool {
+        note.is_some()
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`airdrop`|`recipients: Vec<[u8; 32]>, amount: u64`|`()`|yes|call||"]
+#[doc = "|`rename`|`name: String, tags: std::collections::BTreeSet<String>`|`()`|yes|call||"]
+#[doc = "|`note`|`note: Option<Vec<u8>>`|`bool`|yes|call (read-only)||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "airdrop".to_string(),
+                                arity: 2u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "rename".to_string(),
+                                arity: 2u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "note".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "airdrop" => {
+            if ctx.arguments.len() as u64 > 64000u64 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} accepts arguments of at most {1} bytes.",
+                        "airdrop", 64000u64
+                    ));
+                };
+            }
+            let multi_args = ctx.get_multiple_arguments();
+            if let Some(len) = pchain_sdk::ContractMethodInput::argument_len(&multi_args, 0usize) {
+                if len > 500u32 {
+                    {
+                        ::core::panicking::panic_fmt(format_args!("Argument {0} of method {1} has {2} items, but at most {3} are allowed.",
+                                    0usize, "airdrop", len, 500u32));
+                    };
+                }
+            }
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.total;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["total"])
+            };
+            let _d0: Vec<[u8; 32]> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            contract.airdrop(_d0, _d1);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["total"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "rename" => {
+            let multi_args = ctx.get_multiple_arguments();
+            if let Some(len) = pchain_sdk::ContractMethodInput::argument_len(&multi_args, 0usize) {
+                if len > 64u32 {
+                    {
+                        ::core::panicking::panic_fmt(format_args!("Argument {0} of method {1} has {2} items, but at most {3} are allowed.",
+                                    0usize, "rename", len, 64u32));
+                    };
+                }
+            }
+            if let Some(len) = pchain_sdk::ContractMethodInput::argument_len(&multi_args, 1usize) {
+                if len > 64u32 {
+                    {
+                        ::core::panicking::panic_fmt(format_args!("Argument {0} of method {1} has {2} items, but at most {3} are allowed.",
+                                    1usize, "rename", len, 64u32));
+                    };
+                }
+            }
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {};
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &[])
+            };
+            let _d0: String =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: std::collections::BTreeSet<String> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            contract.rename(_d0, _d1);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &[]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "note" => {
+            if ctx.arguments.len() as u64 > 1024u64 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} accepts arguments of at most {1} bytes.",
+                        "note", 1024u64
+                    ));
+                };
+            }
+            let multi_args = ctx.get_multiple_arguments();
+            let contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {};
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &[])
+            };
+            let _d0: Option<Vec<u8>> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let ret_cb = contract.note(_d0);
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"airdrop\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"recipients\",\"type\":\"Vec<[u8; 32]>\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"rename\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"name\",\"type\":\"String\"},{\"name\":\"tags\",\"type\":\"std::collections::BTreeSet<String>\"}],\"returns\":null},{\"name\":\"note\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"note\",\"type\":\"Option<Vec<u8>>\"}],\"returns\":\"bool\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {
+    let args =
+        ::alloc::boxed::box_assume_init_into_vec_unsafe(::alloc::intrinsics::write_box_via_move(
+            ::alloc::boxed::Box::new_uninit(),
+            [
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [3, 0, 0, 0, 1, 2, 3],
+                    ),
+                ),
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [1, 0],
+                    ),
+                ),
+            ],
+        ));
+    match (&ContractMethodInput::argument_len(&args, 0), &Some(3)) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&ContractMethodInput::argument_len(&args, 1), &None) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&ContractMethodInput::argument_len(&args, 2), &None) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
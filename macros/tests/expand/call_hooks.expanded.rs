@@ -0,0 +1,284 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, view};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    counter: u64,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            counter: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.counter.__save_storage(&field.add(0usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            counter: if names.contains(&"counter") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"counter") {
+            self.counter.__save_storage(&field.add(0usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_counter() -> u64;
+    fn set_counter(_: u64);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_counter() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_counter(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn counter(&self) -> &u64;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn counter(&self) -> &u64 {
+        &self.__inner().counter
+    }
+}
+
+impl MyContract {
+    fn __before_call(method: &str) {
+        pchain_sdk::log(b"Before", method.as_bytes());
+    }
+
+    fn __after_call(method: &str) {
+        pchain_sdk::log(b"After", method.as_bytes());
+    }
+
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+
+    fn counter(contract: pchain_sdk::ReadOnly<'_, Self>) -> u64 {
+        *contract.counter()
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`increment`||`()`|yes|call||"]
+#[doc = "|`counter`||`u64`|yes|view||"]
+#[doc = "|`multicall`|`calls: Vec<EncodedCall>`|`Vec<EncodedResult>`|no|call|Call methods of this contract in order, all or nothing.|"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    fn dispatch(ctx: &pchain_sdk::ContractMethodInput) -> pchain_sdk::ContractMethodOutput {
+        match ctx.method_name.as_str() {
+            "increment" => {
+                let mut contract = {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    let _ = |contract: &MyContract| {
+                        let _ = &contract.counter;
+                    };
+                    MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["counter"])
+                };
+                contract.increment();
+                {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    contract.__save_fields(&pchain_sdk::StoragePath::new(), &["counter"]);
+                }
+                pchain_sdk::ContractMethodOutput::default()
+            }
+            _ => {
+                ::core::panicking::panic_fmt(format_args!(
+                    "Method {0:?} cannot be called by multicall.",
+                    ctx.method_name
+                ));
+            }
+        }
+    }
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    if ctx.method_name.as_str() != pchain_sdk::method::METHODS_METHOD {
+        MyContract::__before_call(ctx.method_name.as_str());
+    }
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "increment".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "counter".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            methods.push(pchain_sdk::method::MethodInfo {
+                name: pchain_sdk::method::MULTICALL_METHOD.to_string(),
+                arity: 1,
+                payable: false,
+                internal_only: false,
+                view: false,
+            });
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "multicall" => {
+            if pchain_sdk::transaction::amount() > 0 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method multicall does not accept an attached amount."
+                    ));
+                };
+            }
+            let multi_args = ctx.get_multiple_arguments();
+            let calls: Vec<pchain_sdk::method::EncodedCall> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0);
+            let results: Vec<pchain_sdk::method::EncodedResult> = calls
+                .into_iter()
+                .enumerate()
+                .map(|(index, call)| {
+                    let ctx = pchain_sdk::ContractMethodInput {
+                        method_name: call.method_name,
+                        arguments: call.arguments,
+                    };
+                    if ctx.method_name.as_str() != pchain_sdk::method::METHODS_METHOD {
+                        MyContract::__before_call(ctx.method_name.as_str());
+                    }
+                    let result = dispatch(&ctx);
+                    if result.is_error() {
+                        {
+                            ::core::panicking::panic_fmt(format_args!(
+                                "Call {0} of multicall to method {1} returned an error.",
+                                index, ctx.method_name
+                            ));
+                        };
+                    }
+                    if ctx.method_name.as_str() != pchain_sdk::method::METHODS_METHOD {
+                        MyContract::__after_call(ctx.method_name.as_str());
+                    }
+                    pchain_sdk::method::EncodedResult {
+                        return_value: result.get(),
+                    }
+                })
+                .collect();
+            pchain_sdk::ContractMethodOutput::set(&results)
+        }
+        "increment" => dispatch(&ctx),
+        "counter" => {
+            let contract = MyContract::__load_storage(&pchain_sdk::StoragePath::new());
+            let ret_cb = MyContract::counter(pchain_sdk::ReadOnly::__new(&contract));
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if ctx.method_name.as_str() != pchain_sdk::method::METHODS_METHOD {
+        MyContract::__after_call(ctx.method_name.as_str());
+    }
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"increment\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":null},{\"name\":\"counter\",\"kind\":\"view\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"u64\"},{\"name\":\"multicall\",\"kind\":\"multicall\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"calls\",\"type\":\"Vec<EncodedCall>\"}],\"returns\":\"Vec<EncodedResult>\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {}
@@ -0,0 +1,29 @@
+use pchain_sdk::{contract, contract_methods, call, view};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(multicall)]
+impl MyContract {
+    fn __before_call(method: &str) {
+        pchain_sdk::log(b"Before", method.as_bytes());
+    }
+
+    fn __after_call(method: &str) {
+        pchain_sdk::log(b"After", method.as_bytes());
+    }
+
+    #[call]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+
+    #[view]
+    fn counter(contract: pchain_sdk::ReadOnly<'_, Self>) -> u64 {
+        *contract.counter()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,332 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    counter: u64,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            counter: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.counter.__save_storage(&field.add(0usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            counter: if names.contains(&"counter") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"counter") {
+            self.counter.__save_storage(&field.add(0usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_counter() -> u64;
+    fn set_counter(_: u64);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_counter() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_counter(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn counter(&self) -> &u64;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn counter(&self) -> &u64 {
+        &self.__inner().counter
+    }
+}
+
+impl MyContract {
+    fn on_callback(&mut self, counter: u64) {
+        self.counter = counter;
+    }
+
+    fn on_helper(&mut self) {}
+
+    fn deposit(&mut self) {}
+
+    fn on_payment(&mut self) {}
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`on_callback`|`counter: u64`|`()`|yes|call, internal only||"]
+#[doc = "|`on_helper`||`()`|yes|call, internal only||"]
+#[doc = "|`deposit`||`()`|1000 to 1000000|call||"]
+#[doc = "|`on_payment`||`()`|1 to any|call, internal only||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "on_callback".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: true,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "on_helper".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: true,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "deposit".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "on_payment".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: true,
+                                view: false,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "on_callback" => {
+            if !pchain_sdk::transaction::is_internal_call() {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} can only be called by an internal call.",
+                        "on_callback"
+                    ));
+                };
+            }
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.counter;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["counter"])
+            };
+            let _d0: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            contract.on_callback(_d0);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["counter"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "on_helper" => {
+            if !pchain_sdk::transaction::is_internal_call()
+                && pchain_sdk::transaction::calling_account()
+                    != [
+                        54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198,
+                        106, 219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+                    ]
+            {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} can only be called by an internal call.",
+                        "on_helper"
+                    ));
+                };
+            }
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {};
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &[])
+            };
+            contract.on_helper();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &[]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "deposit" => {
+            if pchain_sdk::transaction::amount() < 1000u64 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} requires an attached amount of at least {1}.",
+                        "deposit", 1000u64
+                    ));
+                };
+            }
+            if pchain_sdk::transaction::amount() > 1000000u64 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} accepts an attached amount of at most {1}.",
+                        "deposit", 1000000u64
+                    ));
+                };
+            }
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {};
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &[])
+            };
+            contract.deposit();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &[]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "on_payment" => {
+            if !pchain_sdk::transaction::is_internal_call() {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} can only be called by an internal call.",
+                        "on_payment"
+                    ));
+                };
+            }
+            if pchain_sdk::transaction::amount() < 1u64 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method {0} requires an attached amount of at least {1}.",
+                        "on_payment", 1u64
+                    ));
+                };
+            }
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {};
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &[])
+            };
+            contract.on_payment();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &[]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"on_callback\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":true,\"args\":[{\"name\":\"counter\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"on_helper\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":true,\"args\":[],\"returns\":null},{\"name\":\"deposit\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":null},{\"name\":\"on_payment\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":true,\"args\":[],\"returns\":null}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str =
+    "{\"version\":\"1.2.0\",\"author\":\"ParallelChain Lab\",\"license\":\"Apache-2.0\",\"repo\":\"https://github.com/parallelchain-io/pchain-sdk\"}";
+fn main() {
+    if !CONTRACT_METADATA.contains("\"version\":\"1.2.0\"") {
+        ::core::panicking::panic(
+            "assertion failed: CONTRACT_METADATA.contains(\"\\\"version\\\":\\\"1.2.0\\\"\")",
+        )
+    };
+}
@@ -0,0 +1,252 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, contract_config, call};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    orders: u32,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            orders: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.orders.__save_storage(&field.add(0usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            orders: if names.contains(&"orders") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u32>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"orders") {
+            self.orders.__save_storage(&field.add(0usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_orders() -> u32;
+    fn set_orders(_: u32);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_orders() -> u32 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_orders(mut value: u32) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn orders(&self) -> &u32;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn orders(&self) -> &u32 {
+        &self.__inner().orders
+    }
+}
+
+impl MyContract {
+    const FEE: u64 = 1_000;
+    const MIN_PRICE: i64 = -5;
+    const ENABLED: bool = true;
+    const NAME: &'static str = "order \"book\"";
+}
+impl MyContract {
+    #[doc = r" Configuration constants of this contract in JSON, specified by `#[contract_config]`."]
+    pub const CONTRACT_CONFIG: &'static str =
+        "{\"FEE\":1000,\"MIN_PRICE\":-5,\"ENABLED\":true,\"NAME\":\"order \\\"book\\\"\"}";
+    #[doc(hidden)]
+    pub fn __config() -> Option<Vec<(String, String)>> {
+        Some(::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [
+                    ("FEE".to_string(), "1000".to_string()),
+                    ("MIN_PRICE".to_string(), "-5".to_string()),
+                    ("ENABLED".to_string(), "true".to_string()),
+                    ("NAME".to_string(), "order \"book\"".to_string()),
+                ],
+            ),
+        ))
+    }
+}
+impl MyContract {
+    fn place_order(&mut self) {
+        if self.orders < Self::FEE as u32 && Self::ENABLED {
+            self.orders += 1;
+        }
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`place_order`||`()`|yes|call||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [pchain_sdk::method::MethodInfo {
+                            name: "place_order".to_string(),
+                            arity: 0u32,
+                            payable: true,
+                            internal_only: false,
+                            view: false,
+                        }],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "place_order" => {
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.orders;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["orders"])
+            };
+            contract.place_order();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["orders"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"place_order\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":null}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {
+    match (
+        &MyContract::CONTRACT_CONFIG,
+        &r#"{"FEE":1000,"MIN_PRICE":-5,"ENABLED":true,"NAME":"order \"book\""}"#,
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (
+        &MyContract::__config().unwrap()[0],
+        &("FEE".to_string(), "1000".to_string()),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let _ = (MyContract::MIN_PRICE, MyContract::NAME);
+}
@@ -0,0 +1,30 @@
+use pchain_sdk::{contract, contract_methods, contract_config, call};
+
+#[contract]
+pub struct MyContract {
+    orders: u32,
+}
+
+#[contract_config]
+impl MyContract {
+    const FEE: u64 = 1_000;
+    const MIN_PRICE: i64 = -5;
+    const ENABLED: bool = true;
+    const NAME: &'static str = "order \"book\"";
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn place_order(&mut self) {
+        if self.orders < Self::FEE as u32 && Self::ENABLED {
+            self.orders += 1;
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(MyContract::CONTRACT_CONFIG, r#"{"FEE":1000,"MIN_PRICE":-5,"ENABLED":true,"NAME":"order \"book\""}"#);
+    assert_eq!(MyContract::__config().unwrap()[0], ("FEE".to_string(), "1000".to_string()));
+    let _ = (MyContract::MIN_PRICE, MyContract::NAME);
+}
@@ -0,0 +1,321 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    counter: u64,
+    name: String,
+    owner: [u8; 32],
+    history: Vec<u32>,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            counter: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            name: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+            owner: pchain_sdk::Storable::__load_storage(&field.add(2usize as u8)),
+            history: pchain_sdk::Storable::__load_storage(&field.add(3usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.counter.__save_storage(&field.add(0usize as u8));
+        self.name.__save_storage(&field.add(1usize as u8));
+        self.owner.__save_storage(&field.add(2usize as u8));
+        self.history.__save_storage(&field.add(3usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            counter: if names.contains(&"counter") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            name: if names.contains(&"name") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<String>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+            owner: if names.contains(&"owner") {
+                pchain_sdk::Storable::__load_storage(&field.add(2usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<[u8; 32]>(std::marker::PhantomData))
+                    .__unloaded(&field.add(2usize as u8))
+            },
+            history: if names.contains(&"history") {
+                pchain_sdk::Storable::__load_storage(&field.add(3usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<Vec<u32>>(std::marker::PhantomData))
+                    .__unloaded(&field.add(3usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"counter") {
+            self.counter.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"name") {
+            self.name.__save_storage(&field.add(1usize as u8));
+        }
+        if names.contains(&"owner") {
+            self.owner.__save_storage(&field.add(2usize as u8));
+        }
+        if names.contains(&"history") {
+            self.history.__save_storage(&field.add(3usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_counter() -> u64;
+    fn set_counter(_: u64);
+    fn get_name() -> String;
+    fn set_name(_: String);
+    fn get_owner() -> [u8; 32];
+    fn set_owner(_: [u8; 32]);
+    fn get_history() -> Vec<u32>;
+    fn set_history(_: Vec<u32>);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_counter() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_counter(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_name() -> String {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_name(mut value: String) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+    fn get_owner() -> [u8; 32] {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(2usize as u8))
+    }
+    fn set_owner(mut value: [u8; 32]) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(2usize as u8));
+    }
+    fn get_history() -> Vec<u32> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(3usize as u8))
+    }
+    fn set_history(mut value: Vec<u32>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(3usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn counter(&self) -> &u64;
+    fn name(&self) -> &String;
+    fn owner(&self) -> &[u8; 32];
+    fn history(&self) -> &Vec<u32>;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn counter(&self) -> &u64 {
+        &self.__inner().counter
+    }
+    fn name(&self) -> &String {
+        &self.__inner().name
+    }
+    fn owner(&self) -> &[u8; 32] {
+        &self.__inner().owner
+    }
+    fn history(&self) -> &Vec<u32> {
+        &self.__inner().history
+    }
+}
+
+impl MyContract {
+    // receiver &self
+    fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    // receiver &mut self, with arguments
+    fn set_name(&mut self, name: String, counter: u64) {
+        self.name = name;
+        self.counter = counter;
+    }
+
+    // no receiver
+    fn version() -> u32 {
+        1
+    }
+
+    // not a contract method
+    #[allow(dead_code)]
+    fn helper(&self) -> usize {
+        self.history.len()
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`counter`||`u64`|yes|call (read-only)||"]
+#[doc = "|`set_name`|`name: String, counter: u64`|`()`|yes|call||"]
+#[doc = "|`version`||`u32`|yes|call (read-only)||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "counter".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "set_name".to_string(),
+                                arity: 2u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "version".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "counter" => {
+            let contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.counter;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["counter"])
+            };
+            let ret_cb = contract.counter();
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "set_name" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.name;
+                    let _ = &contract.counter;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["name", "counter"])
+            };
+            let _d0: String =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            contract.set_name(_d0, _d1);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["name", "counter"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "version" => {
+            let ret_cb = MyContract::version();
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"counter\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"u64\"},{\"name\":\"set_name\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"name\",\"type\":\"String\"},{\"name\":\"counter\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"version\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"u32\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {}
@@ -0,0 +1,112 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::contract_types;
+
+type PublicAddress = [u8; 32];
+
+pub struct Order {
+    /// Account which placed the order.
+    pub maker: PublicAddress,
+    pub amount: u64,
+    pub side: Side,
+}
+#[automatically_derived]
+impl ::core::clone::Clone for Order {
+    #[inline]
+    fn clone(&self) -> Order {
+        Order {
+            maker: ::core::clone::Clone::clone(&self.maker),
+            amount: ::core::clone::Clone::clone(&self.amount),
+            side: ::core::clone::Clone::clone(&self.side),
+        }
+    }
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for Order {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::debug_struct_field3_finish(
+            f,
+            "Order",
+            "maker",
+            &self.maker,
+            "amount",
+            &self.amount,
+            "side",
+            &&self.side,
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for Order {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for Order {
+    #[inline]
+    fn eq(&self, other: &Order) -> bool {
+        self.amount == other.amount && self.maker == other.maker && self.side == other.side
+    }
+}
+
+pub enum Side {
+    Buy,
+    Sell,
+}
+#[automatically_derived]
+#[doc(hidden)]
+unsafe impl ::core::clone::TrivialClone for Side {}
+#[automatically_derived]
+impl ::core::clone::Clone for Side {
+    #[inline]
+    fn clone(&self) -> Side {
+        *self
+    }
+}
+#[automatically_derived]
+impl ::core::marker::Copy for Side {}
+#[automatically_derived]
+impl ::core::fmt::Debug for Side {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(
+            f,
+            match self {
+                Side::Buy => "Buy",
+                Side::Sell => "Sell",
+            },
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for Side {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for Side {
+    #[inline]
+    fn eq(&self, other: &Side) -> bool {
+        let __self_discr = ::core::intrinsics::discriminant_value(self);
+        let __arg1_discr = ::core::intrinsics::discriminant_value(other);
+        __self_discr == __arg1_discr
+    }
+}
+
+fn main() {
+    let order = Order {
+        maker: [1u8; 32],
+        amount: 10,
+        side: Side::Buy,
+    };
+    match (&order.clone(), &order) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,25 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    owner: [u8; 32],
+}
+
+#[contract_methods(decommissionable)]
+impl MyContract {
+    #[call]
+    fn sunset(&mut self, beneficiary: [u8; 32]) -> u64 {
+        assert!(pchain_sdk::transaction::calling_account() == self.owner);
+        pchain_sdk::lifecycle::decommission(beneficiary)
+    }
+
+    #[call]
+    fn owner(&self) -> [u8; 32] {
+        self.owner
+    }
+}
+
+fn main() {
+    let _: fn(&mut MyContract, [u8; 32]) -> u64 = MyContract::sunset;
+    let _: extern "C" fn() = entrypoint;
+}
@@ -0,0 +1,260 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{Event, types::Log};
+
+#[topic = "Transfer"]
+pub struct TransferEvent {
+    #[indexed]
+    pub from: [u8; 32],
+    #[indexed]
+    pub to: [u8; 32],
+    pub amount: u64,
+}
+impl pchain_sdk::event::Event for TransferEvent {
+    const NAME: &'static str = "Transfer";
+    const VERSION: u32 = 1;
+    const TOPIC: &'static [u8] =
+        b"\xdd\xe8\xbe\xf7\x8c\xbbr\x06\x83\xfa\x1f\xe7k\xfb\x90\x05\x92\t\x9e\xd44n\xd9\x95\xbc\xbcQN\x9a\xa6rV";
+    const SCHEMA: &'static str =
+        "{\"name\":\"Transfer\",\"version\":1,\"topic_sha256\":\"dde8bef78cbb720683fa1fe76bfb900592099ed4346ed995bcbc514e9aa67256\",\"indexed\":[\"from\",\"to\"],\"fields\":[[\"from\",\"[u8; 32]\"],[\"to\",\"[u8; 32]\"],[\"amount\",\"u64\"]]}";
+    const INDEXED: &'static [&'static str] = &["from", "to"];
+    fn topic(&self) -> Vec<u8> {
+        let mut topic = Self::TOPIC.to_vec();
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.from, &mut topic).unwrap();
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.to, &mut topic).unwrap();
+        topic
+    }
+}
+impl pchain_sdk::borsh::BorshSerialize for TransferEvent {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.from, writer)?;
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.to, writer)?;
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+        Ok(())
+    }
+}
+impl pchain_sdk::borsh::BorshDeserialize for TransferEvent {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            from: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            to: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+const _: () = {};
+#[automatically_derived]
+impl ::core::fmt::Debug for TransferEvent {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::debug_struct_field3_finish(
+            f,
+            "TransferEvent",
+            "from",
+            &self.from,
+            "to",
+            &self.to,
+            "amount",
+            &&self.amount,
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for TransferEvent {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for TransferEvent {
+    #[inline]
+    fn eq(&self, other: &TransferEvent) -> bool {
+        self.amount == other.amount && self.from == other.from && self.to == other.to
+    }
+}
+
+pub struct Paused {
+    pub reason: String,
+}
+impl pchain_sdk::event::Event for Paused {
+    const NAME: &'static str = "Paused";
+    const VERSION: u32 = 1;
+    const TOPIC: &'static [u8] =
+        b"\xe1Y\xb0a\x87\xd3i\xa0a_f\xceWu\x82\xb5\xc9\x83\xd4\xeaY\xb3\xa6+p I\xf6\x17S\xf0\xbf";
+    const SCHEMA: &'static str =
+        "{\"name\":\"Paused\",\"version\":1,\"topic_sha256\":\"e159b06187d369a0615f66ce577582b5c983d4ea59b3a62b702049f61753f0bf\",\"indexed\":[],\"fields\":[[\"reason\",\"String\"]]}";
+    const INDEXED: &'static [&'static str] = &[];
+}
+impl pchain_sdk::borsh::BorshSerialize for Paused {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.reason, writer)?;
+        Ok(())
+    }
+}
+impl pchain_sdk::borsh::BorshDeserialize for Paused {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            reason: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+const _: () = {};
+#[automatically_derived]
+impl ::core::fmt::Debug for Paused {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::debug_struct_field1_finish(f, "Paused", "reason", &&self.reason)
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for Paused {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for Paused {
+    #[inline]
+    fn eq(&self, other: &Paused) -> bool {
+        self.reason == other.reason
+    }
+}
+
+fn main() {
+    match (&TransferEvent::NAME, &"Transfer") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&TransferEvent::TOPIC.len(), &32) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&&TransferEvent::TOPIC[..4], &&[0xdd, 0xe8, 0xbe, 0xf7]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&TransferEvent::INDEXED, &&["from", "to"]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !TransferEvent::SCHEMA.contains("\"indexed\":[\"from\",\"to\"]") {
+        ::core::panicking::panic("assertion failed: TransferEvent::SCHEMA.contains(\"\\\"indexed\\\":[\\\"from\\\",\\\"to\\\"]\")")
+    };
+    let transfer = TransferEvent {
+        from: [1u8; 32],
+        to: [2u8; 32],
+        amount: 100,
+    };
+    let topic = transfer.topic();
+    match (&topic.len(), &96) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !topic.starts_with(TransferEvent::TOPIC) {
+        ::core::panicking::panic("assertion failed: topic.starts_with(TransferEvent::TOPIC)")
+    };
+    match (&&topic[32..64], &&[1u8; 32]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&&topic[64..], &&[2u8; 32]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let log = Log {
+        topic,
+        value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&transfer).unwrap(),
+    };
+    match (&TransferEvent::decode(&log), &Some(transfer)) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !Paused::decode(&log).is_none() {
+        ::core::panicking::panic("assertion failed: Paused::decode(&log).is_none()")
+    };
+    let paused = Paused {
+        reason: "upgrade".to_string(),
+    };
+    match (&paused.topic(), &Paused::TOPIC) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !Paused::INDEXED.is_empty() {
+        ::core::panicking::panic("assertion failed: Paused::INDEXED.is_empty()")
+    };
+}
@@ -0,0 +1,294 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{event, use_contract, Event, types::Log};
+
+pub struct TransferV1 {
+    pub to: [u8; 32],
+    pub amount: u64,
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for TransferV1 {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::debug_struct_field2_finish(
+            f,
+            "TransferV1",
+            "to",
+            &self.to,
+            "amount",
+            &&self.amount,
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for TransferV1 {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for TransferV1 {
+    #[inline]
+    fn eq(&self, other: &TransferV1) -> bool {
+        self.amount == other.amount && self.to == other.to
+    }
+}
+impl pchain_sdk::event::Event for TransferV1 {
+    const NAME: &'static str = "Transfer";
+    const VERSION: u32 = 1u32;
+    const TOPIC: &'static [u8] = b"Transfer";
+    const SCHEMA: &'static str =
+        "{\"name\":\"Transfer\",\"version\":1,\"topic\":\"Transfer\",\"fields\":[[\"to\",\"[u8; 32]\"],[\"amount\",\"u64\"]]}";
+}
+impl pchain_sdk::borsh::BorshSerialize for TransferV1 {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.to, writer)?;
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+        Ok(())
+    }
+}
+impl pchain_sdk::borsh::BorshDeserialize for TransferV1 {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            to: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+const _: () = {};
+
+pub struct Transfer {
+    pub to: [u8; 32],
+    pub amount: u64,
+    pub memo: String,
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for Transfer {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::debug_struct_field3_finish(
+            f,
+            "Transfer",
+            "to",
+            &self.to,
+            "amount",
+            &self.amount,
+            "memo",
+            &&self.memo,
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for Transfer {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for Transfer {
+    #[inline]
+    fn eq(&self, other: &Transfer) -> bool {
+        self.amount == other.amount && self.to == other.to && self.memo == other.memo
+    }
+}
+impl pchain_sdk::event::Event for Transfer {
+    const NAME: &'static str = "Transfer";
+    const VERSION: u32 = 2u32;
+    const TOPIC: &'static [u8] = b"Transfer@2";
+    const SCHEMA: &'static str =
+        "{\"name\":\"Transfer\",\"version\":2,\"topic\":\"Transfer@2\",\"fields\":[[\"to\",\"[u8; 32]\"],[\"amount\",\"u64\"],[\"memo\",\"String\"]]}";
+    fn decode_previous(log: &pchain_sdk::types::Log) -> Option<Self> {
+        const _: () = if !(<TransferV1 as pchain_sdk::event::Event>::VERSION < 2u32) {
+            {
+                ::core::panicking::panic_fmt(format_args!(
+                    "previous version of event must have a lower version"
+                ));
+            }
+        };
+        <TransferV1 as pchain_sdk::event::Event>::decode(log).map(Self::from)
+    }
+}
+impl pchain_sdk::borsh::BorshSerialize for Transfer {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.to, writer)?;
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+        pchain_sdk::borsh::BorshSerialize::serialize(&self.memo, writer)?;
+        Ok(())
+    }
+}
+impl pchain_sdk::borsh::BorshDeserialize for Transfer {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            to: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            memo: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+const _: () = {};
+impl From<TransferV1> for Transfer {
+    fn from(v1: TransferV1) -> Self {
+        Self {
+            to: v1.to,
+            amount: v1.amount,
+            memo: String::new(),
+        }
+    }
+}
+pub mod my_token {
+    #[allow(unused_imports)]
+    use super::*;
+    pub struct Transfer {
+        pub to: [u8; 32],
+        pub amount: u64,
+        pub memo: String,
+    }
+    impl Transfer {
+        #[doc = r" Topic of the logs of this event."]
+        pub const TOPIC: &'static [u8] = b"Transfer@2";
+        #[doc = r" Decode the log, if it is of this event."]
+        pub fn decode(log: &pchain_sdk::types::Log) -> Option<Self> {
+            if log.topic.as_slice() != Self::TOPIC {
+                return None;
+            }
+            <Self as pchain_sdk::borsh::BorshDeserialize>::try_from_slice(&log.value).ok()
+        }
+    }
+    impl pchain_sdk::borsh::BorshSerialize for Transfer {
+        fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.to, writer)?;
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.memo, writer)?;
+            Ok(())
+        }
+    }
+    impl pchain_sdk::borsh::BorshDeserialize for Transfer {
+        fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+            Ok(Self {
+                to: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+                amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+                memo: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            })
+        }
+    }
+}
+fn main() {
+    match (&TransferV1::TOPIC, &b"Transfer") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&Transfer::TOPIC, &b"Transfer@2") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&Transfer::TOPIC, &my_token::Transfer::TOPIC) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (
+        &pchain_sdk::event::parse_topic(Transfer::TOPIC),
+        &Some(("Transfer", 2)),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let v1 = TransferV1 {
+        to: [1u8; 32],
+        amount: 100,
+    };
+    let log = Log {
+        topic: TransferV1::TOPIC.to_vec(),
+        value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&v1).unwrap(),
+    };
+    match (
+        &Transfer::decode(&log),
+        &Some(Transfer {
+            to: [1u8; 32],
+            amount: 100,
+            memo: String::new(),
+        }),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let v2 = Transfer {
+        to: [2u8; 32],
+        amount: 5,
+        memo: "rent".to_string(),
+    };
+    let log = Log {
+        topic: Transfer::TOPIC.to_vec(),
+        value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&v2).unwrap(),
+    };
+    if !TransferV1::decode(&log).is_none() {
+        ::core::panicking::panic("assertion failed: TransferV1::decode(&log).is_none()")
+    };
+    match (
+        &my_token::Transfer::decode(&log).map(|t| t.memo),
+        &Some("rent".to_string()),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&Transfer::decode(&log), &Some(v2)) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,27 @@
+use pchain_sdk::{contract, contract_methods, call, fallback};
+
+#[contract]
+pub struct MyContract {
+    unknown_calls: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn unknown_calls(&self) -> u64 {
+        self.unknown_calls
+    }
+
+    #[fallback]
+    fn fallback(&mut self, method: Vec<u8>, arguments: Vec<u8>) -> Vec<u8> {
+        self.unknown_calls += 1;
+        [method, arguments].concat()
+    }
+}
+
+fn main() {
+    let _: fn(&mut MyContract, Vec<u8>, Vec<u8>) -> Vec<u8> = MyContract::fallback;
+    assert_eq!(pchain_sdk::transaction::sanitize_method_name(b"transfer"), "transfer");
+    assert_eq!(pchain_sdk::transaction::sanitize_method_name(b"trans\0fer"), "trans\u{FFFD}fer");
+    assert_eq!(pchain_sdk::transaction::sanitize_method_name(&[0x74, 0xff]), "t\u{FFFD}");
+}
@@ -0,0 +1,401 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, invariant, fields, collections::FastMap};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    owner: [u8; 32],
+    total_supply: u64,
+    balances: FastMap<[u8; 32], u64>,
+    paused: bool,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            owner: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            total_supply: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+            balances: pchain_sdk::Storable::__load_storage(&field.add(2usize as u8)),
+            paused: pchain_sdk::Storable::__load_storage(&field.add(3usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.owner.__save_storage(&field.add(0usize as u8));
+        self.total_supply.__save_storage(&field.add(1usize as u8));
+        self.balances.__save_storage(&field.add(2usize as u8));
+        self.paused.__save_storage(&field.add(3usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            owner: if names.contains(&"owner") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<[u8; 32]>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            total_supply: if names.contains(&"total_supply") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+            balances: if names.contains(&"balances") {
+                pchain_sdk::Storable::__load_storage(&field.add(2usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<FastMap<[u8; 32], u64>>(
+                    std::marker::PhantomData,
+                ))
+                    .__unloaded(&field.add(2usize as u8))
+            },
+            paused: if names.contains(&"paused") {
+                pchain_sdk::Storable::__load_storage(&field.add(3usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<bool>(std::marker::PhantomData))
+                    .__unloaded(&field.add(3usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"owner") {
+            self.owner.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"total_supply") {
+            self.total_supply.__save_storage(&field.add(1usize as u8));
+        }
+        if names.contains(&"balances") {
+            self.balances.__save_storage(&field.add(2usize as u8));
+        }
+        if names.contains(&"paused") {
+            self.paused.__save_storage(&field.add(3usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_owner() -> [u8; 32];
+    fn set_owner(_: [u8; 32]);
+    fn get_total_supply() -> u64;
+    fn set_total_supply(_: u64);
+    fn get_balances() -> FastMap<[u8; 32], u64>;
+    fn set_balances(_: FastMap<[u8; 32], u64>);
+    fn get_paused() -> bool;
+    fn set_paused(_: bool);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_owner() -> [u8; 32] {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_owner(mut value: [u8; 32]) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_total_supply() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_total_supply(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+    fn get_balances() -> FastMap<[u8; 32], u64> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(2usize as u8))
+    }
+    fn set_balances(mut value: FastMap<[u8; 32], u64>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(2usize as u8));
+    }
+    fn get_paused() -> bool {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(3usize as u8))
+    }
+    fn set_paused(mut value: bool) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(3usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn owner(&self) -> &[u8; 32];
+    fn total_supply(&self) -> &u64;
+    fn balances(&self) -> &FastMap<[u8; 32], u64>;
+    fn paused(&self) -> &bool;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn owner(&self) -> &[u8; 32] {
+        &self.__inner().owner
+    }
+    fn total_supply(&self) -> &u64 {
+        &self.__inner().total_supply
+    }
+    fn balances(&self) -> &FastMap<[u8; 32], u64> {
+        &self.__inner().balances
+    }
+    fn paused(&self) -> &bool {
+        &self.__inner().paused
+    }
+}
+
+impl MyContract {
+    fn pause(&mut self, paused: bool) {
+        match (&self.owner, &pchain_sdk::transaction::calling_account()) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        self.paused = paused;
+    }
+    fn mint(&mut self, to: [u8; 32], amount: u64) {
+        let balance = self.balances.get(&to).unwrap_or(0);
+        self.balances.insert(&to, balance + amount);
+        self.total_supply += amount;
+    }
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+    fn supply_covers_balances(&self) -> bool {
+        self.balances.get(&self.owner).unwrap_or(0) <= self.total_supply
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`pause`|`paused: bool`|`()`|yes|call||"]
+#[doc = "|`mint`|`to: [u8; 32], amount: u64`|`()`|yes|call||"]
+#[doc = "|`is_paused`||`bool`|yes|call (read-only)||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "pause".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "mint".to_string(),
+                                arity: 2u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "is_paused".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "pause" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.owner;
+                    let _ = &contract.paused;
+                    let _ = &contract.balances;
+                    let _ = &contract.total_supply;
+                };
+                MyContract::__load_fields(
+                    &pchain_sdk::StoragePath::new(),
+                    &["owner", "paused", "balances", "total_supply"],
+                )
+            };
+            let _d0: bool =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            contract.pause(_d0);
+            if !contract.supply_covers_balances() {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Invariant {0} is violated by {1}.",
+                        "supply_covers_balances", "pause"
+                    ));
+                };
+            }
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(
+                    &pchain_sdk::StoragePath::new(),
+                    &["owner", "paused", "balances", "total_supply"],
+                );
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "mint" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.balances;
+                    let _ = &contract.total_supply;
+                    let _ = &contract.owner;
+                };
+                MyContract::__load_fields(
+                    &pchain_sdk::StoragePath::new(),
+                    &["balances", "total_supply", "owner"],
+                )
+            };
+            let _d0: [u8; 32] =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            contract.mint(_d0, _d1);
+            if !contract.supply_covers_balances() {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Invariant {0} is violated by {1}.",
+                        "supply_covers_balances", "mint"
+                    ));
+                };
+            }
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(
+                    &pchain_sdk::StoragePath::new(),
+                    &["balances", "total_supply", "owner"],
+                );
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "is_paused" => {
+            let contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.paused;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["paused"])
+            };
+            let ret_cb = contract.is_paused();
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => {
+                    if MyContract::__fungible_token_methods()
+                        .into_iter()
+                        .chain(MyContract::__nft_methods())
+                        .any(|info| info.name == ctx.method_name && !info.view)
+                    {
+                        let contract = {
+                            #[allow(unused_imports)]
+                            use pchain_sdk::storage::__AllFields;
+                            MyContract::__load_fields(
+                                &pchain_sdk::StoragePath::new(),
+                                &["balances", "owner", "total_supply"],
+                            )
+                        };
+                        if !contract.supply_covers_balances() {
+                            {
+                                ::core::panicking::panic_fmt(format_args!(
+                                    "Invariant {0} is violated by {1}.",
+                                    "supply_covers_balances", ctx.method_name
+                                ));
+                            };
+                        }
+                    }
+                    callresult
+                }
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"pause\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"paused\",\"type\":\"bool\"}],\"returns\":null},{\"name\":\"mint\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"to\",\"type\":\"[u8; 32]\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"is_paused\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"bool\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {}
@@ -0,0 +1,38 @@
+use pchain_sdk::{contract, contract_methods, call, invariant, fields, collections::FastMap};
+
+#[contract]
+pub struct MyContract {
+    owner: [u8; 32],
+    total_supply: u64,
+    balances: FastMap<[u8; 32], u64>,
+    paused: bool,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn pause(&mut self, paused: bool) {
+        assert_eq!(self.owner, pchain_sdk::transaction::calling_account());
+        self.paused = paused;
+    }
+
+    #[call]
+    #[fields(balances, total_supply)]
+    fn mint(&mut self, to: [u8; 32], amount: u64) {
+        let balance = self.balances.get(&to).unwrap_or(0);
+        self.balances.insert(&to, balance + amount);
+        self.total_supply += amount;
+    }
+
+    #[call]
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    #[invariant]
+    fn supply_covers_balances(&self) -> bool {
+        self.balances.get(&self.owner).unwrap_or(0) <= self.total_supply
+    }
+}
+
+fn main() {}
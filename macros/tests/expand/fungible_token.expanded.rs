@@ -0,0 +1,325 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, standards::fungible_token::FungibleToken};
+
+use pchain_sdk::Storable;
+pub struct MyToken {
+    owner: [u8; 32],
+    token: FungibleToken,
+}
+impl MyToken {
+    #[doc(hidden)]
+    pub const __STANDARD: &'static str = "fungible_token";
+    #[doc(hidden)]
+    pub fn __fungible_token(
+        ctx: &pchain_sdk::ContractMethodInput,
+    ) -> Option<pchain_sdk::ContractMethodOutput> {
+        pchain_sdk::standards::fungible_token::__dispatch(
+            &pchain_sdk::StoragePath::new().add(1u8),
+            ctx,
+        )
+    }
+    #[doc(hidden)]
+    pub fn __fungible_token_methods() -> Vec<pchain_sdk::method::MethodInfo> {
+        pchain_sdk::standards::fungible_token::__methods()
+    }
+}
+impl pchain_sdk::Storable for MyToken {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyToken {
+            owner: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            token: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.owner.__save_storage(&field.add(0usize as u8));
+        self.token.__save_storage(&field.add(1usize as u8));
+    }
+}
+impl MyToken {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyToken {
+            owner: if names.contains(&"owner") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<[u8; 32]>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            token: if names.contains(&"token") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<FungibleToken>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"owner") {
+            self.owner.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"token") {
+            self.token.__save_storage(&field.add(1usize as u8));
+        }
+    }
+}
+trait MyTokenAccesser {
+    fn get() -> MyToken;
+    fn set(&mut self);
+    fn get_owner() -> [u8; 32];
+    fn set_owner(_: [u8; 32]);
+    fn get_token() -> FungibleToken;
+    fn set_token(_: FungibleToken);
+}
+impl MyTokenAccesser for MyToken {
+    fn get() -> MyToken {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_owner() -> [u8; 32] {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_owner(mut value: [u8; 32]) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_token() -> FungibleToken {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_token(mut value: FungibleToken) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyTokenReadOnly {
+    fn owner(&self) -> &[u8; 32];
+    fn token(&self) -> &FungibleToken;
+}
+impl<'a> MyTokenReadOnly for pchain_sdk::ReadOnly<'a, MyToken> {
+    fn owner(&self) -> &[u8; 32] {
+        &self.__inner().owner
+    }
+    fn token(&self) -> &FungibleToken {
+        &self.__inner().token
+    }
+}
+
+impl MyToken {
+    fn init(&mut self) {
+        self.owner = pchain_sdk::transaction::calling_account();
+        self.token = FungibleToken::new("My Token", "MTK", 8);
+    }
+
+    fn mint(&mut self, to: [u8; 32], amount: u64) {
+        // overrides the method of the standard
+
+        match (&self.owner, &pchain_sdk::transaction::calling_account()) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        self.token.mint(to, amount).unwrap();
+    }
+    fn decimals(&self) -> u8 {
+        self.token.metadata().decimals
+    }
+}
+#[doc = "Entrypoint of the contract `MyToken`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`init`||`()`|yes|call||"]
+#[doc = "|`mint`|`to: [u8; 32], amount: u64`|`()`|yes|call||"]
+#[doc = "|`decimals`||`u8`|yes|call (read-only)||"]
+#[doc = "|`name`||`String`|no|view (fungible_token)|Name of the token.|"]
+#[doc = "|`symbol`||`String`|no|view (fungible_token)|Symbol of the token.|"]
+#[doc = "|`total_supply`||`u64`|no|view (fungible_token)|Amount of tokens in existence.|"]
+#[doc = "|`balance_of`|`owner: PublicAddress`|`u64`|no|view (fungible_token)|Balance of the owner.|"]
+#[doc = "|`allowance`|`owner: PublicAddress, spender: PublicAddress`|`u64`|no|view (fungible_token)|Amount that the spender may transfer on behalf of the owner.|"]
+#[doc = "|`transfer`|`to: PublicAddress, amount: u64`|`Result<(), ContractError>`|no|call (fungible_token)|Transfer tokens of the caller.|"]
+#[doc = "|`approve`|`spender: PublicAddress, amount: u64`|`()`|no|call (fungible_token)|Set the allowance of the spender.|"]
+#[doc = "|`transfer_from`|`from: PublicAddress, to: PublicAddress, amount: u64`|`Result<(), ContractError>`|no|call (fungible_token)|Transfer tokens on behalf of the owner.|"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "init".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "mint".to_string(),
+                                arity: 2u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "decimals".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyToken::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyToken::__fungible_token_methods()
+                .into_iter()
+                .chain(MyToken::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "init" => {
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyToken| {
+                    let _ = &contract.owner;
+                    let _ = &contract.token;
+                };
+                MyToken::__load_fields(&pchain_sdk::StoragePath::new(), &["owner", "token"])
+            };
+            contract.init();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["owner", "token"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "mint" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyToken| {
+                    let _ = &contract.owner;
+                    let _ = &contract.token;
+                };
+                MyToken::__load_fields(&pchain_sdk::StoragePath::new(), &["owner", "token"])
+            };
+            let _d0: [u8; 32] =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            contract.mint(_d0, _d1);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["owner", "token"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "decimals" => {
+            let contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyToken| {
+                    let _ = &contract.token;
+                };
+                MyToken::__load_fields(&pchain_sdk::StoragePath::new(), &["token"])
+            };
+            let ret_cb = contract.decimals();
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyToken::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyToken::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyToken::__fungible_token(&ctx).or_else(|| MyToken::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyToken::__STANDARD, "fungible_token") {
+        {
+            ::core::panicking::panic_fmt(format_args!("MyToken must derive the standard declared by the option fungible_token of contract_methods."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyToken\",\"methods\":[{\"name\":\"init\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":null},{\"name\":\"mint\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"to\",\"type\":\"[u8; 32]\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"decimals\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"u8\"},{\"name\":\"name\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"String\"},{\"name\":\"symbol\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"String\"},{\"name\":\"total_supply\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"u64\"},{\"name\":\"balance_of\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"owner\",\"type\":\"PublicAddress\"}],\"returns\":\"u64\"},{\"name\":\"allowance\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"owner\",\"type\":\"PublicAddress\"},{\"name\":\"spender\",\"type\":\"PublicAddress\"}],\"returns\":\"u64\"},{\"name\":\"transfer\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"to\",\"type\":\"PublicAddress\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":\"Result<(), ContractError>\"},{\"name\":\"approve\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"spender\",\"type\":\"PublicAddress\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"transfer_from\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"from\",\"type\":\"PublicAddress\"},{\"name\":\"to\",\"type\":\"PublicAddress\"},{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":\"Result<(), ContractError>\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {
+    let methods = MyToken::__fungible_token_methods();
+    if !methods
+        .iter()
+        .any(|method| method.name == "transfer_from" && method.arity == 3 && !method.view)
+    {
+        ::core::panicking::panic("assertion failed: methods.iter().any(|method|\n        method.name == \"transfer_from\" && method.arity == 3 && !method.view)")
+    };
+}
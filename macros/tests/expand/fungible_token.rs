@@ -0,0 +1,34 @@
+use pchain_sdk::{contract, contract_methods, call, standards::fungible_token::FungibleToken};
+
+#[contract]
+#[derive(FungibleToken)]
+pub struct MyToken {
+    owner: [u8; 32],
+    token: FungibleToken,
+}
+
+#[contract_methods(fungible_token)]
+impl MyToken {
+    #[call]
+    fn init(&mut self) {
+        self.owner = pchain_sdk::transaction::calling_account();
+        self.token = FungibleToken::new("My Token", "MTK", 8);
+    }
+
+    #[call]
+    fn mint(&mut self, to: [u8; 32], amount: u64) {
+        assert_eq!(self.owner, pchain_sdk::transaction::calling_account());
+        self.token.mint(to, amount).unwrap();
+    }
+
+    // overrides the method of the standard
+    #[call]
+    fn decimals(&self) -> u8 {
+        self.token.metadata().decimals
+    }
+}
+
+fn main() {
+    let methods = MyToken::__fungible_token_methods();
+    assert!(methods.iter().any(|method| method.name == "transfer_from" && method.arity == 3 && !method.view));
+}
@@ -0,0 +1,456 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, invariant};
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    deposits: u64,
+    withdrawals: u64,
+    balance: u64,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            deposits: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            withdrawals: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+            balance: pchain_sdk::Storable::__load_storage(&field.add(2usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.deposits.__save_storage(&field.add(0usize as u8));
+        self.withdrawals.__save_storage(&field.add(1usize as u8));
+        self.balance.__save_storage(&field.add(2usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            deposits: if names.contains(&"deposits") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            withdrawals: if names.contains(&"withdrawals") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+            balance: if names.contains(&"balance") {
+                pchain_sdk::Storable::__load_storage(&field.add(2usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(2usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"deposits") {
+            self.deposits.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"withdrawals") {
+            self.withdrawals.__save_storage(&field.add(1usize as u8));
+        }
+        if names.contains(&"balance") {
+            self.balance.__save_storage(&field.add(2usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_deposits() -> u64;
+    fn set_deposits(_: u64);
+    fn get_withdrawals() -> u64;
+    fn set_withdrawals(_: u64);
+    fn get_balance() -> u64;
+    fn set_balance(_: u64);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_deposits() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_deposits(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_withdrawals() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_withdrawals(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+    fn get_balance() -> u64 {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(2usize as u8))
+    }
+    fn set_balance(mut value: u64) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(2usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn deposits(&self) -> &u64;
+    fn withdrawals(&self) -> &u64;
+    fn balance(&self) -> &u64;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn deposits(&self) -> &u64 {
+        &self.__inner().deposits
+    }
+    fn withdrawals(&self) -> &u64 {
+        &self.__inner().withdrawals
+    }
+    fn balance(&self) -> &u64 {
+        &self.__inner().balance
+    }
+}
+
+impl MyContract {
+    fn balance_is_conserved(&self) -> bool {
+        self.deposits - self.withdrawals == self.balance
+    }
+
+    fn withdrawals_are_covered(&self) -> bool {
+        self.withdrawals <= self.deposits
+    }
+
+    fn deposit(&mut self, amount: u64) {
+        self.deposits += amount;
+        self.balance += amount;
+    }
+
+    fn withdraw(&mut self, amount: u64) -> Result<(), String> {
+        if amount > self.balance {
+            return Err("Insufficient balance".to_string());
+        }
+        self.withdrawals += amount;
+        self.balance -= amount;
+        Ok(())
+    }
+
+    fn balance(&self) -> u64 {
+        self.balance
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`deposit`|`amount: u64`|`()`|yes|call||"]
+#[doc = "|`withdraw`|`amount: u64`|`Result<(), String>`|yes|call||"]
+#[doc = "|`balance`||`u64`|yes|call (read-only)||"]
+#[doc = "|`multicall`|`calls: Vec<EncodedCall>`|`Vec<EncodedResult>`|no|call|Call methods of this contract in order, all or nothing.|"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    fn dispatch(ctx: &pchain_sdk::ContractMethodInput) -> pchain_sdk::ContractMethodOutput {
+        match ctx.method_name.as_str() {
+            "deposit" => {
+                let multi_args = ctx.get_multiple_arguments();
+                let mut contract = {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    let _ = |contract: &MyContract| {
+                        let _ = &contract.deposits;
+                        let _ = &contract.balance;
+                        let _ = &contract.withdrawals;
+                    };
+                    MyContract::__load_fields(
+                        &pchain_sdk::StoragePath::new(),
+                        &["deposits", "balance", "withdrawals"],
+                    )
+                };
+                let _d0: u64 =
+                    pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+                contract.deposit(_d0);
+                if !contract.balance_is_conserved() {
+                    {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "Invariant {0} is violated by {1}.",
+                            "balance_is_conserved", "deposit"
+                        ));
+                    };
+                }
+                if !contract.withdrawals_are_covered() {
+                    {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "Invariant {0} is violated by {1}.",
+                            "withdrawals_are_covered", "deposit"
+                        ));
+                    };
+                }
+                {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    contract.__save_fields(
+                        &pchain_sdk::StoragePath::new(),
+                        &["deposits", "balance", "withdrawals"],
+                    );
+                }
+                pchain_sdk::ContractMethodOutput::default()
+            }
+            "withdraw" => {
+                let multi_args = ctx.get_multiple_arguments();
+                let mut contract = {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    let _ = |contract: &MyContract| {
+                        let _ = &contract.balance;
+                        let _ = &contract.withdrawals;
+                        let _ = &contract.deposits;
+                    };
+                    MyContract::__load_fields(
+                        &pchain_sdk::StoragePath::new(),
+                        &["balance", "withdrawals", "deposits"],
+                    )
+                };
+                let _d0: u64 =
+                    pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+                let ret_cb = contract.withdraw(_d0);
+                if ret_cb.is_ok() {
+                    if !contract.balance_is_conserved() {
+                        {
+                            ::core::panicking::panic_fmt(format_args!(
+                                "Invariant {0} is violated by {1}.",
+                                "balance_is_conserved", "withdraw"
+                            ));
+                        };
+                    }
+                    if !contract.withdrawals_are_covered() {
+                        {
+                            ::core::panicking::panic_fmt(format_args!(
+                                "Invariant {0} is violated by {1}.",
+                                "withdrawals_are_covered", "withdraw"
+                            ));
+                        };
+                    }
+                    {
+                        #[allow(unused_imports)]
+                        use pchain_sdk::storage::__AllFields;
+                        contract.__save_fields(
+                            &pchain_sdk::StoragePath::new(),
+                            &["balance", "withdrawals", "deposits"],
+                        );
+                    }
+                }
+                pchain_sdk::ContractMethodOutput::set_result("withdraw", &ret_cb)
+            }
+            "balance" => {
+                let contract = {
+                    #[allow(unused_imports)]
+                    use pchain_sdk::storage::__AllFields;
+                    let _ = |contract: &MyContract| {
+                        let _ = &contract.balance;
+                    };
+                    MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["balance"])
+                };
+                let ret_cb = contract.balance();
+                pchain_sdk::ContractMethodOutput::set(&ret_cb)
+            }
+            _ => {
+                ::core::panicking::panic_fmt(format_args!(
+                    "Method {0:?} cannot be called by multicall.",
+                    ctx.method_name
+                ));
+            }
+        }
+    }
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "deposit".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "withdraw".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "balance".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                        ],
+                    ),
+                );
+            methods.push(pchain_sdk::method::MethodInfo {
+                name: pchain_sdk::method::MULTICALL_METHOD.to_string(),
+                arity: 1,
+                payable: false,
+                internal_only: false,
+                view: false,
+            });
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "multicall" => {
+            if pchain_sdk::transaction::amount() > 0 {
+                {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "Method multicall does not accept an attached amount."
+                    ));
+                };
+            }
+            let multi_args = ctx.get_multiple_arguments();
+            let calls: Vec<pchain_sdk::method::EncodedCall> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0);
+            let results: Vec<pchain_sdk::method::EncodedResult> = calls
+                .into_iter()
+                .enumerate()
+                .map(|(index, call)| {
+                    let ctx = pchain_sdk::ContractMethodInput {
+                        method_name: call.method_name,
+                        arguments: call.arguments,
+                    };
+                    let result = dispatch(&ctx);
+                    if result.is_error() {
+                        {
+                            ::core::panicking::panic_fmt(format_args!(
+                                "Call {0} of multicall to method {1} returned an error.",
+                                index, ctx.method_name
+                            ));
+                        };
+                    }
+                    pchain_sdk::method::EncodedResult {
+                        return_value: result.get(),
+                    }
+                })
+                .collect();
+            pchain_sdk::ContractMethodOutput::set(&results)
+        }
+        "deposit" | "withdraw" | "balance" => dispatch(&ctx),
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => {
+                    if MyContract::__fungible_token_methods()
+                        .into_iter()
+                        .chain(MyContract::__nft_methods())
+                        .any(|info| info.name == ctx.method_name && !info.view)
+                    {
+                        let contract = {
+                            #[allow(unused_imports)]
+                            use pchain_sdk::storage::__AllFields;
+                            MyContract::__load_fields(
+                                &pchain_sdk::StoragePath::new(),
+                                &[
+                                    "deposits",
+                                    "withdrawals",
+                                    "balance",
+                                    "withdrawals",
+                                    "deposits",
+                                ],
+                            )
+                        };
+                        if !contract.balance_is_conserved() {
+                            {
+                                ::core::panicking::panic_fmt(format_args!(
+                                    "Invariant {0} is violated by {1}.",
+                                    "balance_is_conserved", ctx.method_name
+                                ));
+                            };
+                        }
+                        if !contract.withdrawals_are_covered() {
+                            {
+                                ::core::panicking::panic_fmt(format_args!(
+                                    "Invariant {0} is violated by {1}.",
+                                    "withdrawals_are_covered", ctx.method_name
+                                ));
+                            };
+                        }
+                    }
+                    callresult
+                }
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"deposit\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"withdraw\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"amount\",\"type\":\"u64\"}],\"returns\":\"Result<(), String>\"},{\"name\":\"balance\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":\"u64\"},{\"name\":\"multicall\",\"kind\":\"multicall\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"calls\",\"type\":\"Vec<EncodedCall>\"}],\"returns\":\"Vec<EncodedResult>\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {}
@@ -0,0 +1,44 @@
+use pchain_sdk::{contract, contract_methods, call, invariant};
+
+#[contract]
+pub struct MyContract {
+    deposits: u64,
+    withdrawals: u64,
+    balance: u64,
+}
+
+#[contract_methods(multicall)]
+impl MyContract {
+    #[invariant]
+    fn balance_is_conserved(&self) -> bool {
+        self.deposits - self.withdrawals == self.balance
+    }
+
+    #[invariant]
+    fn withdrawals_are_covered(&self) -> bool {
+        self.withdrawals <= self.deposits
+    }
+
+    #[call]
+    fn deposit(&mut self, amount: u64) {
+        self.deposits += amount;
+        self.balance += amount;
+    }
+
+    #[call]
+    fn withdraw(&mut self, amount: u64) -> Result<(), String> {
+        if amount > self.balance {
+            return Err("Insufficient balance".to_string())
+        }
+        self.withdrawals += amount;
+        self.balance -= amount;
+        Ok(())
+    }
+
+    #[call]
+    fn balance(&self) -> u64 {
+        self.balance
+    }
+}
+
+fn main() {}
@@ -0,0 +1,424 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{
+    contract, contract_field, contract_methods, call,
+    collections::{FastMap, IterableMap, Vector},
+    Cacher,
+};
+
+pub struct Inner {
+    value: u64,
+    list: Vector<u64>,
+}
+impl pchain_sdk::Storable for Inner {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        Inner {
+            value: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            list: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.value.__save_storage(&field.add(0usize as u8));
+        self.list.__save_storage(&field.add(1usize as u8));
+    }
+}
+impl Inner {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        Inner {
+            value: if names.contains(&"value") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<u64>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            list: if names.contains(&"list") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<Vector<u64>>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"value") {
+            self.value.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"list") {
+            self.list.__save_storage(&field.add(1usize as u8));
+        }
+    }
+}
+
+pub struct Outer {
+    inner: Inner,
+    flag: bool,
+}
+impl pchain_sdk::Storable for Outer {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        Outer {
+            inner: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            flag: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.inner.__save_storage(&field.add(0usize as u8));
+        self.flag.__save_storage(&field.add(1usize as u8));
+    }
+}
+impl Outer {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        Outer {
+            inner: if names.contains(&"inner") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<Inner>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            flag: if names.contains(&"flag") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<bool>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"inner") {
+            self.inner.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"flag") {
+            self.flag.__save_storage(&field.add(1usize as u8));
+        }
+    }
+}
+
+use pchain_sdk::Storable;
+pub struct MyContract {
+    outer: Outer,
+    balances: FastMap<[u8; 32], u64>,
+    nested: FastMap<String, FastMap<String, u64>>,
+    members: IterableMap<String, (u8, String)>,
+    cached: Cacher<u64>,
+}
+impl pchain_sdk::Storable for MyContract {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyContract {
+            outer: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            balances: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+            nested: pchain_sdk::Storable::__load_storage(&field.add(2usize as u8)),
+            members: pchain_sdk::Storable::__load_storage(&field.add(3usize as u8)),
+            cached: pchain_sdk::Storable::__load_storage(&field.add(4usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.outer.__save_storage(&field.add(0usize as u8));
+        self.balances.__save_storage(&field.add(1usize as u8));
+        self.nested.__save_storage(&field.add(2usize as u8));
+        self.members.__save_storage(&field.add(3usize as u8));
+        self.cached.__save_storage(&field.add(4usize as u8));
+    }
+}
+impl MyContract {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyContract {
+            outer: if names.contains(&"outer") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<Outer>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            balances: if names.contains(&"balances") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<FastMap<[u8; 32], u64>>(
+                    std::marker::PhantomData,
+                ))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+            nested: if names.contains(&"nested") {
+                pchain_sdk::Storable::__load_storage(&field.add(2usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<FastMap<String, FastMap<String, u64>>>(
+                    std::marker::PhantomData,
+                ))
+                    .__unloaded(&field.add(2usize as u8))
+            },
+            members: if names.contains(&"members") {
+                pchain_sdk::Storable::__load_storage(&field.add(3usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<IterableMap<String, (u8, String)>>(
+                    std::marker::PhantomData,
+                ))
+                    .__unloaded(&field.add(3usize as u8))
+            },
+            cached: if names.contains(&"cached") {
+                pchain_sdk::Storable::__load_storage(&field.add(4usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<Cacher<u64>>(std::marker::PhantomData))
+                    .__unloaded(&field.add(4usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"outer") {
+            self.outer.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"balances") {
+            self.balances.__save_storage(&field.add(1usize as u8));
+        }
+        if names.contains(&"nested") {
+            self.nested.__save_storage(&field.add(2usize as u8));
+        }
+        if names.contains(&"members") {
+            self.members.__save_storage(&field.add(3usize as u8));
+        }
+        if names.contains(&"cached") {
+            self.cached.__save_storage(&field.add(4usize as u8));
+        }
+    }
+}
+trait MyContractAccesser {
+    fn get() -> MyContract;
+    fn set(&mut self);
+    fn get_outer() -> Outer;
+    fn set_outer(_: Outer);
+    fn get_balances() -> FastMap<[u8; 32], u64>;
+    fn set_balances(_: FastMap<[u8; 32], u64>);
+    fn get_nested() -> FastMap<String, FastMap<String, u64>>;
+    fn set_nested(_: FastMap<String, FastMap<String, u64>>);
+    fn get_members() -> IterableMap<String, (u8, String)>;
+    fn set_members(_: IterableMap<String, (u8, String)>);
+    fn get_cached() -> Cacher<u64>;
+    fn set_cached(_: Cacher<u64>);
+}
+impl MyContractAccesser for MyContract {
+    fn get() -> MyContract {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_outer() -> Outer {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_outer(mut value: Outer) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_balances() -> FastMap<[u8; 32], u64> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_balances(mut value: FastMap<[u8; 32], u64>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+    fn get_nested() -> FastMap<String, FastMap<String, u64>> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(2usize as u8))
+    }
+    fn set_nested(mut value: FastMap<String, FastMap<String, u64>>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(2usize as u8));
+    }
+    fn get_members() -> IterableMap<String, (u8, String)> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(3usize as u8))
+    }
+    fn set_members(mut value: IterableMap<String, (u8, String)>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(3usize as u8));
+    }
+    fn get_cached() -> Cacher<u64> {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(4usize as u8))
+    }
+    fn set_cached(mut value: Cacher<u64>) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(4usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyContractReadOnly {
+    fn outer(&self) -> &Outer;
+    fn balances(&self) -> &FastMap<[u8; 32], u64>;
+    fn nested(&self) -> &FastMap<String, FastMap<String, u64>>;
+    fn members(&self) -> &IterableMap<String, (u8, String)>;
+    fn cached(&self) -> &Cacher<u64>;
+}
+impl<'a> MyContractReadOnly for pchain_sdk::ReadOnly<'a, MyContract> {
+    fn outer(&self) -> &Outer {
+        &self.__inner().outer
+    }
+    fn balances(&self) -> &FastMap<[u8; 32], u64> {
+        &self.__inner().balances
+    }
+    fn nested(&self) -> &FastMap<String, FastMap<String, u64>> {
+        &self.__inner().nested
+    }
+    fn members(&self) -> &IterableMap<String, (u8, String)> {
+        &self.__inner().members
+    }
+    fn cached(&self) -> &Cacher<u64> {
+        &self.__inner().cached
+    }
+}
+
+impl MyContract {
+    fn balance(&self, owner: [u8; 32]) -> Option<u64> {
+        self.balances.get(&owner)
+    }
+
+    fn push(&mut self, value: u64) {
+        self.outer.inner.list.push(&value);
+        self.outer.inner.value = value;
+    }
+}
+#[doc = "Entrypoint of the contract `MyContract`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`balance`|`owner: [u8; 32]`|`Option<u64>`|yes|call (read-only)||"]
+#[doc = "|`push`|`value: u64`|`()`|yes|call||"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "balance".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: true,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "push".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyContract::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyContract::__fungible_token_methods()
+                .into_iter()
+                .chain(MyContract::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "balance" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.balances;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["balances"])
+            };
+            let _d0: [u8; 32] =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let ret_cb = contract.balance(_d0);
+            pchain_sdk::ContractMethodOutput::set(&ret_cb)
+        }
+        "push" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyContract| {
+                    let _ = &contract.outer;
+                };
+                MyContract::__load_fields(&pchain_sdk::StoragePath::new(), &["outer"])
+            };
+            let _d0: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            contract.push(_d0);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["outer"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyContract::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyContract::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyContract::__fungible_token(&ctx).or_else(|| MyContract::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyContract::__STANDARD, "") {
+        {
+            ::core::panicking::panic_fmt(format_args!("The standard derived by MyContract must be declared as an option of contract_methods, e.g. #[contract_methods(fungible_token)]."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyContract\",\"methods\":[{\"name\":\"balance\",\"kind\":\"call\",\"mutable\":false,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"owner\",\"type\":\"[u8; 32]\"}],\"returns\":\"Option<u64>\"},{\"name\":\"push\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"value\",\"type\":\"u64\"}],\"returns\":null}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {}
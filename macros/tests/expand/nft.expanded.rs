@@ -0,0 +1,354 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{contract, contract_methods, call, standards::nft::NonFungibleToken};
+
+use pchain_sdk::Storable;
+pub struct MyCollection {
+    owner: [u8; 32],
+    nft: NonFungibleToken,
+}
+impl MyCollection {
+    #[doc(hidden)]
+    pub const __STANDARD: &'static str = "nft";
+    #[doc(hidden)]
+    pub fn __nft(
+        ctx: &pchain_sdk::ContractMethodInput,
+    ) -> Option<pchain_sdk::ContractMethodOutput> {
+        pchain_sdk::standards::nft::__dispatch(&pchain_sdk::StoragePath::new().add(1u8), ctx)
+    }
+    #[doc(hidden)]
+    pub fn __nft_methods() -> Vec<pchain_sdk::method::MethodInfo> {
+        pchain_sdk::standards::nft::__methods()
+    }
+}
+impl pchain_sdk::Storable for MyCollection {
+    fn __load_storage(field: &pchain_sdk::StoragePath) -> Self {
+        MyCollection {
+            owner: pchain_sdk::Storable::__load_storage(&field.add(0usize as u8)),
+            nft: pchain_sdk::Storable::__load_storage(&field.add(1usize as u8)),
+        }
+    }
+    fn __save_storage(&mut self, field: &pchain_sdk::StoragePath) {
+        self.owner.__save_storage(&field.add(0usize as u8));
+        self.nft.__save_storage(&field.add(1usize as u8));
+    }
+}
+impl MyCollection {
+    #[doc(hidden)]
+    pub fn __load_fields(field: &pchain_sdk::StoragePath, names: &[&str]) -> Self {
+        #[allow(unused_imports)]
+        use pchain_sdk::storage::{__UnloadedDefault, __UnloadedStorable};
+        MyCollection {
+            owner: if names.contains(&"owner") {
+                pchain_sdk::Storable::__load_storage(&field.add(0usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<[u8; 32]>(std::marker::PhantomData))
+                    .__unloaded(&field.add(0usize as u8))
+            },
+            nft: if names.contains(&"nft") {
+                pchain_sdk::Storable::__load_storage(&field.add(1usize as u8))
+            } else {
+                (&pchain_sdk::storage::__Unloaded::<NonFungibleToken>(std::marker::PhantomData))
+                    .__unloaded(&field.add(1usize as u8))
+            },
+        }
+    }
+    #[doc(hidden)]
+    pub fn __save_fields(&mut self, field: &pchain_sdk::StoragePath, names: &[&str]) {
+        if names.contains(&"owner") {
+            self.owner.__save_storage(&field.add(0usize as u8));
+        }
+        if names.contains(&"nft") {
+            self.nft.__save_storage(&field.add(1usize as u8));
+        }
+    }
+}
+trait MyCollectionAccesser {
+    fn get() -> MyCollection;
+    fn set(&mut self);
+    fn get_owner() -> [u8; 32];
+    fn set_owner(_: [u8; 32]);
+    fn get_nft() -> NonFungibleToken;
+    fn set_nft(_: NonFungibleToken);
+}
+impl MyCollectionAccesser for MyCollection {
+    fn get() -> MyCollection {
+        Self::__load_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn set(&mut self) {
+        self.__save_storage(&pchain_sdk::StoragePath::new())
+    }
+    fn get_owner() -> [u8; 32] {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(0usize as u8))
+    }
+    fn set_owner(mut value: [u8; 32]) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(0usize as u8));
+    }
+    fn get_nft() -> NonFungibleToken {
+        pchain_sdk::Storable::__load_storage(&pchain_sdk::StoragePath::new().add(1usize as u8))
+    }
+    fn set_nft(mut value: NonFungibleToken) {
+        value.__save_storage(&pchain_sdk::StoragePath::new().add(1usize as u8));
+    }
+}
+#[allow(dead_code)]
+trait MyCollectionReadOnly {
+    fn owner(&self) -> &[u8; 32];
+    fn nft(&self) -> &NonFungibleToken;
+}
+impl<'a> MyCollectionReadOnly for pchain_sdk::ReadOnly<'a, MyCollection> {
+    fn owner(&self) -> &[u8; 32] {
+        &self.__inner().owner
+    }
+    fn nft(&self) -> &NonFungibleToken {
+        &self.__inner().nft
+    }
+}
+
+impl MyCollection {
+    fn init(&mut self) {
+        self.owner = pchain_sdk::transaction::calling_account();
+        self.nft = NonFungibleToken::new("My Collection", "MYC", "https://example.com/tokens/");
+    }
+
+    fn mint(&mut self, to: [u8; 32], token_id: u64, uri: Option<String>) {
+        match (&self.owner, &pchain_sdk::transaction::calling_account()) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        self.nft.mint(to, token_id, uri).unwrap();
+    }
+    fn burn(&mut self, token_id: u64) {
+        match (
+            &self.nft.owner_of(token_id),
+            &Some(pchain_sdk::transaction::calling_account()),
+        ) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        self.nft.burn(token_id).unwrap();
+    }
+}
+#[doc = "Entrypoint of the contract `MyCollection`. Callable methods:"]
+#[doc = ""]
+#[doc = "|Method|Arguments|Return|Payable|Kind|Description|"]
+#[doc = "|:---|:---|:---|:---|:---|:---|"]
+#[doc = "|`init`||`()`|yes|call||"]
+#[doc = "|`mint`|`to: [u8; 32], token_id: u64, uri: Option<String>`|`()`|yes|call||"]
+#[doc = "|`burn`|`token_id: u64`|`()`|yes|call||"]
+#[doc = "|`name`||`String`|no|view (nft)|Name of the collection.|"]
+#[doc = "|`symbol`||`String`|no|view (nft)|Symbol of the collection.|"]
+#[doc = "|`total_supply`||`u64`|no|view (nft)|Number of tokens in existence.|"]
+#[doc = "|`balance_of`|`owner: PublicAddress`|`u64`|no|view (nft)|Number of tokens of the owner.|"]
+#[doc = "|`owner_of`|`token_id: u64`|`Option<PublicAddress>`|no|view (nft)|Owner of the token.|"]
+#[doc = "|`get_approved`|`token_id: u64`|`Option<PublicAddress>`|no|view (nft)|Spender approved for the token.|"]
+#[doc = "|`token_uri`|`token_id: u64`|`Option<String>`|no|view (nft)|URI of the metadata of the token.|"]
+#[doc = "|`transfer`|`to: PublicAddress, token_id: u64`|`Result<(), ContractError>`|no|call (nft)|Transfer a token of the caller.|"]
+#[doc = "|`approve`|`spender: PublicAddress, token_id: u64`|`Result<(), ContractError>`|no|call (nft)|Approve the spender for a token of the caller.|"]
+#[doc = "|`transfer_from`|`from: PublicAddress, to: PublicAddress, token_id: u64`|`Result<(), ContractError>`|no|call (nft)|Transfer a token on behalf of its owner.|"]
+pub extern "C" fn entrypoint() {
+    __entrypoint();
+}
+#[doc(hidden)]
+pub fn __entrypoint() {
+    pchain_sdk::panic_handler::__install_default();
+    let mut ctx = pchain_sdk::ContractMethodInput::from_transaction();
+    let callresult: pchain_sdk::ContractMethodOutput = match ctx.method_name.as_str() {
+        "__methods" => {
+            #[allow(unused_mut)]
+            let mut methods: Vec<pchain_sdk::method::MethodInfo> =
+                ::alloc::boxed::box_assume_init_into_vec_unsafe(
+                    ::alloc::intrinsics::write_box_via_move(
+                        ::alloc::boxed::Box::new_uninit(),
+                        [
+                            pchain_sdk::method::MethodInfo {
+                                name: "init".to_string(),
+                                arity: 0u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "mint".to_string(),
+                                arity: 3u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                            pchain_sdk::method::MethodInfo {
+                                name: "burn".to_string(),
+                                arity: 1u32,
+                                payable: true,
+                                internal_only: false,
+                                view: false,
+                            },
+                        ],
+                    ),
+                );
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            if MyCollection::__config().is_some() {
+                methods.push(pchain_sdk::method::MethodInfo {
+                    name: pchain_sdk::method::CONFIG_METHOD.to_string(),
+                    arity: 0,
+                    payable: true,
+                    internal_only: false,
+                    view: true,
+                });
+            }
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            for info in MyCollection::__fungible_token_methods()
+                .into_iter()
+                .chain(MyCollection::__nft_methods())
+            {
+                if !methods.iter().any(|method| method.name == info.name) {
+                    methods.push(info);
+                }
+            }
+            pchain_sdk::ContractMethodOutput::set(&methods)
+        }
+        "init" => {
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyCollection| {
+                    let _ = &contract.owner;
+                    let _ = &contract.nft;
+                };
+                MyCollection::__load_fields(&pchain_sdk::StoragePath::new(), &["owner", "nft"])
+            };
+            contract.init();
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["owner", "nft"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "mint" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyCollection| {
+                    let _ = &contract.owner;
+                    let _ = &contract.nft;
+                };
+                MyCollection::__load_fields(&pchain_sdk::StoragePath::new(), &["owner", "nft"])
+            };
+            let _d0: [u8; 32] =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            let _d1: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 1usize);
+            let _d2: Option<String> =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 2usize);
+            contract.mint(_d0, _d1, _d2);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["owner", "nft"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "burn" => {
+            let multi_args = ctx.get_multiple_arguments();
+            let mut contract = {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                let _ = |contract: &MyCollection| {
+                    let _ = &contract.nft;
+                };
+                MyCollection::__load_fields(&pchain_sdk::StoragePath::new(), &["nft"])
+            };
+            let _d0: u64 =
+                pchain_sdk::ContractMethodInput::parse_multiple_arguments(&multi_args, 0usize);
+            contract.burn(_d0);
+            {
+                #[allow(unused_imports)]
+                use pchain_sdk::storage::__AllFields;
+                contract.__save_fields(&pchain_sdk::StoragePath::new(), &["nft"]);
+            }
+            pchain_sdk::ContractMethodOutput::default()
+        }
+        "config"
+            if {
+                #[allow(unused_imports)]
+                use pchain_sdk::method::__NoContractConfig;
+                MyCollection::__config().is_some()
+            } =>
+        {
+            #[allow(unused_imports)]
+            use pchain_sdk::method::__NoContractConfig;
+            pchain_sdk::ContractMethodOutput::set(&MyCollection::__config().unwrap())
+        }
+        _ => {
+            #[allow(unused_imports)]
+            use pchain_sdk::standards::__NoStandards;
+            match MyCollection::__fungible_token(&ctx).or_else(|| MyCollection::__nft(&ctx)) {
+                Some(callresult) => callresult,
+                None => match ctx.method_name.as_str() {
+                    _ => {
+                        ::core::panicking::panic_fmt(format_args!(
+                            "not implemented: {0}",
+                            format_args!("Method {0:?} is not defined.", ctx.method_name)
+                        ));
+                    }
+                },
+            }
+        }
+    };
+    if let Some(return_value) = callresult.get() {
+        pchain_sdk::return_value(return_value);
+    }
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use pchain_sdk::standards::__NoStandards;
+    if !pchain_sdk::standards::__is_declared(MyCollection::__STANDARD, "nft") {
+        {
+            ::core::panicking::panic_fmt(format_args!("MyCollection must derive the standard declared by the option nft of contract_methods."));
+        }
+    };
+};
+#[doc = r" ABI of this contract in JSON, generated by `#[contract_methods]`."]
+#[allow(non_upper_case_globals)]
+pub static __contract_abi__: &str =
+    "{\"contract\":\"MyCollection\",\"methods\":[{\"name\":\"init\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[],\"returns\":null},{\"name\":\"mint\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"to\",\"type\":\"[u8; 32]\"},{\"name\":\"token_id\",\"type\":\"u64\"},{\"name\":\"uri\",\"type\":\"Option<String>\"}],\"returns\":null},{\"name\":\"burn\",\"kind\":\"call\",\"mutable\":true,\"payable\":true,\"internal_only\":false,\"args\":[{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":null},{\"name\":\"name\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"String\"},{\"name\":\"symbol\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"String\"},{\"name\":\"total_supply\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[],\"returns\":\"u64\"},{\"name\":\"balance_of\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"owner\",\"type\":\"PublicAddress\"}],\"returns\":\"u64\"},{\"name\":\"owner_of\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Option<PublicAddress>\"},{\"name\":\"get_approved\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Option<PublicAddress>\"},{\"name\":\"token_uri\",\"kind\":\"view\",\"mutable\":false,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Option<String>\"},{\"name\":\"transfer\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"to\",\"type\":\"PublicAddress\"},{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Result<(), ContractError>\"},{\"name\":\"approve\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"spender\",\"type\":\"PublicAddress\"},{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Result<(), ContractError>\"},{\"name\":\"transfer_from\",\"kind\":\"call\",\"mutable\":true,\"payable\":false,\"internal_only\":false,\"args\":[{\"name\":\"from\",\"type\":\"PublicAddress\"},{\"name\":\"to\",\"type\":\"PublicAddress\"},{\"name\":\"token_id\",\"type\":\"u64\"}],\"returns\":\"Result<(), ContractError>\"}],\"on_receive\":false,\"fallback\":false}";
+#[doc = r" Metadata of this contract in JSON, specified by `#[contract_methods(meta(..))]`."]
+pub const CONTRACT_METADATA: &str = "{}";
+fn main() {
+    let methods = MyCollection::__nft_methods();
+    if !methods
+        .iter()
+        .any(|method| method.name == "owner_of" && method.arity == 1 && method.view)
+    {
+        ::core::panicking::panic("assertion failed: methods.iter().any(|method|\n        method.name == \"owner_of\" && method.arity == 1 && method.view)")
+    };
+    if !methods
+        .iter()
+        .any(|method| method.name == "transfer_from" && method.arity == 3 && !method.view)
+    {
+        ::core::panicking::panic("assertion failed: methods.iter().any(|method|\n        method.name == \"transfer_from\" && method.arity == 3 && !method.view)")
+    };
+}
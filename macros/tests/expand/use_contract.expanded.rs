@@ -0,0 +1,69 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{use_contract, CallError, ContractError};
+
+pub mod my_contract {
+    #[allow(unused_imports)]
+    use super::*;
+    pub fn print_a_value(value: u64) -> Option<Vec<u8>> {
+        let mut args_builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        pchain_sdk::call_untyped(
+            [
+                54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198, 106,
+                219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+            ],
+            "print_a_value",
+            args_builder.to_call_arguments(),
+            value,
+        )
+    }
+    pub fn get_commodities_price(item: String, value: u64) -> Result<u64, pchain_sdk::CallError> {
+        let mut args_builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        args_builder.add(item);
+        pchain_sdk::try_call(
+            [
+                54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198, 106,
+                219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+            ],
+            "get_commodities_price",
+            args_builder.to_call_arguments(),
+            value,
+        )
+    }
+    pub fn get_history(item: String, value: u64) -> Result<Vec<u64>, pchain_sdk::CallError> {
+        let mut args_builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        args_builder.add(item);
+        pchain_sdk::try_call(
+            [
+                54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198, 106,
+                219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+            ],
+            "get_history",
+            args_builder.to_call_arguments(),
+            value,
+        )
+    }
+    pub fn buy(item: String, quantity: u32, value: u64) -> Result<u64, pchain_sdk::CallError> {
+        let mut args_builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        args_builder.add(item);
+        args_builder.add(quantity);
+        pchain_sdk::try_call_result(
+            [
+                54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198, 106,
+                219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+            ],
+            "buy",
+            args_builder.to_call_arguments(),
+            value,
+        )
+    }
+}
+
+fn main() {
+    let _: fn(u64) -> Option<Vec<u8>> = my_contract::print_a_value;
+    let _: fn(String, u64) -> Result<u64, CallError> = my_contract::get_commodities_price;
+    let _: fn(String, u64) -> Result<Vec<u64>, CallError> = my_contract::get_history;
+    let _: fn(String, u32, u64) -> Result<u64, CallError> = my_contract::buy;
+}
@@ -0,0 +1,16 @@
+use pchain_sdk::{use_contract, CallError, ContractError};
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+pub trait MyContract {
+    fn print_a_value();
+    fn get_commodities_price(item: String) -> u64;
+    fn get_history(item: String) -> Vec<u64>;
+    fn buy(item: String, quantity: u32) -> Result<u64, ContractError>;
+}
+
+fn main() {
+    let _: fn(u64) -> Option<Vec<u8>> = my_contract::print_a_value;
+    let _: fn(String, u64) -> Result<u64, CallError> = my_contract::get_commodities_price;
+    let _: fn(String, u64) -> Result<Vec<u64>, CallError> = my_contract::get_history;
+    let _: fn(String, u32, u64) -> Result<u64, CallError> = my_contract::buy;
+}
@@ -0,0 +1,157 @@
+#![feature(prelude_import)]
+extern crate std;
+#[prelude_import]
+use std::prelude::rust_2021::*;
+use pchain_sdk::{use_contract, types::Log};
+
+pub mod my_token {
+    #[allow(unused_imports)]
+    use super::*;
+    pub fn transfer(to: [u8; 32], amount: u64, value: u64) -> Option<Vec<u8>> {
+        let mut args_builder = pchain_sdk::method::ContractMethodInputBuilder::new();
+        args_builder.add(to);
+        args_builder.add(amount);
+        pchain_sdk::call_untyped(
+            [
+                54, 207, 67, 184, 215, 188, 105, 46, 80, 33, 39, 194, 202, 49, 40, 1, 198, 106,
+                219, 67, 149, 175, 105, 202, 65, 50, 172, 96, 105, 168, 252, 156,
+            ],
+            "transfer",
+            args_builder.to_call_arguments(),
+            value,
+        )
+    }
+
+    pub struct Transfer {
+        pub from: [u8; 32],
+        pub to: [u8; 32],
+        pub amount: u64,
+    }
+    #[automatically_derived]
+    impl ::core::fmt::Debug for Transfer {
+        #[inline]
+        fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+            ::core::fmt::Formatter::debug_struct_field3_finish(
+                f,
+                "Transfer",
+                "from",
+                &self.from,
+                "to",
+                &self.to,
+                "amount",
+                &&self.amount,
+            )
+        }
+    }
+    #[automatically_derived]
+    impl ::core::marker::StructuralPartialEq for Transfer {}
+    #[automatically_derived]
+    impl ::core::cmp::PartialEq for Transfer {
+        #[inline]
+        fn eq(&self, other: &Transfer) -> bool {
+            self.amount == other.amount && self.from == other.from && self.to == other.to
+        }
+    }
+    impl Transfer {
+        #[doc = r" Topic of the logs of this event."]
+        pub const TOPIC: &'static [u8] = b"Transfer";
+        #[doc = r" Decode the log, if it is of this event."]
+        pub fn decode(log: &pchain_sdk::types::Log) -> Option<Self> {
+            if log.topic.as_slice() != Self::TOPIC {
+                return None;
+            }
+            <Self as pchain_sdk::borsh::BorshDeserialize>::try_from_slice(&log.value).ok()
+        }
+    }
+    impl pchain_sdk::borsh::BorshSerialize for Transfer {
+        fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.from, writer)?;
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.to, writer)?;
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+            Ok(())
+        }
+    }
+    impl pchain_sdk::borsh::BorshDeserialize for Transfer {
+        fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+            Ok(Self {
+                from: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+                to: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+                amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            })
+        }
+    }
+
+    pub struct Approval {
+        pub spender: [u8; 32],
+        pub amount: u64,
+    }
+    impl Approval {
+        #[doc = r" Topic of the logs of this event."]
+        pub const TOPIC: &'static [u8] = b"token/approval";
+        #[doc = r" Decode the log, if it is of this event."]
+        pub fn decode(log: &pchain_sdk::types::Log) -> Option<Self> {
+            if log.topic.as_slice() != Self::TOPIC {
+                return None;
+            }
+            <Self as pchain_sdk::borsh::BorshDeserialize>::try_from_slice(&log.value).ok()
+        }
+    }
+    impl pchain_sdk::borsh::BorshSerialize for Approval {
+        fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.spender, writer)?;
+            pchain_sdk::borsh::BorshSerialize::serialize(&self.amount, writer)?;
+            Ok(())
+        }
+    }
+    impl pchain_sdk::borsh::BorshDeserialize for Approval {
+        fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+            Ok(Self {
+                spender: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+                amount: pchain_sdk::borsh::BorshDeserialize::deserialize_reader(reader)?,
+            })
+        }
+    }
+}
+
+fn main() {
+    let _: fn([u8; 32], u64, u64) -> Option<Vec<u8>> = my_token::transfer;
+
+    match (&my_token::Approval::TOPIC, &b"token/approval") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let transfer = my_token::Transfer {
+        from: [1u8; 32],
+        to: [2u8; 32],
+        amount: 100,
+    };
+    let log = Log {
+        topic: my_token::Transfer::TOPIC.to_vec(),
+        value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&transfer).unwrap(),
+    };
+    match (&my_token::Transfer::decode(&log), &Some(transfer)) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !my_token::Approval::decode(&log).is_none() {
+        ::core::panicking::panic("assertion failed: my_token::Approval::decode(&log).is_none()")
+    };
+}
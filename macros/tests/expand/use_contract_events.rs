@@ -0,0 +1,36 @@
+use pchain_sdk::{use_contract, types::Log};
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+pub trait MyToken {
+    fn transfer(to: [u8; 32], amount: u64);
+
+    event! {
+        #[derive(Debug, PartialEq)]
+        pub struct Transfer {
+            pub from: [u8; 32],
+            pub to: [u8; 32],
+            pub amount: u64,
+        }
+    }
+
+    event! {
+        #[topic = "token/approval"]
+        pub struct Approval {
+            pub spender: [u8; 32],
+            pub amount: u64,
+        }
+    }
+}
+
+fn main() {
+    let _: fn([u8; 32], u64, u64) -> Option<Vec<u8>> = my_token::transfer;
+    assert_eq!(my_token::Approval::TOPIC, b"token/approval");
+
+    let transfer = my_token::Transfer { from: [1u8; 32], to: [2u8; 32], amount: 100 };
+    let log = Log {
+        topic: my_token::Transfer::TOPIC.to_vec(),
+        value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&transfer).unwrap(),
+    };
+    assert_eq!(my_token::Transfer::decode(&log), Some(transfer));
+    assert!(my_token::Approval::decode(&log).is_none());
+}
@@ -0,0 +1,17 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Compile tests of the macros. Cases in `ui/pass` must compile, and cases in `ui/fail` must fail with the 
+//! error in the corresponding `.stderr` file. Run with `TRYBUILD=overwrite` to update the `.stderr` files after 
+//! changing an error message on purpose.
+
+#[test]
+fn ui() {
+    // the lock file of the cases with `layout_version`, since trybuild builds them in another directory
+    std::env::set_var("PCHAIN_STORAGE_LAYOUT_LOCK", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/ui/storage_layout.lock"));
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
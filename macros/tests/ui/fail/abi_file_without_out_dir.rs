@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+// the crate has no build script, so OUT_DIR is not set.
+#[contract_methods(abi_file)]
+impl MyContract {
+    #[call]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+}
+
+fn main() {}
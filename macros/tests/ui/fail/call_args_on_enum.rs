@@ -0,0 +1,8 @@
+use pchain_sdk::CallArgs;
+
+#[derive(CallArgs)]
+enum Args {
+    Transfer(u64),
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    fn __before_call(&self, method: &str) {
+        assert!(self.counter < 100, "{} is disabled", method);
+    }
+
+    #[call]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+}
+
+fn main() {}
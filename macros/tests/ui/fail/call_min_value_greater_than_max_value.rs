@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(min_value = 10, max_value = 1)]
+    fn counter(&self) -> u64 {
+        self.counter
+    }
+}
+
+fn main() {}
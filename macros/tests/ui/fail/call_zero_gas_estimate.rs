@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(gas_estimate = 0)]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(max_args_bytes = 0)]
+    fn increment(&mut self, by: u64) {
+        self.counter += by;
+    }
+}
+
+fn main() {}
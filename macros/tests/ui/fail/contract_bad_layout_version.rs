@@ -0,0 +1,8 @@
+use pchain_sdk::contract;
+
+#[contract(layout_version = "1")]
+pub struct MyContract {
+    counter: u64,
+}
+
+fn main() {}
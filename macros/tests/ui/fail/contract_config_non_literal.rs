@@ -0,0 +1,10 @@
+use pchain_sdk::contract_config;
+
+pub struct MyContract;
+
+#[contract_config]
+impl MyContract {
+    const FEE: u64 = 10 * 100;
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use pchain_sdk::contract_methods;
+
+#[contract_methods]
+fn my_contract() {}
+
+fn main() {}
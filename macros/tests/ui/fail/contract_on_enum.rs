@@ -0,0 +1,9 @@
+use pchain_sdk::contract;
+
+#[contract]
+pub enum MyContract {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use pchain_sdk::contract_types;
+
+#[contract_types]
+fn order() {}
+
+fn main() {}
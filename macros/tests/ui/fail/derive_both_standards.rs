@@ -0,0 +1,18 @@
+use pchain_sdk::{contract, contract_methods, call, standards::{fungible_token::FungibleToken, nft::NonFungibleToken}};
+
+#[contract]
+#[derive(FungibleToken, NonFungibleToken)]
+pub struct MyToken {
+    token: FungibleToken,
+    nft: NonFungibleToken,
+}
+
+#[contract_methods(fungible_token)]
+impl MyToken {
+    #[call]
+    fn init(&mut self) {
+        self.token = FungibleToken::new("My Token", "MTK", 8);
+    }
+}
+
+fn main() {}
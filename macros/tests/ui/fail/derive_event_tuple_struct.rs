@@ -0,0 +1,6 @@
+use pchain_sdk::Event;
+
+#[derive(Event)]
+pub struct Paused(String);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, standards::fungible_token::FungibleToken};
+
+#[contract]
+#[derive(FungibleToken)]
+pub struct MyToken {
+    token: FungibleToken,
+}
+
+#[contract_methods]
+impl MyToken {
+    #[call]
+    fn init(&mut self) {
+        self.token = FungibleToken::new("My Token", "MTK", 8);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, standards::fungible_token::FungibleToken};
+
+#[contract]
+#[derive(FungibleToken)]
+pub struct MyToken {
+    balances: pchain_sdk::collections::FastMap<[u8; 32], u64>,
+}
+
+#[contract_methods]
+impl MyToken {
+    #[call]
+    fn balance(&self, owner: [u8; 32]) -> u64 {
+        self.balances.get(&owner).unwrap_or(0)
+    }
+}
+
+fn main() {}
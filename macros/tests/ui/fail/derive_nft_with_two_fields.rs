@@ -0,0 +1,18 @@
+use pchain_sdk::{contract, contract_methods, call, standards::nft::NonFungibleToken};
+
+#[contract]
+#[derive(NonFungibleToken)]
+pub struct MyCollections {
+    art: NonFungibleToken,
+    music: NonFungibleToken,
+}
+
+#[contract_methods]
+impl MyCollections {
+    #[call]
+    fn art_supply(&self) -> u64 {
+        self.art.total_supply()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pchain_sdk::event;
+
+#[event]
+pub struct Deposit {
+    pub amount: u64,
+}
+
+#[event(previous(Deposit))]
+pub struct DepositWithMemo {
+    pub amount: u64,
+    pub memo: String,
+}
+
+fn main() {}
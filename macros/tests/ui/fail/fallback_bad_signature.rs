@@ -0,0 +1,14 @@
+use pchain_sdk::{contract, contract_methods, fallback};
+
+#[contract]
+pub struct MyContract {}
+
+#[contract_methods]
+impl MyContract {
+    #[fallback]
+    fn fallback(&mut self, method: String) {
+        let _ = method;
+    }
+}
+
+fn main() {}
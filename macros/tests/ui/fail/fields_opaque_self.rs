@@ -0,0 +1,23 @@
+use pchain_sdk::{contract, contract_methods, call, fields};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+    deposits: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    #[fields(balance)]
+    fn deposit(&mut self, amount: u64) {
+        self.credit(amount);
+    }
+
+    fn credit(&mut self, amount: u64) {
+        self.balance += amount;
+        self.deposits += 1;
+    }
+}
+
+fn main() {}
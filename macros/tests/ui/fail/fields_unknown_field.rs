@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, fields};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    #[fields(balanse)]
+    fn deposit(&mut self, amount: u64) {
+        pchain_sdk::log(b"deposit", &amount.to_le_bytes());
+    }
+}
+
+fn main() {}
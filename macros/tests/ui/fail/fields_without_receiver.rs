@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, fields};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    #[fields(balance)]
+    fn version() -> u32 {
+        1
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(helper = "Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+    fn counter(&self) -> u64 {
+        self.counter
+    }
+}
+
+fn main() {}
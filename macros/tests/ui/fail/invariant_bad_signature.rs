@@ -0,0 +1,21 @@
+use pchain_sdk::{contract, contract_methods, call, invariant};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[invariant]
+    fn check(&mut self) -> bool {
+        self.counter < 100
+    }
+
+    #[call]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+}
+
+fn main() {}
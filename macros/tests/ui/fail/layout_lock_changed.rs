@@ -0,0 +1,10 @@
+use pchain_sdk::contract;
+
+// locked in tests/ui/storage_layout.lock with `balances: FastMap<[u8; 32], u64>`
+#[contract(layout_version = 1)]
+pub struct ChangedLayout {
+    counter: u64,
+    balances: pchain_sdk::collections::Vector<u64>,
+}
+
+fn main() {}
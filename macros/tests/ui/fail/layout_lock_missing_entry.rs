@@ -0,0 +1,9 @@
+use pchain_sdk::contract;
+
+// not in tests/ui/storage_layout.lock
+#[contract(layout_version = 1)]
+pub struct UnlockedLayout {
+    counter: u64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use pchain_sdk::{contract, contract_methods};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(meta(verison = "1.2.0"))]
+impl MyContract {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {}
+
+#[contract_methods(multicall)]
+impl MyContract {
+    #[call]
+    fn multicall(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, on_receive};
+
+#[contract]
+pub struct MyContract {
+    deposits: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[on_receive]
+    fn receive(&mut self) -> bool {
+        true
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, on_receive};
+
+#[contract]
+pub struct MyContract {
+    deposits: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    #[on_receive]
+    fn receive(&mut self, amount: u64) {
+        self.deposits += amount;
+    }
+}
+
+fn main() {}
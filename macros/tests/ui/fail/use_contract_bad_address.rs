@@ -0,0 +1,8 @@
+use pchain_sdk::use_contract;
+
+#[use_contract("not an address")]
+pub trait MyContract {
+    fn print_a_value();
+}
+
+fn main() {}
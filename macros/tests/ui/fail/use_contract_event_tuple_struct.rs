@@ -0,0 +1,12 @@
+use pchain_sdk::use_contract;
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+pub trait MyToken {
+    fn transfer(to: [u8; 32], amount: u64);
+
+    event! {
+        pub struct Transfer(u64);
+    }
+}
+
+fn main() {}
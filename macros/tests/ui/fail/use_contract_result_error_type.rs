@@ -0,0 +1,8 @@
+use pchain_sdk::use_contract;
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+pub trait MyContract {
+    fn buy(item: String, quantity: u32) -> Result<u64, String>;
+}
+
+fn main() {}
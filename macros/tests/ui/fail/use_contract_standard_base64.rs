@@ -0,0 +1,8 @@
+use pchain_sdk::use_contract;
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo/Jw=")]
+pub trait MyContract {
+    fn print_a_value();
+}
+
+fn main() {}
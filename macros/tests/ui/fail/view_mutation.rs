@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, view, ReadOnly, collections::FastMap};
+
+#[contract]
+pub struct MyContract {
+    balances: FastMap<[u8; 32], u64>,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[view]
+    fn balance_of(contract: ReadOnly<'_, Self>, address: [u8; 32]) -> u64 {
+        contract.balances().insert(&address, 0);
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pchain_sdk::{contract, contract_methods, view};
+
+#[contract]
+pub struct MyContract {
+    fee: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[view]
+    fn fee(&self) -> u64 {
+        self.fee
+    }
+}
+
+fn main() {}
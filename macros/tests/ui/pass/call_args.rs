@@ -0,0 +1,25 @@
+use pchain_sdk::{CallArgs, method::ContractMethodInputBuilder};
+
+#[derive(CallArgs)]
+struct TransferArgs {
+    recipient: [u8; 32],
+    amount: u64,
+    memo: String,
+}
+
+#[derive(CallArgs)]
+struct PairArgs(u32, bool);
+
+#[derive(CallArgs)]
+struct NoArgs;
+
+fn main() {
+    let args = TransferArgs { recipient: [1u8; 32], amount: 100, memo: "rent".to_string() }.into_call_arguments();
+    let expected = ContractMethodInputBuilder::new().add([1u8; 32]).add(100u64).add("rent".to_string()).to_call_arguments();
+    assert_eq!(args, expected);
+
+    let args = PairArgs(7, true).into_call_arguments();
+    assert_eq!(args, ContractMethodInputBuilder::new().add(7u32).add(true).to_call_arguments());
+
+    assert_eq!(NoArgs.into_call_arguments(), ContractMethodInputBuilder::new().to_call_arguments());
+}
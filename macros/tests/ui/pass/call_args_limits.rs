@@ -0,0 +1,31 @@
+use pchain_sdk::{contract, contract_methods, call, ContractMethodInput};
+
+#[contract]
+pub struct MyContract {
+    total: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(max_args_bytes = 64_000, max_len = 500)]
+    fn airdrop(&mut self, recipients: Vec<[u8; 32]>, amount: u64) {
+        self.total += recipients.len() as u64 * amount;
+    }
+
+    #[call(max_len = 64)]
+    fn rename(&mut self, name: String, tags: std::collections::BTreeSet<String>) {
+        let _ = (name, tags);
+    }
+
+    #[call(max_args_bytes = 1_024)]
+    fn note(&self, note: Option<Vec<u8>>) -> bool {
+        note.is_some()
+    }
+}
+
+fn main() {
+    let args = vec![vec![3, 0, 0, 0, 1, 2, 3], vec![1, 0]];
+    assert_eq!(ContractMethodInput::argument_len(&args, 0), Some(3));
+    assert_eq!(ContractMethodInput::argument_len(&args, 1), None);
+    assert_eq!(ContractMethodInput::argument_len(&args, 2), None);
+}
@@ -0,0 +1,27 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(meta(version = "1.2.0", author = "ParallelChain Lab", license = "Apache-2.0", repo = "https://github.com/parallelchain-io/pchain-sdk"))]
+impl MyContract {
+    #[call(internal_only)]
+    fn on_callback(&mut self, counter: u64) {
+        self.counter = counter;
+    }
+
+    #[call(internal_only, helper = "Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+    fn on_helper(&mut self) {}
+
+    #[call(min_value = 1_000, max_value = 1_000_000)]
+    fn deposit(&mut self) {}
+
+    #[call(internal_only, min_value = 1)]
+    fn on_payment(&mut self) {}
+}
+
+fn main() {
+    assert!(CONTRACT_METADATA.contains("\"version\":\"1.2.0\""));
+}
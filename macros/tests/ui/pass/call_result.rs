@@ -0,0 +1,37 @@
+use pchain_sdk::{contract, contract_methods, call, view, ContractError};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn withdraw(&mut self, amount: u64) -> Result<u64, ContractError> {
+        if amount > self.balance {
+            return Err(ContractError::new(1, "Insufficient balance"))
+        }
+        self.balance -= amount;
+        Ok(self.balance)
+    }
+
+    #[call]
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[call]
+    fn version() -> Result<u32, (u8, String)> {
+        Ok(1)
+    }
+
+    #[view]
+    fn balance(contract: pchain_sdk::ReadOnly<'_, Self>) -> Result<u64, String> {
+        Ok(*contract.balance())
+    }
+}
+
+fn main() {
+    assert_eq!(pchain_sdk::error::METHOD_ERROR_TOPIC, b"MethodError");
+}
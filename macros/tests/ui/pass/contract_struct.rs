@@ -0,0 +1,39 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+    name: String,
+    owner: [u8; 32],
+    history: Vec<u32>,
+}
+
+#[contract_methods]
+impl MyContract {
+    // receiver &self
+    #[call]
+    fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    // receiver &mut self, with arguments
+    #[call]
+    fn set_name(&mut self, name: String, counter: u64) {
+        self.name = name;
+        self.counter = counter;
+    }
+
+    // no receiver
+    #[call]
+    fn version() -> u32 {
+        1
+    }
+
+    // not a contract method
+    #[allow(dead_code)]
+    fn helper(&self) -> usize {
+        self.history.len()
+    }
+}
+
+fn main() {}
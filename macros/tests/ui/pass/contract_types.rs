@@ -0,0 +1,24 @@
+use pchain_sdk::contract_types;
+
+type PublicAddress = [u8; 32];
+
+#[contract_types]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Order {
+    /// Account which placed the order.
+    pub maker: PublicAddress,
+    pub amount: u64,
+    pub side: Side,
+}
+
+#[contract_types]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+fn main() {
+    let order = Order { maker: [1u8; 32], amount: 10, side: Side::Buy };
+    assert_eq!(order.clone(), order);
+}
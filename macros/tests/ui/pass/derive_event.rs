@@ -0,0 +1,39 @@
+use pchain_sdk::{Event, types::Log};
+
+#[derive(Event, Debug, PartialEq)]
+#[topic = "Transfer"]
+pub struct TransferEvent {
+    #[indexed]
+    pub from: [u8; 32],
+    #[indexed]
+    pub to: [u8; 32],
+    pub amount: u64,
+}
+
+#[derive(Event, Debug, PartialEq)]
+pub struct Paused {
+    pub reason: String,
+}
+
+fn main() {
+    assert_eq!(TransferEvent::NAME, "Transfer");
+    assert_eq!(TransferEvent::TOPIC.len(), 32);
+    assert_eq!(&TransferEvent::TOPIC[..4], &[0xdd, 0xe8, 0xbe, 0xf7]);
+    assert_eq!(TransferEvent::INDEXED, &["from", "to"]);
+    assert!(TransferEvent::SCHEMA.contains("\"indexed\":[\"from\",\"to\"]"));
+
+    let transfer = TransferEvent { from: [1u8; 32], to: [2u8; 32], amount: 100 };
+    let topic = transfer.topic();
+    assert_eq!(topic.len(), 96);
+    assert!(topic.starts_with(TransferEvent::TOPIC));
+    assert_eq!(&topic[32..64], &[1u8; 32]);
+    assert_eq!(&topic[64..], &[2u8; 32]);
+
+    let log = Log { topic, value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&transfer).unwrap() };
+    assert_eq!(TransferEvent::decode(&log), Some(transfer));
+    assert!(Paused::decode(&log).is_none());
+
+    let paused = Paused { reason: "upgrade".to_string() };
+    assert_eq!(paused.topic(), Paused::TOPIC);
+    assert!(Paused::INDEXED.is_empty());
+}
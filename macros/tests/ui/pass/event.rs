@@ -0,0 +1,51 @@
+use pchain_sdk::{event, use_contract, Event, types::Log};
+
+#[event(name = "Transfer")]
+#[derive(Debug, PartialEq)]
+pub struct TransferV1 {
+    pub to: [u8; 32],
+    pub amount: u64,
+}
+
+#[event(version = 2, previous(TransferV1))]
+#[derive(Debug, PartialEq)]
+pub struct Transfer {
+    pub to: [u8; 32],
+    pub amount: u64,
+    pub memo: String,
+}
+
+impl From<TransferV1> for Transfer {
+    fn from(v1: TransferV1) -> Self {
+        Self { to: v1.to, amount: v1.amount, memo: String::new() }
+    }
+}
+
+#[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+pub trait MyToken {
+    event! {
+        #[version = 2]
+        pub struct Transfer {
+            pub to: [u8; 32],
+            pub amount: u64,
+            pub memo: String,
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(TransferV1::TOPIC, b"Transfer");
+    assert_eq!(Transfer::TOPIC, b"Transfer@2");
+    assert_eq!(Transfer::TOPIC, my_token::Transfer::TOPIC);
+    assert_eq!(pchain_sdk::event::parse_topic(Transfer::TOPIC), Some(("Transfer", 2)));
+
+    let v1 = TransferV1 { to: [1u8; 32], amount: 100 };
+    let log = Log { topic: TransferV1::TOPIC.to_vec(), value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&v1).unwrap() };
+    assert_eq!(Transfer::decode(&log), Some(Transfer { to: [1u8; 32], amount: 100, memo: String::new() }));
+
+    let v2 = Transfer { to: [2u8; 32], amount: 5, memo: "rent".to_string() };
+    let log = Log { topic: Transfer::TOPIC.to_vec(), value: pchain_sdk::borsh::BorshSerialize::try_to_vec(&v2).unwrap() };
+    assert!(TransferV1::decode(&log).is_none());
+    assert_eq!(my_token::Transfer::decode(&log).map(|t| t.memo), Some("rent".to_string()));
+    assert_eq!(Transfer::decode(&log), Some(v2));
+}
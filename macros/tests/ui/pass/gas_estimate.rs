@@ -0,0 +1,26 @@
+use pchain_sdk::{contract, contract_methods, call};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(meta(version = "1.2.0"))]
+impl MyContract {
+    #[call(gas_estimate = 120_000)]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+
+    #[call(min_value = 1_000, gas_estimate = 250_000)]
+    fn deposit(&mut self) {}
+
+    #[call]
+    fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+fn main() {
+    assert_eq!(CONTRACT_METADATA, "{\"version\":\"1.2.0\",\"gas_estimates\":{\"increment\":120000,\"deposit\":250000}}");
+}
@@ -0,0 +1,17 @@
+use pchain_sdk::{contract, contract_methods, call, collections::FastMap};
+
+#[contract(layout_version = 1)]
+pub struct MyContract {
+    counter: u64,
+    balances: FastMap<[u8; 32], u64>,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn counter(&self) -> u64 {
+        self.counter
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use pchain_sdk::{contract, contract_methods, call, view, on_receive, ReadOnly, method::MethodInfo};
+
+#[contract]
+pub struct MyContract {
+    deposits: u64,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call(max_value = 0)]
+    fn reset(&mut self, value: u64) {
+        self.deposits = value;
+    }
+
+    #[view]
+    fn deposits(contract: ReadOnly<'_, Self>) -> u64 {
+        *contract.deposits()
+    }
+
+    #[on_receive]
+    fn receive(&mut self, amount: u64) {
+        self.deposits += amount;
+    }
+}
+
+fn main() {
+    let _: Option<fn([u8; 32]) -> Option<Vec<MethodInfo>>> = Some(pchain_sdk::method::methods_of);
+}
@@ -0,0 +1,35 @@
+use pchain_sdk::{contract, contract_methods, call, view, ContractError, metrics::{self, CallMetrics}};
+
+#[contract]
+pub struct MyContract {
+    balance: u64,
+}
+
+#[contract_methods(metrics, multicall)]
+impl MyContract {
+    #[call]
+    fn deposit(&mut self, amount: u64) {
+        self.balance += amount;
+    }
+
+    #[call]
+    fn withdraw(&mut self, amount: u64) -> Result<u64, ContractError> {
+        if amount > self.balance {
+            return Err(ContractError::new(1, "Insufficient balance"))
+        }
+        self.balance -= amount;
+        Ok(self.balance)
+    }
+
+    #[view]
+    fn balance(contract: pchain_sdk::ReadOnly<'_, Self>) -> u64 {
+        *contract.balance()
+    }
+}
+
+fn main() {
+    assert_eq!(metrics::METRICS_TOPIC, b"Metrics");
+    let value = [1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1];
+    assert_eq!(CallMetrics::decode(&value), Some(CallMetrics { method_id: 1, gas_at_entry: 2, success: true }));
+    assert_ne!(metrics::method_id("deposit"), metrics::method_id("withdraw"));
+}
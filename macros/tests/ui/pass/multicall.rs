@@ -0,0 +1,28 @@
+use pchain_sdk::{contract, contract_methods, call, method::{EncodedCall, EncodedResult}};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(multicall)]
+impl MyContract {
+    #[call]
+    fn increment(&mut self, by: u64) -> u64 {
+        self.counter += by;
+        self.counter
+    }
+
+    #[call]
+    fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+fn main() {
+    let _: extern "C" fn() = entrypoint;
+    let call = EncodedCall::new("increment", pchain_sdk::method::ContractMethodInputBuilder::new().add(1u64).to_call_arguments());
+    assert_eq!(call.method_name, "increment");
+    let result = EncodedResult { return_value: Some(2u64.to_le_bytes().to_vec()) };
+    assert_eq!(result.decode::<u64>(), Some(2));
+}
@@ -0,0 +1,38 @@
+use pchain_sdk::{contract, contract_field, contract_methods, call, collections::{FastMap, IterableMap, Vector}, Cacher};
+
+#[contract_field]
+pub struct Inner {
+    value: u64,
+    list: Vector<u64>,
+}
+
+#[contract_field]
+pub struct Outer {
+    inner: Inner,
+    flag: bool,
+}
+
+#[contract]
+pub struct MyContract {
+    outer: Outer,
+    balances: FastMap<[u8; 32], u64>,
+    nested: FastMap<String, FastMap<String, u64>>,
+    members: IterableMap<String, (u8, String)>,
+    cached: Cacher<u64>,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn balance(&self, owner: [u8; 32]) -> Option<u64> {
+        self.balances.get(&owner)
+    }
+
+    #[call]
+    fn push(&mut self, value: u64) {
+        self.outer.inner.list.push(&value);
+        self.outer.inner.value = value;
+    }
+}
+
+fn main() {}
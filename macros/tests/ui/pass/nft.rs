@@ -0,0 +1,35 @@
+use pchain_sdk::{contract, contract_methods, call, standards::nft::NonFungibleToken};
+
+#[contract]
+#[derive(NonFungibleToken)]
+pub struct MyCollection {
+    owner: [u8; 32],
+    nft: NonFungibleToken,
+}
+
+#[contract_methods(nft)]
+impl MyCollection {
+    #[call]
+    fn init(&mut self) {
+        self.owner = pchain_sdk::transaction::calling_account();
+        self.nft = NonFungibleToken::new("My Collection", "MYC", "https://example.com/tokens/");
+    }
+
+    #[call]
+    fn mint(&mut self, to: [u8; 32], token_id: u64, uri: Option<String>) {
+        assert_eq!(self.owner, pchain_sdk::transaction::calling_account());
+        self.nft.mint(to, token_id, uri).unwrap();
+    }
+
+    #[call]
+    fn burn(&mut self, token_id: u64) {
+        assert_eq!(self.nft.owner_of(token_id), Some(pchain_sdk::transaction::calling_account()));
+        self.nft.burn(token_id).unwrap();
+    }
+}
+
+fn main() {
+    let methods = MyCollection::__nft_methods();
+    assert!(methods.iter().any(|method| method.name == "owner_of" && method.arity == 1 && method.view));
+    assert!(methods.iter().any(|method| method.name == "transfer_from" && method.arity == 3 && !method.view));
+}
@@ -0,0 +1,25 @@
+use pchain_sdk::{contract, contract_methods, call, method::{OutputEnvelope, EnvelopeError}};
+
+#[contract]
+pub struct MyContract {
+    counter: u64,
+}
+
+#[contract_methods(meta(version = "1.0.0"), return_envelope)]
+impl MyContract {
+    #[call]
+    fn increment(&mut self) -> u64 {
+        self.counter += 1;
+        self.counter
+    }
+
+    #[call]
+    fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+fn main() {
+    let envelope: Result<OutputEnvelope, EnvelopeError> = OutputEnvelope::decode(&[1, 0, 42]);
+    assert_eq!(envelope.unwrap().payload, &[42]);
+}
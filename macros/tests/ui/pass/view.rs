@@ -0,0 +1,32 @@
+use pchain_sdk::{contract, contract_methods, contract_field, call, view, ReadOnly, collections::FastMap};
+
+#[contract_field]
+pub struct Settings {
+    pub fee: u64,
+}
+
+#[contract]
+pub struct MyContract {
+    settings: Settings,
+    balances: FastMap<[u8; 32], u64>,
+}
+
+#[contract_methods]
+impl MyContract {
+    #[call]
+    fn set_fee(&mut self, fee: u64) {
+        self.settings.fee = fee;
+    }
+
+    #[view]
+    fn fee(contract: ReadOnly<'_, Self>) -> u64 {
+        contract.settings().fee
+    }
+
+    #[view]
+    fn balance_of(contract: ReadOnly<Self>, address: [u8; 32]) -> u64 {
+        contract.balances().get(&address).unwrap_or(0)
+    }
+}
+
+fn main() {}
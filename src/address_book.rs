@@ -0,0 +1,165 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [AddressBook], a contract field that maps short labels to the addresses of collaborating contracts and
+//! accounts, e.g. the oracle, treasury or router, so that they are configured in one place and can be updated by
+//! the owner instead of being hard-coded constants spread through the code.
+
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::IterableMap, transaction, Storable, StoragePath};
+
+/// Maximum length of a label in bytes.
+pub const MAX_LABEL_LEN: usize = 32;
+
+/// Label of the price oracle, e.g. a contract providing [PriceFeed](crate::oracle::PriceFeed).
+pub const ORACLE: &str = "oracle";
+
+/// Label of the treasury, which receives fees or protocol revenue.
+pub const TREASURY: &str = "treasury";
+
+/// Label of the router, e.g. the entry contract of a protocol.
+pub const ROUTER: &str = "router";
+
+/// Error of updating an [AddressBook].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressBookError {
+    /// The calling account is not the owner of the address book.
+    NotOwner,
+    /// The label is empty or longer than [MAX_LABEL_LEN].
+    InvalidLabel,
+    /// No address is set for the label.
+    NotFound,
+}
+
+impl std::fmt::Display for AddressBookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressBookError::NotOwner => write!(f, "caller is not the owner of the address book"),
+            AddressBookError::InvalidLabel => write!(f, "label is empty or longer than {} bytes", MAX_LABEL_LEN),
+            AddressBookError::NotFound => write!(f, "label not found"),
+        }
+    }
+}
+
+/// `AddressBook` is a contract field that stores labelled addresses and their owner. Updates are only accepted from
+/// the owner, which is checked against the calling account of the transaction.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn set_address(&mut self, label: String, address: PublicAddress) {
+///     self.address_book.set(&label, address).unwrap();
+/// }
+/// 
+/// #[call]
+/// fn pay_fee(&mut self, amount: u64) {
+///     pchain_sdk::transfer(self.address_book.treasury(), amount);
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Owner|P, 0|PublicAddress|
+/// |Entries|P, 1|`IterableMap<String, PublicAddress>`|
+/// 
+/// - P: parent key
+pub struct AddressBook {
+    owner: PublicAddress,
+    entries: IterableMap<String, PublicAddress>,
+}
+
+impl AddressBook {
+    /// Instantiate an empty address book owned by `owner`, e.g. the deployer of the contract.
+    pub fn new(owner: PublicAddress) -> Self {
+        Self { owner, entries: IterableMap::new() }
+    }
+
+    pub fn owner(&self) -> PublicAddress {
+        self.owner
+    }
+
+    /// Transfer the ownership of the address book. Only the owner can call it.
+    pub fn set_owner(&mut self, owner: PublicAddress) -> Result<(), AddressBookError> {
+        self.check_owner()?;
+        self.owner = owner;
+        Ok(())
+    }
+
+    /// Set the address of the label, replacing the existing one. Only the owner can call it.
+    pub fn set(&mut self, label: &str, address: PublicAddress) -> Result<(), AddressBookError> {
+        self.check_owner()?;
+        if label.is_empty() || label.len() > MAX_LABEL_LEN {
+            return Err(AddressBookError::InvalidLabel)
+        }
+        self.entries.insert(&label.to_string(), address);
+        Ok(())
+    }
+
+    /// Remove the address of the label. Only the owner can call it.
+    pub fn remove(&mut self, label: &str) -> Result<(), AddressBookError> {
+        self.check_owner()?;
+        let label = label.to_string();
+        if self.entries.get(&label).is_none() {
+            return Err(AddressBookError::NotFound)
+        }
+        self.entries.remove(&label);
+        Ok(())
+    }
+
+    /// Get the address of the label.
+    pub fn get(&self, label: &str) -> Option<PublicAddress> {
+        self.entries.get(&label.to_string())
+    }
+
+    /// Get the address of the label. It panics if it is not set, which aborts a call that depends on an address
+    /// that has not been configured.
+    pub fn require(&self, label: &str) -> PublicAddress {
+        self.get(label).unwrap_or_else(|| panic!("Address book has no address for {}", label))
+    }
+
+    /// The address of [ORACLE]. It panics if it is not set.
+    pub fn oracle(&self) -> PublicAddress {
+        self.require(ORACLE)
+    }
+
+    /// The address of [TREASURY]. It panics if it is not set.
+    pub fn treasury(&self) -> PublicAddress {
+        self.require(TREASURY)
+    }
+
+    /// The address of [ROUTER]. It panics if it is not set.
+    pub fn router(&self) -> PublicAddress {
+        self.require(ROUTER)
+    }
+
+    /// All labels and their addresses, in order of insertion.
+    pub fn entries(&self) -> Vec<(String, PublicAddress)> {
+        self.entries.iter().collect()
+    }
+
+    fn check_owner(&self) -> Result<(), AddressBookError> {
+        if transaction::calling_account() != self.owner {
+            return Err(AddressBookError::NotOwner)
+        }
+        Ok(())
+    }
+}
+
+impl Storable for AddressBook {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            owner: Storable::__load_storage(&field.add(0)),
+            entries: Storable::__load_storage(&field.add(1)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.owner.__save_storage(&field.add(0));
+        self.entries.__save_storage(&field.add(1));
+    }
+}
@@ -0,0 +1,233 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [TokenAmount], an amount of a token in the base units of its number of decimals, so that amounts of
+//! tokens with different decimals, e.g. an 8-decimal and an 18-decimal token, cannot be mixed up without a
+//! conversion.
+//! 
+//! ```no_run
+//! type Xpll = TokenAmount<8>;
+//! type Bridged = TokenAmount<18>;
+//! 
+//! let deposit: Bridged = "1.5".parse().unwrap();
+//! // converting to fewer decimals fails if it would round the amount
+//! let credited: Xpll = deposit.to_decimals().unwrap();
+//! assert_eq!(credited.to_string(), "1.5");
+//! assert_eq!(credited.base_units(), 150_000_000);
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::{storage, Storable, StoragePath};
+
+/// Error of parsing or converting a [TokenAmount].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    /// The string is not a decimal number, e.g. `1.2.3` or `-1`.
+    Invalid,
+    /// The string has more digits after the decimal point than the decimals of the token.
+    TooManyDecimals { max: u8, found: usize },
+    /// The amount in base units does not fit in u128.
+    Overflow,
+    /// Converting to fewer decimals would round the amount.
+    PrecisionLoss,
+}
+
+impl std::fmt::Display for AmountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmountError::Invalid => write!(f, "amount is not a decimal number"),
+            AmountError::TooManyDecimals { max, found } => write!(f, "amount has {} decimals, but the token has {}", found, max),
+            AmountError::Overflow => write!(f, "amount overflows"),
+            AmountError::PrecisionLoss => write!(f, "amount cannot be converted without rounding"),
+        }
+    }
+}
+
+/// `TokenAmount` is an amount of a token with `DECIMALS` decimals, in base units, e.g. `TokenAmount<8>` of 150_000_000
+/// base units is 1.5 tokens. It is serialized in the same way as u128, and formatted and parsed as a decimal number
+/// of tokens. `DECIMALS` is at most 38.
+/// 
+/// Amounts of the same decimals are added and subtracted by the checked methods, or by `+` and `-`, which panic on
+/// overflow. Amounts of different decimals are converted by [TokenAmount::to_decimals] first.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn deposit(&mut self, amount: TokenAmount<18>) {
+///     // rejects amounts with more than 8 decimals, instead of keeping their dust without crediting it
+///     let amount: TokenAmount<8> = amount.to_decimals().expect("Deposit has more than 8 decimals");
+///     self.total = self.total.checked_add(amount).expect("Total deposit overflows");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount<const DECIMALS: u8>(u128);
+
+impl<const DECIMALS: u8> TokenAmount<DECIMALS> {
+    pub const ZERO: Self = Self(0);
+
+    /// One token, i.e. 10^DECIMALS base units.
+    pub const ONE: Self = Self(10u128.pow(DECIMALS as u32));
+
+    pub const MAX: Self = Self(u128::MAX);
+
+    pub const fn from_base_units(base_units: u128) -> Self {
+        Self(base_units)
+    }
+
+    pub const fn base_units(&self) -> u128 {
+        self.0
+    }
+
+    pub const fn decimals(&self) -> u8 {
+        DECIMALS
+    }
+
+    /// The amount of whole tokens. Returns None if it overflows.
+    pub fn from_tokens(tokens: u128) -> Option<Self> {
+        tokens.checked_mul(Self::ONE.0).map(Self)
+    }
+
+    /// The amount in base units as u64, e.g. for transferring XPLL by [transfer](crate::transfer). Returns None if it
+    /// does not fit in u64.
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(self.0).ok()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// The amount multiplied by `numerator / denominator`, rounded down, e.g. for fees in basis points. Returns None
+    /// if the denominator is zero or the product overflows.
+    pub fn checked_mul_div(self, numerator: u128, denominator: u128) -> Option<Self> {
+        self.0.checked_mul(numerator)?.checked_div(denominator).map(Self)
+    }
+
+    /// The same amount with `TO` decimals. Fails with [AmountError::PrecisionLoss] if `TO` is fewer decimals and the
+    /// amount would be rounded, or [AmountError::Overflow] if `TO` is more decimals and the amount overflows.
+    pub fn to_decimals<const TO: u8>(self) -> Result<TokenAmount<TO>, AmountError> {
+        if TO >= DECIMALS {
+            self.0.checked_mul(pow10(TO - DECIMALS)?).map(TokenAmount).ok_or(AmountError::Overflow)
+        } else {
+            let divisor = pow10(DECIMALS - TO)?;
+            if !self.0.is_multiple_of(divisor) {
+                return Err(AmountError::PrecisionLoss)
+            }
+            Ok(TokenAmount(self.0 / divisor))
+        }
+    }
+
+    /// The amount with `TO` decimals, rounded down if `TO` is fewer decimals. Fails with [AmountError::Overflow] if
+    /// `TO` is more decimals and the amount overflows. The rounded-off dust is lost, so amounts received, e.g. 
+    /// deposits, should be converted by [TokenAmount::to_decimals] instead, unless the dust is refunded.
+    pub fn to_decimals_floor<const TO: u8>(self) -> Result<TokenAmount<TO>, AmountError> {
+        if TO >= DECIMALS {
+            self.to_decimals()
+        } else {
+            Ok(TokenAmount(self.0 / pow10(DECIMALS - TO)?))
+        }
+    }
+
+    /// Parse a decimal number of tokens, e.g. `1.5` or `100`, with at most `DECIMALS` digits after the decimal point.
+    pub fn parse(s: &str) -> Result<Self, AmountError> {
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) || s.ends_with('.') {
+            return Err(AmountError::Invalid)
+        }
+        if fraction.len() > DECIMALS as usize {
+            return Err(AmountError::TooManyDecimals { max: DECIMALS, found: fraction.len() })
+        }
+        let digits = [whole, fraction, &"0".repeat(DECIMALS as usize - fraction.len())].concat();
+        digits.parse::<u128>().map(Self).map_err(|_| AmountError::Overflow)
+    }
+}
+
+/// 10^exp, or [AmountError::Overflow] if it does not fit in u128.
+fn pow10(exp: u8) -> Result<u128, AmountError> {
+    10u128.checked_pow(exp as u32).ok_or(AmountError::Overflow)
+}
+
+impl<const DECIMALS: u8> std::ops::Add for TokenAmount<DECIMALS> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("TokenAmount overflows")
+    }
+}
+
+impl<const DECIMALS: u8> std::ops::Sub for TokenAmount<DECIMALS> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other).expect("TokenAmount underflows")
+    }
+}
+
+impl<const DECIMALS: u8> std::iter::Sum for TokenAmount<DECIMALS> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, amount| sum + amount)
+    }
+}
+
+impl<const DECIMALS: u8> std::str::FromStr for TokenAmount<DECIMALS> {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Formats the amount as a decimal number of tokens without trailing zeros, e.g. `1.5`.
+impl<const DECIMALS: u8> std::fmt::Display for TokenAmount<DECIMALS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = format!("{:0>width$}", self.0, width = DECIMALS as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - DECIMALS as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            f.write_str(whole)
+        } else {
+            write!(f, "{}.{}", whole, fraction)
+        }
+    }
+}
+
+impl<const DECIMALS: u8> BorshSerialize for TokenAmount<DECIMALS> {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<const DECIMALS: u8> BorshDeserialize for TokenAmount<DECIMALS> {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        u128::deserialize_reader(reader).map(Self)
+    }
+}
+
+impl<const DECIMALS: u8> Storable for TokenAmount<DECIMALS> {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
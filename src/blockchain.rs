@@ -7,30 +7,37 @@
 //! Transaction. This includes information about the 'current' Block, e.g., its height and timestamp, but also
 //! information about preceding Blocks. 
 
-use crate::imports;
+use crate::host;
 
 /// Get the `number` field of the Block that contains the Transaction which triggered this Contract call. 
 pub fn block_number() -> u64 {
-    unsafe { imports::block_height() }
+    host::current().block_height()
 }
 
 /// Get the `prev_hash` field of the Block that contains the Transaction which triggered this Contract call.
 pub fn prev_block_hash() -> Vec<u8> {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
+    host::current().prev_block_hash().to_vec()
+}
+
+/// Get the `prev_hash` field of the Block that contains the Transaction which triggered this Contract call, as a 
+/// fixed-size array.
+pub fn prev_block_hash_array() -> [u8; 32] {
+    host::current().prev_block_hash()
+}
 
-    unsafe {
-        imports::prev_block_hash(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8, 32, 32)
-    }
+/// Get the random bytes of the Block that contains the Transaction which triggered this Contract call, e.g. as an 
+/// additional source of entropy for commit-reveal schemes. It requires the host feature 
+/// [RandomBytes](crate::runtime::HostFeature::RandomBytes), which is provided with the feature `random-bytes` of the SDK.
+pub fn random_bytes() -> [u8; 32] {
+    host::current().random_bytes()
 }
 
 /// Get the `timestamp` field of the Block that contains the Transaction which triggered this Contract call.
 pub fn timestamp() -> u32 {
-    unsafe { imports::block_timestamp() }
+    host::current().block_timestamp()
 }
 
 /// Get the balance of current account
 pub fn balance() -> u64 {
-    unsafe { imports::balance() }
+    host::current().balance()
 }
\ No newline at end of file
@@ -0,0 +1,264 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [BridgeVerifier], a contract field that verifies messages relayed from other chains to a bridge endpoint
+//! contract. A message is accepted if a threshold of a set of signers (e.g. the validators or guardians of the bridge)
+//! has signed its [MessageEnvelope], and each message is accepted at most once.
+//! 
+//! The verification is chain-agnostic: the source and destination chains are only identified by IDs agreed by the
+//! bridge, and the payload is opaque to the verifier, which only checks it against the hash in the envelope.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::FastMap, crypto, storage, transaction, Storable, StoragePath};
+
+/// Prefix of the signed bytes of a [MessageEnvelope], which separates them from other messages signed by the same keys.
+pub const BRIDGE_MESSAGE_DOMAIN: &[u8] = b"pchain-bridge-message";
+
+/// The part of a bridge message that is signed.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageEnvelope {
+    /// ID of the source chain, as agreed by the bridge.
+    pub source_chain_id: u64,
+    /// Nonce of the message on the source chain. Each nonce is accepted once per source chain.
+    pub nonce: u64,
+    /// ID of the chain of the bridge endpoint contract, as agreed by the bridge. It prevents a message for an endpoint
+    /// at the same address on another chain from being accepted.
+    pub destination_chain_id: u64,
+    /// Address of the bridge endpoint contract that the message is for.
+    pub destination: PublicAddress,
+    /// SHA256 digest of the payload.
+    pub payload_hash: [u8; 32],
+}
+
+impl MessageEnvelope {
+    /// Envelope of the payload.
+    pub fn new(source_chain_id: u64, nonce: u64, destination_chain_id: u64, destination: PublicAddress, payload: &[u8]) -> Self {
+        Self {
+            source_chain_id,
+            nonce,
+            destination_chain_id,
+            destination,
+            payload_hash: crypto::sha256(payload.to_vec()).try_into().unwrap()
+        }
+    }
+
+    /// The bytes signed by the signers, i.e. [BRIDGE_MESSAGE_DOMAIN] followed by the borsh-serialized envelope.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        [BRIDGE_MESSAGE_DOMAIN.to_vec(), self.try_to_vec().unwrap()].concat()
+    }
+}
+
+/// Signature scheme of the signers of a [BridgeVerifier].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SignatureScheme {
+    /// Signers are ed25519 public keys, i.e. addresses on ParallelChain.
+    #[default]
+    Ed25519,
+    /// Signers are secp256k1 public keys. It requires the host feature [Secp256k1](crate::runtime::HostFeature::Secp256k1).
+    Secp256k1,
+}
+
+/// A signature over the signing bytes of an envelope, by the signer at `signer_index` of the signer set.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BridgeSignature {
+    pub signer_index: u32,
+    pub signature: Vec<u8>,
+}
+
+/// Error of verifying a message in [BridgeVerifier].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeError {
+    /// The message is for a contract on another chain.
+    WrongDestinationChain,
+    /// The message is for another contract.
+    WrongDestination,
+    /// The payload does not match the payload hash of the envelope.
+    PayloadMismatch,
+    /// The message with the source chain ID and nonce has been accepted.
+    AlreadyProcessed,
+    /// The signer index is not in the signer set.
+    UnknownSigner(u32),
+    /// The signer has signed more than once.
+    DuplicateSigner(u32),
+    /// The number of valid signatures is below the threshold.
+    InsufficientSignatures { valid: u32, threshold: u32 },
+}
+
+impl std::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgeError::WrongDestinationChain => write!(f, "message is for another destination chain"),
+            BridgeError::WrongDestination => write!(f, "message is for another destination"),
+            BridgeError::PayloadMismatch => write!(f, "payload does not match the payload hash"),
+            BridgeError::AlreadyProcessed => write!(f, "message already processed"),
+            BridgeError::UnknownSigner(index) => write!(f, "unknown signer {}", index),
+            BridgeError::DuplicateSigner(index) => write!(f, "duplicate signature of signer {}", index),
+            BridgeError::InsufficientSignatures { valid, threshold } => write!(f, "{} valid signatures, {} required", valid, threshold),
+        }
+    }
+}
+
+/// `BridgeVerifier` is a contract field that stores the ID of this chain, a signer set with a signature threshold, 
+/// and the messages that have been accepted.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn receive_message(&mut self, envelope: MessageEnvelope, payload: Vec<u8>, signatures: Vec<BridgeSignature>) {
+///     self.verifier.accept(&envelope, &payload, &signatures).unwrap();
+///     // decode and execute the payload ...
+/// }
+/// ```
+/// 
+/// Every signature is verified by a host call, since the host has no function to verify a batch of signatures, so a 
+/// relayer should only submit the signatures needed to reach the threshold.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Signature Scheme|P, 0|SignatureScheme|
+/// |Signers|P, 1|`Vec<Vec<u8>>`|
+/// |Threshold|P, 2|u32|
+/// |Processed Messages|P, 3|`FastMap<(u64, u64), bool>`|
+/// |Chain ID|P, 4|u64|
+/// 
+/// - P: parent key
+/// - Processed messages are keyed by source chain ID and nonce.
+pub struct BridgeVerifier {
+    chain_id: u64,
+    scheme: SignatureScheme,
+    signers: Vec<Vec<u8>>,
+    threshold: u32,
+    processed: FastMap<(u64, u64), bool>,
+}
+
+impl BridgeVerifier {
+    /// Instantiate with the ID of this chain as agreed by the bridge, the signer set and the number of signatures
+    /// required. It panics if the threshold is 0 or greater than the number of signers.
+    pub fn new(chain_id: u64, scheme: SignatureScheme, signers: Vec<Vec<u8>>, threshold: u32) -> Self {
+        Self::check_threshold(signers.len(), threshold);
+        Self { chain_id, scheme, signers, threshold, processed: FastMap::new() }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn signers(&self) -> &[Vec<u8>] {
+        &self.signers
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Replace the signer set and the threshold, e.g. on rotation of the validators of the bridge. Signatures
+    /// refer to signers by index, so messages signed by the old set cannot be accepted afterwards.
+    pub fn set_signers(&mut self, signers: Vec<Vec<u8>>, threshold: u32) {
+        Self::check_threshold(signers.len(), threshold);
+        self.signers = signers;
+        self.threshold = threshold;
+    }
+
+    /// Check if the message with the source chain ID and nonce has been accepted.
+    pub fn is_processed(&self, source_chain_id: u64, nonce: u64) -> bool {
+        self.processed.get(&(source_chain_id, nonce)).unwrap_or(false)
+    }
+
+    /// Verify that the envelope is signed by at least the threshold of signers. It does not check replay.
+    /// 
+    /// The signatures are verified one at a time, as the host only exposes functions to verify a single ed25519 or
+    /// secp256k1 signature.
+    pub fn verify(&self, envelope: &MessageEnvelope, signatures: &[BridgeSignature]) -> Result<(), BridgeError> {
+        let message = envelope.signing_bytes();
+        let mut signed = vec![false; self.signers.len()];
+        let mut valid = 0;
+        for BridgeSignature { signer_index, signature } in signatures {
+            let signer = self.signers.get(*signer_index as usize).ok_or(BridgeError::UnknownSigner(*signer_index))?;
+            if signed[*signer_index as usize] {
+                return Err(BridgeError::DuplicateSigner(*signer_index))
+            }
+            signed[*signer_index as usize] = true;
+
+            let is_valid = match self.scheme {
+                SignatureScheme::Ed25519 => crypto::verify_ed25519_signature(message.clone(), signature.clone(), signer.clone()),
+                SignatureScheme::Secp256k1 => crypto::verify_secp256k1_signature(message.clone(), signature.clone(), signer.clone()),
+            };
+            if is_valid {
+                valid += 1;
+            }
+        }
+        if valid < self.threshold {
+            return Err(BridgeError::InsufficientSignatures { valid, threshold: self.threshold })
+        }
+        Ok(())
+    }
+
+    /// Accept the message if it is for this contract on this chain, matches the payload, has not been accepted, and
+    /// is signed by at least the threshold of signers. The message is then marked as processed.
+    pub fn accept(&mut self, envelope: &MessageEnvelope, payload: &[u8], signatures: &[BridgeSignature]) -> Result<(), BridgeError> {
+        if envelope.destination_chain_id != self.chain_id {
+            return Err(BridgeError::WrongDestinationChain)
+        }
+        if envelope.destination != transaction::current_account() {
+            return Err(BridgeError::WrongDestination)
+        }
+        if crypto::sha256(payload.to_vec()) != envelope.payload_hash {
+            return Err(BridgeError::PayloadMismatch)
+        }
+        if self.is_processed(envelope.source_chain_id, envelope.nonce) {
+            return Err(BridgeError::AlreadyProcessed)
+        }
+        self.verify(envelope, signatures)?;
+        self.processed.insert(&(envelope.source_chain_id, envelope.nonce), true);
+        Ok(())
+    }
+
+    fn check_threshold(signer_count: usize, threshold: u32) {
+        if threshold == 0 || threshold as usize > signer_count {
+            panic!("Invalid threshold {} for {} signers", threshold, signer_count);
+        }
+    }
+}
+
+impl Storable for SignatureScheme {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
+
+impl Storable for BridgeVerifier {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            chain_id: Storable::__load_storage(&field.add(4)),
+            scheme: Storable::__load_storage(&field.add(0)),
+            signers: Storable::__load_storage(&field.add(1)),
+            threshold: Storable::__load_storage(&field.add(2)),
+            processed: Storable::__load_storage(&field.add(3)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.scheme.__save_storage(&field.add(0));
+        self.signers.__save_storage(&field.add(1));
+        self.threshold.__save_storage(&field.add(2));
+        self.processed.__save_storage(&field.add(3));
+        self.chain_id.__save_storage(&field.add(4));
+    }
+}
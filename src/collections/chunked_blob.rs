@@ -0,0 +1,277 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the collection struct [ChunkedBlob].
+
+use std::collections::BTreeMap;
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::storage;
+use super::CollectionStats;
+use crate::{Storable, StoragePath};
+
+/// Default size of the chunks of a [ChunkedBlob] in bytes.
+pub const DEFAULT_CHUNK_SIZE: u32 = 16 * 1024;
+
+/// Manifest of a [ChunkedBlob] saved in world state.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ChunkManifest {
+    /// Length of the blob in bytes.
+    len: u64,
+    /// Size of the chunks in bytes. The last chunk may be shorter.
+    chunk_size: u32,
+}
+
+/// [ChunkedBlob] is a contract-level data structure that stores a byte array of any length, e.g. a document or a
+/// long list of encoded records, in chunks of a fixed size under separate keys, so that no value in world state
+/// exceeds the chunk size. Parts of the blob can be read and appended without reading the whole blob.
+/// 
+/// ## ChunkedBlob
+/// 
+/// `ChunkedBlob` can be a Contract Field defined in the contract struct. E.g.
+/// 
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     document: ChunkedBlob,
+/// }
+/// 
+/// #[call]
+/// fn upload(&mut self, part: Vec<u8>) {
+///     self.document.append(&part);
+/// }
+/// 
+/// #[call]
+/// fn download(&self, offset: u64, len: u32) -> Vec<u8> {
+///     self.document.read(offset, len as usize)
+/// }
+/// ```
+/// 
+/// The chunk size is [DEFAULT_CHUNK_SIZE] unless specified by [ChunkedBlob::with_chunk_size]. It is saved with the
+/// blob, and cannot be changed afterwards.
+/// 
+/// ### Storage Model
+/// 
+/// Account Storage State Key Format:
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Manifest|P, 0|length (u64) and chunk size (u32)|
+/// |Chunk|P, 1, I|bytes of the chunk (not Borsh-serialized)|
+/// - P: parent key
+/// - I: little endian bytes of index of the chunk (u32)
+/// 
+/// ### Lazy Write
+/// 
+/// Trait `Storage` implements the `ChunkedBlob` so that data can be saved to world state
+/// 1. after execution of action method with receiver `&mut self`; or
+/// 2. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
+/// 
+/// Only the chunks changed since the last save are written. Chunks beyond the end of a shortened blob are deleted.
+#[derive(Clone)]
+pub struct ChunkedBlob {
+    /// Length and chunk size of the blob at runtime.
+    manifest: ChunkManifest,
+    /// Length and chunk size of the blob in world state.
+    saved_manifest: ChunkManifest,
+    /// Contents of the chunks that are to be written to world state, by index.
+    write_set: BTreeMap<u32, Vec<u8>>,
+    /// The key of contract field, which is used to formulate the keys of the chunks.
+    parent_key: Vec<u8>,
+}
+
+impl ChunkedBlob {
+    /// Instantiate an empty blob with chunks of [DEFAULT_CHUNK_SIZE].
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Instantiate an empty blob with chunks of `chunk_size` bytes. It panics if `chunk_size` is 0.
+    pub fn with_chunk_size(chunk_size: u32) -> Self {
+        if chunk_size == 0 {
+            panic!("Chunk size of ChunkedBlob must be positive");
+        }
+        Self {
+            manifest: ChunkManifest { len: 0, chunk_size },
+            saved_manifest: ChunkManifest::default(),
+            write_set: BTreeMap::new(),
+            parent_key: vec![],
+        }
+    }
+
+    /// Length of the blob in bytes.
+    pub fn len(&self) -> u64 {
+        self.manifest.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn chunk_size(&self) -> u32 {
+        self.manifest.chunk_size
+    }
+
+    /// Number of chunks of the blob.
+    pub fn chunk_count(&self) -> u32 {
+        Self::chunks_of(&self.manifest)
+    }
+
+    /// `append` adds bytes to the end of the blob, which does not immediately take effect in Contract Storage. It
+    /// reads the last chunk if it is not full, and writes the chunks from it onwards.
+    pub fn append(&mut self, bytes: &[u8]) {
+        let chunk_size = self.manifest.chunk_size as u64;
+        let mut remaining = bytes;
+        while !remaining.is_empty() {
+            let index = (self.manifest.len / chunk_size) as u32;
+            let offset = (self.manifest.len % chunk_size) as usize;
+            let mut chunk = if offset == 0 { vec![] } else { self.chunk(index) };
+            chunk.truncate(offset);
+            let taken = remaining.len().min(chunk_size as usize - offset);
+            chunk.extend_from_slice(&remaining[..taken]);
+            self.write_set.insert(index, chunk);
+            self.manifest.len += taken as u64;
+            remaining = &remaining[taken..];
+        }
+    }
+
+    /// `set` replaces the content of the blob, which does not immediately take effect in Contract Storage. It does not
+    /// read the existing chunks.
+    pub fn set(&mut self, bytes: &[u8]) {
+        self.clear();
+        self.append(bytes);
+    }
+
+    /// `clear` empties the blob. Its chunks are deleted from Contract Storage when the blob is saved.
+    pub fn clear(&mut self) {
+        self.write_set.clear();
+        self.manifest.len = 0;
+    }
+
+    /// `read` returns at most `len` bytes from `offset`, fewer if the blob ends before. Only the chunks in the range
+    /// are read.
+    pub fn read(&self, offset: u64, len: usize) -> Vec<u8> {
+        let end = offset.saturating_add(len as u64).min(self.manifest.len);
+        if offset >= end {
+            return vec![]
+        }
+        let chunk_size = self.manifest.chunk_size as u64;
+        let mut bytes = Vec::with_capacity((end - offset) as usize);
+        for index in (offset / chunk_size)..=((end - 1) / chunk_size) {
+            let chunk_start = index * chunk_size;
+            let chunk = self.chunk(index as u32);
+            let from = (offset.max(chunk_start) - chunk_start) as usize;
+            let to = (end.min(chunk_start + chunk_size) - chunk_start) as usize;
+            bytes.extend_from_slice(&chunk[from..to]);
+        }
+        bytes
+    }
+
+    /// `read_all` returns the whole blob, which reads all chunks.
+    pub fn read_all(&self) -> Vec<u8> {
+        self.read(0, self.manifest.len as usize)
+    }
+
+    /// `stats` summarizes the blob in world state without reading its chunks. `entries` is the number of chunks, and
+    /// `approx_stored_bytes` is the saved length of the blob.
+    ///
+    /// ```no_run
+    /// let stats = self.document.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        CollectionStats {
+            entries: Some(Self::chunks_of(&self.saved_manifest) as u64),
+            level: 0,
+            pending_writes: self.write_set.len() as u64 + (self.manifest != self.saved_manifest) as u64,
+            approx_stored_bytes: self.saved_manifest.len,
+        }
+    }
+
+    /// The content of the chunk, from the write set or world state.
+    fn chunk(&self, index: u32) -> Vec<u8> {
+        if let Some(chunk) = self.write_set.get(&index) {
+            return chunk.clone()
+        }
+        if self.parent_key.is_empty() || index >= Self::chunks_of(&self.saved_manifest) {
+            return vec![]
+        }
+        let chunk = storage::get(&Self::wskey_chunk(&self.parent_key, index)).unwrap_or_default();
+        if chunk.len() < self.chunk_len(index) {
+            panic!("Chunk {} of ChunkedBlob is missing in world state", index);
+        }
+        chunk
+    }
+
+    /// Expected length of the chunk, from the runtime manifest.
+    fn chunk_len(&self, index: u32) -> usize {
+        let chunk_size = self.manifest.chunk_size as u64;
+        (self.manifest.len.saturating_sub(index as u64 * chunk_size)).min(chunk_size) as usize
+    }
+
+    fn chunks_of(manifest: &ChunkManifest) -> u32 {
+        if manifest.chunk_size == 0 {
+            return 0
+        }
+        manifest.len.div_ceil(manifest.chunk_size as u64) as u32
+    }
+
+    /// Account Storage State Key for saving the manifest.
+    fn wskey_manifest(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[0u8]].concat()
+    }
+
+    /// Account Storage State Key for saving a chunk, keyed by its index.
+    fn wskey_chunk(parent_key: &[u8], index: u32) -> Vec<u8> {
+        [parent_key, &[1u8], &index.to_le_bytes()].concat()
+    }
+}
+
+impl Default for ChunkedBlob {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Storable for ChunkedBlob {
+    fn __load_storage(field: &StoragePath) -> Self {
+        let parent_key = field.get_path().to_vec();
+        let manifest = storage::get(&Self::wskey_manifest(&parent_key))
+            .map(|bytes| ChunkManifest::try_from_slice(&bytes).unwrap())
+            .unwrap_or(ChunkManifest { len: 0, chunk_size: DEFAULT_CHUNK_SIZE });
+        Self {
+            manifest,
+            saved_manifest: manifest,
+            write_set: BTreeMap::new(),
+            parent_key,
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        let field_path = field.get_path().to_vec();
+        // set parent key here for the cases that ChunkedBlob is instantiated first and then assigned to field in contract struct
+        if self.parent_key != field_path {
+            self.parent_key = field_path;
+            self.saved_manifest = storage::get(&Self::wskey_manifest(&self.parent_key))
+                .map(|bytes| ChunkManifest::try_from_slice(&bytes).unwrap())
+                .unwrap_or_default();
+        }
+
+        // save changed chunks, and delete the chunks beyond the end of the blob
+        let chunk_count = self.chunk_count();
+        for (index, chunk) in std::mem::take(&mut self.write_set) {
+            if index < chunk_count {
+                storage::set(&Self::wskey_chunk(&self.parent_key, index), &chunk);
+            }
+        }
+        for index in chunk_count..Self::chunks_of(&self.saved_manifest) {
+            storage::delete(&Self::wskey_chunk(&self.parent_key, index));
+        }
+
+        if self.manifest != self.saved_manifest {
+            storage::set(&Self::wskey_manifest(&self.parent_key), &self.manifest.try_to_vec().unwrap());
+            self.saved_manifest = self.manifest;
+        }
+    }
+}
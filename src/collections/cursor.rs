@@ -0,0 +1,88 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Cursor] and [Page], the types of paginated reads of the collections.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+/// [Cursor] is the position in a collection where a paginated read continues, as returned in a [Page] by `page()`
+/// of [Vector](super::Vector), [FastMap](super::FastMap) and [IterableMap](super::IterableMap). It is opaque to
+/// clients, which pass the cursor of a page unchanged to the view method to get the next page, starting from
+/// [Cursor::start]. E.g.
+/// 
+/// ```no_run
+/// #[view]
+/// fn orders(&self, cursor: Cursor) -> Page<(u64, Order)> {
+///     self.orders.page(&cursor, 20)
+/// }
+/// ```
+/// 
+/// A cursor keeps its position across transactions: entries inserted after it are returned in later pages, and
+/// entries removed before it do not shift it. A cursor issued before the collection was cleared continues to an
+/// empty last page. A cursor of another collection, or of another type, panics or returns unrelated entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+pub struct Cursor(Vec<u8>);
+
+impl Cursor {
+    /// The cursor of the first page.
+    pub fn start() -> Self {
+        Self(vec![])
+    }
+
+    pub fn is_start(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The cursor at `position` of the collection, while the collection is at `generation`, e.g. the level of an
+    /// IterableMap, which changes when the collection is cleared.
+    pub(crate) fn new(generation: u32, position: u64) -> Self {
+        Self((generation, position).try_to_vec().unwrap())
+    }
+
+    /// The position of the cursor in the collection at `generation`. A cursor issued at another generation is
+    /// positioned at the end. It panics if the cursor is malformed.
+    pub(crate) fn position(&self, generation: u32) -> u64 {
+        if self.is_start() {
+            return 0
+        }
+        match <(u32, u64)>::try_from_slice(&self.0) {
+            Ok((g, position)) if g == generation => position,
+            Ok(_) => u64::MAX,
+            Err(_) => panic!("Invalid cursor"),
+        }
+    }
+}
+
+/// [Page] is the result of a paginated read of a collection: at most the requested number of items, and the
+/// cursor of the next page. `next` is None if the collection has no more items.
+/// 
+/// A page may have fewer items than requested while `next` is Some, e.g. if entries after it were removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Cursor>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self { items: vec![], next: None }
+    }
+}
+
+impl<T> BorshSerialize for Page<T> where T: BorshSerialize {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.items.serialize(writer)?;
+        self.next.serialize(writer)
+    }
+}
+
+impl<T> BorshDeserialize for Page<T> where T: BorshDeserialize {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            items: Vec::<T>::deserialize_reader(reader)?,
+            next: Option::<Cursor>::deserialize_reader(reader)?,
+        })
+    }
+}
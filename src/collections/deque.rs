@@ -0,0 +1,312 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the collection struct [Deque].
+
+use std::collections::BTreeMap;
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::storage;
+use super::{CollectionStats, Cursor, Page};
+use crate::{Storable, StoragePath};
+
+/// Position of the front and the back of an empty [Deque], in the middle of the positions so that it can grow in both
+/// directions.
+const INITIAL_POSITION: u64 = 1 << 63;
+
+/// Positions of the elements of a [Deque] saved in world state.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+struct DequeBounds {
+    /// Position of the first element.
+    head: u64,
+    /// Position after the last element.
+    tail: u64,
+}
+
+impl Default for DequeBounds {
+    fn default() -> Self {
+        Self { head: INITIAL_POSITION, tail: INITIAL_POSITION }
+    }
+}
+
+/// [Deque] is a contract-level data structure of a double-ended queue, e.g. a job queue or the orders at a price level
+/// of an order book. Elements are pushed and popped at both ends without moving the other elements, since each element
+/// is stored at its own position, and the deque keeps the positions of its ends.
+/// 
+/// ## Deque
+/// 
+/// `Deque` can be a Contract Field defined in the contract struct. E.g.
+/// 
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     jobs: Deque<Job>,
+/// }
+/// 
+/// #[call]
+/// fn submit(&mut self, job: Job) {
+///     self.jobs.push_back(job);
+/// }
+/// 
+/// #[call]
+/// fn run_next(&mut self) {
+///     if let Some(job) = self.jobs.pop_front() {
+///         // ...
+///     }
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// Account Storage State Key Format:
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Bounds|P, 0|position of the first element, and after the last element (u64, u64)|
+/// |Element|P, 1, I|user defined data (borsh-serialized)|
+/// - P: parent key
+/// - I: little endian bytes of position of the element (u64)
+/// 
+/// Positions of an empty deque start at 2^63, and decrease by `push_front` and increase by `push_back`.
+/// 
+/// ### Lazy Write
+/// 
+/// Trait `Storage` implements the `Deque` so that data can be saved to world state
+/// 1. after execution of action method with receiver `&mut self`; or
+/// 2. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
+/// 
+/// Popped elements are deleted from world state when the deque is saved. [Deque::clear] does not delete the elements,
+/// which are overwritten as the deque grows again.
+#[derive(Clone)]
+pub struct Deque<T> where T: BorshSerialize + BorshDeserialize + Clone {
+    /// Positions of the ends at runtime.
+    bounds: DequeBounds,
+    /// Positions of the ends in world state.
+    saved_bounds: DequeBounds,
+    /// Elements to be written to world state by position, or None to be deleted.
+    write_set: BTreeMap<u64, Option<T>>,
+    /// The key of contract field, which is used to formulate the keys of the elements.
+    parent_key: Vec<u8>,
+}
+
+impl<T> Deque<T> where T: BorshSerialize + BorshDeserialize + Clone {
+    pub fn new() -> Self {
+        Self {
+            bounds: DequeBounds::default(),
+            saved_bounds: DequeBounds::default(),
+            write_set: BTreeMap::new(),
+            parent_key: vec![],
+        }
+    }
+
+    /// Number of elements of the deque.
+    pub fn len(&self) -> u64 {
+        self.bounds.tail - self.bounds.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `push_back` adds the element after the last element, which does not immediately take effect in Contract Storage.
+    pub fn push_back(&mut self, value: T) {
+        self.write_set.insert(self.bounds.tail, Some(value));
+        self.bounds.tail = self.bounds.tail.checked_add(1).expect("Deque is full at the back");
+    }
+
+    /// `push_front` adds the element before the first element, which does not immediately take effect in Contract
+    /// Storage.
+    pub fn push_front(&mut self, value: T) {
+        self.bounds.head = self.bounds.head.checked_sub(1).expect("Deque is full at the front");
+        self.write_set.insert(self.bounds.head, Some(value));
+    }
+
+    /// `pop_back` removes the last element and returns it, reading it from world state if it was not pushed in this
+    /// call. Returns None if the deque is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        self.bounds.tail -= 1;
+        Some(self.take(self.bounds.tail))
+    }
+
+    /// `pop_front` removes the first element and returns it, reading it from world state if it was not pushed in this
+    /// call. Returns None if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        let position = self.bounds.head;
+        self.bounds.head += 1;
+        Some(self.take(position))
+    }
+
+    /// The first element, if any.
+    pub fn front(&self) -> Option<T> {
+        self.get(0)
+    }
+
+    /// The last element, if any.
+    pub fn back(&self) -> Option<T> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// The element at the index from the front, if any.
+    pub fn get(&self, index: u64) -> Option<T> {
+        if index >= self.len() {
+            return None
+        }
+        Some(self.element(self.bounds.head + index))
+    }
+
+    /// `clear` empties the deque, which does not immediately take effect in Contract Storage.
+    pub fn clear(&mut self) {
+        self.write_set.clear();
+        self.bounds.head = self.bounds.tail;
+    }
+
+    /// Iterator of the elements from the front. Iterating is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// self.jobs.iter().for_each(|job| {
+    ///     ...
+    /// });
+    /// ```
+    pub fn iter(&self) -> DequeIter<'_, T> {
+        DequeIter { deque: self, position: self.bounds.head }
+    }
+
+    /// Copy at most `limit` elements from the position of `cursor` into a [Page], with the cursor of the next page.
+    /// A cursor keeps its position when elements are popped from the front, and continues from the new front if it is
+    /// before it. Reading is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let page: Page<Job> = self.jobs.page(&cursor, 20);
+    /// ```
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<T> {
+        let start = cursor.position(0).clamp(self.bounds.head, self.bounds.tail);
+        let end = start.saturating_add(limit as u64).min(self.bounds.tail);
+        let items = (start..end).map(|position| self.element(position)).collect();
+        let next = (end < self.bounds.tail).then(|| Cursor::new(0, end));
+        Page { items, next }
+    }
+
+    /// Summary of the deque in world state. It reads the first element from world state.
+    /// ### Example
+    /// ```no_run
+    /// let stats = self.jobs.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        let entries = self.saved_bounds.tail - self.saved_bounds.head;
+        let sample = if entries > 0 && !self.parent_key.is_empty() {
+            storage::get(&Self::wskey_element(&self.parent_key, self.saved_bounds.head)).map_or(0, |bytes| bytes.len() as u64)
+        } else {
+            0
+        };
+        CollectionStats {
+            entries: Some(entries),
+            level: 0,
+            pending_writes: self.write_set.len() as u64 + (self.bounds != self.saved_bounds) as u64,
+            approx_stored_bytes: entries * sample,
+        }
+    }
+
+    /// The element at the position, from the write set or world state.
+    fn element(&self, position: u64) -> T {
+        match self.write_set.get(&position) {
+            Some(Some(value)) => value.clone(),
+            _ => self.element_from_ws(position)
+        }
+    }
+
+    fn element_from_ws(&self, position: u64) -> T {
+        // parent key absent, cannot query world state data
+        if self.parent_key.is_empty() { panic!("Element of Deque is missing") }
+        let bytes = storage::get(&Self::wskey_element(&self.parent_key, position)).expect("Element of Deque is missing in world state");
+        T::try_from_slice(&bytes).unwrap()
+    }
+
+    /// Remove the element at the position from the write set, and delete it from world state if it is saved there.
+    fn take(&mut self, position: u64) -> T {
+        let value = match self.write_set.remove(&position) {
+            Some(Some(value)) => value,
+            _ => self.element_from_ws(position)
+        };
+        if self.saved_bounds.head <= position && position < self.saved_bounds.tail {
+            self.write_set.insert(position, None);
+        }
+        value
+    }
+
+    fn load_bounds(parent_key: &[u8]) -> DequeBounds {
+        storage::get(&Self::wskey_bounds(parent_key))
+            .map(|bytes| DequeBounds::try_from_slice(&bytes).unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Account Storage State Key for saving the positions of the ends.
+    fn wskey_bounds(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[0u8]].concat()
+    }
+
+    /// Account Storage State Key for saving an element, keyed by its position.
+    fn wskey_element(parent_key: &[u8], position: u64) -> Vec<u8> {
+        [parent_key, &[1u8], &position.to_le_bytes()].concat()
+    }
+}
+
+impl<T> Storable for Deque<T> where T: BorshSerialize + BorshDeserialize + Clone {
+    fn __load_storage(field: &StoragePath) -> Self {
+        let parent_key = field.get_path().to_vec();
+        let bounds = Self::load_bounds(&parent_key);
+        Self {
+            bounds,
+            saved_bounds: bounds,
+            write_set: BTreeMap::new(),
+            parent_key,
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        let field_path = field.get_path().to_vec();
+        // set parent key here for the cases that Deque is instantiated first and then assigned to field in contract struct
+        if self.parent_key != field_path {
+            self.parent_key = field_path;
+            self.saved_bounds = Self::load_bounds(&self.parent_key);
+        }
+
+        for (position, value) in std::mem::take(&mut self.write_set) {
+            match value {
+                Some(value) => storage::set(&Self::wskey_element(&self.parent_key, position), &value.try_to_vec().unwrap()),
+                None => storage::delete(&Self::wskey_element(&self.parent_key, position)),
+            }
+        }
+
+        if self.bounds != self.saved_bounds {
+            storage::set(&Self::wskey_bounds(&self.parent_key), &self.bounds.try_to_vec().unwrap());
+            self.saved_bounds = self.bounds;
+        }
+    }
+}
+
+/// Iterator created by `Deque::iter()`
+pub struct DequeIter<'a, T> where T: BorshSerialize + BorshDeserialize + Clone {
+    deque: &'a Deque<T>,
+    position: u64,
+}
+
+impl<'a, T> Iterator for DequeIter<'a, T> where T: BorshSerialize + BorshDeserialize + Clone {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.position >= self.deque.bounds.tail {
+            return None
+        }
+        let value = self.deque.element(self.position);
+        self.position += 1;
+        Some(value)
+    }
+}
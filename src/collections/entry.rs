@@ -0,0 +1,152 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Entry], the entry of a key in a map returned by `entry()` of [FastMap](super::FastMap) and
+//! [IterableMap](super::IterableMap), and the trait [EntryTarget] of the maps.
+
+/// The trait of maps with `entry()`, i.e. [FastMap](super::FastMap) and [IterableMap](super::IterableMap). Its
+/// functions are called by [Entry], and are not meant to be called by contracts.
+pub trait EntryTarget<K> {
+    type Value;
+
+    /// Read the value of the key into the cache of the map, to be written back when the map is saved. Returns false if
+    /// the key has no value.
+    #[doc(hidden)]
+    fn __load_entry(&mut self, key: &K) -> bool;
+    /// The cached value of a key loaded by `__load_entry`.
+    #[doc(hidden)]
+    fn __entry_ref(&self, key: &K) -> &Self::Value;
+    /// The cached value of a key loaded by `__load_entry`, as mutable.
+    #[doc(hidden)]
+    fn __entry_mut(&mut self, key: &K) -> &mut Self::Value;
+    /// Insert the value of a key that has no value.
+    #[doc(hidden)]
+    fn __insert_entry(&mut self, key: &K, value: Self::Value) -> &mut Self::Value;
+    /// Remove a key loaded by `__load_entry`, and return its cached value.
+    #[doc(hidden)]
+    fn __remove_entry(&mut self, key: &K) -> Self::Value;
+}
+
+/// `Entry` is the entry of a key in a map, which is either occupied by a value or vacant. Creating the entry reads the
+/// value once, so that a read-modify-write takes one read from world state instead of a `get` and an `insert`. As
+/// with `get_mut`, the value of an occupied entry is written back to world state when the map is saved.
+/// 
+/// ### Example
+/// ```no_run
+/// *self.balances.entry(address).or_insert(0) += amount;
+/// 
+/// match self.orders.entry(id) {
+///     Entry::Occupied(entry) if entry.get().is_filled() => { entry.remove(); },
+///     Entry::Occupied(mut entry) => entry.get_mut().fill(amount),
+///     Entry::Vacant(_) => panic!("Order not found"),
+/// }
+/// ```
+pub enum Entry<'a, K, M> where M: EntryTarget<K> {
+    Occupied(OccupiedEntry<'a, K, M>),
+    Vacant(VacantEntry<'a, K, M>),
+}
+
+impl<'a, K, M> Entry<'a, K, M> where M: EntryTarget<K> {
+    /// Read the entry of the key in the map.
+    pub(crate) fn new(map: &'a mut M, key: K) -> Self {
+        if map.__load_entry(&key) {
+            Entry::Occupied(OccupiedEntry { map, key })
+        } else {
+            Entry::Vacant(VacantEntry { map, key })
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// The value of the entry, after inserting `default` if it is vacant.
+    pub fn or_insert(self, default: M::Value) -> &'a mut M::Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// The value of the entry, after inserting the result of `default` if it is vacant.
+    pub fn or_insert_with<F: FnOnce() -> M::Value>(self, default: F) -> &'a mut M::Value {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// The value of the entry, after inserting the default value if it is vacant.
+    pub fn or_default(self) -> &'a mut M::Value where M::Value: Default {
+        self.or_insert_with(Default::default)
+    }
+
+    /// Modify the value of the entry if it is occupied.
+    pub fn and_modify<F: FnOnce(&mut M::Value)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            },
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// The entry of a key that has a value, in [Entry].
+pub struct OccupiedEntry<'a, K, M> where M: EntryTarget<K> {
+    map: &'a mut M,
+    key: K,
+}
+
+impl<'a, K, M> OccupiedEntry<'a, K, M> where M: EntryTarget<K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &M::Value {
+        self.map.__entry_ref(&self.key)
+    }
+
+    pub fn get_mut(&mut self) -> &mut M::Value {
+        self.map.__entry_mut(&self.key)
+    }
+
+    /// The value as mutable, with the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut M::Value {
+        self.map.__entry_mut(&self.key)
+    }
+
+    /// Replace the value, and return the previous value.
+    pub fn insert(&mut self, value: M::Value) -> M::Value {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the key from the map, and return its value.
+    pub fn remove(self) -> M::Value {
+        self.map.__remove_entry(&self.key)
+    }
+}
+
+/// The entry of a key that has no value, in [Entry].
+pub struct VacantEntry<'a, K, M> where M: EntryTarget<K> {
+    map: &'a mut M,
+    key: K,
+}
+
+impl<'a, K, M> VacantEntry<'a, K, M> where M: EntryTarget<K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert the value, which does not immediately take effect in Contract Storage.
+    pub fn insert(self, value: M::Value) -> &'a mut M::Value {
+        self.map.__insert_entry(&self.key, value)
+    }
+}
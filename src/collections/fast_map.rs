@@ -8,6 +8,7 @@
 use std::{marker::PhantomData, collections::BTreeMap};
 use borsh::{BorshSerialize, BorshDeserialize};
 use crate::{storage::{self}, Storable, StoragePath};
+use super::{MapKey, CollectionStats, Cursor, Entry, EntryTarget, Page};
 
 /// [FastMap] is a contract-level data structure to provide abstraction by utilizing Get and Set operations 
 /// associated with Contract Storage. It supports lazy read/write on key-value tuples.
@@ -31,31 +32,69 @@ use crate::{storage::{self}, Storable, StoragePath};
 /// |Component|Key|Value (Data type) |
 /// |:---|:---|:---|
 /// |Key-Value|P, E, K| `Cell` |
+/// |Purge Cursor|P, X| u32 |
+/// |Edition Log Length|P, X, E| u32 |
+/// |Edition Log Entry|P, X, E, I| K |
+/// |Index Flag|P, X, X| bool |
 /// 
 /// - P: parent key
 /// - E: little endian bytes of edition number (u32)
-/// - K: user defined key
+/// - K: user defined key, encoded by [MapKey]
+/// - X: little endian bytes of the reserved edition number `u32::MAX`
+/// - I: little endian bytes of index (u32)
 /// 
 /// In account storage state, the key format is `parent key` + `edition` (u32, 4 bytes) + `user defined key`. If nested FastMap is 
 /// inserted to FastMap as a value, `parent key` would be the key of the FastMap being inserted. Actual value to be stored
 /// into world state is borsh-serialized structure of `Cell` which is either a value (bytes) or information of nested map.
 /// 
+/// ### Editions
+/// 
+/// Replacing or removing a nested FastMap does not delete its children. Instead, the edition of the nested map is increased
+/// so that children written in older editions are no longer reachable. A nested FastMap keeps a log of the keys written in
+/// each of its editions, so that the unreachable children can be reclaimed incrementally with [FastMap::purge_old_editions].
+/// 
+/// The edition `u32::MAX` is reserved for the edition log. A contract call that would increase an edition to it panics, rather 
+/// than wrapping around and making children of edition 0 reachable again.
+/// 
+/// ### Key Index
+/// 
+/// A `FastMap` created by [FastMap::with_index] also logs the keys written to it when it is a contract field, which 
+/// enables [FastMap::keys]. The index is the edition log of the current edition, so it costs one extra write per 
+/// new key, and no extra reads for `get` and `insert`. E.g.
+/// 
+/// ```no_run
+/// #[call]
+/// fn init(&mut self) {
+///     self.balances = FastMap::with_index();
+/// }
+/// ```
+/// 
+/// Only keys written after the index is enabled are indexed. Assigning `FastMap::with_index()` to a contract field
+/// that already has entries keeps them reachable by `get`, since the edition of a contract field never changes, but 
+/// they are not returned by [FastMap::keys]. Such a field is indexed by creating it with the index from the start, or
+/// by moving its entries into a new indexed field, e.g. in batches across calls.
+/// 
 /// ### Lazy Write
 /// 
 /// Trait `Storage` implements the `FastMap` so that data can be saved to world state
 /// 
 /// 1. after execution of action method with receiver `&mut self`; or
 /// 2. explicitly calling the setter `Self::set()`.
+/// 3. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
 pub struct FastMap<K, V> 
-    where K: BorshSerialize, 
+    where K: MapKey, 
           V: Insertable {
     parent_key: Vec<u8>,
     write_set: BTreeMap<Vec<u8>, UpdateOperation<V>>,
+    /// The key index is requested by [FastMap::with_index], and is not yet flagged in world state.
+    with_index: bool,
+    /// The map is a value of another `FastMap`, so that its keys are logged by edition.
+    nested: bool,
     _marker: PhantomData<Box<(K, V)>>
 }
 
 impl<K, V> FastMap<K, V> 
-    where K: BorshSerialize, 
+    where K: MapKey, 
           V: Insertable {
 
     /// New instance of `FastMap` detached to world state, which is mainly used for being a nested map as a value of parent `FastMap`
@@ -66,7 +105,116 @@ impl<K, V> FastMap<K, V>
     /// self.fast_map.insert(&"fast_map".to_string(), fast_map);
     /// ```
     pub fn new() -> Self {
-        Self { parent_key: vec![], write_set: BTreeMap::default(), _marker: PhantomData }
+        Self { parent_key: vec![], write_set: BTreeMap::default(), with_index: false, nested: false, _marker: PhantomData }
+    }
+
+    /// New instance of `FastMap` that maintains an index of its keys, so that they can be iterated by [FastMap::keys].
+    /// The index is kept once the map is saved to world state. Keys already in world state at the key of the map are
+    /// not indexed, as explained in the Key Index section of [FastMap].
+    /// ### Example
+    /// ```no_run
+    /// self.fast_map = FastMap::with_index();
+    /// ```
+    pub fn with_index() -> Self {
+        Self { with_index: true, ..Self::new() }
+    }
+
+    /// New instance of `FastMap` with the key-value pairs, e.g. for initializing or migrating a contract field. 
+    /// Panics if there are more than `limit` pairs, to bound the writes of a single call.
+    /// ### Example
+    /// ```no_run
+    /// self.fast_map = FastMap::from_iter(initial_balances, 100);
+    /// ```
+    pub fn from_iter(entries: impl IntoIterator<Item = (K, V)>, limit: usize) -> Self {
+        let mut map = Self::new();
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            if i >= limit {
+                panic!("FastMap::from_iter exceeds the limit of {} entries", limit);
+            }
+            map.insert(&key, value);
+        }
+        map
+    }
+
+    /// Check if the map maintains an index of its keys.
+    pub fn is_indexed(&self) -> bool {
+        self.with_index || (!self.parent_key.is_empty() && Self::index_flag(&self.parent_key))
+    }
+
+    /// Iterator to iterating keys in the map, in the order they were first saved to world state (keys saved in the same 
+    /// call are in the order of their encoding). Keys inserted in this call but not yet saved follow in the order of their encoding. Iterating is a Lazy Read operation. Panics if the map is not created 
+    /// by [FastMap::with_index].
+    /// ### Example
+    /// ```no_run
+    /// self.fast_map.keys().for_each(|k| {
+    ///     ...
+    /// });
+    /// ```
+    pub fn keys(&self) -> FastMapKeys<'_, K, V> {
+        if !self.is_indexed() {
+            panic!("FastMap is not indexed. Create it by FastMap::with_index() to iterate its keys.");
+        }
+        let (edition, len) = if self.parent_key.is_empty() {
+            (0, 0)
+        } else {
+            let edition = Self::edition(&self.parent_key);
+            (edition, load_u32(&Self::wskey_log_len(&self.parent_key, edition)))
+        };
+        // new keys that are not yet logged
+        let pending = self.write_set.iter()
+            .filter(|(k, op)| {
+                matches!(op, UpdateOperation::Insert(..)) && 
+                (self.parent_key.is_empty() || storage::get(&Self::make_child_key(self.parent_key.to_vec(), edition, k.to_vec())).is_none())
+            })
+            .map(|(k, _)| k.clone())
+            .collect();
+        FastMapKeys { fast_map: self, edition, idx: 0, len, pending, pending_idx: 0 }
+    }
+
+    /// Copy at most `limit` key-value pairs, in order of [FastMap::keys] from the position of `cursor`, into a [Page],
+    /// with the cursor of the next page. It panics if the map is not indexed. A cursor issued before the map was
+    /// replaced continues to an empty page. Reading is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let page: Page<(String, u64)> = self.fast_map.page(&cursor, 20);
+    /// ```
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<(K, V)> {
+        let mut keys = self.keys();
+        let position = cursor.position(keys.edition);
+        if position < keys.len as u64 {
+            keys.idx = position as u32;
+        } else {
+            keys.idx = keys.len;
+            keys.pending_idx = usize::try_from(position - keys.len as u64).unwrap_or(usize::MAX).min(keys.pending.len());
+        }
+        // same order as FastMapKeys, reading each value once
+        let mut items = Vec::new();
+        while items.len() < limit && keys.idx < keys.len {
+            let wskey_log_entry = Self::wskey_log_entry(&self.parent_key, keys.edition, keys.idx);
+            keys.idx += 1;
+            if let Some(key) = storage::get(&wskey_log_entry).and_then(|bytes| K::from_map_key(&bytes)) {
+                if let Some(value) = self.get(&key) {
+                    items.push((key, value));
+                }
+            }
+        }
+        while items.len() < limit && keys.pending_idx < keys.pending.len() {
+            let bytes = &keys.pending[keys.pending_idx];
+            keys.pending_idx += 1;
+            if let Some(key) = K::from_map_key(bytes) {
+                if let Some(value) = self.get(&key) {
+                    items.push((key, value));
+                }
+            }
+        }
+        let next = if keys.idx < keys.len {
+            Some(Cursor::new(keys.edition, keys.idx as u64))
+        } else if keys.pending_idx < keys.pending.len() {
+            Some(Cursor::new(keys.edition, keys.len as u64 + keys.pending_idx as u64))
+        } else {
+            None
+        };
+        Page { items, next }
     }
 
     /// Get data either from cached value or world state.
@@ -82,7 +230,7 @@ impl<K, V> FastMap<K, V>
     /// }
     /// ```
     pub fn get(&self, key: &K) -> Option<V> {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
 
         match self.write_set.get(&key_bs) {
             Some(UpdateOperation::Delete) => return None,
@@ -114,7 +262,7 @@ impl<K, V> FastMap<K, V>
         match self.get(key) {
             Some(v) => {
                 self.insert_inner(key, v, false);
-                let key_bs = key.try_to_vec().unwrap();
+                let key_bs = key.to_map_key();
                 match self.write_set.get_mut(&key_bs) {
                     Some(UpdateOperation::Insert(mut_v, _)) => Some(mut_v),
                     _=> None
@@ -124,6 +272,15 @@ impl<K, V> FastMap<K, V>
         }
     }
 
+    /// Get the entry of the key for in-place modification, which reads the value once from the cache or world state.
+    /// ### Example
+    /// ```no_run
+    /// *self.fast_map.entry(key).or_insert(0) += 1;
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, Self> {
+        Entry::new(self, key)
+    }
+
     /// Insert data to the cache of the `FastMap`. The value will be stored to world state after contract execution.
     /// ### Example
     /// ```no_run
@@ -134,7 +291,7 @@ impl<K, V> FastMap<K, V>
     }
 
     fn insert_inner(&mut self, key: &K, value: V, new_record: bool) -> Option<&mut V> {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
         self.write_set.insert(key_bs.clone(), UpdateOperation::Insert(value, new_record));
         match self.write_set.get_mut(&key_bs) {
             Some(UpdateOperation::Insert(mut_insertable, _)) => Some(mut_insertable),
@@ -148,10 +305,143 @@ impl<K, V> FastMap<K, V>
     /// self.fast_map.remove(key);
     /// ```
     pub fn remove(&mut self, key: &K) {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
         self.write_set.insert(key_bs, UpdateOperation::Delete);
     }
 
+    /// Remove the children of this nested `FastMap` that were written in older editions and are no longer reachable. 
+    /// At most `limit` keys are removed from world state in one call, so that the clean-up can be spread across multiple 
+    /// transactions. It performs actual Write to world state. Returns the number of keys removed.
+    /// ### Example
+    /// ```no_run
+    /// if let Some(nested_map) = self.fast_map.get_mut(key) {
+    ///     let removed = nested_map.purge_old_editions(100);
+    /// }
+    /// ```
+    pub fn purge_old_editions(&mut self, limit: u32) -> u32 {
+        if self.parent_key.is_empty() { // newly created map that is not yet saved to world state
+            return 0
+        }
+        let edition = Self::edition(&self.parent_key);
+        let (removed, _) = Self::purge_editions(&self.parent_key, edition, limit);
+        removed
+    }
+
+    /// Summary of the map in world state. It reads the edition, the length of the edition log and the first logged 
+    /// entry from world state. The number of entries is only known if the keys are logged, i.e. the map is nested or 
+    /// created by [FastMap::with_index]. Otherwise `entries` is None and `approx_stored_bytes` is 0.
+    /// ### Example
+    /// ```no_run
+    /// let stats = self.fast_map.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        if self.parent_key.is_empty() { // newly created map that is not yet saved to world state
+            return CollectionStats { entries: Some(0), pending_writes: self.write_set.len() as u64, ..Default::default() }
+        }
+        let edition = Self::edition(&self.parent_key);
+        let pending_writes = self.write_set.len() as u64;
+        let logged = self.nested || Self::index_flag(&self.parent_key);
+        if !logged {
+            return CollectionStats { entries: None, level: edition, pending_writes, approx_stored_bytes: 0 }
+        }
+        let entries = load_u32(&Self::wskey_log_len(&self.parent_key, edition)) as u64;
+        let sample = storage::get(&Self::wskey_log_entry(&self.parent_key, edition, 0)).map_or(0, |key| {
+            let vkey = Self::make_child_key(self.parent_key.to_vec(), edition, key);
+            storage::get(&vkey).map_or(0, |bytes| (vkey.len() + bytes.len()) as u64)
+        });
+        CollectionStats {
+            entries: Some(entries),
+            level: edition,
+            pending_writes,
+            approx_stored_bytes: entries * sample
+        }
+    }
+
+    /// Remove the logged children of the editions before `until`, starting from the edition stored in the purge cursor.
+    /// Returns the number of keys removed and whether all of these editions are purged.
+    fn purge_editions(parent_key: &[u8], until: u32, limit: u32) -> (u32, bool) {
+        let wskey_cursor = Self::wskey_purge_cursor(parent_key);
+        let mut edition = load_u32(&wskey_cursor);
+        let mut removed = 0;
+        while edition < until {
+            let wskey_log_len = Self::wskey_log_len(parent_key, edition);
+            let mut len = load_u32(&wskey_log_len);
+            while len > 0 {
+                if removed >= limit {
+                    storage::set(&wskey_log_len, len.try_to_vec().unwrap().as_slice());
+                    storage::set(&wskey_cursor, edition.try_to_vec().unwrap().as_slice());
+                    return (removed, false)
+                }
+                let wskey_log_entry = Self::wskey_log_entry(parent_key, edition, len - 1);
+                if let Some(key) = storage::get(&wskey_log_entry) {
+                    let (n, done) = V::purge(Self::make_child_key(parent_key.to_vec(), edition, key), limit - removed);
+                    removed += n;
+                    if !done {
+                        storage::set(&wskey_log_len, len.try_to_vec().unwrap().as_slice());
+                        storage::set(&wskey_cursor, edition.try_to_vec().unwrap().as_slice());
+                        return (removed, false)
+                    }
+                    storage::delete(&wskey_log_entry);
+                }
+                len -= 1;
+            }
+            storage::delete(&wskey_log_len);
+            edition += 1;
+        }
+        storage::set(&wskey_cursor, edition.try_to_vec().unwrap().as_slice());
+        (removed, true)
+    }
+
+    /// Append a user defined key to the log of keys written in the edition.
+    fn append_log(parent_key: &[u8], edition: u32, key: &[u8]) {
+        let wskey_log_len = Self::wskey_log_len(parent_key, edition);
+        let len = load_u32(&wskey_log_len);
+        storage::set(&Self::wskey_log_entry(parent_key, edition, len), key);
+        storage::set(&wskey_log_len, (len + 1).try_to_vec().unwrap().as_slice());
+    }
+
+    /// Account Storage State Key for saving whether the map maintains an index of its keys.
+    fn wskey_index_flag(parent_key: &[u8]) -> Vec<u8> {
+        [
+            parent_key,
+            LOG_EDITION.to_le_bytes().as_slice(),
+            LOG_EDITION.to_le_bytes().as_slice()
+        ].concat()
+    }
+
+    fn index_flag(parent_key: &[u8]) -> bool {
+        storage::get(&Self::wskey_index_flag(parent_key)).is_some_and(|bytes| {
+            bool::deserialize(&mut bytes.as_slice()).unwrap_or(false)
+        })
+    }
+
+    /// Account Storage State Key for saving the first edition that is not yet purged.
+    fn wskey_purge_cursor(parent_key: &[u8]) -> Vec<u8> {
+        [
+            parent_key,
+            LOG_EDITION.to_le_bytes().as_slice()
+        ].concat()
+    }
+
+    /// Account Storage State Key for saving the number of keys logged in an edition.
+    fn wskey_log_len(parent_key: &[u8], edition: u32) -> Vec<u8> {
+        [
+            parent_key,
+            LOG_EDITION.to_le_bytes().as_slice(),
+            edition.to_le_bytes().as_slice()
+        ].concat()
+    }
+
+    /// Account Storage State Key for saving a key logged in an edition, keyed by index of the log entry.
+    fn wskey_log_entry(parent_key: &[u8], edition: u32, index: u32) -> Vec<u8> {
+        [
+            parent_key,
+            LOG_EDITION.to_le_bytes().as_slice(),
+            edition.to_le_bytes().as_slice(),
+            index.to_le_bytes().as_slice()
+        ].concat()
+    }
+
     fn child_key(&self, key: Vec<u8>) -> Vec<u8> {
         let edition = Self::edition(&self.parent_key);
         Self::make_child_key(self.parent_key.to_vec(), edition, key)
@@ -166,11 +456,12 @@ impl<K, V> FastMap<K, V>
 
 }
 
-impl<K, V> Insertable for  FastMap<K, V> 
-    where K: BorshSerialize, 
+impl<K, V> FastMap<K, V> 
+    where K: MapKey, 
           V: Insertable {
-    /// Save to world state by `FastMap`'s storage model
-    fn save(&mut self, key: Vec<u8>, is_new: bool){ 
+    /// Save to world state by `FastMap`'s storage model. Keys are logged by edition only if this map is nested, 
+    /// because the editions of a map in contract field never change.
+    fn save_inner(&mut self, key: Vec<u8>, is_new: bool, log_keys: bool) {
         if self.parent_key.is_empty() {
             self.parent_key = key;
         }
@@ -178,7 +469,8 @@ impl<K, V> Insertable for  FastMap<K, V>
         let edition = match storage::get(&self.parent_key) {
             Some(bytes) => {
                 match Cell::deserialize(&mut bytes.as_slice()) {
-                    Ok(c) => c.edition + u32::from(is_new),
+                    Ok(c) if is_new => next_edition(c.edition),
+                    Ok(c) => c.edition,
                     Err(_) => 0,
                 }
             },
@@ -188,10 +480,19 @@ impl<K, V> Insertable for  FastMap<K, V>
         let c = Cell { edition, data: Some(self.parent_key.try_to_vec().unwrap()) };
         storage::set(&self.parent_key, c.try_to_vec().unwrap().as_slice());
 
+        if self.with_index {
+            storage::set(&Self::wskey_index_flag(&self.parent_key), true.try_to_vec().unwrap().as_slice());
+            self.with_index = false;
+        }
+        let log_keys = log_keys || (!self.write_set.is_empty() && Self::index_flag(&self.parent_key));
+
         self.write_set.iter_mut().for_each(|(k, v)| {
             let vkey = Self::make_child_key(self.parent_key.to_vec(), edition, k.clone());
             match v {
                 UpdateOperation::Insert(v, is_new) => {
+                    if log_keys && *is_new && storage::get(&vkey).is_none() {
+                        Self::append_log(&self.parent_key, edition, k);
+                    }
                     v.save(vkey, *is_new);
                 },
                 UpdateOperation::Delete => {
@@ -199,11 +500,73 @@ impl<K, V> Insertable for  FastMap<K, V>
                 },
             }
         });
+        // written changes are read from world state, so that a later save does not write them again
+        self.write_set.clear();
+    }
+}
+
+impl<K, V> EntryTarget<K> for FastMap<K, V>
+    where K: MapKey,
+          V: Insertable {
+    type Value = V;
+
+    fn __load_entry(&mut self, key: &K) -> bool {
+        self.get_mut(key).is_some()
+    }
+
+    fn __entry_ref(&self, key: &K) -> &V {
+        match self.write_set.get(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of FastMap is not loaded")
+        }
+    }
+
+    fn __entry_mut(&mut self, key: &K) -> &mut V {
+        match self.write_set.get_mut(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of FastMap is not loaded")
+        }
+    }
+
+    fn __insert_entry(&mut self, key: &K, value: V) -> &mut V {
+        self.insert_inner(key, value, true).unwrap()
+    }
+
+    fn __remove_entry(&mut self, key: &K) -> V {
+        let value = match self.write_set.remove(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of FastMap is not loaded")
+        };
+        self.remove(key);
+        value
+    }
+}
+
+impl<K, V> Insertable for  FastMap<K, V> 
+    where K: MapKey, 
+          V: Insertable {
+    /// Save to world state by `FastMap`'s storage model
+    fn save(&mut self, key: Vec<u8>, is_new: bool){ 
+        self.nested = true;
+        self.save_inner(key, is_new, true);
+    }
+
+    /// Remove the children of all editions of the nested map, and then the nested map itself.
+    fn purge(key: Vec<u8>, limit: u32) -> (u32, bool) {
+        let edition = Self::edition(&key);
+        let (removed, done) = Self::purge_editions(&key, edition + 1, limit);
+        if !done || removed >= limit {
+            return (removed, false)
+        }
+        storage::delete(&Self::wskey_purge_cursor(&key));
+        storage::delete(&Self::wskey_index_flag(&key));
+        storage::delete(&key);
+        (removed + 1, true)
     }
 }
 
 impl<K, V> BorshSerialize for FastMap<K, V> 
-    where K: BorshSerialize, 
+    where K: MapKey, 
           V: Insertable {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         // Serialization of `FastMap` itself takes only parent_key to be stored.
@@ -212,20 +575,22 @@ impl<K, V> BorshSerialize for FastMap<K, V>
 }
 
 impl<K, V> BorshDeserialize for FastMap<K, V>
-    where K: BorshSerialize, 
+    where K: MapKey, 
           V: Insertable {
     fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let parent_key = Vec::<u8>::deserialize_reader(reader)?;
         Ok(Self{
             parent_key,
             write_set: BTreeMap::default(),
+            with_index: false,
+            nested: true,
             _marker: PhantomData,
         })
     }
 }
 
 impl<K, V> Storable for FastMap<K, V> 
-    where K: BorshSerialize, 
+    where K: MapKey, 
           V: Insertable {
     
     /// This method is called at the beginning of contract execution, if this `FastMap` is a field of the Contract Struct.
@@ -233,13 +598,50 @@ impl<K, V> Storable for FastMap<K, V>
         Self {
             parent_key: field.get_path().to_vec(),
             write_set: BTreeMap::default(),
+            with_index: false,
+            nested: false,
             _marker: PhantomData,
         }
     }
 
     /// This method is called at the end of contract execution, if this `FastMap` is a field of the Contract Struct.
     fn __save_storage(&mut self, field: &StoragePath) {
-        self.save(field.get_path().to_vec(), false);
+        self.save_inner(field.get_path().to_vec(), false, false);
+    }
+}
+
+/// Return data type for `FastMap::keys()`
+pub struct FastMapKeys<'a, K, V>
+    where K: MapKey,
+          V: Insertable {
+    fast_map: &'a FastMap<K, V>,
+    edition: u32,
+    idx: u32,
+    len: u32,
+    pending: Vec<Vec<u8>>,
+    pending_idx: usize,
+}
+
+impl<'a, K, V> Iterator for FastMapKeys<'a, K, V>
+    where K: MapKey,
+          V: Insertable {
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        // keys logged in world state, skipping removed keys
+        while self.idx < self.len {
+            let wskey_log_entry = FastMap::<K, V>::wskey_log_entry(&self.fast_map.parent_key, self.edition, self.idx);
+            self.idx += 1;
+            if let Some(key) = storage::get(&wskey_log_entry).and_then(|bytes| K::from_map_key(&bytes)) {
+                if self.fast_map.get(&key).is_some() {
+                    return Some(key)
+                }
+            }
+        }
+        // keys that are newly inserted
+        let bytes = self.pending.get(self.pending_idx)?;
+        self.pending_idx += 1;
+        K::from_map_key(bytes)
     }
 }
 
@@ -280,7 +682,7 @@ pub trait Insertable : BorshSerialize + BorshDeserialize {
     fn save(&mut self, key: Vec<u8>, is_new: bool) {
         let edition = storage::get(&key).map_or(0, |bytes| {
             Cell::deserialize(&mut bytes.as_slice()).map_or(0, |c| 
-                c.edition + u32::from(is_new)
+                if is_new { next_edition(c.edition) } else { c.edition }
             )
         });
         let c = Cell { edition, data: Some(self.try_to_vec().unwrap()) };
@@ -289,11 +691,40 @@ pub trait Insertable : BorshSerialize + BorshDeserialize {
 
     fn delete(key: Vec<u8>) {
         let edition = storage::get(&key).map_or(0, |bytes| {
-            Cell::deserialize(&mut bytes.as_slice()).map_or(0, |c| c.edition + 1)
+            Cell::deserialize(&mut bytes.as_slice()).map_or(0, |c| next_edition(c.edition))
         });
         let c = Cell { edition, data: None };
         storage::set(&key, c.try_to_vec().unwrap().as_slice());
     }
+
+    /// Remove the cell that is no longer reachable, together with the data stored under it. At most `limit` keys 
+    /// are removed. Returns the number of keys removed and whether the removal is completed.
+    fn purge(key: Vec<u8>, limit: u32) -> (u32, bool) {
+        if limit == 0 {
+            return (0, false)
+        }
+        // Setting an empty value removes the key from world state.
+        storage::delete(&key);
+        (1, true)
+    }
+}
+
+/// The edition reserved for the keys of edition logs. Editions of data never reach this value.
+const LOG_EDITION: u32 = u32::MAX;
+
+/// Returns the edition following `edition`. It panics if editions are exhausted, because wrapping around 
+/// would make the data written in edition 0 reachable again.
+fn next_edition(edition: u32) -> u32 {
+    match edition.checked_add(1) {
+        Some(next) if next != LOG_EDITION => next,
+        _ => panic!("FastMap edition is exhausted"),
+    }
+}
+
+fn load_u32(key: &[u8]) -> u32 {
+    storage::get(key).map_or(0, |bytes| {
+        u32::deserialize(&mut bytes.as_slice()).unwrap_or(0)
+    })
 }
 
 
@@ -309,9 +740,23 @@ macro_rules! define_primitives {
 define_primitives!(
     u8, u16, u32, u64, u128,
     i8, i16, i32, i64, i128,
-    String, bool, usize,
-    [u8;32]
+    String, bool, usize
+);
+// Data types in pchain_types. Aliases of byte arrays, e.g. `PublicAddress` and `Sha256Hash`, are covered by the impl for arrays.
+define_primitives!(
+    pchain_types::blockchain::Log,
+    pchain_types::blockchain::Command,
+    pchain_types::runtime::CallInput,
+    pchain_types::runtime::CreateDepositInput,
+    pchain_types::runtime::SetDepositSettingsInput,
+    pchain_types::runtime::TopUpDepositInput,
+    pchain_types::runtime::WithdrawDepositInput,
+    pchain_types::runtime::StakeDepositInput,
+    pchain_types::runtime::UnstakeDepositInput,
+    pchain_types::rpc::Deposit,
+    pchain_types::rpc::Stake
 );
+impl<T, const N: usize> Insertable for [T; N] where T: BorshSerialize + BorshDeserialize {}
 impl<T> Insertable for Option<T> where T: BorshSerialize + BorshDeserialize {}
 impl<T> Insertable for Vec<T> where T: BorshSerialize + BorshDeserialize {}
 macro_rules! impl_tuple {
@@ -0,0 +1,225 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the collection struct [InternTable].
+
+use std::{cell::OnceCell, collections::BTreeMap};
+
+use crate::storage;
+use super::CollectionStats;
+use crate::{Storable, StoragePath};
+
+/// [InternTable] is a contract-level data structure that maps strings to compact ids of type u32 and back. Contracts
+/// that store many repeated labels, e.g. token symbols or country codes, can store the ids of the labels in their
+/// records, and resolve the labels only when they are returned, e.g. in view methods.
+/// 
+/// ## InternTable
+/// 
+/// `InternTable` can be a Contract Field defined in the contract struct. E.g.
+/// 
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     countries: InternTable,
+///     residence: FastMap<PublicAddress, u32>,
+/// }
+/// 
+/// #[call]
+/// fn register(&mut self, country: String) {
+///     let id = self.countries.intern(&country);
+///     self.residence.insert(&transaction::calling_account(), id);
+/// }
+/// 
+/// #[view]
+/// fn country_of(contract: ReadOnly<'_, Self>, account: PublicAddress) -> Option<String> {
+///     contract.residence().get(&account).and_then(|id| contract.countries().resolve(id))
+/// }
+/// ```
+/// 
+/// Ids are assigned from 0 in the order the strings are first interned, and never change. Strings cannot be removed,
+/// so that ids stored in other fields stay valid.
+/// 
+/// ### Storage Model
+/// 
+/// Account Storage State Key Format:
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Length|P, 0|number of strings (u32)|
+/// |String-Id|P, 1, S|id of the string (u32)|
+/// |Id-String|P, 2, I|the string (UTF-8 bytes, not Borsh-serialized)|
+/// - P: parent key
+/// - S: UTF-8 bytes of the string
+/// - I: little endian bytes of the id (u32)
+/// 
+/// ### Lazy Read
+/// 
+/// Nothing is read from world state when the contract is loaded. The length is read when a string is interned or
+/// [InternTable::len] is called, and [InternTable::id_of] and [InternTable::resolve] read one key each.
+/// 
+/// ### Lazy Write
+/// 
+/// Trait `Storage` implements the `InternTable` so that data can be saved to world state
+/// 1. after execution of action method with receiver `&mut self`; or
+/// 2. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
+/// 
+/// Only the strings interned since the last save are written, together with the length.
+#[derive(Clone)]
+pub struct InternTable {
+    /// Number of strings at runtime. Empty until it is read from world state.
+    len: OnceCell<u32>,
+    /// Strings interned since the last save, by id.
+    write_set: BTreeMap<u32, String>,
+    /// Ids of the strings in the write set.
+    new_ids: BTreeMap<String, u32>,
+    /// The key of contract field, which is used to formulate the keys of the strings.
+    parent_key: Vec<u8>,
+}
+
+impl InternTable {
+    /// Instantiate an empty table.
+    pub fn new() -> Self {
+        Self {
+            len: OnceCell::from(0),
+            write_set: BTreeMap::new(),
+            new_ids: BTreeMap::new(),
+            parent_key: vec![],
+        }
+    }
+
+    /// Number of strings in the table.
+    pub fn len(&self) -> u32 {
+        *self.len.get_or_init(|| Self::len_in_ws(&self.parent_key))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `intern` returns the id of the string, and assigns the next id to it if it is not yet in the table, which does
+    /// not immediately take effect in Contract Storage. It panics if the table has `u32::MAX` strings.
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.id_of(name) {
+            return id
+        }
+        let id = self.len();
+        if id == u32::MAX {
+            panic!("InternTable cannot hold more than {} strings", u32::MAX);
+        }
+        self.len = OnceCell::from(id + 1);
+        self.write_set.insert(id, name.to_string());
+        self.new_ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// `id_of` returns the id of the string, or None if it is not in the table. It does not assign an id.
+    pub fn id_of(&self, name: &str) -> Option<u32> {
+        if let Some(id) = self.new_ids.get(name) {
+            return Some(*id)
+        }
+        if self.parent_key.is_empty() {
+            return None
+        }
+        storage::get(&Self::wskey_id(&self.parent_key, name))
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// `resolve` returns the string of the id, or None if no string has the id.
+    pub fn resolve(&self, id: u32) -> Option<String> {
+        if let Some(name) = self.write_set.get(&id) {
+            return Some(name.clone())
+        }
+        if self.parent_key.is_empty() {
+            return None
+        }
+        storage::get(&Self::wskey_name(&self.parent_key, id))
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+    }
+
+    /// `stats` summarizes the table in world state with at most two reads. `entries` is the number of strings, and
+    /// `approx_stored_bytes` is estimated by the size of the string of id 0, which is stored twice.
+    ///
+    /// ```no_run
+    /// let stats = self.countries.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        if self.parent_key.is_empty() { // newly created table that is not yet saved to world state
+            return CollectionStats { entries: Some(0), pending_writes: self.write_set.len() as u64, ..Default::default() }
+        }
+        let entries = Self::len_in_ws(&self.parent_key) as u64;
+        let sample = if entries > 0 {
+            storage::get(&Self::wskey_name(&self.parent_key, 0)).map_or(0, |bytes| 2 * bytes.len() as u64 + 4)
+        } else {
+            0
+        };
+        CollectionStats {
+            entries: Some(entries),
+            level: 0,
+            pending_writes: self.write_set.len() as u64,
+            approx_stored_bytes: entries * sample,
+        }
+    }
+
+    fn len_in_ws(parent_key: &[u8]) -> u32 {
+        if parent_key.is_empty() {
+            return 0
+        }
+        storage::get(&Self::wskey_len(parent_key))
+            .map_or(0, |bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Account Storage State Key for saving the number of strings.
+    fn wskey_len(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[0u8]].concat()
+    }
+
+    /// Account Storage State Key for saving the id of a string, keyed by the string.
+    fn wskey_id(parent_key: &[u8], name: &str) -> Vec<u8> {
+        [parent_key, &[1u8], name.as_bytes()].concat()
+    }
+
+    /// Account Storage State Key for saving a string, keyed by its id.
+    fn wskey_name(parent_key: &[u8], id: u32) -> Vec<u8> {
+        [parent_key, &[2u8], &id.to_le_bytes()].concat()
+    }
+}
+
+impl Default for InternTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Storable for InternTable {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            len: OnceCell::new(),
+            write_set: BTreeMap::new(),
+            new_ids: BTreeMap::new(),
+            parent_key: field.get_path().to_vec(),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        let field_path = field.get_path().to_vec();
+        // set parent key here for the cases that InternTable is instantiated first and then assigned to field in contract struct
+        if self.parent_key != field_path {
+            self.parent_key = field_path;
+            if Self::len_in_ws(&self.parent_key) > 0 {
+                panic!("InternTable cannot replace a non-empty InternTable in world state");
+            }
+        }
+
+        if self.write_set.is_empty() {
+            return
+        }
+        for (id, name) in std::mem::take(&mut self.write_set) {
+            storage::set(&Self::wskey_id(&self.parent_key, &name), &id.to_le_bytes());
+            storage::set(&Self::wskey_name(&self.parent_key, id), name.as_bytes());
+        }
+        self.new_ids.clear();
+        storage::set(&Self::wskey_len(&self.parent_key), &self.len().to_le_bytes());
+    }
+}
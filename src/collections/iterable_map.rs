@@ -8,6 +8,7 @@
 use std::{marker::PhantomData, collections::BTreeMap};
 use borsh::{BorshDeserialize, BorshSerialize};
 use crate::{storage::{self}, Storable, StoragePath};
+use super::{MapKey, CollectionStats, Cursor, Entry, EntryTarget, Page};
 
 /// [IterableMap] is a contract-level data structure to provide abstraction by utilizing Get and Set operations associated with Contract Storage.
 /// It supports lazy read/write on key-value tuples which can also be iterated as a vector.
@@ -33,11 +34,14 @@ use crate::{storage::{self}, Storable, StoragePath};
 /// |Key-Index|P, 1, L, K|`KeyIndexCell`|
 /// |Index-Key|P, 2, L, I|`ValueCell` (data: K)|
 /// |Index-Value|P, 3, L, I|`ValueCell`|
+/// |Purge Queue|P, 4|`PurgeQueue` (positions of the first and after the last task)|
+/// |Purge Task|P, 5, Q|`PurgeTask`|
 /// 
 /// - P: parent key
 /// - L: map level
 /// - I: little endian bytes of index (u32)
-/// - K: user defined key
+/// - K: user defined key, encoded by [MapKey]
+/// - Q: little endian bytes of the position in the purge queue (u64)
 /// 
 /// ### Lazy Write
 /// 
@@ -45,17 +49,37 @@ use crate::{storage::{self}, Storable, StoragePath};
 /// 
 /// 1. after execution of action method with receiver `&mut self`; or
 /// 2. explicitly calling the setter `Self::set()`.
+/// 3. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
+/// 
+/// ### Iteration Order
+/// 
+/// Iterators of `IterableMap` visit keys in insertion order, which is the order of their indices I. This order is 
+/// part of the storage model and does not depend on the encoding of keys:
+/// 
+/// - A new key is placed after all existing keys, including keys inserted earlier in the same call.
+/// - Removing a key does not move other keys.
+/// - Updating a key, or removing and inserting it again, keeps its original position.
+/// - After [clear](IterableMap::clear), the map is empty and positions restart from the beginning.
+/// 
+/// ### Removing Nested Maps
+/// 
+/// Removing a key whose value is a nested map, or clearing a map, makes the children unreachable immediately, but 
+/// does not delete them, because the number of children is unbounded. Instead, the children are added to the purge 
+/// queue of the map, and are deleted incrementally by [purge_removed](IterableMap::purge_removed), including the 
+/// children of maps nested in them.
 #[derive(Clone)]
 pub struct IterableMap<K, V> 
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     parent_key: Vec<u8>,
     write_set: BTreeMap<Vec<u8>, UpdateOperation<V>>,
+    /// Keys of new records in `write_set`, in order of insertion.
+    insert_order: InsertOrder,
     _marker: PhantomData<Box<(K, V)>>
 }
 
 impl<K, V> IterableMap<K, V>
-    where K: BorshSerialize + BorshDeserialize, 
+    where K: MapKey, 
           V: Iterable + Clone {
     
     /// Instantiate new instance of `IterableMap`. It does not interact with world state if it is not inserted into 
@@ -66,7 +90,7 @@ impl<K, V> IterableMap<K, V>
     /// self.iterable_map.insert(&"nested_map".to_string(), nested_map);
     /// ```
     pub fn new() -> Self {
-        Self { parent_key: vec![], write_set: BTreeMap::default(), _marker: PhantomData::default() }
+        Self { parent_key: vec![], write_set: BTreeMap::default(), insert_order: InsertOrder::default(), _marker: PhantomData::default() }
     }
 
     /// Get data either from cached value or world state.
@@ -82,7 +106,7 @@ impl<K, V> IterableMap<K, V>
     /// }
     /// ```
     pub fn get(&self, key: &K) -> Option<V> {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
         self.get_inner(&key_bs).map(|(v, _)| v)
     }
 
@@ -108,7 +132,7 @@ impl<K, V> IterableMap<K, V>
     /// }
     /// ```
     pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
         self.get_mut_inner(&key_bs)
     }
 
@@ -125,18 +149,30 @@ impl<K, V> IterableMap<K, V>
         }
     }
 
+    /// Get the entry of the key for in-place modification, which reads the value once from the cache or world state.
+    /// ### Example
+    /// ```no_run
+    /// *self.iterable_map.entry(key).or_insert(0) += 1;
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, Self> {
+        Entry::new(self, key)
+    }
+
     /// Insert value to `IterableMap`. It returns a mutable reference to the inserted value in cache.
     /// ### Example
     /// ```no_run
     /// self.iterable_map.insert(key, value);
     /// ```
     pub fn insert(&mut self, key: &K, value: V) -> Option<&mut V> {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
         let new_record = !self.is_key_used(&key_bs);
         self.insert_inner(&key_bs, value, new_record)
     }
 
     fn insert_inner(&mut self, key_bs: &Vec<u8>, value: V, new_record: bool) -> Option<&mut V> {
+        if new_record {
+            self.insert_order.push(key_bs);
+        }
         self.write_set.insert(key_bs.clone(), UpdateOperation::Insert(value, new_record));
         match self.write_set.get_mut(key_bs) {
             Some(UpdateOperation::Insert(mut_value,  _)) => Some(mut_value),
@@ -146,7 +182,8 @@ impl<K, V> IterableMap<K, V>
 
     /// Remove key from `IterableMap`.
     pub fn remove(&mut self, key: &K) {
-        let key_bs = key.try_to_vec().unwrap();
+        let key_bs = key.to_map_key();
+        self.insert_order.remove(&key_bs);
         self.write_set.insert(key_bs, UpdateOperation::Delete);
     }
 
@@ -160,9 +197,53 @@ impl<K, V> IterableMap<K, V>
     /// ```
     pub fn clear(&mut self) {
         self.write_set.clear();
+        self.insert_order.clear();
+        if !self.parent_key.is_empty() {
+            let map_info_cell = self.get_map_info();
+            if map_info_cell.sequence > 0 {
+                self.enqueue_purge(PurgeTask { parent_key: self.parent_key.clone(), level: map_info_cell.level, index: 0, last_level: map_info_cell.level });
+            }
+        }
         self.new_ws_map_info();
     }
 
+    /// Delete the children of removed nested maps, and of previous levels of cleared maps, in the purge queue of this 
+    /// map. At most `limit` entries are deleted from world state in one call, so that the clean-up can be spread across 
+    /// multiple transactions. It performs actual Write to world state. Returns the number of entries deleted.
+    /// ### Example
+    /// ```no_run
+    /// self.iterable_map.remove(&key); // the value is a nested map
+    /// // in the same call or later
+    /// let deleted = self.iterable_map.purge_removed(100);
+    /// ```
+    pub fn purge_removed(&mut self, limit: u32) -> u32 {
+        if self.parent_key.is_empty() { // newly created map that is not yet saved to world state
+            return 0
+        }
+        let mut queue = PurgeQueue::load(&self.parent_key);
+        let mut deleted = 0;
+        while deleted < limit {
+            let mut task = match queue.front() {
+                Some(task) => task,
+                None => break
+            };
+            if purge_entries(&mut task, &mut queue, limit - deleted, &mut deleted) {
+                queue.pop_front();
+            } else {
+                queue.set_front(&task);
+            }
+        }
+        queue.save();
+        deleted
+    }
+
+    /// Add a task to the purge queue of this map in world state.
+    fn enqueue_purge(&self, task: PurgeTask) {
+        let mut queue = PurgeQueue::load(&self.parent_key);
+        queue.push(&task);
+        queue.save();
+    }
+
     /// Iterator to iterating keys in the map as `MapKey`. Iterating is a Lazy Read operation.
     /// ### Example
     /// ```no_run
@@ -172,9 +253,7 @@ impl<K, V> IterableMap<K, V>
     /// ```
     pub fn keys(&self) -> IterableMapKeys<K, V> {
         let map_info_cell = self.get_map_info();
-        let extends: Vec<Vec<u8>> = self.write_set.iter().filter_map(|w|{
-            match w.1 { UpdateOperation::Insert(_, true) => Some(w.0.clone()), _ => None }
-        }).collect();
+        let extends = self.insert_order.to_vec();
         IterableMapKeys { iterable_map: self, idx: 0, level: map_info_cell.level, len: map_info_cell.sequence as usize, ext_idx: 0, extends }
     }
 
@@ -187,14 +266,75 @@ impl<K, V> IterableMap<K, V>
     /// ```
     pub fn values(&self) -> IterableMapValues<K, V> {
         let map_info_cell = self.get_map_info();
-        let extends: Vec<Vec<u8>> = self.write_set.iter().filter_map(|w|{
-            match w.1 { UpdateOperation::Insert(_, true) => Some(w.0.clone()), _ => None }
-        }).collect();
+        let extends = self.insert_order.to_vec();
         IterableMapValues{ iterable_map: self, idx: 0, level: map_info_cell.level, len: map_info_cell.sequence as usize, extends, ext_idx: 0 }
     }
 
-    /// Mutable Iterator to iterating values in the map as `&mut Iterable`. Iterating is a Lazy Read operation.
-    /// It is expensive operation because the values are expected to save back to storage at the end of contract execution.
+    /// Iterator to iterating key-value pairs in the map. Iterating is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// self.iterable_map.iter().for_each(|(k, v)|{
+    ///     ...
+    /// });
+    /// ```
+    pub fn iter(&self) -> IterableMapIter<'_, K, V> {
+        let map_info_cell = self.get_map_info();
+        let extends = self.insert_order.to_vec();
+        IterableMapIter { iterable_map: self, idx: 0, level: map_info_cell.level, len: map_info_cell.sequence as usize, ext_idx: 0, extends }
+    }
+
+    /// Iterator to iterating key-value pairs in the map, starting from the position of `key` (inclusive). 
+    /// A key that has been removed still marks its position, so that iteration can be resumed across calls, 
+    /// e.g. for pagination. The iterator is empty if `key` has never been inserted.
+    /// ### Example
+    /// ```no_run
+    /// let page: Vec<(K, V)> = self.iterable_map.iter_from(&last_key).skip(1).take(10).collect();
+    /// ```
+    pub fn iter_from(&self, key: &K) -> IterableMapIter<'_, K, V> {
+        let key_bs = key.to_map_key();
+        let mut iter = self.iter();
+        match self.get_index(&key_bs, iter.level) {
+            Some(index) if (index as usize) < iter.len => {
+                iter.idx = index as usize;
+            },
+            _ => {
+                iter.idx = iter.len;
+                iter.ext_idx = iter.extends.iter().position(|k| k == &key_bs).unwrap_or(iter.extends.len());
+            }
+        }
+        iter
+    }
+
+    /// Copy at most `limit` key-value pairs, in iteration order from the position of `cursor`, into a [Page], with the
+    /// cursor of the next page. A cursor issued before the map was cleared continues to an empty page. Reading is a
+    /// Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let page: Page<(String, u64)> = self.iterable_map.page(&cursor, 20);
+    /// ```
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<(K, V)> {
+        let mut iter = self.iter();
+        // entries inserted in this call are positioned after the entries in world state, as they will be when saved
+        let position = cursor.position(iter.level);
+        if position < iter.len as u64 {
+            iter.idx = position as usize;
+        } else {
+            iter.idx = iter.len;
+            iter.ext_idx = usize::try_from(position - iter.len as u64).unwrap_or(usize::MAX).min(iter.extends.len());
+        }
+        let items: Vec<(K, V)> = iter.by_ref().take(limit).collect();
+        let next = if iter.idx < iter.len {
+            Some(Cursor::new(iter.level, iter.idx as u64))
+        } else if iter.ext_idx < iter.extends.len() {
+            Some(Cursor::new(iter.level, (iter.len + iter.ext_idx) as u64))
+        } else {
+            None
+        };
+        Page { items, next }
+    }
+
+    /// Mutable Iterator to iterating values in the map as `&mut Iterable`. All values are read before the first one is 
+    /// returned. It is expensive operation because the values are expected to save back to storage at the end of contract execution.
     /// ### Example
     /// ```no_run
     /// self.iterable_map.values_mut().for_each(|v|{
@@ -203,10 +343,59 @@ impl<K, V> IterableMap<K, V>
     /// ```
     pub fn values_mut(&mut self) -> IterableMapValuesMut<K, V> {
         let map_info_cell = self.get_map_info();
-        let extends: Vec<Vec<u8>> = self.write_set.iter().filter_map(|w|{
-            match w.1 { UpdateOperation::Insert(_, true) => Some(w.0.clone()), _ => None }
+        let mut keys = vec![];
+        // keys that can be found in world state
+        for idx in 0..map_info_cell.sequence {
+            let ws_index_key = self.wskey_index_key(map_info_cell.level, &idx);
+            if let Some(bytes) = Vec::<u8>::load(ws_index_key) {
+                if let Some((value, is_new_record)) = self.get_inner(&bytes) {
+                    self.insert_inner(&bytes, value, is_new_record);
+                    keys.push(bytes);
+                }
+            }
+        }
+        // keys that are newly inserted
+        keys.extend(self.insert_order.iter().filter(|bytes| matches!(self.write_set.get(*bytes), Some(UpdateOperation::Insert(..)))).cloned());
+
+        // values are mutably borrowed from the write set all at once, and returned in order of iteration
+        let mut values: BTreeMap<&Vec<u8>, &mut V> = self.write_set.iter_mut().filter_map(|(bytes, ops)| match ops {
+            UpdateOperation::Insert(value, _) => Some((bytes, value)),
+            UpdateOperation::Delete => None
         }).collect();
-        IterableMapValuesMut{iterable_map: self, idx: 0, level: map_info_cell.level, len: map_info_cell.sequence as usize, ext_idx: 0, extends }
+        let values: Vec<&mut V> = keys.iter().filter_map(|bytes| values.remove(bytes)).collect();
+        IterableMapValuesMut { values: values.into_iter(), _marker: PhantomData }
+    }
+
+    /// Copy at most `limit` key-value pairs, in iteration order, into a `BTreeMap`. Reading is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let snapshot: BTreeMap<String, u64> = self.iterable_map.to_btreemap(100);
+    /// ```
+    pub fn to_btreemap(&self, limit: usize) -> BTreeMap<K, V> where K: Ord {
+        self.iter().take(limit).collect()
+    }
+
+    /// Summary of the map in world state. It reads the map information and the first entry from world state.
+    /// ### Example
+    /// ```no_run
+    /// let stats = self.iterable_map.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        let map_info_cell = self.get_map_info();
+        let sample = if map_info_cell.sequence > 0 {
+            [
+                self.wskey_index_key(map_info_cell.level, &0),
+                self.wskey_index_value(map_info_cell.level, &0)
+            ].iter().map(|k| storage::get(k).map_or(0, |bytes| bytes.len() as u64)).sum()
+        } else {
+            0
+        };
+        CollectionStats {
+            entries: Some(map_info_cell.sequence as u64),
+            level: map_info_cell.level,
+            pending_writes: self.write_set.len() as u64,
+            approx_stored_bytes: map_info_cell.sequence as u64 * sample
+        }
     }
 
     // Map information
@@ -308,14 +497,15 @@ impl<K, V> IterableMap<K, V>
             let ws_index_key = self.wskey_index_key(level, &index);
             Vec::<u8>::delete(ws_index_key);
 
-            // 2. set Index-Value to None
+            // 2. if it is a nested map, queue its children for purging
             let ws_index_value = self.wskey_index_value(level, &index);
-            V::delete(ws_index_value.clone());
-
-            // 2a. if it is a nested map, set Sequence of the nested map to 0
-            if V::is_map(ws_index_value) {
-                self.new_ws_map_info();
+            if V::is_map(ws_index_value.clone()) {
+                let nested_map_info = MapInfoCell::load([ws_index_value.as_slice(), &[0u8]].concat()).unwrap();
+                self.enqueue_purge(PurgeTask { parent_key: ws_index_value.clone(), level: 0, index: 0, last_level: nested_map_info.level });
             }
+
+            // 3. set Index-Value to None
+            V::delete(ws_index_value);
         }
     }
 
@@ -392,8 +582,45 @@ impl<K, V> IterableMap<K, V>
     }
 }
 
+impl<K, V> EntryTarget<K> for IterableMap<K, V>
+    where K: MapKey,
+          V: Iterable + Clone {
+    type Value = V;
+
+    fn __load_entry(&mut self, key: &K) -> bool {
+        self.get_mut(key).is_some()
+    }
+
+    fn __entry_ref(&self, key: &K) -> &V {
+        match self.write_set.get(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of IterableMap is not loaded")
+        }
+    }
+
+    fn __entry_mut(&mut self, key: &K) -> &mut V {
+        match self.write_set.get_mut(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of IterableMap is not loaded")
+        }
+    }
+
+    fn __insert_entry(&mut self, key: &K, value: V) -> &mut V {
+        self.insert(key, value).unwrap()
+    }
+
+    fn __remove_entry(&mut self, key: &K) -> V {
+        let value = match self.write_set.remove(&key.to_map_key()) {
+            Some(UpdateOperation::Insert(value, _)) => value,
+            _ => panic!("Entry of IterableMap is not loaded")
+        };
+        self.remove(key);
+        value
+    }
+}
+
 impl<K, V> Iterable for IterableMap<K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     
     fn save(&mut self, key: Vec<u8>) {
@@ -412,9 +639,7 @@ impl<K, V> Iterable for IterableMap<K, V>
         self.write_set.iter().for_each(|(key, ops)| {
             let map_info_cell = self.get_map_info();
             match ops {
-                UpdateOperation::Insert(value, true) => {
-                    self.add_to_ws(key, map_info_cell.level, map_info_cell.sequence, value.clone());
-                },
+                UpdateOperation::Insert(_, true) => {}, // added below in order of insertion
                 UpdateOperation::Insert(value, false) => {
                     self.set_to_ws(key, map_info_cell.level, value.clone());
                 },
@@ -423,11 +648,61 @@ impl<K, V> Iterable for IterableMap<K, V>
                 },
             }
         });
+
+        self.insert_order.iter().for_each(|key| {
+            if let Some(UpdateOperation::Insert(value, true)) = self.write_set.get(key) {
+                let map_info_cell = self.get_map_info();
+                self.add_to_ws(key, map_info_cell.level, map_info_cell.sequence, value.clone());
+            }
+        });
+        // written changes are read from world state, so that a later save does not insert them again
+        self.write_set.clear();
+        self.insert_order.clear();
+    }
+}
+
+/// Keys in order of insertion, which are pushed and removed in O(log n).
+#[derive(Clone, Default)]
+struct InsertOrder {
+    /// Keys by their sequence number of insertion.
+    keys: BTreeMap<u64, Vec<u8>>,
+    /// Sequence numbers of the keys.
+    sequences: BTreeMap<Vec<u8>, u64>,
+    next_sequence: u64,
+}
+
+impl InsertOrder {
+    /// Append the key, unless it is already in the order.
+    fn push(&mut self, key: &[u8]) {
+        if self.sequences.contains_key(key) {
+            return
+        }
+        self.keys.insert(self.next_sequence, key.to_vec());
+        self.sequences.insert(key.to_vec(), self.next_sequence);
+        self.next_sequence += 1;
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        if let Some(sequence) = self.sequences.remove(key) {
+            self.keys.remove(&sequence);
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.keys.values()
+    }
+
+    fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().cloned().collect()
     }
 }
 
 impl<K, V> Storable for IterableMap<K, V> 
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     
     /// This method is called at the beginning of contract execution, if this `IterableMap` is a field of the Contract Struct.
@@ -435,6 +710,7 @@ impl<K, V> Storable for IterableMap<K, V>
         Self {
             parent_key: field.get_path().to_vec(),
             write_set: BTreeMap::default(),
+            insert_order: InsertOrder::default(),
             _marker: PhantomData,
         }
     }
@@ -446,7 +722,7 @@ impl<K, V> Storable for IterableMap<K, V>
 }
 
 impl<K, V> BorshSerialize for IterableMap<K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         // Serialization of `IterableMap` itself takes only parent_key to be stored.
@@ -455,13 +731,14 @@ impl<K, V> BorshSerialize for IterableMap<K, V>
 }
 
 impl<K, V> BorshDeserialize for IterableMap<K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let parent_key = Vec::<u8>::deserialize_reader(reader)?;
         Ok(Self{
             parent_key,
             write_set: BTreeMap::default(),
+            insert_order: InsertOrder::default(),
             _marker: PhantomData,
         })
     }
@@ -469,7 +746,7 @@ impl<K, V> BorshDeserialize for IterableMap<K, V>
 
 /// Return data type for `IterableMap::keys()`
 pub struct IterableMapKeys<'a, K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     iterable_map: &'a IterableMap<K, V>,
     idx: usize,
@@ -480,7 +757,7 @@ pub struct IterableMapKeys<'a, K, V>
 }
 
 impl<'a, K, V> Iterator for IterableMapKeys<'a, K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     type Item = K;
 
@@ -489,14 +766,17 @@ impl<'a, K, V> Iterator for IterableMapKeys<'a, K, V>
             if self.idx >= self.len  {
                 if let Some(bytes) = self.extends.get(self.ext_idx) {
                     self.ext_idx += 1;
-                    return Some(K::deserialize(&mut bytes.as_slice()).unwrap())
+                    return Some(K::from_map_key(bytes).unwrap())
                 }
                 return None
             } else {
                 let ws_index_key = self.iterable_map.wskey_index_key(self.level, &(self.idx as u32));
                 if let Some(bytes) = Vec::<u8>::load(ws_index_key) {
-                    self.idx += 1;
-                    return Some(K::deserialize(&mut bytes.as_slice()).unwrap())
+                    // skip the keys removed in this call, as the other iterators do
+                    if !matches!(self.iterable_map.write_set.get(&bytes), Some(UpdateOperation::Delete)) {
+                        self.idx += 1;
+                        return Some(K::from_map_key(&bytes).unwrap())
+                    }
                 }
             }
             self.idx += 1;
@@ -506,7 +786,7 @@ impl<'a, K, V> Iterator for IterableMapKeys<'a, K, V>
 
 /// Return data type for `IterableMap::values()`
 pub struct IterableMapValues<'a, K, V>
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     iterable_map: &'a IterableMap<K, V>,
     idx: usize,
@@ -517,7 +797,7 @@ pub struct IterableMapValues<'a, K, V>
 }
 
 impl<'a, K, V> Iterator for IterableMapValues<'a, K, V> 
-    where K: BorshSerialize + BorshDeserialize,
+    where K: MapKey,
           V: Iterable + Clone {
     type Item = V;
 
@@ -550,50 +830,42 @@ impl<'a, K, V> Iterator for IterableMapValues<'a, K, V>
     }
 }
 
-/// Mutable iterator created by `IterableMap::values_mut()`
-pub struct IterableMapValuesMut<'a, K, V> 
-    where K: BorshSerialize + BorshDeserialize,
+/// Return data type for `IterableMap::iter()` and `IterableMap::iter_from()`
+pub struct IterableMapIter<'a, K, V>
+    where K: MapKey,
           V: Iterable + Clone {
-    iterable_map: &'a mut IterableMap<K, V>,
+    iterable_map: &'a IterableMap<K, V>,
     idx: usize,
     level: u32,
     len: usize,
     ext_idx: usize,
-    extends: Vec<Vec<u8>>
+    extends: Vec<Vec<u8>>,
 }
 
-impl<'a, K, V> Iterator for IterableMapValuesMut<'a, K, V> 
-    where K: BorshSerialize + BorshDeserialize,
+impl<'a, K, V> Iterator for IterableMapIter<'a, K, V> 
+    where K: MapKey,
           V: Iterable + Clone {
-    type Item = &'a mut V;
+    type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             if self.idx >= self.len {
                 // keys that are newly inserted
                 if let Some(bytes) = self.extends.get(self.ext_idx) {
-                    return match self.iterable_map.write_set.get_mut(bytes) {
-                        Some(UpdateOperation::Insert(mut_value, _)) => {
-                            self.ext_idx += 1;
-                            Some(unsafe{
-                                let r = mut_value as * const V;
-                                &mut *(r as *mut V)
-                            })
-                        },
-                        _ => None
-                    };
-                };
+                    self.ext_idx += 1;
+                    if let Some(UpdateOperation::Insert(value, _)) = self.iterable_map.write_set.get(bytes) {
+                        return Some((K::from_map_key(bytes).unwrap(), value.clone()));
+                    }
+                    continue;
+                }
                 return None;
             } else {
                 // keys that can be found in world state
                 let ws_index_key = self.iterable_map.wskey_index_key(self.level, &(self.idx as u32));
                 if let Some(bytes) = Vec::<u8>::load(ws_index_key) {
-                    if let Some(mut_value) = self.iterable_map.get_mut_inner(&bytes) {
+                    if let Some((value, _)) = self.iterable_map.get_inner(&bytes) {
                         self.idx += 1;
-                        return Some(unsafe{
-                            let r = mut_value as * const V;
-                            &mut *(r as *mut V)
-                        });
+                        return Some((K::from_map_key(&bytes).unwrap(), value));
                     }
                 }
             }
@@ -602,12 +874,30 @@ impl<'a, K, V> Iterator for IterableMapValuesMut<'a, K, V>
     }
 }
 
+/// Mutable iterator created by `IterableMap::values_mut()`
+pub struct IterableMapValuesMut<'a, K, V> 
+    where K: MapKey,
+          V: Iterable + Clone {
+    values: std::vec::IntoIter<&'a mut V>,
+    _marker: PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for IterableMapValuesMut<'a, K, V> 
+    where K: MapKey,
+          V: Iterable + Clone {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next()
+    }
+}
+
 /// The trait that applies to most of the data types used as value of `IterableMap`.
 /// Actual data stored to world state is in format of `ValueCell`.
 pub trait Iterable : BorshSerialize + BorshDeserialize {
     fn is_map(key: Vec<u8>) -> bool {
-        storage::get(&key).map_or(false, |bytes|{
-            ValueCell::deserialize(&mut bytes.as_slice()).map_or(false, |c| c.is_map)
+        storage::get(&key).is_some_and(|bytes| {
+            ValueCell::deserialize(&mut bytes.as_slice()).is_ok_and(|c| c.is_map)
         })
     }
     
@@ -671,6 +961,143 @@ impl Iterable for MapInfoCell {
     fn delete(_key: Vec<u8>) { unreachable!() }
 }
 
+/// PurgeTask defines the children of a removed nested map, or of previous levels of a cleared map, that are not 
+/// yet deleted. Levels from `level` to `last_level` under `parent_key` are deleted, starting from index `index`.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct PurgeTask {
+    parent_key: Vec<u8>,
+    level: u32,
+    index: u32,
+    last_level: u32,
+}
+
+/// PurgeQueue is the queue of [PurgeTask]s of a map in world state. Each task is stored in its own key, so that a
+/// task is added or removed with a constant number of reads and writes, however long the queue is.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct PurgeQueue {
+    #[borsh_skip]
+    parent_key: Vec<u8>,
+    /// Position of the first task.
+    head: u64,
+    /// Position after the last task.
+    tail: u64,
+}
+
+impl PurgeQueue {
+    fn load(parent_key: &[u8]) -> Self {
+        let (head, tail) = storage::get(&Self::wskey_bounds(parent_key))
+            .and_then(|bytes| <(u64, u64)>::try_from_slice(&bytes).ok())
+            .unwrap_or((0, 0));
+        Self { parent_key: parent_key.to_vec(), head, tail }
+    }
+
+    /// Save the positions. Positions restart from 0 once the queue is empty.
+    fn save(&mut self) {
+        if self.head == self.tail {
+            self.head = 0;
+            self.tail = 0;
+            storage::delete(&Self::wskey_bounds(&self.parent_key));
+        } else {
+            storage::set(&Self::wskey_bounds(&self.parent_key), &self.try_to_vec().unwrap());
+        }
+    }
+
+    fn push(&mut self, task: &PurgeTask) {
+        storage::set(&Self::wskey_task(&self.parent_key, self.tail), &task.try_to_vec().unwrap());
+        self.tail += 1;
+    }
+
+    fn front(&self) -> Option<PurgeTask> {
+        if self.head == self.tail {
+            return None
+        }
+        storage::get(&Self::wskey_task(&self.parent_key, self.head))
+            .map(|bytes| PurgeTask::try_from_slice(&bytes).unwrap())
+    }
+
+    fn set_front(&self, task: &PurgeTask) {
+        storage::set(&Self::wskey_task(&self.parent_key, self.head), &task.try_to_vec().unwrap());
+    }
+
+    fn pop_front(&mut self) {
+        storage::delete(&Self::wskey_task(&self.parent_key, self.head));
+        self.head += 1;
+    }
+
+    /// Move the tasks of the queue of another map, e.g. of a removed nested map, to the end of this queue.
+    fn take_over(&mut self, parent_key: &[u8]) {
+        let mut other = Self::load(parent_key);
+        while let Some(task) = other.front() {
+            self.push(&task);
+            other.pop_front();
+        }
+        other.save();
+    }
+
+    /// Account Storage State Key for saving the positions of the queue: [P, 4].
+    fn wskey_bounds(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[4u8]].concat()
+    }
+
+    /// Account Storage State Key for saving the task at the position: [P, 5, Q].
+    fn wskey_task(parent_key: &[u8], position: u64) -> Vec<u8> {
+        [parent_key, &[5u8], &position.to_le_bytes()].concat()
+    }
+}
+
+/// Delete at most `limit` entries of the task, adding the number of deleted entries to `deleted`. The children of 
+/// entries that are nested maps are added to `queue`. Returns true if the task is completed.
+/// 
+/// The indices of a level are contiguous from 0, and the Index-Key cell of a removed entry is kept (with no data), 
+/// so a missing Index-Key cell marks the end of a level.
+fn purge_entries(task: &mut PurgeTask, queue: &mut PurgeQueue, limit: u32, deleted: &mut u32) -> bool {
+    let mut count = 0;
+    while task.level <= task.last_level {
+        let level_bytes = task.level.to_le_bytes();
+        let index_bytes = task.index.to_le_bytes();
+        let ws_index_key = [task.parent_key.as_slice(), &[2u8], &level_bytes, &index_bytes].concat();
+        let index_key_cell = match storage::get(&ws_index_key) {
+            Some(bytes) => ValueCell::deserialize(&mut bytes.as_slice()).ok(),
+            None => {
+                // end of this level
+                task.level += 1;
+                task.index = 0;
+                continue;
+            }
+        };
+        if count >= limit {
+            return false
+        }
+
+        // 1. delete Key-Index and Index-Key
+        if let Some(key) = index_key_cell.and_then(|c| c.data).and_then(|data| Vec::<u8>::deserialize(&mut data.as_slice()).ok()) {
+            storage::delete(&[task.parent_key.as_slice(), &[1u8], &level_bytes, &key].concat());
+        }
+        storage::delete(&ws_index_key);
+
+        // 2. delete Index-Value, queueing the children of a nested map
+        let ws_index_value = [task.parent_key.as_slice(), &[3u8], &level_bytes, &index_bytes].concat();
+        if <Vec<u8> as Iterable>::is_map(ws_index_value.clone()) {
+            let nested_map_info = MapInfoCell::load([ws_index_value.as_slice(), &[0u8]].concat()).unwrap();
+            queue.push(&PurgeTask { parent_key: ws_index_value.clone(), level: 0, index: 0, last_level: nested_map_info.level });
+        }
+        storage::delete(&ws_index_value);
+
+        task.index += 1;
+        count += 1;
+        *deleted += 1;
+    }
+
+    // The map is removed, unless a new map is inserted to the same key (with a higher level) in the meantime.
+    // Its own purge queue is taken over.
+    let wskey_map_info = [task.parent_key.as_slice(), &[0u8]].concat();
+    if MapInfoCell::load(wskey_map_info.clone()).unwrap().level <= task.last_level {
+        queue.take_over(&task.parent_key);
+        storage::delete(&wskey_map_info);
+    }
+    true
+}
+
 /// KeyIndexCell defines the data stored for Key-Index mapping in storage model of [IterableMap]
 #[derive(BorshSerialize, BorshDeserialize)]
 struct KeyIndexCell {
@@ -699,9 +1126,23 @@ macro_rules! define_primitives {
 define_primitives!(
     u8, u16, u32, u64, u128,
     i8, i16, i32, i64, i128,
-    String, bool, usize,
-    [u8;32]
+    String, bool, usize
+);
+// Data types in pchain_types. Aliases of byte arrays, e.g. `PublicAddress` and `Sha256Hash`, are covered by the impl for arrays.
+define_primitives!(
+    pchain_types::blockchain::Log,
+    pchain_types::blockchain::Command,
+    pchain_types::runtime::CallInput,
+    pchain_types::runtime::CreateDepositInput,
+    pchain_types::runtime::SetDepositSettingsInput,
+    pchain_types::runtime::TopUpDepositInput,
+    pchain_types::runtime::WithdrawDepositInput,
+    pchain_types::runtime::StakeDepositInput,
+    pchain_types::runtime::UnstakeDepositInput,
+    pchain_types::rpc::Deposit,
+    pchain_types::rpc::Stake
 );
+impl<T, const N: usize> Iterable for [T; N] where T: BorshSerialize + BorshDeserialize {}
 impl<T> Iterable for Option<T> where T: BorshSerialize + BorshDeserialize {}
 impl<T> Iterable for Vec<T> where T: BorshSerialize + BorshDeserialize {}
 macro_rules! impl_tuple {
@@ -0,0 +1,208 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the collection struct [IterableSet].
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::storage;
+use super::{CollectionStats, Cursor, IterableMap, IterableMapIter, MapKey, Page};
+use crate::{Storable, StoragePath};
+
+/// [IterableSet] is a contract-level data structure that stores a set of distinct members, which can be tested for
+/// membership without reading the other members, and iterated in insertion order.
+/// 
+/// ## IterableSet
+/// 
+/// `IterableSet` can be a Contract Field defined in the contract struct. E.g.
+/// 
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     whitelist: IterableSet<PublicAddress>,
+/// }
+/// 
+/// #[call]
+/// fn join(&mut self) {
+///     self.whitelist.insert(&transaction::calling_account());
+/// }
+/// 
+/// #[view]
+/// fn is_whitelisted(contract: ReadOnly<'_, Self>, address: PublicAddress) -> bool {
+///     contract.whitelist().contains(&address)
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// Account Storage State Key Format:
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Length|P, 0|number of members (u64)|
+/// |Members|P, 1|[IterableMap] of the members to `true`|
+/// - P: parent key
+/// 
+/// Members follow the iteration order of [IterableMap]: insertion order, where removing a member does not move the
+/// others.
+/// 
+/// ### Lazy Write
+/// 
+/// Trait `Storage` implements the `IterableSet` so that data can be saved to world state
+/// 1. after execution of action method with receiver `&mut self`; or
+/// 2. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
+#[derive(Clone)]
+pub struct IterableSet<T> where T: MapKey {
+    members: IterableMap<T, bool>,
+    /// Number of members at runtime.
+    len: u64,
+    /// Number of members in world state.
+    saved_len: u64,
+    /// The key of contract field, which is used to formulate the keys of the set.
+    parent_key: Vec<u8>,
+}
+
+impl<T> IterableSet<T> where T: MapKey {
+    /// Instantiate an empty set. It does not interact with world state until it is assigned to a contract field.
+    pub fn new() -> Self {
+        Self { members: IterableMap::new(), len: 0, saved_len: 0, parent_key: vec![] }
+    }
+
+    /// Number of members of the set.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check if the value is a member of the set. It reads only the value from world state.
+    pub fn contains(&self, value: &T) -> bool {
+        self.members.get(value).is_some()
+    }
+
+    /// `insert` adds the value to the set, which does not immediately take effect in Contract Storage. Returns false
+    /// if the value is already a member.
+    /// ### Example
+    /// ```no_run
+    /// if !self.whitelist.insert(&address) {
+    ///     // already whitelisted
+    /// }
+    /// ```
+    pub fn insert(&mut self, value: &T) -> bool {
+        if self.contains(value) {
+            return false
+        }
+        self.members.insert(value, true);
+        self.len += 1;
+        true
+    }
+
+    /// `remove` removes the value from the set, which does not immediately take effect in Contract Storage. Returns
+    /// false if the value is not a member.
+    pub fn remove(&mut self, value: &T) -> bool {
+        if !self.contains(value) {
+            return false
+        }
+        self.members.remove(value);
+        self.len -= 1;
+        true
+    }
+
+    /// `clear` empties the set. Like [IterableMap::clear], the members in world state become unreachable immediately.
+    pub fn clear(&mut self) {
+        self.members.clear();
+        self.len = 0;
+    }
+
+    /// Iterator of the members in insertion order. Iterating is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// self.whitelist.iter().for_each(|address| {
+    ///     ...
+    /// });
+    /// ```
+    pub fn iter(&self) -> IterableSetIter<'_, T> {
+        IterableSetIter { members: self.members.iter() }
+    }
+
+    /// Read at most `limit` members from the position of the cursor, in iteration order, as in [IterableMap::page].
+    /// ### Example
+    /// ```no_run
+    /// let page: Page<PublicAddress> = self.whitelist.page(&cursor, 20);
+    /// ```
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<T> {
+        let page = self.members.page(cursor, limit);
+        Page { items: page.items.into_iter().map(|(member, _)| member).collect(), next: page.next }
+    }
+
+    /// Summary of the set in world state, as of its [IterableMap] of members, except that `entries` is the number of
+    /// members.
+    /// ### Example
+    /// ```no_run
+    /// let stats = self.whitelist.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        let stats = self.members.stats();
+        CollectionStats {
+            entries: Some(self.saved_len),
+            pending_writes: stats.pending_writes + (self.len != self.saved_len) as u64,
+            ..stats
+        }
+    }
+
+    /// Account Storage State Key for saving the length.
+    fn wskey_len(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[0u8]].concat()
+    }
+
+    fn load_len(parent_key: &[u8]) -> u64 {
+        storage::get(&Self::wskey_len(parent_key))
+            .map(|bytes| u64::try_from_slice(&bytes).unwrap())
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Storable for IterableSet<T> where T: MapKey {
+    fn __load_storage(field: &StoragePath) -> Self {
+        let parent_key = field.get_path().to_vec();
+        let len = Self::load_len(&parent_key);
+        Self {
+            members: IterableMap::__load_storage(&field.add(1)),
+            len,
+            saved_len: len,
+            parent_key,
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        let field_path = field.get_path().to_vec();
+        // set parent key here for the cases that IterableSet is instantiated first and then assigned to field in contract struct
+        if self.parent_key != field_path {
+            self.parent_key = field_path;
+            self.saved_len = Self::load_len(&self.parent_key);
+        }
+
+        self.members.__save_storage(&field.add(1));
+        if self.len != self.saved_len {
+            storage::set(&Self::wskey_len(&self.parent_key), &self.len.try_to_vec().unwrap());
+            self.saved_len = self.len;
+        }
+    }
+}
+
+/// Return data type for `IterableSet::iter()`
+pub struct IterableSetIter<'a, T> where T: MapKey {
+    members: IterableMapIter<'a, T, bool>,
+}
+
+impl<'a, T> Iterator for IterableSetIter<'a, T> where T: MapKey {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.members.next().map(|(member, _)| member)
+    }
+}
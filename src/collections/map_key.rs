@@ -0,0 +1,167 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the trait [MapKey] for the data types used as key of [FastMap](crate::collections::FastMap) and 
+//! [IterableMap](crate::collections::IterableMap).
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+/// The trait that applies to the data types used as key of map collections. The encoded key becomes the last component 
+/// of the key in world state, which is concatenated after the parent key and other fixed-length components.
+/// 
+/// The encoding must be prefix-free: the encoding of a key is never a prefix of the encoding of another key of the 
+/// same type. Otherwise, a key of a nested map could be confused with a key of its parent map. The default encoding 
+/// is borsh-serialization, which is prefix-free: variable-length data (e.g. `String` and `Vec<T>`) is prefixed with 
+/// its length, and the components of compound data (e.g. tuples) are encoded in order. 
+/// 
+/// Integers are encoded in big endian instead, with the sign bit of signed integers flipped, so that the order of the 
+/// encoded keys in world state is the order of the integers. Tuples, arrays and `Option` concatenate the encodings of 
+/// their components, so that e.g. `(u32, u64)` keys are ordered by the first and then the second component. Note that 
+/// the keys of integers are not the same as the borsh-serialized keys that `FastMap` used before this trait.
+/// 
+/// Custom data types can use the default encoding if their borsh-serialization is derived. E.g.
+/// 
+/// ```no_run
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// struct OrderId {
+///     market: String,
+///     sequence: u64,
+/// }
+/// impl MapKey for OrderId {}
+/// ```
+pub trait MapKey : BorshSerialize + BorshDeserialize {
+    /// Encode the key to the bytes used in the key in world state.
+    fn to_map_key(&self) -> Vec<u8> {
+        self.try_to_vec().unwrap()
+    }
+
+    /// Decode the key from the bytes encoded by `to_map_key`. Returns None if the bytes are not a valid encoding.
+    fn from_map_key(bytes: &[u8]) -> Option<Self> {
+        let mut bytes = bytes;
+        let key = Self::read_map_key(&mut bytes)?;
+        bytes.is_empty().then_some(key)
+    }
+
+    /// Decode the key from the start of the bytes, and advance the bytes past it. It is used to decode the components 
+    /// of compound keys. Types that override `to_map_key` override this method instead of `from_map_key`.
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        Self::deserialize(bytes).ok()
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl MapKey for $t {
+                fn to_map_key(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+                    let (head, rest) = bytes.split_first_chunk()?;
+                    *bytes = rest;
+                    Some(Self::from_be_bytes(*head))
+                }
+            }
+        )*
+    }
+}
+impl_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl MapKey for $t {
+                fn to_map_key(&self) -> Vec<u8> {
+                    ((*self as $u) ^ (1 << (<$u>::BITS - 1))).to_map_key()
+                }
+
+                fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+                    <$u>::read_map_key(bytes).map(|u| (u ^ (1 << (<$u>::BITS - 1))) as $t)
+                }
+            }
+        )*
+    }
+}
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl MapKey for usize {
+    /// `usize` is encoded as `u64`, as it is in borsh-serialization.
+    fn to_map_key(&self) -> Vec<u8> {
+        (*self as u64).to_map_key()
+    }
+
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        u64::read_map_key(bytes).and_then(|u| usize::try_from(u).ok())
+    }
+}
+
+impl MapKey for String {}
+impl MapKey for bool {}
+
+impl<T, const N: usize> MapKey for [T; N] where T: MapKey {
+    fn to_map_key(&self) -> Vec<u8> {
+        self.iter().flat_map(MapKey::to_map_key).collect()
+    }
+
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        let items = (0..N).map(|_| T::read_map_key(bytes)).collect::<Option<Vec<T>>>()?;
+        items.try_into().ok()
+    }
+}
+
+impl<T> MapKey for Option<T> where T: MapKey {
+    /// `None` is encoded as 0, and `Some` as 1 followed by the encoding of the value, so that `None` is ordered first.
+    fn to_map_key(&self) -> Vec<u8> {
+        match self {
+            None => vec![0],
+            Some(value) => [vec![1], value.to_map_key()].concat(),
+        }
+    }
+
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::read_map_key(bytes)? {
+            0 => Some(None),
+            1 => T::read_map_key(bytes).map(Some),
+            _ => None,
+        }
+    }
+}
+
+impl<T> MapKey for Vec<T> where T: MapKey {
+    /// The length is borsh-serialized (u32 little endian), followed by the encodings of the items.
+    fn to_map_key(&self) -> Vec<u8> {
+        let len = u32::try_from(self.len()).expect("Vec key is too long");
+        [len.to_le_bytes().to_vec(), self.iter().flat_map(MapKey::to_map_key).collect()].concat()
+    }
+
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        let len = u32::deserialize(bytes).ok()?;
+        (0..len).map(|_| T::read_map_key(bytes)).collect()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($idx:tt $name:ident)+) => {
+      impl<$($name),+> MapKey for ($($name),+)
+      where $($name: MapKey,)+
+      {
+          fn to_map_key(&self) -> Vec<u8> {
+              [$(self.$idx.to_map_key()),+].concat()
+          }
+
+          fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+              Some(($($name::read_map_key(bytes)?),+))
+          }
+      }
+    };
+}
+impl_tuple!(0 T0 1 T1);
+impl_tuple!(0 T0 1 T1 2 T2);
+impl_tuple!(0 T0 1 T1 2 T2 3 T3);
+impl_tuple!(0 T0 1 T1 2 T2 3 T3 4 T4);
+impl_tuple!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5);
+impl_tuple!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6);
+impl_tuple!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7);
@@ -8,17 +8,65 @@
 //! 
 //! Collections:
 //! - [Vector]
+//! - [Deque]
 //! - [FastMap]
 //! - [IterableMap]
+//! - [IterableSet]
+//! - [ChunkedBlob]
+//! - [InternTable]
+//! 
+//! Keys of the map collections implement the trait [MapKey]. The state of a collection can be summarized by its 
+//! `stats()` as [CollectionStats]. A record can refer to a record in another map by its key as a [Ref]. The value of 
+//! a key in a map can be modified in place through its [Entry].
+//! 
+//! Vector, Deque and the maps are read page by page with `page()`, which returns a [Page] and the [Cursor] of the next page.
 
 pub(crate) mod vector;
 #[allow(unused_imports)]
 pub use vector::*;
 
+pub(crate) mod deque;
+#[allow(unused_imports)]
+pub use deque::*;
+
 pub(crate) mod fast_map;
 #[allow(unused_imports)]
 pub use fast_map::*;
 
 pub(crate) mod iterable_map;
 #[allow(unused_imports)]
-pub use iterable_map::*;
\ No newline at end of file
+pub use iterable_map::*;
+
+pub(crate) mod iterable_set;
+#[allow(unused_imports)]
+pub use iterable_set::*;
+
+pub(crate) mod chunked_blob;
+#[allow(unused_imports)]
+pub use chunked_blob::*;
+
+pub(crate) mod intern_table;
+#[allow(unused_imports)]
+pub use intern_table::*;
+
+pub(crate) mod cursor;
+#[allow(unused_imports)]
+pub use cursor::*;
+
+pub(crate) mod entry;
+#[allow(unused_imports)]
+pub use entry::*;
+
+pub(crate) mod map_key;
+#[allow(unused_imports)]
+pub use map_key::*;
+
+pub(crate) mod reference;
+#[allow(unused_imports)]
+pub use reference::*;
+
+pub(crate) mod slots;
+
+pub(crate) mod stats;
+#[allow(unused_imports)]
+pub use stats::*;
\ No newline at end of file
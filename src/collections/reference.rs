@@ -0,0 +1,170 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Ref], a key of a record in another collection, and the trait [RefTarget] of the collections it refers to.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::{storage, Storable, StoragePath};
+use super::{FastMap, Insertable, Iterable, IterableMap, MapKey};
+
+/// Error of creating or validating a [Ref].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefError {
+    /// The referenced key does not exist in the target collection.
+    Dangling,
+}
+
+impl std::fmt::Display for RefError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefError::Dangling => write!(f, "referenced key does not exist in the target collection"),
+        }
+    }
+}
+
+/// The trait of collections that a [Ref] can refer to, i.e. [IterableMap] and [FastMap].
+pub trait RefTarget<K> {
+    type Value;
+
+    /// Get the record of the key, if any.
+    fn get_ref(&self, key: &K) -> Option<Self::Value>;
+}
+
+impl<K, V> RefTarget<K> for IterableMap<K, V>
+    where K: MapKey,
+          V: Iterable + Clone {
+    type Value = V;
+
+    fn get_ref(&self, key: &K) -> Option<V> {
+        self.get(key)
+    }
+}
+
+impl<K, V> RefTarget<K> for FastMap<K, V>
+    where K: MapKey,
+          V: Insertable {
+    type Value = V;
+
+    fn get_ref(&self, key: &K) -> Option<V> {
+        self.get(key)
+    }
+}
+
+/// `Ref` is a key of a record in another collection, like a foreign key in relational data. It is serialized in the
+/// same way as the key, so it can be a contract field, a value of [IterableMap] or [FastMap], or a key of a map.
+/// 
+/// A `Ref` is weak: removing the referenced record does not update or remove the references to it. The reference
+/// can be validated when it is written, by [Ref::checked], and is resolved when it is read, by [Ref::resolve],
+/// which returns None for a dangling reference.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct Marketplace {
+///     sellers: IterableMap<PublicAddress, Seller>,
+///     listings: IterableMap<u64, Listing>,
+/// }
+/// 
+/// #[derive(BorshSerialize, BorshDeserialize, Clone)]
+/// struct Listing {
+///     seller: Ref<PublicAddress>,
+///     price: u64,
+/// }
+/// impl Iterable for Listing {}
+/// 
+/// #[call]
+/// fn list(&mut self, id: u64, price: u64) {
+///     let seller = Ref::checked(transaction::calling_account(), &self.sellers).unwrap();
+///     self.listings.insert(&id, Listing { seller, price });
+/// }
+/// 
+/// #[view]
+/// fn seller_of(&self, id: u64) -> Option<Seller> {
+///     self.listings.get(&id)?.seller.resolve(&self.sellers)
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ref<K>(K);
+
+impl<K> Ref<K> {
+    /// Instantiate a reference without checking that the key exists.
+    pub fn new(key: K) -> Self {
+        Self(key)
+    }
+
+    /// Instantiate a reference to a key that exists in the target collection. It reads the record of the key.
+    pub fn checked<T: RefTarget<K>>(key: K, target: &T) -> Result<Self, RefError> {
+        let reference = Self(key);
+        reference.validate(target)?;
+        Ok(reference)
+    }
+
+    pub fn key(&self) -> &K {
+        &self.0
+    }
+
+    pub fn into_key(self) -> K {
+        self.0
+    }
+
+    /// Get the referenced record from the target collection. Returns None if the reference is dangling.
+    pub fn resolve<T: RefTarget<K>>(&self, target: &T) -> Option<T::Value> {
+        target.get_ref(&self.0)
+    }
+
+    /// Check that the referenced key exists in the target collection.
+    pub fn validate<T: RefTarget<K>>(&self, target: &T) -> Result<(), RefError> {
+        match self.resolve(target) {
+            Some(_) => Ok(()),
+            None => Err(RefError::Dangling),
+        }
+    }
+}
+
+impl<K> From<K> for Ref<K> {
+    fn from(key: K) -> Self {
+        Self(key)
+    }
+}
+
+impl<K> BorshSerialize for Ref<K> where K: BorshSerialize {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<K> BorshDeserialize for Ref<K> where K: BorshDeserialize {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        K::deserialize_reader(reader).map(Self)
+    }
+}
+
+impl<K> MapKey for Ref<K> where K: MapKey {
+    fn to_map_key(&self) -> Vec<u8> {
+        self.0.to_map_key()
+    }
+
+    fn read_map_key(bytes: &mut &[u8]) -> Option<Self> {
+        K::read_map_key(bytes).map(Self)
+    }
+}
+
+impl<K> Iterable for Ref<K> where K: BorshSerialize + BorshDeserialize {}
+
+impl<K> Insertable for Ref<K> where K: BorshSerialize + BorshDeserialize {}
+
+impl<K> Storable for Ref<K> where K: BorshSerialize + BorshDeserialize + Default {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
@@ -0,0 +1,127 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Slots], the cache of lazily loaded elements of collections.
+
+use std::cell::OnceCell;
+
+/// Number of bits of the index consumed by each level of the tree.
+const BITS_PER_LEVEL: u32 = 4;
+
+/// Number of children of a node.
+const FANOUT: usize = 1 << BITS_PER_LEVEL;
+
+/// Level of the leaves, which hold the elements. Indices are u32, so the tree has 8 levels.
+const LEAF_LEVEL: u32 = u32::BITS / BITS_PER_LEVEL - 1;
+
+/// `Slots` is a sparse array of elements indexed by u32, in which a missing element can be initialized through a
+/// shared reference. It is a tree of [OnceCell]s that is only allocated along the paths to initialized elements,
+/// and elements never move once initialized, so a reference to an element obtained from `&self` stays valid until
+/// the next mutable access. This lets collections hand out references to lazily loaded elements without unsafe code.
+#[derive(Clone)]
+pub(crate) struct Slots<T> {
+    root: Node<T>,
+}
+
+#[derive(Clone)]
+enum Node<T> {
+    Inner(Box<[OnceCell<Node<T>>; FANOUT]>),
+    Leaf(Box<[OnceCell<T>; FANOUT]>),
+}
+
+impl<T> Node<T> {
+    fn new(level: u32) -> Self {
+        if level == LEAF_LEVEL {
+            Node::Leaf(Box::new(std::array::from_fn(|_| OnceCell::new())))
+        } else {
+            Node::Inner(Box::new(std::array::from_fn(|_| OnceCell::new())))
+        }
+    }
+
+    fn collect_mut<'a>(&'a mut self, elements: &mut Vec<&'a mut T>) {
+        match self {
+            Node::Inner(children) => children.iter_mut()
+                .filter_map(|child| child.get_mut())
+                .for_each(|child| child.collect_mut(elements)),
+            Node::Leaf(cells) => elements.extend(cells.iter_mut().filter_map(|cell| cell.get_mut())),
+        }
+    }
+}
+
+/// Position of the index in the node at the level.
+fn position(index: u32, level: u32) -> usize {
+    ((index >> ((LEAF_LEVEL - level) * BITS_PER_LEVEL)) as usize) & (FANOUT - 1)
+}
+
+impl<T> Slots<T> {
+    pub(crate) fn new() -> Self {
+        Self { root: Node::new(0) }
+    }
+
+    /// Get the element at the index, initializing it by `f` if it is missing.
+    pub(crate) fn get_or_init(&self, index: u32, f: impl FnOnce() -> T) -> &T {
+        let mut node = &self.root;
+        for level in 0..LEAF_LEVEL {
+            node = match node {
+                Node::Inner(children) => children[position(index, level)].get_or_init(|| Node::new(level + 1)),
+                Node::Leaf(_) => unreachable!()
+            };
+        }
+        match node {
+            Node::Leaf(cells) => cells[position(index, LEAF_LEVEL)].get_or_init(f),
+            Node::Inner(_) => unreachable!()
+        }
+    }
+
+    /// Get the element at the index, if it is initialized.
+    pub(crate) fn get_mut(&mut self, index: u32) -> Option<&mut T> {
+        self.cell_mut(index).get_mut()
+    }
+
+    /// Set the element at the index, replacing the existing one.
+    pub(crate) fn insert(&mut self, index: u32, value: T) -> &mut T {
+        let cell = self.cell_mut(index);
+        *cell = OnceCell::from(value);
+        cell.get_mut().unwrap()
+    }
+
+    /// Remove the element at the index. The nodes on its path are kept.
+    pub(crate) fn remove(&mut self, index: u32) -> Option<T> {
+        self.cell_mut(index).take()
+    }
+
+    /// Mutable references to the initialized elements, in order of index.
+    pub(crate) fn values_mut(&mut self) -> Vec<&mut T> {
+        let mut elements = vec![];
+        self.root.collect_mut(&mut elements);
+        elements
+    }
+
+    fn cell_mut(&mut self, index: u32) -> &mut OnceCell<T> {
+        let mut node = &mut self.root;
+        for level in 0..LEAF_LEVEL {
+            node = match node {
+                Node::Inner(children) => {
+                    let child = &mut children[position(index, level)];
+                    if child.get().is_none() {
+                        let _ = child.set(Node::new(level + 1));
+                    }
+                    child.get_mut().unwrap()
+                },
+                Node::Leaf(_) => unreachable!()
+            };
+        }
+        match node {
+            Node::Leaf(cells) => &mut cells[position(index, LEAF_LEVEL)],
+            Node::Inner(_) => unreachable!()
+        }
+    }
+}
+
+impl<T> Default for Slots<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,42 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the struct [CollectionStats].
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+/// [CollectionStats] summarizes the state of a collection, as returned by `stats()` of [Vector](super::Vector), 
+/// [Deque](super::Deque), [FastMap](super::FastMap), [IterableMap](super::IterableMap), 
+/// [IterableSet](super::IterableSet), [ChunkedBlob](super::ChunkedBlob) and [InternTable](super::InternTable). It 
+/// takes at most a few reads from world state regardless of the size of the collection, so it is cheap enough to be 
+/// returned from a view method. E.g.
+/// 
+/// ```no_run
+/// #[view]
+/// fn balances_stats(&self) -> CollectionStats {
+///     self.balances.stats()
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct CollectionStats {
+    /// Number of entries saved in world state, or None if the collection does not keep track of it. 
+    /// - Vector: the length of the vector.
+    /// - Deque: the length of the deque.
+    /// - ChunkedBlob: the number of chunks.
+    /// - InternTable: the number of strings.
+    /// - IterableMap: the number of indices used in the current level, including keys that were removed.
+    /// - IterableSet: the number of members.
+    /// - FastMap: the number of keys written in the current edition of a nested map or a map created by 
+    ///   [with_index](super::FastMap::with_index), including keys that were removed. It is None for other maps in 
+    ///   contract field, whose keys are not logged.
+    pub entries: Option<u64>,
+    /// The level of an IterableMap, or the edition of a FastMap. It is always 0 for Vector, Deque, ChunkedBlob and 
+    /// InternTable.
+    pub level: u32,
+    /// Number of updates in the cache that are not yet saved to world state.
+    pub pending_writes: u64,
+    /// Approximate number of bytes of the entries saved in world state, estimated by the size of the first entry.
+    pub approx_stored_bytes: u64,
+}
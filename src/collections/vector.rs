@@ -5,14 +5,15 @@
 
 //! Defines the collection struct [Vector].
 
-use std::cell::RefCell;
-use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::ops::Index;
 use std::ops::IndexMut;
 use borsh::{BorshSerialize, BorshDeserialize};
 
 use crate::storage;
 use crate::{Storable, StoragePath};
+use super::{CollectionStats, Cursor, Page};
+use super::slots::Slots;
 
 /// [Vector] is a contract-level data structure to provide abstraction by utilizing Get and Set operations associated with Contract Storage.
 /// It supports lazy read/write on elements that can be iterated.
@@ -49,6 +50,9 @@ use crate::{Storable, StoragePath};
 /// });
 /// ```
 /// 
+/// `iter` reads elements as they are iterated, while `iter_mut` reads all elements before the first one is returned. 
+/// To modify a part of a long vector, index the elements instead.
+/// 
 /// ### Storage Model
 /// 
 /// Account Storage State Key Format:
@@ -65,10 +69,13 @@ use crate::{Storable, StoragePath};
 /// Trait `Storage` implements the `Vector` so that data can be saved to world state
 /// 1. after execution of action method with receiver `&mut self`; or
 /// 2. explicitly calling the setter `Self::set()`.
+/// 3. calling [flush_storage](crate::storage::flush_storage) on the contract, e.g. before an internal call.
 #[derive(Clone, Default)]
 pub struct Vector<T> where T: Storable {
-    write_set: RefCell<BTreeMap<usize, T>>,
-    read_set: RefCell<BTreeMap<usize, T>>,
+    /// Elements that have been read or written, which are cached until the vector is dropped.
+    cache: Slots<T>,
+    /// Indices of the elements in `cache` that are to be written to world state.
+    write_set: BTreeSet<usize>,
     /// runtime length of the vector
     length: usize,
     /// The key of contract field, which is used to formulate the key for elements.
@@ -83,15 +90,14 @@ pub struct VectorIter<'a, T> where T: Storable + Clone {
 
 /// Mutable Iterator created by `Vector::iter_mut()`
 pub struct VectorIterMut<'a, T> where T: Storable + Clone {
-    vector: &'a mut Vector<T>,
-    idx: usize,
+    elements: std::vec::IntoIter<&'a mut T>,
 }
 
 impl<'a, T> Vector<T> where T: Storable + Clone {
     pub fn new() -> Self {
         Self {
-            write_set: RefCell::new(BTreeMap::new()),
-            read_set: RefCell::new(BTreeMap::new()),
+            cache: Slots::new(),
+            write_set: BTreeSet::new(),
             length:0,
             parent_key: vec![]
         }
@@ -109,7 +115,7 @@ impl<'a, T> Vector<T> where T: Storable + Clone {
 
     /// `push` adds item to the last of vector, which does not immediately take effect in Contract Storage.
     pub fn push(&mut self, value: &T) {
-        self.write_set.get_mut().insert(self.length, value.clone());
+        self.write_to_write_set(self.length, value.clone());
         self.length += 1;
     }
 
@@ -118,75 +124,35 @@ impl<'a, T> Vector<T> where T: Storable + Clone {
     pub fn pop(&mut self) {
         if self.length > 0 {
             self.length -= 1;
-            self.write_set.get_mut().remove(&(self.length));
-            self.read_set.get_mut().remove(&(self.length)); 
+            self.write_set.remove(&(self.length));
+            self.cache.remove(self.length as u32);
         }
     }
 
+    /// get reference from the cache, reading from world state if it is not cached.
     fn get(&self, idx: usize) -> &T {
-        self.get_mut(idx)
-    }
-
-    /// get mutable reference from read_set
-    #[allow(clippy::mut_from_ref)]
-    fn get_mut(&self, idx: usize) -> &mut T {
         if idx >= self.length {
             panic!()
         }
 
-        // get from write set
-        if let Some(value) = self.write_set.borrow_mut().get_mut(&idx) {
-            // cache to read set and return reference of it
-            return self.write_to_read_set(idx, value.clone())
-        }
-        
-        // if not in write set, get from read set
-        if let Some(value) = self.read_set.borrow_mut().get_mut(&idx) {
-            return unsafe {
-                let r = value as * const T;
-                &mut *(r as *mut T) 
-            }
-        }
-
-        // parent key absent, cannot query world state data
-        if self.parent_key.is_empty() { panic!() }
+        self.cache.get_or_init(idx as u32, || {
+            // parent key absent, cannot query world state data
+            if self.parent_key.is_empty() { panic!() }
 
-        // if not in read set, read from storage
-        let value = T::__load_storage(&StoragePath::new().append(Self::wskey_index(self.parent_key.clone(), idx)));
-
-        // cache to read set and return reference of it
-        self.write_to_read_set(idx, value)
+            T::__load_storage(&StoragePath::new().append(Self::wskey_index(self.parent_key.clone(), idx)))
+        })
     }
 
-    #[allow(clippy::mut_from_ref)]
-    fn write_to_read_set(&self, idx: usize, value: T) -> &mut T {
-        let mut read_set = self.read_set.borrow_mut();
-        read_set.insert(idx, value);
-
-        match read_set.get_mut(&idx) {
-            Some(value) => {
-                unsafe{
-                    let r = value as * const T;
-                    &mut *(r as *mut T) 
-                }
-            }
-            None => unreachable!()
-        }
+    /// get mutable reference from the cache, and mark the element to be written to world state.
+    fn get_mut(&mut self, idx: usize) -> &mut T {
+        self.get(idx);
+        self.write_set.insert(idx);
+        self.cache.get_mut(idx as u32).unwrap()
     }
 
     fn write_to_write_set(&mut self, idx: usize, value: T) -> &mut T {
-        let mut write_set = self.write_set.borrow_mut();
-        write_set.insert(idx, value);
-
-        match write_set.get_mut(&idx) {
-            Some(value) => {
-                unsafe{
-                    let r = value as * const T;
-                    &mut *(r as *mut T) 
-                }
-            }
-            None => unreachable!()
-        }
+        self.write_set.insert(idx);
+        self.cache.insert(idx as u32, value)
     }
 
     /// `iter` returns `VectorIter` which implements Iterator
@@ -196,7 +162,60 @@ impl<'a, T> Vector<T> where T: Storable + Clone {
 
     /// `iter_mut` returns `VectorIterMut` which implements Iterator
     pub fn iter_mut(&'a mut self) -> VectorIterMut<'a, T> {
-        VectorIterMut { vector: self, idx: 0 }
+        for idx in 0..self.length {
+            self.get(idx);
+        }
+        self.write_set.extend(0..self.length);
+        let length = self.length;
+        let mut elements = self.cache.values_mut();
+        elements.truncate(length);
+        VectorIterMut { elements: elements.into_iter() }
+    }
+
+    /// Copy at most `limit` elements from the beginning of the vector into a `Vec`. Reading is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let first_page: Vec<u64> = self.vector.to_vec(100);
+    /// ```
+    pub fn to_vec(&self, limit: usize) -> Vec<T> {
+        self.iter().take(limit).cloned().collect()
+    }
+
+    /// Copy at most `limit` elements from the position of `cursor` into a [Page], with the cursor of the next page.
+    /// Reading is a Lazy Read operation.
+    /// ### Example
+    /// ```no_run
+    /// let page: Page<u64> = self.vector.page(&cursor, 100);
+    /// ```
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<T> {
+        let start = cursor.position(0).min(self.length as u64) as usize;
+        let end = start.saturating_add(limit).min(self.length);
+        let items = (start..end).map(|idx| self.get(idx).clone()).collect();
+        let next = (end < self.length).then(|| Cursor::new(0, end as u64));
+        Page { items, next }
+    }
+
+    /// Summary of the vector in world state. It reads the length and the first element from world state.
+    /// ### Example
+    /// ```no_run
+    /// let stats = self.vector.stats();
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        if self.parent_key.is_empty() { // newly created vector that is not yet saved to world state
+            return CollectionStats { entries: Some(0), pending_writes: self.write_set.len() as u64, ..Default::default() }
+        }
+        let entries = Self::len_in_ws(self.parent_key.clone()) as u64;
+        let sample = if entries > 0 {
+            storage::get(&Self::wskey_index(self.parent_key.clone(), 0)).map_or(0, |bytes| bytes.len() as u64)
+        } else {
+            0
+        };
+        CollectionStats {
+            entries: Some(entries),
+            level: 0,
+            pending_writes: self.write_set.len() as u64,
+            approx_stored_bytes: entries * sample
+        }
     }
 
     /// The length of the vector, which is the data stored in world state.
@@ -241,16 +260,7 @@ impl<'a, T> Iterator for VectorIterMut<'a, T> where T: Storable + Clone {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx >= self.vector.len() {
-            return None
-        };
-        let value = self.vector.get(self.idx);
-        let ret = self.vector.write_to_write_set(self.idx, value.clone());
-        self.idx += 1;
-        Some(unsafe{
-            let r = ret as * const T;
-            &mut *(r as *mut T) 
-        })
+        self.elements.next()
     }
 }
 
@@ -264,9 +274,7 @@ impl<T> Index<usize> for Vector<T> where T: Storable + Clone {
 
 impl<T> IndexMut<usize> for Vector<T> where T: Storable + Clone {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let value = self.get(index);
-        // return reference to write set
-        self.write_to_write_set(index, value.clone())
+        self.get_mut(index)
     }
 }
 
@@ -274,8 +282,8 @@ impl<T> Storable for Vector<T> where T: Storable + Clone {
     fn __load_storage(field: &StoragePath) -> Self {
         let parent_key = field.get_path().to_vec();
         Self {
-            write_set: RefCell::new(BTreeMap::new()),
-            read_set: RefCell::new(BTreeMap::new()),
+            cache: Slots::new(),
+            write_set: BTreeSet::new(),
             length: Self::len_in_ws(parent_key.clone()),
             parent_key,
         }
@@ -294,9 +302,10 @@ impl<T> Storable for Vector<T> where T: Storable + Clone {
         }
 
         // save changes to world state
-        let mut write_set = self.write_set.borrow_mut();
-        write_set.iter_mut().for_each(|(idx, v)|{
-            v.__save_storage(&StoragePath::new().append(Self::wskey_index(self.parent_key.clone(), *idx)));
-        });
+        for idx in std::mem::take(&mut self.write_set) {
+            if let Some(v) = self.cache.get_mut(idx as u32) {
+                v.__save_storage(&StoragePath::new().append(Self::wskey_index(self.parent_key.clone(), idx)));
+            }
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [CommitReveal], a contract field that implements the commit-reveal scheme used by e.g. sealed-bid auctions 
+//! and fair randomness games. An account first commits to a value by its hash, and later reveals the value. Since the
+//! hash includes a secret salt and the address of the account, the value cannot be learnt or copied by others before 
+//! it is revealed.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, crypto, transaction, collections::{FastMap, Insertable}, Storable, StoragePath};
+
+/// Compute the hash that commits `sender` to `value`. It is the SHA256 digest of the borsh-serialized value, the salt 
+/// and the address of the sender, concatenated. Clients compute the same hash off-chain before committing.
+pub fn commitment<T: BorshSerialize>(value: &T, salt: &[u8], sender: PublicAddress) -> [u8; 32] {
+    let input = [
+        value.try_to_vec().unwrap().as_slice(),
+        salt,
+        sender.as_slice()
+    ].concat();
+    crypto::sha256(input).try_into().unwrap()
+}
+
+/// A commitment stored in [CommitReveal].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Commitment {
+    /// The hash computed by [commitment].
+    pub hash: [u8; 32],
+    /// Block timestamp at which the commitment was made.
+    pub committed_at: u32,
+}
+
+impl Insertable for Commitment {}
+
+/// Error of [CommitReveal::reveal].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevealError {
+    /// The calling account has no commitment.
+    NoCommitment,
+    /// The reveal delay since the commitment has not passed.
+    TooEarly,
+    /// The commitment has expired.
+    Expired,
+    /// The value and salt do not match the commitment.
+    Mismatch,
+}
+
+impl std::fmt::Display for RevealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RevealError::NoCommitment => write!(f, "no commitment"),
+            RevealError::TooEarly => write!(f, "commitment cannot be revealed yet"),
+            RevealError::Expired => write!(f, "commitment has expired"),
+            RevealError::Mismatch => write!(f, "value does not match commitment"),
+        }
+    }
+}
+
+/// `CommitReveal` is a contract field that stores one commitment per account.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn commit_bid(&mut self, hash: [u8; 32]) {
+///     self.bids.commit(hash);
+/// }
+/// 
+/// #[call]
+/// fn reveal_bid(&mut self, amount: u64, salt: Vec<u8>) {
+///     self.bids.reveal(&amount, &salt).unwrap();
+///     // ...
+/// }
+/// ```
+/// 
+/// A commitment can be revealed when at least `reveal_delay` seconds have passed since it was made, and it expires 
+/// when more than `expiry` seconds have passed. Times are measured by block timestamp.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Commitments|P, 0|`FastMap<PublicAddress, Commitment>`|
+/// |Reveal Delay|P, 1|u32|
+/// |Expiry|P, 2|u32|
+/// 
+/// - P: parent key
+pub struct CommitReveal {
+    commitments: FastMap<PublicAddress, Commitment>,
+    reveal_delay: u32,
+    expiry: u32,
+}
+
+impl CommitReveal {
+    pub fn new(reveal_delay: u32, expiry: u32) -> Self {
+        Self { commitments: FastMap::new(), reveal_delay, expiry }
+    }
+
+    pub fn reveal_delay(&self) -> u32 {
+        self.reveal_delay
+    }
+
+    pub fn expiry(&self) -> u32 {
+        self.expiry
+    }
+
+    /// Store the commitment of the calling account, replacing its previous commitment, if any.
+    pub fn commit(&mut self, hash: [u8; 32]) {
+        self.commitments.insert(&transaction::calling_account(), Commitment { hash, committed_at: blockchain::timestamp() });
+    }
+
+    /// Get the commitment of the account.
+    pub fn commitment_of(&self, account: PublicAddress) -> Option<Commitment> {
+        self.commitments.get(&account)
+    }
+
+    /// Verify the value and salt revealed by the calling account against its commitment, without removing it.
+    pub fn verify<T: BorshSerialize>(&self, value: &T, salt: &[u8]) -> Result<(), RevealError> {
+        let sender = transaction::calling_account();
+        let c = self.commitments.get(&sender).ok_or(RevealError::NoCommitment)?;
+        let elapsed = blockchain::timestamp().saturating_sub(c.committed_at);
+        if elapsed < self.reveal_delay {
+            return Err(RevealError::TooEarly)
+        }
+        if elapsed > self.expiry {
+            return Err(RevealError::Expired)
+        }
+        if commitment(value, salt, sender) != c.hash {
+            return Err(RevealError::Mismatch)
+        }
+        Ok(())
+    }
+
+    /// Verify the value and salt revealed by the calling account, and remove its commitment if it matches, so that 
+    /// the value cannot be revealed twice.
+    pub fn reveal<T: BorshSerialize>(&mut self, value: &T, salt: &[u8]) -> Result<(), RevealError> {
+        self.verify(value, salt)?;
+        self.commitments.remove(&transaction::calling_account());
+        Ok(())
+    }
+
+    /// Remove the commitment of the account, e.g. after it has expired.
+    pub fn remove(&mut self, account: PublicAddress) {
+        self.commitments.remove(&account);
+    }
+}
+
+impl Storable for CommitReveal {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            commitments: Storable::__load_storage(&field.add(0)),
+            reveal_delay: Storable::__load_storage(&field.add(1)),
+            expiry: Storable::__load_storage(&field.add(2)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.commitments.__save_storage(&field.add(0));
+        self.reveal_delay.__save_storage(&field.add(1));
+        self.expiry.__save_storage(&field.add(2));
+    }
+}
@@ -8,58 +8,36 @@
 //! In order to reduce gas costs, these functions are implemented in native (not-WASM) code that lives outside of the 
 //! WASM runtime, and exposed to calls through the handles defined in this module.
 
-use crate::imports;
+use crate::host;
 
 /// Computes the SHA256 digest (32 bytes) of arbitrary input.
 pub fn sha256(input: Vec<u8>) -> Vec<u8>{
-    let input_ptr = input.as_ptr();
-
-    let mut val_ptr: u32 = 0;
-    let val_ptr_ptr = &mut val_ptr;
-
-    unsafe {
-        imports::sha256(input_ptr, input.len() as u32, val_ptr_ptr);
-        Vec::<u8>::from_raw_parts(val_ptr as *mut u8, 32, 32)
-    }
+    host::current().sha256(&input)
 }
 
 /// Computes the Keccak256 digest (32 bytes) of arbitrary input.
 pub fn keccak256(input: Vec<u8>) -> Vec<u8>{
-    let input_ptr = input.as_ptr();
-
-    let mut val_ptr: u32 = 0;
-    let val_ptr_ptr = &mut val_ptr;
-
-    unsafe {
-        imports::keccak256(input_ptr, input.len() as u32, val_ptr_ptr);
-        Vec::<u8>::from_raw_parts(val_ptr as *mut u8,  32, 32)
-    }
+    host::current().keccak256(&input)
 }
 
 /// Computes the RIPEMD160 digest (20 bytes) of arbitrary input.
 pub fn ripemd(input: Vec<u8>) -> Vec<u8>{
-    let input_ptr = input.as_ptr();
-
-    let mut val_ptr: u32 = 0;
-    let val_ptr_ptr = &mut val_ptr;
-
-    unsafe {
-        imports::ripemd(input_ptr, input.len() as u32, val_ptr_ptr);
-        Vec::<u8>::from_raw_parts(val_ptr as *mut u8, 20, 20)
-    }
+    host::current().ripemd(&input)
 }
 
 /// Returns whether an Ed25519 signature was produced by a specified by a specified address over some specified message.
 /// Contract call fails if the input `address` or `signature` is not valid.
 pub fn verify_ed25519_signature(input: Vec<u8>, signature: Vec<u8>, address: Vec<u8>) -> bool {
-    let input_ptr = input.as_ptr();
-    let signature_ptr = signature.as_ptr();
-    let address_ptr = address.as_ptr();
-
-    let value;
-    unsafe {
-        value = imports::verify_ed25519_signature(input_ptr, input.len() as u32, signature_ptr, address_ptr);
-    }
+    host::current().verify_ed25519_signature(&input, &signature, &address)
+}
 
-    value != 0
+/// Returns whether a secp256k1 signature was produced by the public key over the SHA256 digest of some specified message.
+/// The signature is 64 bytes of `r` and `s`, and the public key is a SEC1 encoded point (33 or 65 bytes). Contract call
+/// fails if the input `public_key` or `signature` is not valid.
+/// 
+/// It imports the host function only if the feature `secp256k1` of the SDK is enabled, and otherwise fails the call. 
+/// It requires the host feature [Secp256k1](crate::runtime::HostFeature::Secp256k1), which can be checked by 
+/// [runtime::supports](crate::runtime::supports).
+pub fn verify_secp256k1_signature(input: Vec<u8>, signature: Vec<u8>, public_key: Vec<u8>) -> bool {
+    host::current().verify_secp256k1_signature(&input, &signature, &public_key)
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [ContractError], the standardized error returned by contract methods to their callers.
+//! 
+//! A contract method that returns `Result<T, ContractError>` places the borsh-serialized `Result` (the error envelope)
+//! in its return value. A calling contract decodes it by [call_result](crate::call_result), or by declaring the method
+//! with the same return type in a trait with `use_contract`, where the error is [CallError::Failed](crate::CallError::Failed):
+//! 
+//! ```no_run
+//! #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+//! pub trait Token {
+//!     fn transfer(to: [u8; 32], amount: u64) -> Result<(), ContractError>;
+//! }
+//! 
+//! match token::transfer(to, amount, 0) {
+//!     Ok(()) => {},
+//!     Err(CallError::Failed(e)) if e.code == INSUFFICIENT_BALANCE => {},
+//!     Err(e) => panic!("{}", e),
+//! }
+//! ```
+//! 
+//! Unlike a panic, which aborts the whole transaction, a returned error lets the caller decide how to handle the failure.
+//! 
+//! A method with attribute `call` declared to return `Result<T, E>`, for any `E: BorshSerialize`, fails softly when it
+//! returns `Err`: the changes to the contract fields are not saved, as if it panicked, so that an early return by `?`
+//! does not leave the fields half-updated, and the error is logged with the topic [METHOD_ERROR_TOPIC], so that it can
+//! be found in the receipt even if the method was called by another contract. The transaction is not reverted, so the
+//! effects that take place immediately, i.e. logs, internal calls and transfers, and writes by
+//! [storage](crate::storage), are not undone. A method should therefore make them only after the last point where it
+//! can return `Err`, or panic instead. A call of multicall that returns `Err` aborts the multicall, as if it panicked.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+/// Error returned by a contract method, identified by a code defined by the contract, with a message for humans.
+/// Codes from [ContractError::RESERVED_CODES] are reserved for errors reported by the SDK.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContractError {
+    pub code: u32,
+    pub message: String,
+}
+
+impl ContractError {
+    /// The range of codes reserved for the SDK.
+    pub const RESERVED_CODES: std::ops::RangeInclusive<u32> = 0xFFFF_0000..=u32::MAX;
+
+    /// The called method returned no value.
+    pub const NO_RETURN_VALUE: u32 = 0xFFFF_0000;
+
+    /// The return value of the called method is not the expected `Result<T, ContractError>`.
+    pub const INVALID_RETURN_VALUE: u32 = 0xFFFF_0001;
+
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    /// Check if the error is reported by the SDK instead of the called method.
+    pub fn is_reserved(&self) -> bool {
+        Self::RESERVED_CODES.contains(&self.code)
+    }
+}
+
+impl std::fmt::Display for ContractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "contract error {}: {}", self.code, self.message)
+    }
+}
+
+/// Topic of the log emitted when a method with attribute `call` returns `Err`. The value is the borsh-serialized
+/// method name and error, i.e. `(String, E)`.
+pub const METHOD_ERROR_TOPIC: &[u8] = b"MethodError";
+
+/// Emit the log of an error returned by the method.
+pub(crate) fn log_method_error<E: BorshSerialize>(method: &str, error: &E) {
+    let value = (method, error).try_to_vec().unwrap();
+    crate::internal::log(METHOD_ERROR_TOPIC, &value);
+}
+
+/// Topic of the log emitted by [require!](crate::require) before it aborts the call.
+pub const REQUIRE_FAILED_TOPIC: &[u8] = b"RequireFailed";
+
+/// `require!(cond, "message", args...)` aborts the call if the condition is false, like `assert!`. Before panicking,
+/// it emits a log with the topic [REQUIRE_FAILED_TOPIC] and the value `"<method>: <message>"`, so that the receipt of
+/// the failed transaction shows which check failed.
+/// 
+/// ### Example
+/// ```no_run
+/// require!(amount <= balance, "Insufficient balance. Required: {}, Available: {}", amount, balance);
+/// ```
+#[macro_export]
+macro_rules! require {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::error::require_failed(::std::format!($($arg)+))
+        }
+    };
+}
+
+/// Emit the log of a failed [require!](crate::require) and panic with the message.
+#[doc(hidden)]
+pub fn require_failed(message: String) -> ! {
+    let method = crate::transaction::method();
+    crate::internal::log(REQUIRE_FAILED_TOPIC, format!("{}: {}", method, message).as_bytes());
+    panic!("{}", message);
+}
@@ -0,0 +1,133 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Event], the trait of versioned events implemented by the macro [event](crate::event) or by
+//! `#[derive(Event)]`, and the encoding of their topics.
+//! 
+//! An event is emitted as a log whose value is the Borsh-serialized struct. The topic records the name and the
+//! version of the layout of the struct:
+//! - version 1: the name, e.g. `Transfer`, which is the same as the topic of unversioned events,
+//! - version n > 1: the name and the version separated by [VERSION_SEPARATOR], e.g. `Transfer@2`.
+//! 
+//! Changing the fields of an event changes the layout of its value, so it must come with a new version. The
+//! struct of the previous version is kept, renamed and declared with the same name, and the new version converts
+//! from it, so that logs emitted before the change are still decoded:
+//! 
+//! ```no_run
+//! #[event(name = "Transfer")]
+//! pub struct TransferV1 { pub from: PublicAddress, pub to: PublicAddress, pub amount: u64 }
+//! 
+//! #[event(version = 2, previous(TransferV1))]
+//! pub struct Transfer { pub from: PublicAddress, pub to: PublicAddress, pub amount: u64, pub memo: String }
+//! 
+//! impl From<TransferV1> for Transfer {
+//!     fn from(v1: TransferV1) -> Self {
+//!         Self { from: v1.from, to: v1.to, amount: v1.amount, memo: String::new() }
+//!     }
+//! }
+//! 
+//! // decodes logs of both versions
+//! let transfer: Option<Transfer> = Transfer::decode(&log);
+//! ```
+//! 
+//! The schemas of the events ([Event::SCHEMA]) are embedded in the custom section `pchain_events` of the WASM
+//! module, one JSON object per line, so that indexers can find the layouts of all versions from the deployed code.
+//! 
+//! ## Indexed events
+//! 
+//! `#[derive(Event)]` defines an event whose topic is the SHA256 hash of its name, followed by the Borsh-serialized
+//! fields marked `#[indexed]`, so that indexers can filter the logs by the values of those fields, e.g. the transfers
+//! to an address, by the prefix of their topics. The value of the log is the Borsh-serialized struct, including the
+//! indexed fields. The name is the name of the struct, unless specified by `#[topic = ".."]`. Derived events have no
+//! versions, and their schemas have the hash of the name (`topic_sha256`) and the indexed fields instead of the topic:
+//! 
+//! ```no_run
+//! #[derive(Event)]
+//! #[topic = "Transfer"]
+//! pub struct TransferEvent {
+//!     #[indexed]
+//!     pub from: PublicAddress,
+//!     #[indexed]
+//!     pub to: PublicAddress,
+//!     pub amount: u64,
+//! }
+//! 
+//! // topic: sha256("Transfer") ++ from ++ to
+//! TransferEvent { from, to, amount }.emit();
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::{internal, types::Log};
+
+/// Separator of the name and the version in the topic of an event of version greater than 1.
+pub const VERSION_SEPARATOR: char = '@';
+
+/// `Event` is a struct that is emitted as a log. It is implemented by the macro [event](crate::event) or by
+/// `#[derive(Event)]`.
+pub trait Event: BorshSerialize + BorshDeserialize {
+    /// Name of the event, which is the same for all versions.
+    const NAME: &'static str;
+
+    /// Version of the layout of the event.
+    const VERSION: u32;
+
+    /// Topic of the logs of this version of the event, i.e. `topic(NAME, VERSION)`.
+    const TOPIC: &'static [u8];
+
+    /// Schema of this version of the event in JSON, e.g.
+    /// `{"name":"Transfer","version":2,"topic":"Transfer@2","fields":[["from","PublicAddress"],..]}`.
+    const SCHEMA: &'static str;
+
+    /// Names of the fields appended to the topic of the logs, for events derived with `#[indexed]` fields.
+    const INDEXED: &'static [&'static str] = &[];
+
+    /// Topic of the log of the event, which is [Event::TOPIC] followed by the Borsh-serialized indexed fields.
+    fn topic(&self) -> Vec<u8> {
+        Self::TOPIC.to_vec()
+    }
+
+    /// Emit the event as a log of this transaction.
+    fn emit(&self) {
+        internal::log(&self.topic(), &self.try_to_vec().unwrap())
+    }
+
+    /// Decode the log, if it is of this event, in this version or a previous version.
+    fn decode(log: &Log) -> Option<Self> {
+        let indexed_topic = !Self::INDEXED.is_empty() && log.topic.starts_with(Self::TOPIC);
+        if log.topic.as_slice() == Self::TOPIC || indexed_topic {
+            return Self::try_from_slice(&log.value).ok()
+        }
+        Self::decode_previous(log)
+    }
+
+    /// Decode the log, if it is of a previous version of this event. It is implemented by the macro if the
+    /// previous version is specified.
+    #[doc(hidden)]
+    fn decode_previous(_log: &Log) -> Option<Self> {
+        None
+    }
+}
+
+/// The topic of logs of the version of the event.
+pub fn topic(name: &str, version: u32) -> Vec<u8> {
+    match version {
+        1 => name.as_bytes().to_vec(),
+        _ => format!("{}{}{}", name, VERSION_SEPARATOR, version).into_bytes()
+    }
+}
+
+/// The name and the version of the event from the topic of its log. A topic without version is of version 1,
+/// including topics of unversioned events. Returns None if the topic is not UTF-8 or has an invalid version.
+pub fn parse_topic(topic: &[u8]) -> Option<(&str, u32)> {
+    let topic = std::str::from_utf8(topic).ok()?;
+    match topic.rsplit_once(VERSION_SEPARATOR) {
+        Some((name, version)) => match version.parse::<u32>() {
+            Ok(version) if version > 1 => Some((name, version)),
+            _ => None
+        },
+        None => Some((topic, 1))
+    }
+}
@@ -7,6 +7,7 @@
 //! can allocate segments in WASM linear memory. The name of this module is rather awkward, since `alloc` *is not* the 
 //! only function exported from Contract WASM modules. We plan to merge this module with imports in a future version of the SDK.
 
+#[cfg(target_arch = "wasm32")]
 #[no_mangle]
 pub extern "C" fn alloc(len: u32) -> *mut u8 {
     let mut buf = Vec::with_capacity(len as usize);
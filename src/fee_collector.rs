@@ -0,0 +1,210 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [FeeCollector], a contract field that accrues the fees charged by the methods of a contract, in XPLL
+//! or in tokens, and pays them out to a recipient chosen by the admin. It keeps the accounting of fees in one
+//! place, e.g. in marketplaces that charge different fees per method and per payment token.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::IterableMap, internal, method::ContractMethodInputBuilder, transaction, Storable, StoragePath};
+
+/// Method of token contracts called by [FeeCollector::sweep] with arguments `(recipient: PublicAddress, amount: u64)`.
+pub const TOKEN_TRANSFER_METHOD: &str = "transfer";
+
+/// Topic of the log emitted by [FeeCollector::accrue].
+pub const FEE_ACCRUED_TOPIC: &[u8] = b"FeeAccrued";
+
+/// Topic of the logs emitted by [FeeCollector::sweep], one per token.
+pub const FEES_SWEPT_TOPIC: &[u8] = b"FeesSwept";
+
+/// Value of the log emitted by [FeeCollector::accrue].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeAccruedEvent {
+    /// Address of the token contract, or None for XPLL.
+    pub token: Option<PublicAddress>,
+    pub method: String,
+    pub amount: u64,
+}
+
+/// Value of the logs emitted by [FeeCollector::sweep].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeesSweptEvent {
+    /// Address of the token contract, or None for XPLL.
+    pub token: Option<PublicAddress>,
+    pub recipient: PublicAddress,
+    pub amount: u64,
+}
+
+/// Error of [FeeCollector].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeCollectorError {
+    /// The calling account is not the admin.
+    NotAdmin,
+    /// No fees have accrued.
+    NothingToSweep,
+}
+
+impl std::fmt::Display for FeeCollectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeeCollectorError::NotAdmin => write!(f, "caller is not the admin of the fee collector"),
+            FeeCollectorError::NothingToSweep => write!(f, "no fees have accrued"),
+        }
+    }
+}
+
+/// `FeeCollector` is a contract field that stores the fees accrued per token and per method, and the admin who
+/// sweeps them. A token is the address of a token contract, or None for XPLL. The fees must already be held by
+/// this contract when they are accrued, e.g. deducted from the amount attached to the call.
+/// 
+/// [FeeCollector::sweep] pays all accrued fees to a recipient: XPLL by transfer, and tokens by calling
+/// [TOKEN_TRANSFER_METHOD] of the token contracts. A token contract that calls back into this contract cannot
+/// sweep the fees again, since the calling account of the re-entrant call is the token contract, not the admin.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn buy(&mut self, item: u64) {
+///     let fee = pchain_sdk::transaction::amount() / 100;
+///     self.fees.accrue(None, "buy", fee);
+///     // ...
+/// }
+/// 
+/// #[call]
+/// fn sweep_fees(&mut self, recipient: PublicAddress) {
+///     self.fees.sweep(recipient).unwrap();
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Admin|P, 0|PublicAddress|
+/// |Accrued Fees|P, 1|`IterableMap<(Option<PublicAddress>, String), u64>`|
+/// 
+/// - P: parent key
+/// 
+/// The accrued fees are cleared in world state before they are paid, so that fees accrued by a call back into this
+/// contract, e.g. from a token contract, are kept for the next sweep.
+pub struct FeeCollector {
+    admin: PublicAddress,
+    accrued: IterableMap<(Option<PublicAddress>, String), u64>,
+    /// The key of the contract field, which is known once it is loaded from or saved to world state.
+    scope: Option<StoragePath>,
+}
+
+impl FeeCollector {
+    pub fn new(admin: PublicAddress) -> Self {
+        Self { admin, accrued: IterableMap::new(), scope: None }
+    }
+
+    pub fn admin(&self) -> PublicAddress {
+        self.admin
+    }
+
+    /// Transfer the admin role. Only the admin can call it.
+    pub fn set_admin(&mut self, admin: PublicAddress) -> Result<(), FeeCollectorError> {
+        self.check_admin()?;
+        self.admin = admin;
+        Ok(())
+    }
+
+    /// Accrue a fee charged by the method, in the token (None for XPLL), and log a [FeeAccruedEvent].
+    pub fn accrue(&mut self, token: Option<PublicAddress>, method: &str, amount: u64) {
+        if amount == 0 {
+            return
+        }
+        let key = (token, method.to_string());
+        let accrued = self.accrued.get(&key).unwrap_or(0).saturating_add(amount);
+        self.accrued.insert(&key, accrued);
+        let event = FeeAccruedEvent { token, method: key.1, amount };
+        internal::log(FEE_ACCRUED_TOPIC, &event.try_to_vec().unwrap());
+    }
+
+    /// Accrue a fee in XPLL charged by the method of this call.
+    pub fn accrue_native(&mut self, amount: u64) {
+        self.accrue(None, &transaction::method(), amount)
+    }
+
+    /// The fees accrued by the method in the token.
+    pub fn accrued(&self, token: Option<PublicAddress>, method: &str) -> u64 {
+        self.accrued.get(&(token, method.to_string())).unwrap_or(0)
+    }
+
+    /// The fees accrued by all methods in the token.
+    pub fn total(&self, token: Option<PublicAddress>) -> u64 {
+        self.accrued.iter()
+            .filter(|((t, _), _)| *t == token)
+            .fold(0u64, |total, (_, amount)| total.saturating_add(amount))
+    }
+
+    /// Pay all accrued fees to the recipient. Only the admin can call it. Each token is paid in one transfer or
+    /// call, and logged as a [FeesSweptEvent]. Returns the amounts paid per token, in order of first accrual.
+    pub fn sweep(&mut self, recipient: PublicAddress) -> Result<Vec<(Option<PublicAddress>, u64)>, FeeCollectorError> {
+        self.check_admin()?;
+        let mut totals: Vec<(Option<PublicAddress>, u64)> = vec![];
+        for ((token, _), amount) in self.accrued.iter() {
+            match totals.iter_mut().find(|(t, _)| *t == token) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => totals.push((token, amount)),
+            }
+        }
+        if totals.is_empty() {
+            return Err(FeeCollectorError::NothingToSweep)
+        }
+        self.accrued.clear();
+        // a re-entrant call loads the fee collector from world state
+        self.flush();
+
+        for (token, amount) in totals.iter() {
+            match token {
+                None => internal::transfer(recipient, *amount),
+                Some(token) => {
+                    let arguments = ContractMethodInputBuilder::new()
+                        .add(recipient)
+                        .add(*amount)
+                        .to_call_arguments();
+                    internal::call_untyped(*token, TOKEN_TRANSFER_METHOD, arguments, 0);
+                }
+            }
+            let event = FeesSweptEvent { token: *token, recipient, amount: *amount };
+            internal::log(FEES_SWEPT_TOPIC, &event.try_to_vec().unwrap());
+        }
+        Ok(totals)
+    }
+
+    fn check_admin(&self) -> Result<(), FeeCollectorError> {
+        if transaction::calling_account() != self.admin {
+            return Err(FeeCollectorError::NotAdmin)
+        }
+        Ok(())
+    }
+
+    /// Save the fee collector to world state now, if it is a contract field.
+    fn flush(&mut self) {
+        if let Some(scope) = self.scope.clone() {
+            self.__save_storage(&scope);
+        }
+    }
+}
+
+impl Storable for FeeCollector {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            admin: Storable::__load_storage(&field.add(0)),
+            accrued: Storable::__load_storage(&field.add(1)),
+            scope: Some(field.clone()),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.admin.__save_storage(&field.add(0));
+        self.accrued.__save_storage(&field.add(1));
+        self.scope = Some(field.clone());
+    }
+}
@@ -0,0 +1,176 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [FlashLoan], a contract field that lends the balance of this contract to another contract within a single 
+//! call. The loan is transferred to the borrower, the borrower's callback is called, and the call aborts unless the
+//! balance of this contract has grown by the fee afterwards. Since aborting reverts the transfer, the loan is never
+//! outstanding after the call.
+//! 
+//! The balance only proves repayment if the borrower cannot make it grow by calling the lender, e.g. by depositing 
+//! the loan as its own funds. While the callback runs, the flag [OPEN_LOAN_KEY] is set, and the lender rejects every
+//! call by [reject_during_loan] in its `__before_call` hook. [FlashLoan::lend] panics in a contract without the hook.
+
+use std::cell::Cell;
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, internal, method::ContractMethodInputBuilder, storage, transaction, Storable, StoragePath};
+
+/// Key of the flag of an open flash loan in the storage of the lender, which is set while the callback of the borrower
+/// runs. Contract fields never use this key unless the contract has 256 fields.
+pub const OPEN_LOAN_KEY: &[u8] = b"\xffpchain_sdk/flash_loan";
+
+thread_local! {
+    /// The contract that called [reject_during_loan] in its hook. It is an address rather than a flag since the
+    /// contracts of an internal call run in the same thread in the tests of the feature `testing`.
+    static GUARDED: Cell<Option<PublicAddress>> = const { Cell::new(None) };
+}
+
+/// Panics, which aborts the whole transaction, if a flash loan of this contract is open, i.e. the call is made during 
+/// the callback of a borrower. A lender calls it in `__before_call`, so that the borrower cannot call any method of the 
+/// lender before it repays the loan.
+pub fn reject_during_loan() {
+    GUARDED.with(|guarded| guarded.set(Some(transaction::current_account())));
+    if storage::get(OPEN_LOAN_KEY).is_some() {
+        panic!("Calls are rejected while a flash loan is open");
+    }
+}
+
+/// Topic of the log emitted by [FlashLoan::lend] after the loan is repaid.
+pub const FLASH_LOAN_TOPIC: &[u8] = b"FlashLoan";
+
+/// Value of the log emitted by [FlashLoan::lend] after the loan is repaid.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FlashLoanEvent {
+    pub borrower: PublicAddress,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+/// `FlashLoan` is a contract field that stores the fee rate of flash loans and the fees collected.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct Lender {
+///     flash_loan: FlashLoan,
+/// }
+/// 
+/// #[contract_methods]
+/// impl Lender {
+///     fn __before_call(_method: &str) {
+///         pchain_sdk::flash_loan::reject_during_loan();
+///     }
+/// 
+///     #[call]
+///     fn flash_loan(&mut self, amount: u64, data: Vec<u8>) -> u64 {
+///         // the calling contract is the borrower. 
+///         self.flash_loan.lend(pchain_sdk::transaction::calling_account(), amount, "on_flash_loan", data)
+///     }
+/// }
+/// ```
+/// 
+/// The borrower is a contract that implements the callback, which is called with the amount, the fee and the data
+/// passed to `lend`. It has to transfer `amount + fee` back to the lender before it returns:
+/// ```no_run
+/// #[call(internal_only)]
+/// fn on_flash_loan(&mut self, amount: u64, fee: u64, data: Vec<u8>) {
+///     // ...
+///     pchain_sdk::transfer(lender, amount + fee);
+/// }
+/// ```
+/// 
+/// The borrower must repay by transfer, since a call to the lender during the callback is rejected.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Fee Basis Points|P, 0|u32|
+/// |Collected Fees|P, 1|u64|
+/// 
+/// - P: parent key
+pub struct FlashLoan {
+    fee_basis_points: u32,
+    collected_fees: u64,
+}
+
+impl FlashLoan {
+    pub fn new(fee_basis_points: u32) -> Self {
+        Self { fee_basis_points, collected_fees: 0 }
+    }
+
+    /// The fee of a loan in basis points (1/10000) of the loan amount.
+    pub fn fee_basis_points(&self) -> u32 {
+        self.fee_basis_points
+    }
+
+    pub fn set_fee_basis_points(&mut self, fee_basis_points: u32) {
+        self.fee_basis_points = fee_basis_points;
+    }
+
+    /// Total fees received from repaid loans.
+    pub fn collected_fees(&self) -> u64 {
+        self.collected_fees
+    }
+
+    /// The fee of a loan of `amount`, rounded up so that a non-zero fee rate never results in a free loan.
+    pub fn fee(&self, amount: u64) -> u64 {
+        let fee = (amount as u128 * self.fee_basis_points as u128).div_ceil(10_000);
+        u64::try_from(fee).expect("Flash loan fee overflows")
+    }
+
+    /// Lend `amount` to `borrower` and call `callback` of the borrower with arguments `(amount: u64, fee: u64, data: Vec<u8>)`.
+    /// It panics, which aborts the call and reverts the loan, if the balance of this contract is insufficient, or if the 
+    /// balance has not grown by the fee after the callback returns. Otherwise, it logs a [FlashLoanEvent] and returns the fee.
+    /// 
+    /// It also panics if [reject_during_loan] was not called in this call, i.e. the contract does not reject the calls
+    /// of the borrower during the callback.
+    pub fn lend(&mut self, borrower: PublicAddress, amount: u64, callback: &str, data: Vec<u8>) -> u64 {
+        if GUARDED.with(Cell::get) != Some(transaction::current_account()) {
+            panic!("FlashLoan::lend requires pchain_sdk::flash_loan::reject_during_loan in __before_call");
+        }
+        let fee = self.fee(amount);
+        let balance_before = blockchain::balance();
+        if amount > balance_before {
+            panic!("Insufficient balance for flash loan");
+        }
+
+        storage::set(OPEN_LOAN_KEY, &[1]);
+        internal::transfer(borrower, amount);
+
+        let arguments = ContractMethodInputBuilder::new()
+            .add(amount)
+            .add(fee)
+            .add(data)
+            .to_call_arguments();
+        internal::call_untyped(borrower, callback, arguments, 0);
+        storage::delete(OPEN_LOAN_KEY);
+
+        let balance_after = blockchain::balance();
+        if balance_after < balance_before.saturating_add(fee) {
+            panic!("Flash loan is not repaid");
+        }
+
+        self.collected_fees = self.collected_fees.saturating_add(fee);
+        internal::log(FLASH_LOAN_TOPIC, &FlashLoanEvent { borrower, amount, fee }.try_to_vec().unwrap());
+        fee
+    }
+}
+
+impl Storable for FlashLoan {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            fee_basis_points: Storable::__load_storage(&field.add(0)),
+            collected_fees: Storable::__load_storage(&field.add(1)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.fee_basis_points.__save_storage(&field.add(0));
+        self.collected_fees.__save_storage(&field.add(1));
+    }
+}
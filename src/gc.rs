@@ -0,0 +1,92 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Gc], a runner of incremental storage cleanup. Removing data from collections is often deferred, e.g.
+//! [FastMap::purge_old_editions](crate::collections::FastMap::purge_old_editions) and
+//! [IterableMap::purge_removed](crate::collections::IterableMap::purge_removed), and contracts have their own
+//! cleanup such as purging expired orders. A contract registers its cleanup tasks with `Gc` in a method, usually
+//! named `run_gc`, which anyone can call to do a bounded amount of cleanup.
+//! 
+//! A task is a closure over the contract that does at most `limit` units of work (e.g. removed entries), and returns
+//! the units done. It must resume where the previous call stopped, which the purge functions of the collections do.
+//! A task is finished when it does less work than its limit.
+//! 
+//! ### Example
+//! ```no_run
+//! #[call]
+//! fn run_gc(&mut self, limit: u32) -> u32 {
+//!     Gc::new()
+//!         .task(|contract: &mut Self, limit| contract.orders.purge_removed(limit))
+//!         .task(|contract: &mut Self, limit| contract.expire_orders(limit))
+//!         .run(self, limit)
+//! }
+//! ```
+
+use crate::{runtime::{self, HostFeature}, transaction};
+
+/// Default number of units of work given to a task between checks of the remaining gas.
+pub const DEFAULT_GC_BATCH_SIZE: u32 = 16;
+
+/// A cleanup task registered by [Gc::task], which is called with the contract and a limit of units of work, and
+/// returns the units done.
+type GcTask<'a, C> = Box<dyn FnMut(&mut C, u32) -> u32 + 'a>;
+
+/// `Gc` runs cleanup tasks in order of registration, within a limit of units of work, and while the transaction
+/// has enough gas. The remaining gas is only checked if the runtime provides
+/// [HostFeature::GasRemaining]. Otherwise, the work is only bounded by the limit.
+pub struct Gc<'a, C> {
+    tasks: Vec<GcTask<'a, C>>,
+    batch_size: u32,
+    min_gas: u64,
+}
+
+impl<'a, C> Gc<'a, C> {
+    pub fn new() -> Self {
+        Self { tasks: vec![], batch_size: DEFAULT_GC_BATCH_SIZE, min_gas: 0 }
+    }
+
+    /// Register a cleanup task, which is called with the contract and a limit of units of work, and returns the
+    /// units done.
+    pub fn task(mut self, task: impl FnMut(&mut C, u32) -> u32 + 'a) -> Self {
+        self.tasks.push(Box::new(task));
+        self
+    }
+
+    /// Set the number of units of work given to a task between checks of the remaining gas. It panics if it is 0.
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        if batch_size == 0 {
+            panic!("Batch size of Gc must be positive");
+        }
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Stop before a batch if the remaining gas is below `min_gas`, which should cover a batch of the most expensive
+    /// task and the rest of the call, e.g. saving the contract.
+    pub fn reserve_gas(mut self, min_gas: u64) -> Self {
+        self.min_gas = min_gas;
+        self
+    }
+
+    /// Run the tasks on the contract with at most `limit` units of work in total. Returns the units done.
+    pub fn run(mut self, contract: &mut C, limit: u32) -> u32 {
+        let metered = self.min_gas > 0 && runtime::supports(HostFeature::GasRemaining);
+        let mut done = 0;
+        for task in self.tasks.iter_mut() {
+            loop {
+                let batch = self.batch_size.min(limit - done);
+                if batch == 0 || (metered && transaction::gas_remaining() < self.min_gas) {
+                    return done
+                }
+                let work = task(contract, batch).min(batch);
+                done += work;
+                if work < batch {
+                    break
+                }
+            }
+        }
+        done
+    }
+}
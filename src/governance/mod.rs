@@ -0,0 +1,18 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines contract fields for on-chain governance.
+//! 
+//! Components:
+//! - [Proposals]: proposal lifecycle with voting, quorum, deadline and delayed execution
+//! - [Snapshots]: balance checkpoints for vote weights at a past block height
+
+pub(crate) mod proposals;
+#[allow(unused_imports)]
+pub use proposals::*;
+
+pub(crate) mod snapshots;
+#[allow(unused_imports)]
+pub use snapshots::*;
@@ -0,0 +1,297 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the component struct [Proposals].
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{FastMap, Insertable, IterableMap, Iterable}, internal, Storable, StoragePath};
+
+/// Error of voting on or executing a proposal in [Proposals].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProposalError {
+    /// There is no proposal with the ID.
+    NotFound,
+    /// The voting period of the proposal has ended.
+    VotingClosed,
+    /// The voter has already voted on the proposal.
+    AlreadyVoted,
+    /// The proposal cannot be executed in its current state.
+    NotExecutable(ProposalState),
+}
+
+impl std::fmt::Display for ProposalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProposalError::NotFound => write!(f, "proposal not found"),
+            ProposalError::VotingClosed => write!(f, "voting period has ended"),
+            ProposalError::AlreadyVoted => write!(f, "already voted"),
+            ProposalError::NotExecutable(state) => write!(f, "proposal is not executable in state {:?}", state),
+        }
+    }
+}
+
+/// A vote cast on a proposal.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteKind {
+    For,
+    Against,
+    Abstain,
+}
+
+impl Insertable for VoteKind {}
+
+/// State of a proposal, derived from its votes and the current block height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalState {
+    /// The voting period has not ended.
+    Active,
+    /// The voting period has ended without reaching the quorum, or with no more votes for than against.
+    Defeated,
+    /// The proposal has passed, but the execution delay has not elapsed.
+    Queued,
+    /// The proposal has passed and can be executed.
+    Ready,
+    /// The proposal has been executed.
+    Executed,
+}
+
+/// A proposal stored in [Proposals]. Its action is a contract call to be made once it passes.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct Proposal {
+    pub proposer: PublicAddress,
+    pub description: String,
+    /// Address of the contract to call. It can be the address of this contract.
+    pub target: PublicAddress,
+    /// Name of the method to call.
+    pub method: String,
+    /// Arguments of the call, e.g. constructed by `ContractMethodInputBuilder::to_call_arguments`.
+    pub arguments: Vec<u8>,
+    /// Amount to transfer to the target in the call.
+    pub value: u64,
+    /// Block height at which vote weights should be taken, e.g. by [Snapshots::balance_at](super::Snapshots::balance_at).
+    pub snapshot_height: u64,
+    /// Last block height in which votes are accepted.
+    pub deadline: u64,
+    /// First block height in which the proposal can be executed if it passes.
+    pub executable_from: u64,
+    pub for_votes: u64,
+    pub against_votes: u64,
+    pub abstain_votes: u64,
+    pub executed: bool,
+}
+
+impl Iterable for Proposal {}
+
+/// `Proposals` handles the lifecycle of governance proposals: creation, voting, and execution of the proposed
+/// contract call once the proposal passes and an execution delay (a timelock) has elapsed. A proposal passes if
+/// the votes for and abstaining reach the quorum, and there are more votes for than against.
+/// 
+/// The weights of votes are given by the contract, e.g. from [Snapshots](super::Snapshots) at the snapshot height
+/// of the proposal, and `Proposals` records who has voted so that each voter votes once.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn vote(&mut self, id: u64, kind: u8) {
+///     let voter = transaction::calling_account();
+///     let proposal = self.proposals.proposal(id).unwrap();
+///     let weight = self.snapshots.balance_at(voter, proposal.snapshot_height);
+///     let kind = match kind { 0 => VoteKind::For, 1 => VoteKind::Against, _ => VoteKind::Abstain };
+///     self.proposals.vote(id, voter, kind, weight).unwrap();
+/// }
+/// 
+/// #[call]
+/// fn execute(&mut self, id: u64) -> Option<Vec<u8>> {
+///     // permissionless: anyone can execute a proposal once it is ready.
+///     self.proposals.execute(id).unwrap()
+/// }
+/// ```
+/// 
+/// The proposals are saved to world state before the proposed call is made, so that a proposal cannot be executed
+/// again by a re-entrant call, e.g. by its target calling `execute` back. Other fields of the contract are saved after
+/// the outer call returns, so they may overwrite the changes made by a proposal that calls this contract itself.
+/// Flush them by [flush_storage](crate::storage::flush_storage) before executing if the proposal depends on them.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Proposals|P, 0|`IterableMap<u64, Proposal>`|
+/// |Votes|P, 1|`FastMap<(u64, PublicAddress), VoteKind>`|
+/// |Next Proposal ID|P, 2|u64|
+/// |Voting Period|P, 3|u64|
+/// |Quorum|P, 4|u64|
+/// |Execution Delay|P, 5|u64|
+/// 
+/// - P: parent key
+pub struct Proposals {
+    proposals: IterableMap<u64, Proposal>,
+    votes: FastMap<(u64, PublicAddress), VoteKind>,
+    next_id: u64,
+    voting_period: u64,
+    quorum: u64,
+    execution_delay: u64,
+    /// The key of the contract field, which is known once it is loaded from or saved to world state.
+    scope: Option<StoragePath>,
+}
+
+impl Proposals {
+    /// Instantiate with the voting period and the execution delay in number of blocks, and the quorum in vote weight.
+    pub fn new(voting_period: u64, quorum: u64, execution_delay: u64) -> Self {
+        Self {
+            proposals: IterableMap::new(),
+            votes: FastMap::new(),
+            next_id: 0,
+            voting_period,
+            quorum,
+            execution_delay,
+            scope: None,
+        }
+    }
+
+    pub fn voting_period(&self) -> u64 {
+        self.voting_period
+    }
+
+    pub fn quorum(&self) -> u64 {
+        self.quorum
+    }
+
+    pub fn execution_delay(&self) -> u64 {
+        self.execution_delay
+    }
+
+    /// Set the parameters of new proposals. Existing proposals keep their deadline and execution delay, but are
+    /// judged by the new quorum.
+    pub fn set_parameters(&mut self, voting_period: u64, quorum: u64, execution_delay: u64) {
+        self.voting_period = voting_period;
+        self.quorum = quorum;
+        self.execution_delay = execution_delay;
+    }
+
+    /// Create a proposal to call `method` of `target`. Voting starts in this block, and the snapshot height is the
+    /// previous block, whose balances are final. Returns the ID of the proposal.
+    pub fn create(&mut self, proposer: PublicAddress, description: String, target: PublicAddress, method: String, arguments: Vec<u8>, value: u64) -> u64 {
+        let block_number = blockchain::block_number();
+        let deadline = block_number.saturating_add(self.voting_period);
+        let proposal = Proposal {
+            proposer,
+            description,
+            target,
+            method,
+            arguments,
+            value,
+            snapshot_height: block_number.saturating_sub(1),
+            deadline,
+            executable_from: deadline.saturating_add(1).saturating_add(self.execution_delay),
+            for_votes: 0,
+            against_votes: 0,
+            abstain_votes: 0,
+            executed: false,
+        };
+        let id = self.next_id;
+        self.proposals.insert(&id, proposal);
+        self.next_id += 1;
+        id
+    }
+
+    /// Get a proposal.
+    pub fn proposal(&self, id: u64) -> Option<Proposal> {
+        self.proposals.get(&id)
+    }
+
+    /// The vote of the voter on the proposal.
+    pub fn vote_of(&self, id: u64, voter: PublicAddress) -> Option<VoteKind> {
+        self.votes.get(&(id, voter))
+    }
+
+    /// Cast a vote with the weight. Each voter can vote once on each proposal.
+    pub fn vote(&mut self, id: u64, voter: PublicAddress, kind: VoteKind, weight: u64) -> Result<(), ProposalError> {
+        let proposal = self.proposals.get_mut(&id).ok_or(ProposalError::NotFound)?;
+        if blockchain::block_number() > proposal.deadline {
+            return Err(ProposalError::VotingClosed)
+        }
+        if self.votes.get(&(id, voter)).is_some() {
+            return Err(ProposalError::AlreadyVoted)
+        }
+        let tally = match kind {
+            VoteKind::For => &mut proposal.for_votes,
+            VoteKind::Against => &mut proposal.against_votes,
+            VoteKind::Abstain => &mut proposal.abstain_votes,
+        };
+        *tally = tally.saturating_add(weight);
+        self.votes.insert(&(id, voter), kind);
+        Ok(())
+    }
+
+    /// The state of the proposal in the block of this transaction.
+    pub fn state(&self, id: u64) -> Option<ProposalState> {
+        self.proposals.get(&id).map(|proposal| self.state_of(&proposal))
+    }
+
+    /// Execute the proposed call if the proposal is [ProposalState::Ready]. Returns the return value of the call. The
+    /// proposal is marked executed and the proposals are saved to world state before the call.
+    pub fn execute(&mut self, id: u64) -> Result<Option<Vec<u8>>, ProposalError> {
+        let proposal = self.proposals.get(&id).ok_or(ProposalError::NotFound)?;
+        match self.state_of(&proposal) {
+            ProposalState::Ready => {},
+            state => return Err(ProposalError::NotExecutable(state))
+        }
+        if let Some(proposal) = self.proposals.get_mut(&id) {
+            proposal.executed = true;
+        }
+        // a re-entrant call loads the proposals from world state
+        if let Some(scope) = self.scope.clone() {
+            self.__save_storage(&scope);
+        }
+        Ok(internal::call_untyped(proposal.target, &proposal.method, proposal.arguments, proposal.value))
+    }
+
+    /// Remove a proposal, e.g. a defeated or executed one. Its votes are kept.
+    pub fn remove(&mut self, id: u64) {
+        self.proposals.remove(&id);
+    }
+
+    fn state_of(&self, proposal: &Proposal) -> ProposalState {
+        let block_number = blockchain::block_number();
+        if proposal.executed {
+            ProposalState::Executed
+        } else if block_number <= proposal.deadline {
+            ProposalState::Active
+        } else if proposal.for_votes.saturating_add(proposal.abstain_votes) < self.quorum || proposal.for_votes <= proposal.against_votes {
+            ProposalState::Defeated
+        } else if block_number < proposal.executable_from {
+            ProposalState::Queued
+        } else {
+            ProposalState::Ready
+        }
+    }
+}
+
+impl Storable for Proposals {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            proposals: Storable::__load_storage(&field.add(0)),
+            votes: Storable::__load_storage(&field.add(1)),
+            next_id: Storable::__load_storage(&field.add(2)),
+            voting_period: Storable::__load_storage(&field.add(3)),
+            quorum: Storable::__load_storage(&field.add(4)),
+            execution_delay: Storable::__load_storage(&field.add(5)),
+            scope: Some(field.clone()),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.proposals.__save_storage(&field.add(0));
+        self.votes.__save_storage(&field.add(1));
+        self.next_id.__save_storage(&field.add(2));
+        self.voting_period.__save_storage(&field.add(3));
+        self.quorum.__save_storage(&field.add(4));
+        self.execution_delay.__save_storage(&field.add(5));
+        self.scope = Some(field.clone());
+    }
+}
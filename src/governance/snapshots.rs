@@ -0,0 +1,158 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the component struct [Snapshots].
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{FastMap, Insertable}, Storable, StoragePath};
+
+/// A balance recorded by [Snapshots] at a block height.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub block_height: u64,
+    pub value: u64,
+}
+
+impl Insertable for Checkpoint {}
+
+/// `Snapshots` records checkpoints of the balances of addresses, and of the total supply, whenever they change.
+/// The balance at a past block height is then found by binary search over the checkpoints of the address, e.g.
+/// to weigh votes by the balances at the creation of a proposal, so that tokens cannot be moved to vote twice.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn transfer(&mut self, to: PublicAddress, amount: u64) {
+///     let from = transaction::calling_account();
+///     // ... update balances
+///     self.snapshots.record_transfer(from, self.balance_of(from), to, self.balance_of(to));
+/// }
+/// 
+/// fn vote_weight(&self, voter: PublicAddress, proposal: &Proposal) -> u64 {
+///     self.snapshots.balance_at(voter, proposal.snapshot_height)
+/// }
+/// ```
+/// 
+/// Checkpoints are taken at the block height of the transaction. Multiple changes in the same block overwrite
+/// the checkpoint of that block, so a balance at a height is the balance at the end of that block. Since the
+/// current block is not yet final, the snapshot of a proposal should be taken at a height before its creation.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Checkpoint Counts|P, 0|`FastMap<Option<PublicAddress>, u32>`|
+/// |Checkpoints|P, 1|`FastMap<(Option<PublicAddress>, u32), Checkpoint>`|
+/// 
+/// - P: parent key
+/// - The checkpoints of the total supply are keyed by `None`.
+pub struct Snapshots {
+    counts: FastMap<Option<PublicAddress>, u32>,
+    checkpoints: FastMap<(Option<PublicAddress>, u32), Checkpoint>,
+}
+
+impl Snapshots {
+    pub fn new() -> Self {
+        Self { counts: FastMap::new(), checkpoints: FastMap::new() }
+    }
+
+    /// Record the balance of the address at the current block height.
+    pub fn record(&mut self, address: PublicAddress, balance: u64) {
+        self.record_inner(Some(address), balance)
+    }
+
+    /// Record the balances of both addresses of a transfer, after the transfer.
+    pub fn record_transfer(&mut self, from: PublicAddress, from_balance: u64, to: PublicAddress, to_balance: u64) {
+        self.record(from, from_balance);
+        self.record(to, to_balance);
+    }
+
+    /// Record the total supply at the current block height, e.g. after minting or burning.
+    pub fn record_total(&mut self, total_supply: u64) {
+        self.record_inner(None, total_supply)
+    }
+
+    /// The balance of the address at the end of the block at `block_height`. It is 0 before the first checkpoint.
+    pub fn balance_at(&self, address: PublicAddress, block_height: u64) -> u64 {
+        self.value_at(Some(address), block_height)
+    }
+
+    /// The total supply at the end of the block at `block_height`.
+    pub fn total_at(&self, block_height: u64) -> u64 {
+        self.value_at(None, block_height)
+    }
+
+    /// The latest recorded balance of the address.
+    pub fn latest(&self, address: PublicAddress) -> u64 {
+        self.latest_checkpoint(Some(address)).map_or(0, |c| c.value)
+    }
+
+    /// The number of checkpoints of the address.
+    pub fn checkpoint_count(&self, address: PublicAddress) -> u32 {
+        self.counts.get(&Some(address)).unwrap_or(0)
+    }
+
+    /// The checkpoint of the address at the index, in order of block height.
+    pub fn checkpoint(&self, address: PublicAddress, index: u32) -> Option<Checkpoint> {
+        self.checkpoints.get(&(Some(address), index))
+    }
+
+    fn latest_checkpoint(&self, key: Option<PublicAddress>) -> Option<Checkpoint> {
+        let count = self.counts.get(&key).unwrap_or(0);
+        if count == 0 {
+            return None
+        }
+        self.checkpoints.get(&(key, count - 1))
+    }
+
+    fn record_inner(&mut self, key: Option<PublicAddress>, value: u64) {
+        let block_height = blockchain::block_number();
+        let count = self.counts.get(&key).unwrap_or(0);
+        match self.latest_checkpoint(key) {
+            Some(latest) if latest.block_height == block_height => {
+                self.checkpoints.insert(&(key, count - 1), Checkpoint { block_height, value });
+            },
+            _ => {
+                self.checkpoints.insert(&(key, count), Checkpoint { block_height, value });
+                self.counts.insert(&key, count + 1);
+            }
+        }
+    }
+
+    /// Binary search for the last checkpoint at or before the block height.
+    fn value_at(&self, key: Option<PublicAddress>, block_height: u64) -> u64 {
+        let count = self.counts.get(&key).unwrap_or(0);
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let checkpoint = self.checkpoints.get(&(key, mid)).unwrap();
+            if checkpoint.block_height <= block_height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return 0
+        }
+        self.checkpoints.get(&(key, low - 1)).map_or(0, |c| c.value)
+    }
+}
+
+impl Storable for Snapshots {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            counts: Storable::__load_storage(&field.add(0)),
+            checkpoints: Storable::__load_storage(&field.add(1)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.counts.__save_storage(&field.add(0));
+        self.checkpoints.__save_storage(&field.add(1));
+    }
+}
@@ -0,0 +1,160 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the trait [Host], the functions that the SDK expects the runtime to provide. It is a safe counterpart of the
+//! externally-defined functions of the Contract Binary Interface. 
+//! 
+//! On WASM targets (`target_arch = "wasm32"`), the SDK calls the imported functions directly and no Host needs to be set. 
+//! On other targets, a contract crate compiles as a plain Rust library: there are no extern imports, and the SDK calls
+//! the Host set by [set_host] instead. This allows the business logic of a contract to be reused off-chain, e.g. for 
+//! simulation and reporting, by implementing `Host` over an in-memory or database-backed world state.
+//! 
+//! ### Example
+//! ```no_run
+//! struct Simulation { /* world state, call context, ... */ }
+//! impl Host for Simulation { /* ... */ }
+//! 
+//! pchain_sdk::host::set_host(Rc::new(Simulation::new()));
+//! // Methods of the contract can now be called natively, e.g. through the generated `entrypoint()`.
+//! entrypoint();
+//! ```
+
+use pchain_types::{blockchain::Command, cryptography::PublicAddress, runtime::CallInput};
+
+use crate::runtime::HostFeature;
+
+/// The functions provided by the runtime to a contract call.
+pub trait Host {
+    // Account State Accessors
+
+    /// Get the value associated with the key in the storage of this contract.
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+    /// Get the value associated with the key in the storage of the Network Account.
+    fn get_network_storage(&self, key: &[u8]) -> Option<Vec<u8>>;
+    /// Bind the value to the key in the storage of this contract.
+    fn set(&self, key: &[u8], value: &[u8]);
+    /// Balance of this contract.
+    fn balance(&self) -> u64;
+
+    // Block Field Getters
+
+    fn block_height(&self) -> u64;
+    fn block_timestamp(&self) -> u32;
+    fn prev_block_hash(&self) -> [u8; 32];
+    /// Random bytes of the block. By default, it panics, since [HostFeature::RandomBytes] is not part of the base 
+    /// interface.
+    fn random_bytes(&self) -> [u8; 32] {
+        panic!("Host feature {:?} is not supported by this runtime", HostFeature::RandomBytes)
+    }
+
+    // Call Context Getters
+
+    fn calling_account(&self) -> PublicAddress;
+    fn current_account(&self) -> PublicAddress;
+    /// Method name of this call, as bytes.
+    fn method(&self) -> Vec<u8>;
+    /// Borsh-serialized arguments of this call.
+    fn arguments(&self) -> Vec<u8>;
+    fn amount(&self) -> u64;
+    fn is_internal_call(&self) -> bool;
+    fn transaction_hash(&self) -> [u8; 32];
+    /// Priority fee per gas of the transaction. By default, it panics, since [HostFeature::FeeContext] is not part of 
+    /// the base interface.
+    fn priority_fee_per_gas(&self) -> u64 {
+        panic!("Host feature {:?} is not supported by this runtime", HostFeature::FeeContext)
+    }
+    /// Price per gas paid by the transaction, i.e. the base fee of the block plus the priority fee. By default, it 
+    /// panics, since [HostFeature::FeeContext] is not part of the base interface.
+    fn gas_price(&self) -> u64 {
+        panic!("Host feature {:?} is not supported by this runtime", HostFeature::FeeContext)
+    }
+    /// Gas remaining in the transaction. By default, it panics, since [HostFeature::GasRemaining] is not part of the 
+    /// base interface.
+    fn gas_remaining(&self) -> u64 {
+        panic!("Host feature {:?} is not supported by this runtime", HostFeature::GasRemaining)
+    }
+
+    // Internal Call Triggers
+
+    /// Call another contract. Returns the return value of the call, which is empty if there is no return value.
+    fn call(&self, input: CallInput) -> Vec<u8>;
+    fn return_value(&self, value: &[u8]);
+    fn transfer(&self, recipient: PublicAddress, amount: u64);
+
+    // Network Command Triggers
+
+    /// Defer a network command to be executed after success of this call.
+    fn defer(&self, command: Command);
+
+    // Logging
+
+    fn log(&self, topic: &[u8], value: &[u8]);
+
+    /// Emit the logs in order. By default, each log is emitted by [Host::log].
+    fn log_batch(&self, logs: &[(&[u8], &[u8])]) {
+        for (topic, value) in logs {
+            self.log(topic, value);
+        }
+    }
+
+    // Cryptographic operations
+
+    fn sha256(&self, input: &[u8]) -> Vec<u8>;
+    fn keccak256(&self, input: &[u8]) -> Vec<u8>;
+    fn ripemd(&self, input: &[u8]) -> Vec<u8>;
+    fn verify_ed25519_signature(&self, input: &[u8], signature: &[u8], address: &[u8]) -> bool;
+    /// Returns whether a secp256k1 signature was produced by the public key over the input. By default, it panics, 
+    /// since [HostFeature::Secp256k1] is not part of the base interface.
+    fn verify_secp256k1_signature(&self, _input: &[u8], _signature: &[u8], _public_key: &[u8]) -> bool {
+        panic!("Host feature {:?} is not supported by this runtime", HostFeature::Secp256k1)
+    }
+
+    // Feature Detection
+
+    /// Check if the host provides the functions of the feature. By default, only the features of the base interface
+    /// are provided.
+    fn supports(&self, feature: HostFeature) -> bool {
+        feature.is_in_base_interface()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{cell::RefCell, rc::Rc};
+    use super::Host;
+
+    thread_local! {
+        static HOST: RefCell<Option<Rc<dyn Host>>> = RefCell::new(None);
+    }
+
+    /// Set the Host called by the SDK in this thread. Returns the Host being replaced, if any.
+    pub fn set_host(host: Rc<dyn Host>) -> Option<Rc<dyn Host>> {
+        HOST.with(|h| h.borrow_mut().replace(host))
+    }
+
+    /// Remove the Host set in this thread. Returns the Host being removed, if any.
+    pub fn take_host() -> Option<Rc<dyn Host>> {
+        HOST.with(|h| h.borrow_mut().take())
+    }
+
+    /// The Host set in this thread. It panics if no Host is set. With the feature `trace`, the calls to the Host are
+    /// recorded by [trace](crate::trace).
+    pub(crate) fn current() -> Rc<dyn Host> {
+        let host = HOST.with(|h| h.borrow().clone())
+            .expect("No Host is set. Call pchain_sdk::host::set_host before calling a contract outside of WASM runtime.");
+        #[cfg(feature = "trace")]
+        let host: Rc<dyn Host> = Rc::new(crate::trace::TracedHost(host));
+        host
+    }
+}
+
+/// The Host of the WASM runtime, which is the imported functions.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn current() -> &'static dyn Host {
+    &crate::imports::WasmHost
+}
@@ -0,0 +1,74 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [IdSequence], a contract field that generates unique IDs, e.g. of orders, proposals and tokens.
+
+use crate::{collections::FastMap, Storable, StoragePath};
+
+/// `IdSequence` generates unique u64 IDs in independent namespaces. IDs of each namespace start from 0 and
+/// increase by 1. The counters are cached during the call and written to world state once, when the contract is saved.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct Exchange {
+///     ids: IdSequence,
+///     orders: FastMap<u64, Order>,
+/// }
+/// 
+/// #[contract_methods]
+/// impl Exchange {
+///     #[call]
+///     fn place_order(&mut self, order: Order) -> u64 {
+///         let id = self.ids.next("orders");
+///         self.orders.insert(&id, order);
+///         id
+///     }
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Next IDs|P, 0|`FastMap<String, u64>`|
+/// 
+/// - P: parent key
+pub struct IdSequence {
+    counters: FastMap<String, u64>,
+}
+
+impl IdSequence {
+    pub fn new() -> Self {
+        Self { counters: FastMap::new() }
+    }
+
+    /// Returns a new ID in the namespace. Panics if the IDs of the namespace are exhausted.
+    pub fn next(&mut self, namespace: &str) -> u64 {
+        let namespace = namespace.to_string();
+        let id = self.counters.get(&namespace).unwrap_or(0);
+        let next = id.checked_add(1).expect("IDs of the namespace are exhausted");
+        self.counters.insert(&namespace, next);
+        id
+    }
+
+    /// The ID that will be returned by the next call to [IdSequence::next], which is also the number of IDs
+    /// generated in the namespace.
+    pub fn peek(&self, namespace: &str) -> u64 {
+        self.counters.get(&namespace.to_string()).unwrap_or(0)
+    }
+}
+
+impl Storable for IdSequence {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            counters: Storable::__load_storage(&field.add(0)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.counters.__save_storage(&field.add(0));
+    }
+}
@@ -6,6 +6,13 @@
 //! Defines the signatures of the externally-defined functions that Contract WASM modules expect 
 //! to be linked to the WASM runtime during module instantiation. The definitions (function bodies) of these functions
 //! should follow a version of Contract Binary Interface.
+//! 
+//! [WasmHost] implements the trait [Host](crate::host::Host) by calling these functions. This module is only compiled 
+//! on WASM targets.
+
+use pchain_types::{blockchain::{Command, Log}, cryptography::PublicAddress, runtime::CallInput, serialization::Serializable};
+
+use crate::{host::Host, runtime::HostFeature};
 
 extern "C" {
     // Account State Accessors
@@ -18,6 +25,8 @@ extern "C" {
     pub(crate) fn block_height() -> u64;
     pub(crate) fn block_timestamp() -> u32;
     pub(crate) fn prev_block_hash(hash_ptr_ptr: *const u32);
+    #[cfg(feature = "random-bytes")]
+    pub(crate) fn random_bytes(bytes_ptr_ptr: *const u32);
 
     // Call Context Getters
     pub(crate) fn calling_account(address_ptr_ptr: *const u32);
@@ -27,6 +36,12 @@ extern "C" {
     pub(crate) fn amount() -> u64;
     pub(crate) fn is_internal_call() -> i32;
     pub(crate) fn transaction_hash(hash_ptr_ptr: *const u32);
+    #[cfg(feature = "fee-context")]
+    pub(crate) fn priority_fee_per_gas() -> u64;
+    #[cfg(feature = "fee-context")]
+    pub(crate) fn gas_price() -> u64;
+    #[cfg(feature = "gas-remaining")]
+    pub(crate) fn gas_remaining() -> u64;
 
     // Internal Call Triggers
     pub(crate) fn call(call_input_ptr: *const u8, call_input_len: u32, rval_ptr_ptr: *const u32) -> u32;
@@ -43,12 +58,294 @@ extern "C" {
 
     // Logging
     pub(crate) fn _log(log_ptr: *const u8, log_len: u32);
+    #[cfg(feature = "log-batch")]
+    pub(crate) fn _log_batch(logs_ptr: *const u8, logs_len: u32);
 
     // Cryptographic operations
     pub(crate) fn sha256(msg_ptr: *const u8, msg_len: u32, digest_ptr_ptr: *const u32);
     pub(crate) fn keccak256(msg_ptr: *const u8, msg_len: u32, digest_ptr_ptr: *const u32);
     pub(crate) fn ripemd(msg_ptr: *const u8, msg_len: u32, digest_ptr_ptr: *const u32);
     pub(crate) fn verify_ed25519_signature(msg_ptr: *const u8, msg_len: u32, signature_ptr: *const u8, address_ptr: *const u8) -> i32;
+    #[cfg(feature = "secp256k1")]
+    pub(crate) fn verify_secp256k1_signature(msg_ptr: *const u8, msg_len: u32, signature_ptr: *const u8, public_key_ptr: *const u8, public_key_len: u32) -> i32;
 
 }
 
+/// The Host of the WASM runtime. Its methods call the imported functions.
+pub(crate) struct WasmHost;
+
+impl WasmHost {
+    /// Take ownership of a segment of `len` bytes that the host wrote at the offset written by `f` into the pointer it is given.
+    fn read_bytes(len: usize, f: impl FnOnce(*const u32)) -> Vec<u8> {
+        let mut val_ptr: u32 = 0;
+        let val_ptr_ptr = &mut val_ptr;
+        f(val_ptr_ptr);
+        unsafe { Vec::<u8>::from_raw_parts(val_ptr as *mut u8, len, len) }
+    }
+
+    fn read_32_bytes(f: impl FnOnce(*const u32)) -> [u8; 32] {
+        TryInto::<[u8;32]>::try_into(Self::read_bytes(32, f)).unwrap()
+    }
+}
+
+impl Host for WasmHost {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let key_ptr = key.as_ptr();
+
+        // `get` needs to get two things:
+        //   * A WASM linear memory offset pointing to where the host process
+        //     wrote the UTF-8 encoded result of the DB get: `val_ptr`.
+        //   * The byte-wise length of the gotten value: `val_len`.
+        // 
+        // `val_len` is returned directly, but the problem is that WASM does not
+        // yet support multiple return values, so we can't return `val_ptr` alongside it.
+        // Instead, we allocate a space in the heap for the host to write `val_ptr` in,
+        // and then tell the host to write `val` there by passing it `val_ptr_ptr` through
+        // `raw_get`.
+        // `val_len` is negative if the key cannot be found in world-state.
+        //
+        // When val_ptr leaves this scope, it is deallocated (we have no further use
+        // for it).
+        let mut val_ptr: u32 = 0;
+        let val_ptr_ptr = &mut val_ptr;
+
+        unsafe {
+            match get(key_ptr, key.len() as u32, val_ptr_ptr) {
+                val_len if val_len < 0 => {
+                    None
+                },
+                val_len => {
+                    // If module execution reaches this point, we can assume that the `get` has succeeded.
+                    //
+                    // This Vec<u8> takes ownership of the segment of memory, letting the Rust ownership
+                    // system to Drop it later.
+                    Some(Vec::<u8>::from_raw_parts(val_ptr as *mut u8, val_len as usize, val_len as usize))
+                }
+            }
+        }
+    }
+
+    fn get_network_storage(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let key_ptr = key.as_ptr();
+
+        let mut val_ptr: u32 = 0;
+        let val_ptr_ptr = &mut val_ptr;
+
+        unsafe {
+            match get_network_storage(key_ptr, key.len() as u32, val_ptr_ptr) {
+                val_len if val_len < 0 => {
+                    None
+                },
+                val_len => {
+                    Some(Vec::<u8>::from_raw_parts(val_ptr as *mut u8, val_len as usize, val_len as usize))
+                }
+            }
+        }
+    }
+
+    fn set(&self, key: &[u8], value: &[u8]) {
+        let key_ptr = key.as_ptr();
+        let val_ptr = value.as_ptr();
+        unsafe {
+            set(key_ptr, key.len() as u32, val_ptr, value.len() as u32);
+        } 
+    }
+
+    fn balance(&self) -> u64 {
+        unsafe { balance() }
+    }
+
+    fn block_height(&self) -> u64 {
+        unsafe { block_height() }
+    }
+
+    fn block_timestamp(&self) -> u32 {
+        unsafe { block_timestamp() }
+    }
+
+    fn prev_block_hash(&self) -> [u8; 32] {
+        Self::read_32_bytes(|ptr_ptr| unsafe { prev_block_hash(ptr_ptr) })
+    }
+
+    #[cfg(feature = "random-bytes")]
+    fn random_bytes(&self) -> [u8; 32] {
+        Self::read_32_bytes(|ptr_ptr| unsafe { random_bytes(ptr_ptr) })
+    }
+
+    fn calling_account(&self) -> PublicAddress {
+        Self::read_32_bytes(|ptr_ptr| unsafe { calling_account(ptr_ptr) })
+    }
+
+    fn current_account(&self) -> PublicAddress {
+        Self::read_32_bytes(|ptr_ptr| unsafe { current_account(ptr_ptr) })
+    }
+
+    fn method(&self) -> Vec<u8> {
+        let mut method_ptr: u32 = 0;
+        let method_ptr_ptr = &mut method_ptr;
+
+        unsafe {
+            let method_len = method(method_ptr_ptr);
+            Vec::<u8>::from_raw_parts(method_ptr as *mut u8, method_len as usize, method_len as usize)
+        }
+    }
+
+    fn arguments(&self) -> Vec<u8> {
+        let mut args_ptr: u32 = 0;
+        let args_ptr_ptr = &mut args_ptr;
+
+        unsafe {
+            let args_len = arguments(args_ptr_ptr);
+            Vec::<u8>::from_raw_parts(args_ptr as *mut u8, args_len as usize, args_len as usize)
+        }
+    }
+
+    fn amount(&self) -> u64 {
+        unsafe { amount() }
+    }
+
+    fn is_internal_call(&self) -> bool {
+        unsafe { is_internal_call() != 0 }
+    }
+
+    fn transaction_hash(&self) -> [u8; 32] {
+        Self::read_32_bytes(|ptr_ptr| unsafe { transaction_hash(ptr_ptr) })
+    }
+
+    #[cfg(feature = "fee-context")]
+    fn priority_fee_per_gas(&self) -> u64 {
+        unsafe { priority_fee_per_gas() }
+    }
+
+    #[cfg(feature = "fee-context")]
+    fn gas_price(&self) -> u64 {
+        unsafe { gas_price() }
+    }
+
+    #[cfg(feature = "gas-remaining")]
+    fn gas_remaining(&self) -> u64 {
+        unsafe { gas_remaining() }
+    }
+
+    fn call(&self, input: CallInput) -> Vec<u8> {
+        let call_command = Command::Call(input).serialize();
+        let call_ptr: *const u8 = call_command.as_ptr();
+        let call_len = call_command.len() as u32;
+
+        let mut return_val_ptr: u32 = 0;
+        let return_val_ptr_ptr = &mut return_val_ptr;
+
+        unsafe {
+            let return_val_len = call(call_ptr, call_len, return_val_ptr_ptr);
+            Vec::<u8>::from_raw_parts(return_val_ptr as *mut u8, return_val_len as usize, return_val_len as usize)
+        }
+    }
+
+    fn return_value(&self, value: &[u8]) {
+        let value_ptr = value.as_ptr();
+        let value_len = value.len() as u32;
+        unsafe {           
+            return_value(value_ptr, value_len);
+        }
+    }
+
+    fn transfer(&self, recipient: PublicAddress, amount: u64) {
+        let mut transfer_bytes = Vec::new();
+        transfer_bytes.append(&mut recipient.to_vec());
+        transfer_bytes.append(&mut amount.to_le_bytes().to_vec());
+
+        let transfer_ptr = transfer_bytes.as_ptr();
+        unsafe { transfer(transfer_ptr) }
+    }
+
+    fn defer(&self, command: Command) {
+        let defer_fn = match &command {
+            Command::CreateDeposit(_) => defer_create_deposit,
+            Command::SetDepositSettings(_) => defer_set_deposit_settings,
+            Command::TopUpDeposit(_) => defer_topup_deposit,
+            Command::WithdrawDeposit(_) => defer_withdraw_deposit,
+            Command::StakeDeposit(_) => defer_stake_deposit,
+            Command::UnstakeDeposit(_) => defer_unstake_deposit,
+            _ => panic!("Command cannot be deferred from a contract call."),
+        };
+        let command = command.serialize();
+        let command_ptr = command.as_ptr();
+        let command_len = command.len() as u32;
+        unsafe { defer_fn(command_ptr, command_len) }
+    }
+
+    fn log(&self, topic: &[u8], value: &[u8]) {
+        let event = Log { 
+            topic: topic.to_vec(), 
+            value: value.to_vec()
+        };
+        let serialized_event = Log::serialize(&event);
+
+        let log_ptr= serialized_event.as_ptr();
+        let log_len = serialized_event.len() as u32;
+
+        unsafe {
+            _log(log_ptr, log_len);
+        }
+    }
+
+    #[cfg(feature = "log-batch")]
+    fn log_batch(&self, logs: &[(&[u8], &[u8])]) {
+        let events: Vec<Log> = logs.iter()
+            .map(|(topic, value)| Log { topic: topic.to_vec(), value: value.to_vec() })
+            .collect();
+        let serialized_events = borsh::BorshSerialize::try_to_vec(&events).unwrap();
+
+        unsafe {
+            _log_batch(serialized_events.as_ptr(), serialized_events.len() as u32);
+        }
+    }
+
+    fn sha256(&self, input: &[u8]) -> Vec<u8> {
+        Self::read_bytes(32, |ptr_ptr| unsafe { sha256(input.as_ptr(), input.len() as u32, ptr_ptr) })
+    }
+
+    fn keccak256(&self, input: &[u8]) -> Vec<u8> {
+        Self::read_bytes(32, |ptr_ptr| unsafe { keccak256(input.as_ptr(), input.len() as u32, ptr_ptr) })
+    }
+
+    fn ripemd(&self, input: &[u8]) -> Vec<u8> {
+        Self::read_bytes(20, |ptr_ptr| unsafe { ripemd(input.as_ptr(), input.len() as u32, ptr_ptr) })
+    }
+
+    fn verify_ed25519_signature(&self, input: &[u8], signature: &[u8], address: &[u8]) -> bool {
+        let input_ptr = input.as_ptr();
+        let signature_ptr = signature.as_ptr();
+        let address_ptr = address.as_ptr();
+
+        let value;
+        unsafe {
+            value = verify_ed25519_signature(input_ptr, input.len() as u32, signature_ptr, address_ptr);
+        }
+
+        value != 0
+    }
+
+    #[cfg(feature = "secp256k1")]
+    fn verify_secp256k1_signature(&self, input: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+        // the host reads exactly 64 bytes of signature
+        if signature.len() != 64 {
+            panic!("Invalid secp256k1 signature");
+        }
+        let value;
+        unsafe {
+            value = verify_secp256k1_signature(input.as_ptr(), input.len() as u32, signature.as_ptr(), public_key.as_ptr(), public_key.len() as u32);
+        }
+
+        value != 0
+    }
+
+    fn supports(&self, feature: HostFeature) -> bool {
+        feature.is_in_base_interface() 
+            || (cfg!(feature = "log-batch") && feature == HostFeature::LogBatch)
+            || (cfg!(feature = "fee-context") && feature == HostFeature::FeeContext)
+            || (cfg!(feature = "gas-remaining") && feature == HostFeature::GasRemaining)
+            || (cfg!(feature = "random-bytes") && feature == HostFeature::RandomBytes)
+            || (cfg!(feature = "secp256k1") && feature == HostFeature::Secp256k1)
+    }
+}
@@ -6,63 +6,131 @@
 //! Internal functions that interacts within this transaction context. For example, setting data to receipts,
 //! calling other contracts, transfer to other account.
 
-use pchain_types::{cryptography::PublicAddress, serialization::{Serializable, Deserializable}, blockchain::Log, runtime::CallInput};
+use pchain_types::{cryptography::PublicAddress, serialization::Deserializable, runtime::CallInput};
 
-use crate::imports;
+use crate::{host, error::ContractError, method::OutputEnvelope};
 
 /// `return_value` places `value` in the receipt of a transaction.
-pub fn return_value(value: Vec<u8>) {    
-    let value_ptr = value.as_ptr();
-    let value_len = value.len() as u32;
-    unsafe {           
-        imports::return_value(value_ptr, value_len);
-    }
+pub fn return_value(value: Vec<u8>) {
+    host::current().return_value(&value)
 }
 
 /// `log` saves message with a topic to receipt of a transaction.
 pub fn log(topic: &[u8], value: &[u8]) {
-    let event = Log { 
-        topic: topic.to_vec(), 
-        value: value.to_vec()
-    };
-    let serialized_event = Log::serialize(&event);
-
-    let log_ptr= serialized_event.as_ptr();
-    let log_len = serialized_event.len() as u32;
+    host::current().log(topic, value)
+}
 
-    unsafe {
-        imports::_log(log_ptr, log_len);
-    }
+/// `log_batch` saves the logs to receipt of a transaction in order. With the feature `log-batch`, they are emitted 
+/// with one host call instead of one call per log, which is cheaper for a large number of logs, e.g. in batch mints.
+pub fn log_batch(logs: &[(&[u8], &[u8])]) {
+    host::current().log_batch(logs)
 }
 
 /// A call to contract. The caller should already know the data type of return value from the function call.
 /// It returns Option of T where T is return value from the function. 
 /// If data type T is different from the actual return value type of the function, None is returned.
+/// 
+/// Like the other typed calls, it reads the return value as plain Borsh, so methods of a contract with 
+/// `return_envelope` are called by [try_call_envelope].
 pub fn call<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Option<T> {
     let return_value = call_untyped(address, method_name, arguments, value)?;
     T::deserialize(&mut return_value.as_slice()).map_or(None, |value| Some(value))
 }
 
+/// A call to contract that aborts if the return value is not of type T, e.g. because the interface of the called 
+/// contract differs from what the caller expects. It panics with "return type mismatch calling <address>::<method> 
+/// (got N bytes)", where the address is in hex. It returns None only if the method returns no value.
+pub fn call_strict<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Option<T> {
+    match try_call(address, method_name, arguments, value) {
+        Ok(value) => Some(value),
+        Err(CallError::NoReturnValue) => None,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+/// A call to contract that returns a [CallError] if the method returns no value, or a value which is not of type T.
+pub fn try_call<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Result<T, CallError> {
+    let return_value = call_untyped(address, method_name, arguments, value).ok_or(CallError::NoReturnValue)?;
+    T::try_from_slice(&return_value).map_err(|_| CallError::ReturnTypeMismatch { 
+        address, 
+        method: method_name.to_string(), 
+        len: return_value.len() 
+    })
+}
+
+/// A call to contract whose method returns `Result<T, ContractError>`, as [call_result], but the error returned by
+/// the method is [CallError::Failed], so that it is distinguished from a return value that is missing or cannot be
+/// decoded. Functions generated by `use_contract` for such methods call this function.
+pub fn try_call_result<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Result<T, CallError> {
+    try_call::<Result<T, ContractError>>(address, method_name, arguments, value)?.map_err(CallError::Failed)
+}
+
+/// A call to a method of a contract with `return_envelope`, as [try_call], but the return value is decoded as an 
+/// [OutputEnvelope](crate::method::OutputEnvelope) of any version before its payload is deserialized. A return value
+/// that is not an envelope is [CallError::ReturnTypeMismatch].
+pub fn try_call_envelope<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Result<T, CallError> {
+    let return_value = call_untyped(address, method_name, arguments, value).ok_or(CallError::NoReturnValue)?;
+    let mismatch = || CallError::ReturnTypeMismatch { address, method: method_name.to_string(), len: return_value.len() };
+    let envelope = OutputEnvelope::decode(&return_value).map_err(|_| mismatch())?;
+    T::try_from_slice(envelope.payload).map_err(|_| mismatch())
+}
+
+/// Error of [try_call], [try_call_result] and [try_call_envelope].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallError {
+    /// The called method returned no value.
+    NoReturnValue,
+    /// The return value cannot be deserialized as the expected type. All bytes of the return value must be consumed.
+    ReturnTypeMismatch { address: PublicAddress, method: String, len: usize },
+    /// The called method returned an error in its error envelope.
+    Failed(ContractError),
+}
+
+impl std::fmt::Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallError::NoReturnValue => write!(f, "no return value"),
+            CallError::ReturnTypeMismatch { address, method, len } => {
+                let address: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+                write!(f, "return type mismatch calling {}::{} (got {} bytes)", address, method, len)
+            },
+            CallError::Failed(error) => write!(f, "call failed with {}", error),
+        }
+    }
+}
+
+/// Converts the error into the error returned by the called method, or an error with the code
+/// [ContractError::NO_RETURN_VALUE] or [ContractError::INVALID_RETURN_VALUE], as returned by [call_result], so that a
+/// method returning `Result<T, ContractError>` can propagate it by `?`.
+impl From<CallError> for ContractError {
+    fn from(error: CallError) -> Self {
+        match error {
+            CallError::NoReturnValue => ContractError::new(ContractError::NO_RETURN_VALUE, error.to_string()),
+            CallError::ReturnTypeMismatch { .. } => ContractError::new(ContractError::INVALID_RETURN_VALUE, error.to_string()),
+            CallError::Failed(error) => error,
+        }
+    }
+}
+
+/// A call to contract whose method returns `Result<T, ContractError>`. The error envelope returned by the method is 
+/// decoded, so that its error is returned as is. If the method returns no value or a value of another type, the error 
+/// has the code [ContractError::NO_RETURN_VALUE] or [ContractError::INVALID_RETURN_VALUE].
+pub fn call_result<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Result<T, ContractError> {
+    let return_value = call_untyped(address, method_name, arguments, value)
+        .ok_or_else(|| ContractError::new(ContractError::NO_RETURN_VALUE, format!("{} returned no value", method_name)))?;
+    <Result<T, ContractError> as borsh::BorshDeserialize>::deserialize(&mut return_value.as_slice())
+        .unwrap_or_else(|_| Err(ContractError::new(ContractError::INVALID_RETURN_VALUE, format!("{} returned an invalid value", method_name))))
+}
+
 /// A call to contract, with vector of bytes as return type.
 /// It returns Option of Vec of bytes. Interpretation on the bytes depends on caller
 pub fn call_untyped(contract_address: PublicAddress, method_name: &str, arguments: Vec<u8>, value: u64) -> Option<Vec<u8>> {
-    let call_command = pchain_types::blockchain::Command::Call( CallInput{ 
+    let return_value = host::current().call(CallInput{ 
         target: contract_address, 
         method: method_name.to_string(), 
         arguments: <Vec<Vec<u8>>>::deserialize(&arguments).ok(), 
         amount: if value > 0 { Some(value) } else { None }
-    }).serialize();
-
-    let call_ptr: *const u8 = call_command.as_ptr();
-    let call_len = call_command.len() as u32;
-
-    let mut return_val_ptr: u32 = 0;
-    let return_val_ptr_ptr = &mut return_val_ptr;
-
-    let return_value = unsafe {
-        let return_val_len = imports::call(call_ptr, call_len, return_val_ptr_ptr);
-        Vec::<u8>::from_raw_parts(return_val_ptr as *mut u8, return_val_len as usize, return_val_len as usize)
-    };
+    });
 
     if return_value.is_empty() { 
         None
@@ -73,10 +141,19 @@ pub fn call_untyped(contract_address: PublicAddress, method_name: &str, argument
 
 /// transfer balance amount to another address. 
 pub fn transfer(recipient: PublicAddress, amount: u64) {
-    let mut transfer_bytes = Vec::new();
-    transfer_bytes.append(&mut recipient.to_vec());
-    transfer_bytes.append(&mut amount.to_le_bytes().to_vec());
+    host::current().transfer(recipient, amount)
+}
+
+/// A call to a view method of another contract, i.e. a call without attached amount, which replaces `view` 
+/// (`raw_view`) of the legacy SDK. The runtime has no read-only call, so changes made by the called method are 
+/// committed as in [call]. Only methods that do not change state, e.g. with attribute `view`, should be called.
+pub fn call_view<T: borsh::BorshDeserialize>(address: PublicAddress, method_name: &str, arguments: Vec<u8>) -> Option<T> {
+    call(address, method_name, arguments, 0)
+}
 
-    let transfer_ptr = transfer_bytes.as_ptr();
-    unsafe { imports::transfer(transfer_ptr) }
+/// Transfer balance amount to another address, and return the balance of this contract after the transfer, which 
+/// replaces `pay` (`raw_pay`) of the legacy SDK.
+pub fn pay(recipient: PublicAddress, amount: u64) -> u64 {
+    transfer(recipient, amount);
+    host::current().balance()
 }
\ No newline at end of file
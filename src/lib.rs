@@ -6,6 +6,9 @@
 //! The ParallelChain Mainnet Contract SDK (pchain-sdk) provides Rust structs, functions, types, and macros that 
 //! aid with the development of smart contracts executable in WebAssembly (WASM) engines implementing 
 //! the ParallelChain Mainnet Contract Binary Interface Subprotocol.
+//! 
+//! On targets other than WASM, a contract crate compiles as a plain Rust library that calls the [Host](host::Host) 
+//! set by [host::set_host] instead of the imported functions.
 
 #![cfg_attr(
     feature = "cargo-clippy",
@@ -14,33 +17,122 @@
     )
 )]
 
+pub mod address_book;
+
+pub mod amount;
+pub use amount::TokenAmount;
+
 pub mod blockchain;
 
+pub mod bridge;
+
+pub mod commit_reveal;
+
 pub mod crypto;
 
+pub mod error;
+pub use error::ContractError;
+
+pub mod event;
+pub use event::Event;
+
 mod exports;
 
+pub mod fee_collector;
+
+pub mod flash_loan;
+
+pub mod gc;
+
+pub mod governance;
+
+pub mod host;
+
+pub mod id_sequence;
+
+#[cfg(target_arch = "wasm32")]
 mod imports;
 
 pub mod internal;
 pub use internal::*;
 
+pub mod lifecycle;
+
+pub mod memo;
+pub use memo::Memo;
+
+pub mod merkle_distributor;
+
 pub mod method;
-pub use method::{ContractMethodInput, ContractMethodOutput};
+pub use method::{ContractMethodInput, ContractMethodOutput, CallArgs};
+
+pub mod metrics;
 
 pub mod network;
 
+pub mod nonce;
+
+pub mod oracle;
+
+pub mod panic_handler;
+pub use panic_handler::set_panic_handler;
+
+pub mod parameters;
+
+pub mod quota;
+
+pub mod random;
+
+pub mod rate_limit;
+
+#[cfg(all(feature = "receipt", not(target_arch = "wasm32")))]
+pub mod receipt;
+
+pub mod runtime;
+
+pub mod scheduler;
+
+pub mod staking;
+
+pub mod standards;
+
 pub mod storage;
-pub use storage::{Storable, StoragePath, Cacher};
+pub use storage::{Storable, StoragePath, Cacher, LazyOption, ReadOnly, flush_storage};
+
+#[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+pub mod testing;
+
+pub mod token;
+
+#[cfg(all(feature = "trace", not(target_arch = "wasm32")))]
+pub mod trace;
 
 pub mod transaction;
 
+pub mod types;
+
+pub mod validate;
+
 pub mod collections;
 
+// Used by the code generated by the macros.
+#[doc(hidden)]
+pub use borsh;
+
 pub use pchain_sdk_macros::{
     contract,
     contract_methods,
     contract_field,
+    contract_types,
+    contract_config,
+    event,
     call,
+    on_receive,
+    fallback,
+    invariant,
+    fields,
+    view,
     use_contract,
+    CallArgs,
+    Event,
 };
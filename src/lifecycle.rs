@@ -0,0 +1,106 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [decommission], the standard sequence of sunsetting a contract. The runtime cannot delete a contract, so
+//! a decommissioned contract stays deployed, but it holds no balance and rejects all calls.
+//! 
+//! The calls are rejected by the entrypoint generated by `#[contract_methods(decommissionable)]`, which reads the
+//! flag [DECOMMISSIONED_KEY] before dispatching each call. `decommission` panics in a contract without the option,
+//! since its calls would not be rejected.
+//! 
+//! ```no_run
+//! #[contract_methods(decommissionable)]
+//! impl MyContract {
+//!     #[call]
+//!     fn sunset(&mut self, beneficiary: PublicAddress) {
+//!         assert!(pchain_sdk::transaction::calling_account() == self.owner);
+//!         pchain_sdk::lifecycle::decommission(beneficiary);
+//!     }
+//! }
+//! ```
+
+use std::cell::Cell;
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, internal, storage, transaction};
+
+/// Key of the flag of a decommissioned contract in its storage. Contract fields never use this key unless the
+/// contract has 256 fields.
+pub const DECOMMISSIONED_KEY: &[u8] = b"\xffpchain_sdk/decommissioned";
+
+/// Topic of the log emitted by [decommission], which is the last log of the contract.
+pub const DECOMMISSIONED_TOPIC: &[u8] = b"Decommissioned";
+
+/// Value of the log emitted by [decommission], which is also stored as the flag.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DecommissionedEvent {
+    /// The account that called the contract to decommission it.
+    pub calling_account: PublicAddress,
+    /// The account that received the remaining balance.
+    pub beneficiary: PublicAddress,
+    /// The remaining balance transferred to the beneficiary.
+    pub amount: u64,
+    pub block_number: u64,
+}
+
+thread_local! {
+    /// Set by the entrypoint of a decommissionable contract.
+    static REJECTS_CALLS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Check if the contract is decommissioned.
+pub fn is_decommissioned() -> bool {
+    storage::get(DECOMMISSIONED_KEY).is_some()
+}
+
+/// The log emitted when the contract was decommissioned, if it is.
+pub fn decommissioned() -> Option<DecommissionedEvent> {
+    storage::get(DECOMMISSIONED_KEY).and_then(|bytes| DecommissionedEvent::try_from_slice(&bytes).ok())
+}
+
+/// Decommission the contract: transfer the remaining balance, including the amount attached to this call, to the
+/// beneficiary, set the flag so that later calls are rejected, and emit a [DecommissionedEvent]. Returns the amount
+/// transferred. The caller must check that the calling account is allowed to decommission the contract.
+/// 
+/// It panics if the contract is already decommissioned, if the beneficiary is the contract itself, or if the
+/// contract is not declared with `#[contract_methods(decommissionable)]`.
+pub fn decommission(beneficiary: PublicAddress) -> u64 {
+    if !REJECTS_CALLS.with(Cell::get) {
+        panic!("Contract is not decommissionable. Declare it with #[contract_methods(decommissionable)].");
+    }
+    if is_decommissioned() {
+        panic!("Contract is already decommissioned");
+    }
+    if beneficiary == transaction::current_account() {
+        panic!("Beneficiary of decommission cannot be the contract itself");
+    }
+
+    let amount = blockchain::balance();
+    if amount > 0 {
+        internal::transfer(beneficiary, amount);
+    }
+    let event = DecommissionedEvent {
+        calling_account: transaction::calling_account(),
+        beneficiary,
+        amount,
+        block_number: blockchain::block_number(),
+    };
+    let bytes = event.try_to_vec().unwrap();
+    storage::set(DECOMMISSIONED_KEY, &bytes);
+    internal::log(DECOMMISSIONED_TOPIC, &bytes);
+    amount
+}
+
+/// Reject the call if the contract is decommissioned. Called by the entrypoint of a decommissionable contract
+/// before dispatching the call.
+#[doc(hidden)]
+pub fn __require_active() {
+    REJECTS_CALLS.with(|rejects_calls| rejects_calls.set(true));
+    if is_decommissioned() {
+        panic!("Contract is decommissioned");
+    }
+}
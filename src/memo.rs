@@ -0,0 +1,103 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Memo], a reference attached to a call of a contract method, e.g. the deposit reference of an exchange or
+//! the invoice number of a payment processor attached to a token transfer.
+//! 
+//! By convention, the memo is an optional argument after the arguments of the method. Since the arguments after the
+//! declared ones are ignored, any method accepts calls with or without a memo, so the caller attaches it in the same
+//! way to the transfers of every token built with the SDK, and the token only reads it if it cares.
+//! 
+//! ```no_run
+//! // caller
+//! let memo = Memo::from_text("INV-2023-0042").unwrap();
+//! let args = ContractMethodInputBuilder::new().add(to).add(amount).add_memo(&memo).to_call_arguments();
+//! 
+//! // token contract
+//! #[call]
+//! fn transfer(&mut self, to: PublicAddress, amount: u64) {
+//!     // transfer has 2 arguments, so the memo is the third
+//!     if let Some(memo) = pchain_sdk::memo::read(2) {
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::method::ContractMethodInput;
+
+/// Maximum length of a memo in bytes.
+pub const MAX_MEMO_LEN: usize = 256;
+
+/// Error of creating or decoding a [Memo].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoError {
+    /// The memo is longer than [MAX_MEMO_LEN].
+    TooLong { len: usize },
+    /// The argument after the declared arguments is not a memo.
+    Invalid,
+}
+
+impl std::fmt::Display for MemoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoError::TooLong { len } => write!(f, "memo has {} bytes, but at most {} bytes are allowed", len, MAX_MEMO_LEN),
+            MemoError::Invalid => write!(f, "argument is not a memo"),
+        }
+    }
+}
+
+/// `Memo` is a reference of at most [MAX_MEMO_LEN] bytes attached to a call, usually text. It is serialized in the
+/// same way as `Vec<u8>`.
+#[derive(BorshSerialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Memo(Vec<u8>);
+
+impl Memo {
+    pub fn new(bytes: Vec<u8>) -> Result<Self, MemoError> {
+        if bytes.len() > MAX_MEMO_LEN {
+            return Err(MemoError::TooLong { len: bytes.len() })
+        }
+        Ok(Self(bytes))
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, MemoError> {
+        Self::new(text.as_bytes().to_vec())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The memo as text. None if it is not UTF-8.
+    pub fn as_text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.0).ok()
+    }
+}
+
+/// Fails if the memo is longer than [MAX_MEMO_LEN], so that a decoded memo is always valid.
+impl BorshDeserialize for Memo {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        Self::new(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl std::fmt::Display for Memo {
+    /// Formats the memo as text, or in hex if it is not UTF-8.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.as_text() {
+            Some(text) => f.write_str(text),
+            None => self.0.iter().try_for_each(|b| write!(f, "{:02x}", b)),
+        }
+    }
+}
+
+/// The memo of the current call, which is the argument after the `declared_args` arguments of the method. Returns
+/// None if the call has no memo. Panics if the argument is not a valid memo, which aborts the call.
+pub fn read(declared_args: usize) -> Option<Memo> {
+    ContractMethodInput::from_transaction().memo(declared_args)
+        .unwrap_or_else(|e| panic!("Invalid memo: {}", e))
+}
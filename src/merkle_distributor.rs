@@ -0,0 +1,203 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [MerkleDistributor], a contract field that distributes an airdrop committed by a merkle root. Each entry
+//! of the airdrop is a leaf `(index, account, amount)` of the merkle tree, which is built off-chain. The account claims
+//! its entry with a merkle proof, so the contract only stores the root and a bit set of claimed indices.
+//! 
+//! The leaves are hashed by [leaf_hash], and the pairs of nodes are hashed by [node_hash] in sorted order, so a
+//! proof is a list of sibling hashes without their positions.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::FastMap, crypto, internal, method::ContractMethodInputBuilder, ContractError, Storable, StoragePath};
+
+/// Topic of the log emitted by [MerkleDistributor::claim].
+pub const CLAIMED_TOPIC: &[u8] = b"Claimed";
+
+/// Name of the method called on the token contract by [MerkleDistributor::claim], with arguments
+/// `(to: PublicAddress, amount: u64)`, which returns `Result<(), ContractError>`.
+pub const TOKEN_TRANSFER_METHOD: &str = "transfer";
+
+/// Value of the log emitted by [MerkleDistributor::claim].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ClaimedEvent {
+    pub index: u64,
+    pub account: PublicAddress,
+    pub amount: u64,
+}
+
+/// Error of claiming from a [MerkleDistributor].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimError {
+    /// The entry at the index has been claimed.
+    AlreadyClaimed,
+    /// The proof does not prove the entry against the merkle root.
+    InvalidProof,
+    /// The token contract returned an error, or no value, from [TOKEN_TRANSFER_METHOD].
+    TransferFailed(ContractError),
+}
+
+impl std::fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClaimError::AlreadyClaimed => write!(f, "already claimed"),
+            ClaimError::InvalidProof => write!(f, "invalid merkle proof"),
+            ClaimError::TransferFailed(error) => write!(f, "token transfer failed: {}", error),
+        }
+    }
+}
+
+/// The hash of a leaf, i.e. the SHA256 digest of the borsh-serialized `(index, account, amount)`.
+pub fn leaf_hash(index: u64, account: PublicAddress, amount: u64) -> [u8; 32] {
+    let bytes = (index, account, amount).try_to_vec().unwrap();
+    crypto::sha256(bytes).try_into().unwrap()
+}
+
+/// The hash of a parent node, i.e. the SHA256 digest of the concatenation of its children in sorted order.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    crypto::sha256([first.as_slice(), second.as_slice()].concat()).try_into().unwrap()
+}
+
+/// Check if the proof proves the leaf against the merkle root.
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling));
+    &computed == root
+}
+
+/// `MerkleDistributor` is a contract field that stores the merkle root of an airdrop and tracks the claimed entries.
+/// The airdrop is paid in the native balance of this contract, or in a token whose contract implements
+/// [TOKEN_TRANSFER_METHOD] and holds a balance for this contract.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct Airdrop {
+///     distributor: MerkleDistributor,
+/// }
+/// 
+/// #[contract_methods]
+/// impl Airdrop {
+///     #[call]
+///     fn claim(&mut self, index: u64, account: PublicAddress, amount: u64, proof: Vec<[u8; 32]>) {
+///         // permissionless: the amount is always paid to the account of the entry.
+///         self.distributor.claim(index, account, amount, &proof).unwrap();
+///     }
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Merkle Root|P, 0|[u8; 32]|
+/// |Token|P, 1|`Option<PublicAddress>`|
+/// |Claimed Bit Set|P, 2|`FastMap<u64, u64>`|
+/// 
+/// - P: parent key
+/// - The bit set is stored in words of 64 bits. The claim of index `i` is bit `i % 64` of word `i / 64`.
+/// 
+/// The claimed bit set is saved to world state before the amount is paid, so that the token contract or the account
+/// cannot claim the same entry again by calling back into this contract.
+pub struct MerkleDistributor {
+    merkle_root: [u8; 32],
+    token: Option<PublicAddress>,
+    claimed: FastMap<u64, u64>,
+    /// The key of the contract field, which is known once it is loaded from or saved to world state.
+    scope: Option<StoragePath>,
+}
+
+impl MerkleDistributor {
+    /// Instantiate a distributor of the native balance of this contract.
+    pub fn new(merkle_root: [u8; 32]) -> Self {
+        Self { merkle_root, token: None, claimed: FastMap::new(), scope: None }
+    }
+
+    /// Instantiate a distributor of the token of the token contract.
+    pub fn with_token(merkle_root: [u8; 32], token: PublicAddress) -> Self {
+        Self { merkle_root, token: Some(token), claimed: FastMap::new(), scope: None }
+    }
+
+    pub fn merkle_root(&self) -> [u8; 32] {
+        self.merkle_root
+    }
+
+    /// Address of the token contract, or None if the airdrop is paid in the native balance.
+    pub fn token(&self) -> Option<PublicAddress> {
+        self.token
+    }
+
+    /// Check if the entry at the index has been claimed.
+    pub fn is_claimed(&self, index: u64) -> bool {
+        let word = self.claimed.get(&(index / 64)).unwrap_or(0);
+        word & (1 << (index % 64)) != 0
+    }
+
+    /// Claim the entry `(index, account, amount)` with the merkle proof, and pay the amount to the account. It logs
+    /// a [ClaimedEvent]. Nothing is paid if the entry has been claimed or the proof is invalid. If the token contract
+    /// fails the transfer, the entry is left unclaimed.
+    pub fn claim(&mut self, index: u64, account: PublicAddress, amount: u64, proof: &[[u8; 32]]) -> Result<(), ClaimError> {
+        if self.is_claimed(index) {
+            return Err(ClaimError::AlreadyClaimed)
+        }
+        if !verify_proof(&self.merkle_root, leaf_hash(index, account, amount), proof) {
+            return Err(ClaimError::InvalidProof)
+        }
+        self.set_claimed(index, true);
+        // a re-entrant call loads the claimed bit set from world state
+        self.flush();
+
+        match self.token {
+            Some(token) => {
+                let arguments = ContractMethodInputBuilder::new()
+                    .add(account)
+                    .add(amount)
+                    .to_call_arguments();
+                if let Err(error) = internal::call_result::<()>(token, TOKEN_TRANSFER_METHOD, arguments, 0) {
+                    self.set_claimed(index, false);
+                    self.flush();
+                    return Err(ClaimError::TransferFailed(error))
+                }
+            },
+            None => internal::transfer(account, amount)
+        }
+
+        internal::log(CLAIMED_TOPIC, &ClaimedEvent { index, account, amount }.try_to_vec().unwrap());
+        Ok(())
+    }
+
+    fn set_claimed(&mut self, index: u64, claimed: bool) {
+        let word = self.claimed.get(&(index / 64)).unwrap_or(0);
+        let word = if claimed { word | (1 << (index % 64)) } else { word & !(1 << (index % 64)) };
+        self.claimed.insert(&(index / 64), word);
+    }
+
+    /// Save the distributor to world state now, if it is a contract field.
+    fn flush(&mut self) {
+        if let Some(scope) = self.scope.clone() {
+            self.__save_storage(&scope);
+        }
+    }
+}
+
+impl Storable for MerkleDistributor {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            merkle_root: Storable::__load_storage(&field.add(0)),
+            token: Storable::__load_storage(&field.add(1)),
+            claimed: Storable::__load_storage(&field.add(2)),
+            scope: Some(field.clone()),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.merkle_root.__save_storage(&field.add(0));
+        self.token.__save_storage(&field.add(1));
+        self.claimed.__save_storage(&field.add(2));
+        self.scope = Some(field.clone());
+    }
+}
@@ -14,8 +14,9 @@
 //! the creation and consumption of ContractMethodInput in expanded code. 
 
 use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
 
-use crate::transaction;
+use crate::{internal, memo::{Memo, MemoError}, transaction};
 
 /// Input of a contract method in a call, which consists of method name and its borsh-serialized arguments.
 pub struct ContractMethodInput {
@@ -47,6 +48,38 @@ impl ContractMethodInput {
         let bs = args[idx].clone();
         BorshDeserialize::deserialize(&mut bs.as_ref()).unwrap()
     }
+
+    /// Number of items of the collection serialized as the indexed argument, e.g. `Vec<T>`, read from its length 
+    /// prefix without deserializing the items. Returns None if the argument is missing or shorter than the prefix.
+    pub fn argument_len(args: &[Vec<u8>], idx: usize) -> Option<u32> {
+        let prefix = args.get(idx)?.get(..4)?;
+        Some(u32::from_le_bytes(prefix.try_into().unwrap()))
+    }
+
+    /// Deserialize the indexed argument as a list of items, e.g. `Vec<(PublicAddress, u64)>` for batch methods
+    /// such as airdrops. The argument is encoded by [ContractMethodInputBuilder::add_vec_of].
+    /// ### Example
+    /// ```no_run
+    /// let transfers: Vec<(PublicAddress, u64)> = ctx.parse_vec_of(0);
+    /// ```
+    pub fn parse_vec_of<T: BorshDeserialize>(&self, idx: usize) -> Vec<T> {
+        let args = self.get_multiple_arguments();
+        let bs = args.get(idx).unwrap_or_else(|| panic!("Argument {} is missing", idx));
+        BorshDeserialize::deserialize(&mut bs.as_slice())
+            .unwrap_or_else(|_| panic!("Argument {} is not a list of the expected type", idx))
+    }
+
+    /// The [Memo] attached after the `declared_args` arguments of the method by [ContractMethodInputBuilder::add_memo].
+    /// Returns None if there is no argument after them.
+    pub fn memo(&self, declared_args: usize) -> Result<Option<Memo>, MemoError> {
+        let args = <Vec<Vec<u8>>>::try_from_slice(&self.arguments).map_err(|_| MemoError::Invalid)?;
+        args.get(declared_args).map(|bs| {
+            Memo::try_from_slice(bs).map_err(|_| match <Vec<u8>>::try_from_slice(bs) {
+                Ok(bytes) => MemoError::TooLong { len: bytes.len() },
+                Err(_) => MemoError::Invalid,
+            })
+        }).transpose()
+    }
 }
 
 
@@ -77,6 +110,21 @@ impl ContractMethodInputBuilder {
         self.args.push(arg.try_to_vec().unwrap());
         self
     }
+    /// Add a list of items as one argument, which is decoded as `Vec<T>`. Items can be tuples of references, so that 
+    /// the list can be built from existing collections without cloning. E.g.
+    /// ```no_run
+    /// // decoded as Vec<(PublicAddress, u64)>
+    /// args_builder.add_vec_of(recipients.iter().zip(amounts.iter()));
+    /// ```
+    pub fn add_vec_of<T: BorshSerialize>(&mut self, items: impl IntoIterator<Item = T>) -> &mut Self {
+        let items: Vec<T> = items.into_iter().collect();
+        self.args.push(items.try_to_vec().unwrap());
+        self
+    }
+    /// Attach the memo to the call, as the argument after the arguments of the method. It has to be added last.
+    pub fn add_memo(&mut self, memo: &Memo) -> &mut Self {
+        self.add(memo)
+    }
     pub fn to_call_arguments(&self) -> Vec<u8> {
         // use pchain_types for serialization for consistency with runtime
         <Vec<Vec<u8>> as pchain_types::serialization::Serializable>::serialize(&self.args)
@@ -84,17 +132,222 @@ impl ContractMethodInputBuilder {
 }
 
 
+/// The arguments of a contract method as a struct, which can be derived by `#[derive(CallArgs)]`, so that the 
+/// arguments of a call are named fields instead of positions in [ContractMethodInputBuilder].
+pub trait CallArgs {
+    /// Serialize the arguments in the format of [ContractMethodInputBuilder::to_call_arguments].
+    fn into_call_arguments(self) -> Vec<u8>;
+}
+
+
 /// Encapsulates the return value as serialized bytes from contract method. 
-/// None if the contract method does not specify return value.
+/// None if the contract method does not specify return value. The second field is true if the return value is an
+/// error returned by a method declared with `Result`.
 #[derive(BorshSerialize, BorshDeserialize, Default)]
-pub struct ContractMethodOutput(Option<Vec<u8>>);
+pub struct ContractMethodOutput(Option<Vec<u8>>, #[borsh_skip] bool);
 
 impl ContractMethodOutput {
     pub fn set<T: BorshSerialize>(result :&T) -> Self {
-        Self(Some(T::try_to_vec(result).unwrap()))
+        Self(Some(T::try_to_vec(result).unwrap()), false)
+    }
+
+    /// Encode the result of a method returning `Result<T, E>`. The return value is the borsh-serialized `Result`, and
+    /// an error is also logged with the topic [METHOD_ERROR_TOPIC](crate::error::METHOD_ERROR_TOPIC).
+    pub fn set_result<T: BorshSerialize, E: BorshSerialize>(method: &str, result: &Result<T, E>) -> Self {
+        if let Err(error) = result {
+            crate::error::log_method_error(method, error);
+            crate::metrics::record_failure();
+        }
+        Self(Some(result.try_to_vec().unwrap()), result.is_err())
+    }
+
+    /// Check if the output is an error encoded by [ContractMethodOutput::set_result], which aborts a multicall.
+    pub fn is_error(&self) -> bool {
+        self.1
     }
 
     pub fn get(self) -> Option<Vec<u8>> {
         self.0
     }
-}
\ No newline at end of file
+
+    /// Wrap the bytes of a return value that is already serialized.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self(Some(bytes), false)
+    }
+
+    /// Encode the return value in an [OutputEnvelope] of the current version. None if there is no return value, so
+    /// that a method without return value returns nothing with or without the envelope.
+    pub fn to_envelope(self) -> Option<Vec<u8>> {
+        self.0.map(|payload| [vec![OUTPUT_ENVELOPE_VERSION, 0], payload].concat())
+    }
+}
+
+/// Version of the [OutputEnvelope] written by [ContractMethodOutput::to_envelope].
+pub const OUTPUT_ENVELOPE_VERSION: u8 = 1;
+
+/// `OutputEnvelope` is the versioned encoding of the return value of a contract method, which contracts opt into by
+/// `#[contract_methods(return_envelope)]`. Without it, the return value is the borsh-serialized value itself, which 
+/// is referred to as version 0 and has no prefix. The SDK version stamp of a contract records which one it returns.
+/// 
+/// An envelope of version 1 or later is encoded as:
+/// 
+/// |Bytes|Content|
+/// |:---|:---|
+/// |1|version|
+/// |1|header length (H)|
+/// |H|header|
+/// |rest|payload, i.e. the borsh-serialized return value|
+/// 
+/// The header of version 1 is empty. Forward compatibility follows these rules:
+/// 1. A later version may only add fields to the end of the header. 
+/// 2. A decoder skips the part of the header it does not understand, so any decoder of version 1 or later reads the 
+///    payload of any later version.
+/// 3. A change that cannot follow rule 1, e.g. changing the encoding of the payload, must not be made by a new version 
+///    of the envelope, but by a new method or a new calldata version.
+/// 
+/// The return value cannot tell whether it is an envelope, e.g. `[1, 0, ..]` is also the start of some plain Borsh 
+/// values, so callers must know how the called contract returns values. [call](crate::call), 
+/// [try_call](crate::try_call), [call_result](crate::call_result) and the functions generated by `use_contract` read
+/// plain Borsh, and [try_call_envelope](crate::try_call_envelope) reads an envelope.
+/// 
+/// ### Example
+/// ```no_run
+/// let balance: u64 = pchain_sdk::try_call_envelope(address, "balance_of", args, 0)?;
+/// 
+/// // or, with the raw return value
+/// let bytes = pchain_sdk::call_untyped(address, "balance_of", args, 0).unwrap();
+/// let balance = u64::try_from_slice(OutputEnvelope::decode(&bytes)?.payload)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputEnvelope<'a> {
+    pub version: u8,
+    /// Header fields of the version, which are not interpreted by this version of the SDK.
+    pub header: &'a [u8],
+    pub payload: &'a [u8],
+}
+
+/// Error of decoding an [OutputEnvelope].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The bytes are shorter than the version and header.
+    Truncated,
+    /// The version is 0, which has no envelope.
+    InvalidVersion,
+}
+
+impl std::fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvelopeError::Truncated => write!(f, "output envelope is truncated"),
+            EnvelopeError::InvalidVersion => write!(f, "output envelope has version 0"),
+        }
+    }
+}
+
+impl<'a> OutputEnvelope<'a> {
+    /// Decode the envelope of any version from 1, following the rules of forward compatibility.
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, EnvelopeError> {
+        let (version, header_len) = match bytes {
+            [version, header_len, ..] => (*version, *header_len as usize),
+            _ => return Err(EnvelopeError::Truncated)
+        };
+        if version == 0 {
+            return Err(EnvelopeError::InvalidVersion)
+        }
+        if bytes.len() < 2 + header_len {
+            return Err(EnvelopeError::Truncated)
+        }
+        Ok(Self { version, header: &bytes[2..2 + header_len], payload: &bytes[2 + header_len..] })
+    }
+}
+
+
+/// Name of the hidden method generated by `#[contract_methods]`, which returns the callable methods of the contract 
+/// as `Vec<MethodInfo>`.
+pub const METHODS_METHOD: &str = "__methods";
+
+/// Description of a callable method of a contract, returned by the generated method [METHODS_METHOD], so that tools 
+/// and other contracts can discover the capabilities of a contract without its source or ABI.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MethodInfo {
+    /// Name of the method. The method with attribute `on_receive`, which handles plain transfers, has an empty name.
+    pub name: String,
+    /// Number of arguments of a call to the method.
+    pub arity: u32,
+    /// The method accepts an attached amount, i.e. it is not restricted by `max_value = 0`.
+    pub payable: bool,
+    /// The method can only be called by an internal call.
+    pub internal_only: bool,
+    /// The contract is not saved after the method is called, e.g. the method has attribute `view`.
+    pub view: bool,
+}
+
+/// Get the callable methods of a contract by calling its method [METHODS_METHOD]. The call fails if the contract 
+/// does not have the method, e.g. it is built with an older version of the SDK.
+pub fn methods_of(contract: PublicAddress) -> Option<Vec<MethodInfo>> {
+    internal::call(contract, METHODS_METHOD, ContractMethodInputBuilder::new().to_call_arguments(), 0)
+}
+
+/// Name of the method generated for a contract with `#[contract_config]`, which returns the configuration constants 
+/// of the contract as `Vec<(String, String)>` of names and values.
+pub const CONFIG_METHOD: &str = "config";
+
+/// Fallback of the configuration of a contract without `#[contract_config]`, which generates an inherent function 
+/// `__config` that takes precedence over this one.
+#[doc(hidden)]
+pub trait __NoContractConfig {
+    fn __config() -> Option<Vec<(String, String)>> {
+        None
+    }
+}
+
+impl<T> __NoContractConfig for T {}
+
+/// Get the configuration constants of a contract by calling its method [CONFIG_METHOD]. The call fails if the contract 
+/// does not have `#[contract_config]`.
+pub fn config_of(contract: PublicAddress) -> Option<Vec<(String, String)>> {
+    internal::call(contract, CONFIG_METHOD, ContractMethodInputBuilder::new().to_call_arguments(), 0)
+}
+
+/// Name of the method generated by `#[contract_methods(multicall)]`, which calls methods of the contract in order in 
+/// one transaction. Its only argument is `Vec<EncodedCall>`, and it returns `Vec<EncodedResult>` in the same order. 
+/// If any call panics or returns `Err`, the transaction fails and none of the calls take effect.
+pub const MULTICALL_METHOD: &str = "multicall";
+
+/// A call in the argument of [MULTICALL_METHOD], to a method of the contract with attribute `call`. Views, the 
+/// fallback and `multicall` itself cannot be called. The calls are made without attached amount.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncodedCall {
+    pub method_name: String,
+    /// Arguments encoded by [ContractMethodInputBuilder::to_call_arguments].
+    pub arguments: Vec<u8>,
+}
+
+impl EncodedCall {
+    pub fn new(method_name: &str, arguments: Vec<u8>) -> Self {
+        Self { method_name: method_name.to_string(), arguments }
+    }
+}
+
+/// The result of an [EncodedCall] returned by [MULTICALL_METHOD].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncodedResult {
+    /// The borsh-serialized return value, which is not enveloped even if the contract uses `return_envelope`. 
+    /// None if the method has no return value.
+    pub return_value: Option<Vec<u8>>,
+}
+
+impl EncodedResult {
+    /// Deserialize the return value. None if there is no return value or it is not of type `T`.
+    pub fn decode<T: BorshDeserialize>(&self) -> Option<T> {
+        self.return_value.as_ref().and_then(|bytes| T::try_from_slice(bytes).ok())
+    }
+}
+
+/// Call methods of a contract generated with `#[contract_methods(multicall)]` in one transaction, by calling its 
+/// method [MULTICALL_METHOD]. The call fails if any of the calls fails.
+pub fn multicall(contract: PublicAddress, calls: Vec<EncodedCall>) -> Option<Vec<EncodedResult>> {
+    let mut args = ContractMethodInputBuilder::new();
+    args.add(calls);
+    internal::call(contract, MULTICALL_METHOD, args.to_call_arguments(), 0)
+}
@@ -0,0 +1,84 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [CallMetrics], the log emitted for every call of a contract declared with `#[contract_methods(metrics)]`,
+//! so that operators can analyze the usage of the contract from the receipts without logging in every method.
+//! 
+//! The log is emitted by the entrypoint after the method returns. A call that panics is reverted with its logs, so
+//! the log of a failed call is only found for methods returning `Result`, which fail with `Err` without panicking.
+//! 
+//! ```no_run
+//! #[contract_methods(metrics)]
+//! impl MyContract {
+//!     #[call]
+//!     fn deposit(&mut self) { /* ... */ }
+//! }
+//! 
+//! // off-chain
+//! let metrics = CallMetrics::decode(&log.value).unwrap();
+//! if metrics.method_id == pchain_sdk::metrics::method_id("deposit") { /* ... */ }
+//! ```
+
+use std::cell::Cell;
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::{internal, runtime::{self, HostFeature}, transaction};
+
+/// Topic of the log emitted for every call of a contract with `#[contract_methods(metrics)]`.
+pub const METRICS_TOPIC: &[u8] = b"Metrics";
+
+/// Value of the log with topic [METRICS_TOPIC], which takes 13 bytes.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallMetrics {
+    /// Id of the called method by [method_id].
+    pub method_id: u32,
+    /// The gas remaining in the transaction when the call entered the contract, or 0 if the runtime does not provide
+    /// [HostFeature::GasRemaining].
+    pub gas_at_entry: u64,
+    /// False if the method, or any method called by `multicall`, returned `Err`.
+    pub success: bool,
+}
+
+impl CallMetrics {
+    pub fn decode(value: &[u8]) -> Option<Self> {
+        Self::try_from_slice(value).ok()
+    }
+}
+
+/// Id of a method in [CallMetrics], which is the first 4 bytes of the SHA256 hash of the method name in big endian.
+pub fn method_id(method: &str) -> u32 {
+    let hash = pchain_types::cryptography::sha256(method.as_bytes());
+    u32::from_be_bytes(hash[..4].try_into().unwrap())
+}
+
+thread_local! {
+    /// Cleared by a method that returns `Err` during the call.
+    static SUCCESS: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Record that a method returned `Err`. Called when the return value of the method is set.
+pub(crate) fn record_failure() {
+    SUCCESS.with(|success| success.set(false));
+}
+
+/// The gas remaining when the call enters the contract. Called by the entrypoint of a contract with metrics before
+/// dispatching the call.
+#[doc(hidden)]
+pub fn __enter() -> u64 {
+    SUCCESS.with(|success| success.set(true));
+    if runtime::supports(HostFeature::GasRemaining) {
+        transaction::gas_remaining()
+    } else {
+        0
+    }
+}
+
+/// Emit the log of the call. Called by the entrypoint of a contract with metrics after the method returns.
+#[doc(hidden)]
+pub fn __emit(method_id: u32, gas_at_entry: u64) {
+    let metrics = CallMetrics { method_id, gas_at_entry, success: SUCCESS.with(Cell::get) };
+    internal::log(METRICS_TOPIC, &metrics.try_to_vec().unwrap());
+}
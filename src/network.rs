@@ -6,10 +6,12 @@
 //! Defines functions to defer a network command that to be executed after success of this contract call.
 //! This command is considered as part of the contract call. 
 //! i.e. There is no modification on the field commands in the Transaction, and no additional Command Receipt for it.
+//! 
+//! Multiple commands can be validated and deferred together by [NetworkCommands::builder].
 
-use pchain_types::{blockchain::Command, serialization::Serializable, cryptography::PublicAddress, runtime::{CreateDepositInput, SetDepositSettingsInput, TopUpDepositInput, WithdrawDepositInput, StakeDepositInput, UnstakeDepositInput}};
+use pchain_types::{blockchain::Command, cryptography::PublicAddress, runtime::{CreateDepositInput, SetDepositSettingsInput, TopUpDepositInput, WithdrawDepositInput, StakeDepositInput, UnstakeDepositInput}};
 
-use crate::imports;
+use crate::host;
 
 /// Instantiation of Deposit in state.
 /// This execution is deferred to be executed after success of this contract call.
@@ -18,10 +20,8 @@ pub fn defer_create_deposit(
     balance: u64,
     auto_stake_rewards: bool,
 ) {
-    let command = Command::CreateDeposit(CreateDepositInput{ operator, balance, auto_stake_rewards }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_create_deposit(command_ptr, command_len) }
+    let command = Command::CreateDeposit(CreateDepositInput{ operator, balance, auto_stake_rewards });
+    host::current().defer(command)
 }
 
 /// Update settings of an existing Deposit.
@@ -30,10 +30,8 @@ pub fn defer_set_deposit_settings(
     operator: PublicAddress,
     auto_stake_rewards: bool,
 ) {
-    let command = Command::SetDepositSettings( SetDepositSettingsInput{ operator, auto_stake_rewards }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_set_deposit_settings(command_ptr, command_len) }
+    let command = Command::SetDepositSettings( SetDepositSettingsInput{ operator, auto_stake_rewards });
+    host::current().defer(command)
 }
 
 /// Increase balance of an existing Deposit.
@@ -42,10 +40,8 @@ pub fn defer_topup_deposit(
     operator: PublicAddress,
     amount: u64,
 ) {
-    let command = Command::TopUpDeposit( TopUpDepositInput{ operator, amount }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_topup_deposit(command_ptr, command_len) }
+    let command = Command::TopUpDeposit( TopUpDepositInput{ operator, amount });
+    host::current().defer(command)
 }
 
 /// Withdraw balance from an existing Deposit.
@@ -54,10 +50,8 @@ pub fn defer_withdraw_deposit(
     operator: PublicAddress,
     max_amount: u64,
 ) {
-    let command = Command::WithdrawDeposit( WithdrawDepositInput{ operator, max_amount }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_withdraw_deposit(command_ptr, command_len) }
+    let command = Command::WithdrawDeposit( WithdrawDepositInput{ operator, max_amount });
+    host::current().defer(command)
 }
 
 /// Increase stakes to an existing Pool
@@ -66,10 +60,8 @@ pub fn defer_stake_deposit(
     operator: PublicAddress,
     max_amount: u64,
 ) {
-    let command = Command::StakeDeposit( StakeDepositInput{ operator, max_amount }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_stake_deposit(command_ptr, command_len) }
+    let command = Command::StakeDeposit( StakeDepositInput{ operator, max_amount });
+    host::current().defer(command)
 }
 
 /// Remove stakes from an existing Pool.
@@ -78,8 +70,139 @@ pub fn defer_unstake_deposit(
     operator: PublicAddress,
     max_amount: u64,
 ) {
-    let command = Command::UnstakeDeposit( UnstakeDepositInput{ operator, max_amount }).serialize();
-    let command_ptr = command.as_ptr();
-    let command_len = command.len() as u32;
-    unsafe { imports:: defer_unstake_deposit(command_ptr, command_len) }
+    let command = Command::UnstakeDeposit( UnstakeDepositInput{ operator, max_amount });
+    host::current().defer(command)
+}
+
+/// Entry point to build a sequence of network commands to be deferred.
+/// 
+/// # Basic example
+/// ```no_run
+/// let summary = NetworkCommands::builder()
+///     .topup_deposit(operator, 1_000)
+///     .stake_deposit(operator, 1_000)
+///     .submit()
+///     .unwrap();
+/// pchain_sdk::log("network_commands".as_bytes(), summary.to_string().as_bytes());
+/// ```
+pub struct NetworkCommands;
+
+impl NetworkCommands {
+    pub fn builder() -> NetworkCommandsBuilder {
+        NetworkCommandsBuilder { commands: vec![] }
+    }
+}
+
+/// Builder to queue network commands, which are validated and then deferred in the order they are added.
+pub struct NetworkCommandsBuilder {
+    commands: Vec<Command>
+}
+
+impl NetworkCommandsBuilder {
+    pub fn create_deposit(&mut self, operator: PublicAddress, balance: u64, auto_stake_rewards: bool) -> &mut Self {
+        self.commands.push(Command::CreateDeposit(CreateDepositInput{ operator, balance, auto_stake_rewards }));
+        self
+    }
+
+    pub fn set_deposit_settings(&mut self, operator: PublicAddress, auto_stake_rewards: bool) -> &mut Self {
+        self.commands.push(Command::SetDepositSettings(SetDepositSettingsInput{ operator, auto_stake_rewards }));
+        self
+    }
+
+    pub fn topup_deposit(&mut self, operator: PublicAddress, amount: u64) -> &mut Self {
+        self.commands.push(Command::TopUpDeposit(TopUpDepositInput{ operator, amount }));
+        self
+    }
+
+    pub fn withdraw_deposit(&mut self, operator: PublicAddress, max_amount: u64) -> &mut Self {
+        self.commands.push(Command::WithdrawDeposit(WithdrawDepositInput{ operator, max_amount }));
+        self
+    }
+
+    pub fn stake_deposit(&mut self, operator: PublicAddress, max_amount: u64) -> &mut Self {
+        self.commands.push(Command::StakeDeposit(StakeDepositInput{ operator, max_amount }));
+        self
+    }
+
+    pub fn unstake_deposit(&mut self, operator: PublicAddress, max_amount: u64) -> &mut Self {
+        self.commands.push(Command::UnstakeDeposit(UnstakeDepositInput{ operator, max_amount }));
+        self
+    }
+
+    /// Validate all the queued commands. Nothing is deferred if any of the commands is invalid.
+    pub fn validate(&self) -> Result<(), NetworkCommandError> {
+        let current_account = crate::transaction::current_account();
+        self.commands.iter().enumerate().try_for_each(|(index, command)| {
+            let (operator, amount) = match command {
+                Command::CreateDeposit(input) => (input.operator, Some(input.balance)),
+                Command::SetDepositSettings(input) => (input.operator, None),
+                Command::TopUpDeposit(input) => (input.operator, Some(input.amount)),
+                Command::WithdrawDeposit(input) => (input.operator, Some(input.max_amount)),
+                Command::StakeDeposit(input) => (input.operator, Some(input.max_amount)),
+                Command::UnstakeDeposit(input) => (input.operator, Some(input.max_amount)),
+                _ => unreachable!()
+            };
+            if operator == [0u8; 32] || operator == current_account {
+                return Err(NetworkCommandError::InvalidOperator { index })
+            }
+            if amount == Some(0) {
+                return Err(NetworkCommandError::ZeroAmount { index })
+            }
+            Ok(())
+        })
+    }
+
+    /// Validate the queued commands and defer them in the order they are added. 
+    /// It returns the summary of the deferred commands, which can be used for logging.
+    pub fn submit(&mut self) -> Result<NetworkCommandsSummary, NetworkCommandError> {
+        self.validate()?;
+        let commands = std::mem::take(&mut self.commands);
+        commands.iter().for_each(|command| {
+            match command.clone() {
+                Command::CreateDeposit(input) => defer_create_deposit(input.operator, input.balance, input.auto_stake_rewards),
+                Command::SetDepositSettings(input) => defer_set_deposit_settings(input.operator, input.auto_stake_rewards),
+                Command::TopUpDeposit(input) => defer_topup_deposit(input.operator, input.amount),
+                Command::WithdrawDeposit(input) => defer_withdraw_deposit(input.operator, input.max_amount),
+                Command::StakeDeposit(input) => defer_stake_deposit(input.operator, input.max_amount),
+                Command::UnstakeDeposit(input) => defer_unstake_deposit(input.operator, input.max_amount),
+                _ => unreachable!()
+            }
+        });
+        Ok(NetworkCommandsSummary { commands })
+    }
+}
+
+/// Error returned by [NetworkCommandsBuilder] if a queued command is invalid. `index` is the position of the command in the queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkCommandError {
+    /// The operator is the zero address, or the address of this contract.
+    InvalidOperator { index: usize },
+    /// The balance or amount of the command is zero.
+    ZeroAmount { index: usize },
+}
+
+impl std::fmt::Display for NetworkCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkCommandError::InvalidOperator { index } => write!(f, "invalid operator in network command {}", index),
+            NetworkCommandError::ZeroAmount { index } => write!(f, "zero amount in network command {}", index),
+        }
+    }
+}
+
+/// The network commands deferred by [NetworkCommandsBuilder::submit], in the order they are deferred.
+pub struct NetworkCommandsSummary {
+    pub commands: Vec<Command>
+}
+
+impl std::fmt::Display for NetworkCommandsSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, command) in self.commands.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {:?}", index, command)?;
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
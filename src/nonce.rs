@@ -0,0 +1,131 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [NonceManager], a contract field that protects signed messages, e.g. of meta-transactions and permits, 
+//! from being replayed. Each message carries a nonce of its signer (or is identified by its hash), which can be 
+//! consumed only once.
+
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::FastMap, Storable, StoragePath};
+
+/// Error of consuming a nonce or a message hash in [NonceManager].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonceError {
+    /// The nonce is not the next sequential nonce of the address.
+    InvalidNonce { expected: u64 },
+    /// The nonce or message hash has already been consumed.
+    AlreadyUsed,
+}
+
+impl std::fmt::Display for NonceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NonceError::InvalidNonce { expected } => write!(f, "invalid nonce, expected {}", expected),
+            NonceError::AlreadyUsed => write!(f, "nonce already used"),
+        }
+    }
+}
+
+/// `NonceManager` tracks consumed nonces in three independent ways:
+/// 
+/// 1. Sequential nonces: each address has a next nonce, starting from 0. Messages of an address must be consumed in order.
+/// 2. Unordered nonces: any nonce of an address can be consumed once, in any order. Used nonces are stored in a bitmap.
+/// 3. Message hashes: any hash, e.g. of a signed message, can be consumed once.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn execute_signed(&mut self, signer: PublicAddress, nonce: u64, message: Vec<u8>, signature: Vec<u8>) {
+///     // verify the signature over the message and nonce ...
+///     self.nonces.consume(signer, nonce).unwrap();
+///     // ...
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Sequential Nonces|P, 0|`FastMap<PublicAddress, u64>`|
+/// |Unordered Nonce Bitmap|P, 1|`FastMap<(PublicAddress, u64), u64>`|
+/// |Message Hashes|P, 2|`FastMap<[u8; 32], bool>`|
+/// 
+/// - P: parent key
+/// - The bitmap is keyed by address and `nonce / 64`. Bit `nonce % 64` of the value is set if the nonce is used.
+pub struct NonceManager {
+    sequential: FastMap<PublicAddress, u64>,
+    bitmap: FastMap<(PublicAddress, u64), u64>,
+    hashes: FastMap<[u8; 32], bool>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self { sequential: FastMap::new(), bitmap: FastMap::new(), hashes: FastMap::new() }
+    }
+
+    /// The next sequential nonce of the address.
+    pub fn next_nonce(&self, address: PublicAddress) -> u64 {
+        self.sequential.get(&address).unwrap_or(0)
+    }
+
+    /// Consume the sequential nonce of the address. It must be equal to [NonceManager::next_nonce].
+    pub fn consume(&mut self, address: PublicAddress, nonce: u64) -> Result<(), NonceError> {
+        let expected = self.next_nonce(address);
+        if nonce != expected {
+            return Err(NonceError::InvalidNonce { expected })
+        }
+        self.sequential.insert(&address, expected + 1);
+        Ok(())
+    }
+
+    /// Check if the unordered nonce of the address is used.
+    pub fn is_used(&self, address: PublicAddress, nonce: u64) -> bool {
+        let word = self.bitmap.get(&(address, nonce / 64)).unwrap_or(0);
+        word & (1 << (nonce % 64)) != 0
+    }
+
+    /// Consume the unordered nonce of the address.
+    pub fn consume_unordered(&mut self, address: PublicAddress, nonce: u64) -> Result<(), NonceError> {
+        let key = (address, nonce / 64);
+        let word = self.bitmap.get(&key).unwrap_or(0);
+        let bit = 1 << (nonce % 64);
+        if word & bit != 0 {
+            return Err(NonceError::AlreadyUsed)
+        }
+        self.bitmap.insert(&key, word | bit);
+        Ok(())
+    }
+
+    /// Check if the message hash is used.
+    pub fn is_hash_used(&self, hash: [u8; 32]) -> bool {
+        self.hashes.get(&hash).unwrap_or(false)
+    }
+
+    /// Consume the message hash.
+    pub fn consume_hash(&mut self, hash: [u8; 32]) -> Result<(), NonceError> {
+        if self.is_hash_used(hash) {
+            return Err(NonceError::AlreadyUsed)
+        }
+        self.hashes.insert(&hash, true);
+        Ok(())
+    }
+}
+
+impl Storable for NonceManager {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            sequential: Storable::__load_storage(&field.add(0)),
+            bitmap: Storable::__load_storage(&field.add(1)),
+            hashes: Storable::__load_storage(&field.add(2)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.sequential.__save_storage(&field.add(0));
+        self.bitmap.__save_storage(&field.add(1));
+        self.hashes.__save_storage(&field.add(2));
+    }
+}
@@ -0,0 +1,84 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [PriceFeed], the standard format of prices provided by oracle contracts, and helpers for consuming them 
+//! with staleness checks.
+//! 
+//! An oracle contract provides a price feed by a view method named [LATEST_PRICE_METHOD] which takes no arguments and
+//! returns a `PriceFeed`. A consumer can either call it by [latest_price] or [require_fresh], or declare the oracle with
+//! `use_contract`:
+//! 
+//! ```no_run
+//! #[use_contract("Ns9DuNe8aS5QISfCyjEoAcZq20OVr2nKQTKsYGmo_Jw=")]
+//! pub trait PriceOracle {
+//!     fn latest_price() -> pchain_sdk::oracle::PriceFeed;
+//! }
+//! 
+//! let price = price_oracle::latest_price(0).unwrap().require_fresh(600).value;
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, internal, method::ContractMethodInputBuilder};
+
+/// Name of the method of an oracle contract that returns its latest [PriceFeed].
+pub const LATEST_PRICE_METHOD: &str = "latest_price";
+
+/// A price reported by an oracle.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriceFeed {
+    /// The price as an integer, scaled by `10^decimals`.
+    pub value: u128,
+    /// Number of decimal places of `value`.
+    pub decimals: u8,
+    /// Block timestamp at which the price was updated.
+    pub timestamp: u32,
+    /// Identifier of the update, which increases with each update of the price.
+    pub round_id: u64,
+}
+
+impl PriceFeed {
+    /// Number of seconds since the price was updated, relative to the timestamp of the current block.
+    pub fn age(&self) -> u32 {
+        blockchain::timestamp().saturating_sub(self.timestamp)
+    }
+
+    /// Check if the price was updated no more than `max_age` seconds ago. A price with a timestamp later than
+    /// the current block is considered fresh.
+    pub fn is_fresh(&self, max_age: u32) -> bool {
+        self.age() <= max_age
+    }
+
+    /// Returns the price if it is fresh. It panics otherwise, which aborts the call instead of using a stale price.
+    pub fn require_fresh(self, max_age: u32) -> Self {
+        if !self.is_fresh(max_age) {
+            panic!("Price feed is stale: updated {} seconds ago, max age is {} seconds", self.age(), max_age);
+        }
+        self
+    }
+
+    /// The price scaled to `decimals` decimal places, truncating extra digits. Returns None if it overflows.
+    pub fn scaled_to(&self, decimals: u8) -> Option<u128> {
+        if decimals >= self.decimals {
+            10u128.checked_pow((decimals - self.decimals) as u32)?.checked_mul(self.value)
+        } else {
+            Some(self.value / 10u128.checked_pow((self.decimals - decimals) as u32)?)
+        }
+    }
+}
+
+/// Get the latest price from the oracle contract. Returns None if the oracle does not return a `PriceFeed`.
+pub fn latest_price(oracle: PublicAddress) -> Option<PriceFeed> {
+    internal::call(oracle, LATEST_PRICE_METHOD, ContractMethodInputBuilder::new().to_call_arguments(), 0)
+}
+
+/// Get the latest price from the oracle contract, which must have been updated no more than `max_age` seconds ago.
+/// It panics if the oracle does not return a price or if the price is stale.
+pub fn require_fresh(oracle: PublicAddress, max_age: u32) -> PriceFeed {
+    latest_price(oracle)
+        .expect("Oracle does not return a price feed")
+        .require_fresh(max_age)
+}
@@ -0,0 +1,53 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the hook for customizing what a contract does when it panics, before the call traps. 
+//! 
+//! By default, the entrypoint generated by `#[contract_methods]` installs a handler that does nothing in release builds 
+//! for WASM, so that a panic aborts the call without formatting its message. A contract can instead log the reason of 
+//! the abort in the receipt of the failed transaction, e.g. by [log_panic]:
+//! 
+//! ```no_run
+//! #[call]
+//! fn withdraw(&mut self, amount: u64) {
+//!     pchain_sdk::set_panic_handler(pchain_sdk::panic_handler::log_panic);
+//!     // ...
+//! }
+//! ```
+
+use std::panic::PanicHookInfo;
+
+use crate::{internal, transaction};
+
+/// Topic of the log emitted by [log_panic].
+pub const PANIC_TOPIC: &[u8] = b"Panic";
+
+/// Call the handler when the contract panics, before the call traps. It replaces the handler installed by the 
+/// entrypoint or by a previous call.
+pub fn set_panic_handler(handler: fn(&PanicHookInfo)) {
+    std::panic::set_hook(Box::new(handler));
+}
+
+/// A panic handler that emits a log with the topic [PANIC_TOPIC] and the value `"<method>: <message> at <file>:<line>"`, 
+/// like `require!` does for failed checks.
+pub fn log_panic(info: &PanicHookInfo) {
+    let message = match (info.payload().downcast_ref::<&str>(), info.payload().downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => String::new()
+    };
+    let location = info.location().map(|l| format!(" at {}:{}", l.file(), l.line())).unwrap_or_default();
+    internal::log(PANIC_TOPIC, format!("{}: {}{}", transaction::method(), message, location).as_bytes());
+}
+
+/// A panic handler that does nothing, so that the call traps without formatting the message.
+pub fn abort_silently(_info: &PanicHookInfo) {}
+
+/// Install the default handler, [abort_silently], in release builds for WASM. Called by the generated entrypoint.
+#[doc(hidden)]
+pub fn __install_default() {
+    #[cfg(all(target_arch = "wasm32", not(debug_assertions)))]
+    set_panic_handler(abort_silently);
+}
@@ -0,0 +1,254 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Parameters], a contract field that stores named configuration values, e.g. fee rates or limits, whose
+//! updates are staged with an activation timestamp instead of taking effect immediately. Users can see an update
+//! (from its log) before it applies, which gives the guarantees of a timelock without a separate timelock contract.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{IterableMap, Iterable}, internal, transaction, Storable, StoragePath};
+
+/// Topic of the log emitted when an update is staged.
+pub const PARAMETER_STAGED_TOPIC: &[u8] = b"ParameterStaged";
+
+/// Topic of the log emitted when a staged update is cancelled.
+pub const PARAMETER_CANCELLED_TOPIC: &[u8] = b"ParameterCancelled";
+
+/// Topic of the log emitted when a staged update is applied.
+pub const PARAMETER_APPLIED_TOPIC: &[u8] = b"ParameterApplied";
+
+/// Value of the logs emitted by [Parameters].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ParameterEvent {
+    pub name: String,
+    /// The Borsh-serialized value.
+    pub value: Vec<u8>,
+    pub activation_timestamp: u32,
+}
+
+/// An update of a parameter staged in [Parameters].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingParameter {
+    /// The Borsh-serialized value.
+    pub value: Vec<u8>,
+    /// The update applies when the block timestamp is at least this value.
+    pub activation_timestamp: u32,
+}
+
+impl PendingParameter {
+    /// Check if the update applies in the block of this transaction.
+    pub fn is_active(&self) -> bool {
+        blockchain::timestamp() >= self.activation_timestamp
+    }
+}
+
+impl Iterable for PendingParameter {}
+
+/// Error of updating [Parameters].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParametersError {
+    /// The calling account is not the admin.
+    NotAdmin,
+    /// The activation timestamp is earlier than the block timestamp plus the minimum delay.
+    DelayTooShort,
+    /// The parameter already has a value, so it can only be updated by staging.
+    AlreadyInitialized,
+    /// No update is staged for the parameter.
+    NotFound,
+    /// The staged update is active, so it can no longer be cancelled.
+    AlreadyActive,
+}
+
+impl std::fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParametersError::NotAdmin => write!(f, "caller is not the admin of the parameters"),
+            ParametersError::DelayTooShort => write!(f, "activation timestamp is earlier than the minimum delay"),
+            ParametersError::AlreadyInitialized => write!(f, "parameter is already initialized"),
+            ParametersError::NotFound => write!(f, "no update is staged for the parameter"),
+            ParametersError::AlreadyActive => write!(f, "staged update is already active"),
+        }
+    }
+}
+
+/// `Parameters` is a contract field that stores named parameters, their staged updates, the admin who stages
+/// updates and the minimum delay between staging an update and its activation. Values are Borsh-serialized, so a
+/// parameter can be of any Borsh type, which must be the same in every read and update.
+/// 
+/// A staged update is applied lazily: [Parameters::get] returns the staged value once it is active, and stores it
+/// as the current value, so no transaction is needed at the activation time. [Parameters::peek] returns the same
+/// value without storing it, e.g. in views. There is at most one staged update per parameter; staging another one
+/// replaces it.
+/// 
+/// ### Example
+/// ```no_run
+/// const FEE_RATE: &str = "fee_rate";
+/// 
+/// #[call]
+/// fn set_fee_rate(&mut self, fee_rate: u64) {
+///     // active after one day
+///     let activation = pchain_sdk::blockchain::timestamp() + 86_400;
+///     self.parameters.stage(FEE_RATE, &fee_rate, activation).unwrap();
+/// }
+/// 
+/// #[call]
+/// fn swap(&mut self, amount: u64) {
+///     let fee_rate: u64 = self.parameters.get(FEE_RATE).unwrap();
+///     // ...
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Admin|P, 0|PublicAddress|
+/// |Minimum Delay|P, 1|u32|
+/// |Values|P, 2|`IterableMap<String, Vec<u8>>`|
+/// |Staged Updates|P, 3|`IterableMap<String, PendingParameter>`|
+/// 
+/// - P: parent key
+pub struct Parameters {
+    admin: PublicAddress,
+    min_delay: u32,
+    values: IterableMap<String, Vec<u8>>,
+    pending: IterableMap<String, PendingParameter>,
+}
+
+impl Parameters {
+    /// Instantiate parameters without values. Updates are staged by `admin`, and apply at least `min_delay`
+    /// seconds after they are staged.
+    pub fn new(admin: PublicAddress, min_delay: u32) -> Self {
+        Self { admin, min_delay, values: IterableMap::new(), pending: IterableMap::new() }
+    }
+
+    pub fn admin(&self) -> PublicAddress {
+        self.admin
+    }
+
+    /// The minimum number of seconds between staging an update and its activation.
+    pub fn min_delay(&self) -> u32 {
+        self.min_delay
+    }
+
+    /// Transfer the admin role. Only the admin can call it. It takes effect immediately.
+    pub fn set_admin(&mut self, admin: PublicAddress) -> Result<(), ParametersError> {
+        self.check_admin()?;
+        self.admin = admin;
+        Ok(())
+    }
+
+    /// Set the initial value of a parameter without delay, e.g. in the constructor of the contract. Only the admin
+    /// can call it, and only if the parameter has no value.
+    pub fn initialize<T: BorshSerialize>(&mut self, name: &str, value: &T) -> Result<(), ParametersError> {
+        self.check_admin()?;
+        let name = name.to_string();
+        if self.values.get(&name).is_some() {
+            return Err(ParametersError::AlreadyInitialized)
+        }
+        self.values.insert(&name, value.try_to_vec().unwrap());
+        Ok(())
+    }
+
+    /// Stage an update of the parameter, which applies from `activation_timestamp`. Only the admin can call it.
+    /// The activation timestamp must be at least the minimum delay after the block timestamp. It replaces the
+    /// staged update of the parameter, if any, unless that update is already active.
+    pub fn stage<T: BorshSerialize>(&mut self, name: &str, value: &T, activation_timestamp: u32) -> Result<(), ParametersError> {
+        self.check_admin()?;
+        if (activation_timestamp as u64) < blockchain::timestamp() as u64 + self.min_delay as u64 {
+            return Err(ParametersError::DelayTooShort)
+        }
+        // an active update is applied rather than replaced, since readers already see its value.
+        let name_key = name.to_string();
+        if let Some(active) = self.pending.get(&name_key).filter(PendingParameter::is_active) {
+            self.apply(&name_key, active);
+        }
+        let pending = PendingParameter { value: value.try_to_vec().unwrap(), activation_timestamp };
+        Self::emit(PARAMETER_STAGED_TOPIC, name, &pending);
+        self.pending.insert(&name_key, pending);
+        Ok(())
+    }
+
+    /// Cancel the staged update of the parameter. Only the admin can call it. An active update cannot be cancelled,
+    /// even if it is not yet applied, since readers already see its value.
+    pub fn cancel(&mut self, name: &str) -> Result<(), ParametersError> {
+        self.check_admin()?;
+        let name = name.to_string();
+        let pending = self.pending.get(&name).ok_or(ParametersError::NotFound)?;
+        if pending.is_active() {
+            return Err(ParametersError::AlreadyActive)
+        }
+        Self::emit(PARAMETER_CANCELLED_TOPIC, &name, &pending);
+        self.pending.remove(&name);
+        Ok(())
+    }
+
+    /// Get the value of the parameter, applying the staged update if it is active. It panics if the value cannot
+    /// be deserialized into `T`.
+    pub fn get<T: BorshDeserialize>(&mut self, name: &str) -> Option<T> {
+        let name = name.to_string();
+        if let Some(pending) = self.pending.get(&name).filter(PendingParameter::is_active) {
+            self.apply(&name, pending);
+        }
+        self.values.get(&name).map(|value| Self::deserialize(&name, &value))
+    }
+
+    /// Get the value of the parameter, including the staged update if it is active, without applying the update.
+    /// It panics if the value cannot be deserialized into `T`.
+    pub fn peek<T: BorshDeserialize>(&self, name: &str) -> Option<T> {
+        let name = name.to_string();
+        match self.pending.get(&name).filter(PendingParameter::is_active) {
+            Some(pending) => Some(Self::deserialize(&name, &pending.value)),
+            None => self.values.get(&name).map(|value| Self::deserialize(&name, &value)),
+        }
+    }
+
+    /// The staged update of the parameter, which may already be active.
+    pub fn pending(&self, name: &str) -> Option<PendingParameter> {
+        self.pending.get(&name.to_string())
+    }
+
+    fn apply(&mut self, name: &String, pending: PendingParameter) {
+        Self::emit(PARAMETER_APPLIED_TOPIC, name, &pending);
+        self.values.insert(name, pending.value);
+        self.pending.remove(name);
+    }
+
+    fn emit(topic: &[u8], name: &str, pending: &PendingParameter) {
+        let event = ParameterEvent { name: name.to_string(), value: pending.value.clone(), activation_timestamp: pending.activation_timestamp };
+        internal::log(topic, &event.try_to_vec().unwrap());
+    }
+
+    fn deserialize<T: BorshDeserialize>(name: &str, value: &[u8]) -> T {
+        T::try_from_slice(value).unwrap_or_else(|_| panic!("Parameter {} cannot be deserialized into the requested type", name))
+    }
+
+    fn check_admin(&self) -> Result<(), ParametersError> {
+        if transaction::calling_account() != self.admin {
+            return Err(ParametersError::NotAdmin)
+        }
+        Ok(())
+    }
+}
+
+impl Storable for Parameters {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            admin: Storable::__load_storage(&field.add(0)),
+            min_delay: Storable::__load_storage(&field.add(1)),
+            values: Storable::__load_storage(&field.add(2)),
+            pending: Storable::__load_storage(&field.add(3)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.admin.__save_storage(&field.add(0));
+        self.min_delay.__save_storage(&field.add(1));
+        self.values.__save_storage(&field.add(2));
+        self.pending.__save_storage(&field.add(3));
+    }
+}
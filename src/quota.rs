@@ -0,0 +1,144 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [StorageQuota], a contract field that limits the bytes of contract storage attributable to each account,
+//! so that a single account cannot bloat the state of a shared contract, e.g. a registry or a message board.
+
+use borsh::BorshSerialize;
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{collections::{FastMap, MapKey}, Storable, StoragePath};
+
+/// Error of charging a [StorageQuota].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaError {
+    /// The bytes would exceed the limit of the account.
+    Exceeded { limit: u64, usage: u64, requested: u64 },
+}
+
+impl std::fmt::Display for QuotaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaError::Exceeded { limit, usage, requested } =>
+                write!(f, "storage quota exceeded: {} bytes requested, {} of {} bytes used", requested, usage, limit),
+        }
+    }
+}
+
+/// `StorageQuota` counts the bytes written on behalf of each account against a limit. The contract charges the
+/// account when it inserts data for it, and releases the bytes when the data is removed. The size of an entry of a
+/// map collection is the size of its encoded key and its borsh-serialized value, which approximates the storage it
+/// takes.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn post(&mut self, id: u64, message: String) {
+///     let author = transaction::calling_account();
+///     self.quota.charge_entry(author, &id, &message).unwrap();
+///     self.messages.insert(&id, message);
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Usage|P, 0|`FastMap<PublicAddress, u64>`|
+/// |Default Limit|P, 1|u64|
+/// |Limits|P, 2|`FastMap<PublicAddress, u64>`|
+/// 
+/// - P: parent key
+pub struct StorageQuota {
+    usage: FastMap<PublicAddress, u64>,
+    default_limit: u64,
+    limits: FastMap<PublicAddress, u64>,
+}
+
+impl StorageQuota {
+    /// Instantiate a quota which allows `default_limit` bytes for each account.
+    pub fn new(default_limit: u64) -> Self {
+        Self { usage: FastMap::new(), default_limit, limits: FastMap::new() }
+    }
+
+    /// The bytes charged to the account.
+    pub fn usage(&self, account: PublicAddress) -> u64 {
+        self.usage.get(&account).unwrap_or(0)
+    }
+
+    /// The limit of the account, which is the default limit unless it is set by [StorageQuota::set_limit].
+    pub fn limit(&self, account: PublicAddress) -> u64 {
+        self.limits.get(&account).unwrap_or(self.default_limit)
+    }
+
+    /// The bytes that can still be charged to the account.
+    pub fn remaining(&self, account: PublicAddress) -> u64 {
+        self.limit(account).saturating_sub(self.usage(account))
+    }
+
+    pub fn default_limit(&self) -> u64 {
+        self.default_limit
+    }
+
+    pub fn set_default_limit(&mut self, default_limit: u64) {
+        self.default_limit = default_limit;
+    }
+
+    /// Set the limit of the account, e.g. for a premium account. It can be lower than the current usage, in which
+    /// case nothing more can be charged until enough bytes are released.
+    pub fn set_limit(&mut self, account: PublicAddress, limit: u64) {
+        self.limits.insert(&account, limit);
+    }
+
+    /// Charge `bytes` to the account. Nothing is charged if it would exceed the limit.
+    pub fn charge(&mut self, account: PublicAddress, bytes: u64) -> Result<(), QuotaError> {
+        let usage = self.usage(account);
+        let limit = self.limit(account);
+        match usage.checked_add(bytes) {
+            Some(new_usage) if new_usage <= limit => {
+                self.usage.insert(&account, new_usage);
+                Ok(())
+            },
+            _ => Err(QuotaError::Exceeded { limit, usage, requested: bytes })
+        }
+    }
+
+    /// Release `bytes` charged to the account, e.g. after its data is removed.
+    pub fn release(&mut self, account: PublicAddress, bytes: u64) {
+        let usage = self.usage(account);
+        self.usage.insert(&account, usage.saturating_sub(bytes));
+    }
+
+    /// Charge the size of an entry of a map collection to the account.
+    pub fn charge_entry<K: MapKey, V: BorshSerialize>(&mut self, account: PublicAddress, key: &K, value: &V) -> Result<(), QuotaError> {
+        self.charge(account, Self::entry_size(key, value))
+    }
+
+    /// Release the size of an entry of a map collection from the account.
+    pub fn release_entry<K: MapKey, V: BorshSerialize>(&mut self, account: PublicAddress, key: &K, value: &V) {
+        self.release(account, Self::entry_size(key, value))
+    }
+
+    /// The size of an entry of a map collection, i.e. the size of its encoded key and its borsh-serialized value.
+    pub fn entry_size<K: MapKey, V: BorshSerialize>(key: &K, value: &V) -> u64 {
+        (key.to_map_key().len() + value.try_to_vec().unwrap().len()) as u64
+    }
+}
+
+impl Storable for StorageQuota {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            usage: Storable::__load_storage(&field.add(0)),
+            default_limit: Storable::__load_storage(&field.add(1)),
+            limits: Storable::__load_storage(&field.add(2)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.usage.__save_storage(&field.add(0));
+        self.default_limit.__save_storage(&field.add(1));
+        self.limits.__save_storage(&field.add(2));
+    }
+}
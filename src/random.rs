@@ -0,0 +1,96 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines deterministic pseudo-random selection, e.g. for raffles and matchmaking. The randomness is derived from
+//! the hash of the previous block and a salt given by the contract, so anyone can recompute and audit a selection
+//! from the block and the salt.
+//! 
+//! The hash of the previous block is known before the transaction is executed, and can be influenced by the block
+//! proposer. Do not use it where the outcome is worth manipulating. Combine it with [commit_reveal](crate::commit_reveal)
+//! instead.
+//! 
+//! ### Example
+//! ```no_run
+//! let mut participants: Vec<PublicAddress> = self.participants.to_vec(1000);
+//! random::shuffle_in_place(&mut participants, &self.round.to_le_bytes());
+//! ```
+
+use crate::{blockchain, crypto};
+
+/// `Rng` generates a deterministic sequence of random numbers from a seed. Each block of 32 bytes is the SHA256
+/// digest of the seed and a counter.
+pub struct Rng {
+    seed: [u8; 32],
+    counter: u64,
+    buffer: Vec<u8>,
+}
+
+impl Rng {
+    /// New generator seeded by the hash of the previous block and the salt.
+    pub fn new(salt: &[u8]) -> Self {
+        let seed = crypto::sha256([blockchain::prev_block_hash(), salt.to_vec()].concat());
+        Self::from_seed(seed.try_into().unwrap())
+    }
+
+    /// New generator with the seed, e.g. to reproduce a selection off-chain.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self { seed, counter: 0, buffer: vec![] }
+    }
+
+    /// The next random u64.
+    pub fn next_u64(&mut self) -> u64 {
+        if self.buffer.len() < 8 {
+            let block = crypto::sha256([self.seed.as_slice(), &self.counter.to_le_bytes()].concat());
+            self.counter += 1;
+            self.buffer.extend(block);
+        }
+        let bytes: Vec<u8> = self.buffer.drain(..8).collect();
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    }
+
+    /// A random number uniformly distributed in `0..bound`. Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            panic!("Rng::below requires a positive bound");
+        }
+        // reject the values in the incomplete range at the top, which would bias the result
+        let zone = u64::MAX - (u64::MAX % bound + 1) % bound;
+        loop {
+            let value = self.next_u64();
+            if value <= zone {
+                return value % bound
+            }
+        }
+    }
+}
+
+/// Shuffle the elements with the Fisher-Yates algorithm, using a [Rng] seeded by the previous block and the salt.
+pub fn shuffle_in_place<T>(values: &mut [T], salt: &[u8]) {
+    let mut rng = Rng::new(salt);
+    for i in (1..values.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        values.swap(i, j);
+    }
+}
+
+/// Select `k` distinct numbers from `0..n`, using a [Rng] seeded by the previous block and the salt. The numbers
+/// are in order of selection. Panics if `k` is greater than `n`.
+pub fn sample_without_replacement(n: u64, k: u64, salt: &[u8]) -> Vec<u64> {
+    if k > n {
+        panic!("Cannot sample {} numbers out of {}", k, n);
+    }
+    let mut rng = Rng::new(salt);
+    // Floyd's algorithm, which needs exactly k random numbers
+    let mut selected: Vec<u64> = Vec::with_capacity(k as usize);
+    for j in (n - k)..n {
+        let t = rng.below(j + 1);
+        if selected.contains(&t) {
+            selected.push(j);
+        } else {
+            selected.push(t);
+        }
+    }
+    selected
+}
@@ -0,0 +1,185 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [RateLimiter], a contract field that limits how much can be done in a window of time, globally or per 
+//! account, and pauses itself like a circuit breaker after the limit is breached too many times. It is useful for 
+//! e.g. withdrawals from a bridge or a faucet.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{FastMap, Insertable}, Storable, StoragePath};
+
+/// The usage counted in the current window of a [RateLimiter].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Default)]
+struct WindowCounter {
+    /// Index of the window, which is the block timestamp divided by the window length.
+    window: u64,
+    /// The usage counted in the window.
+    count: u64,
+}
+
+impl Insertable for WindowCounter {}
+
+/// `RateLimiter` is a contract field that counts usage in fixed windows of block timestamp.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn withdraw(&mut self, amount: u64) -> bool {
+///     self.rate_limiter.require_not_paused();
+///     // at most 1,000,000 per account per hour
+///     if !self.rate_limiter.check_and_add_for(calling_account(), amount, 1_000_000, 3600) {
+///         // the breach is recorded only if the call does not panic
+///         return false;
+///     }
+///     // ...
+///     true
+/// }
+/// ```
+/// 
+/// A limit that is exceeded is a breach. After `breach_threshold` breaches, the rate limiter is paused, and all 
+/// checks fail until [RateLimiter::resume] is called, e.g. by the owner of the contract.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Counters|P, 0|`FastMap<Option<PublicAddress>, WindowCounter>`|
+/// |Breach Threshold|P, 1|u32|
+/// |Breaches|P, 2|u32|
+/// |Paused|P, 3|bool|
+/// 
+/// - P: parent key
+/// - The counter of the global limit is keyed by `None`.
+pub struct RateLimiter {
+    counters: FastMap<Option<PublicAddress>, WindowCounter>,
+    breach_threshold: u32,
+    breaches: u32,
+    paused: bool,
+}
+
+impl RateLimiter {
+    /// Instantiate a rate limiter which is paused after `breach_threshold` breaches. It is never paused
+    /// automatically if `breach_threshold` is 0.
+    pub fn new(breach_threshold: u32) -> Self {
+        Self { counters: FastMap::new(), breach_threshold, breaches: 0, paused: false }
+    }
+
+    /// Count one usage against the global limit of `limit` per `window` seconds. Returns false, without counting, 
+    /// if the limit would be exceeded or if the rate limiter is paused.
+    pub fn check_and_increment(&mut self, limit: u64, window: u32) -> bool {
+        self.check_and_add_inner(None, 1, limit, window)
+    }
+
+    /// Count one usage of `account` against the limit of `limit` per `window` seconds for each account.
+    pub fn check_and_increment_for(&mut self, account: PublicAddress, limit: u64, window: u32) -> bool {
+        self.check_and_add_inner(Some(account), 1, limit, window)
+    }
+
+    /// Count `amount` against the global limit of `limit` per `window` seconds, e.g. the total amount withdrawn.
+    pub fn check_and_add(&mut self, amount: u64, limit: u64, window: u32) -> bool {
+        self.check_and_add_inner(None, amount, limit, window)
+    }
+
+    /// Count `amount` of `account` against the limit of `limit` per `window` seconds for each account.
+    pub fn check_and_add_for(&mut self, account: PublicAddress, amount: u64, limit: u64, window: u32) -> bool {
+        self.check_and_add_inner(Some(account), amount, limit, window)
+    }
+
+    /// The usage counted in the current window of `window` seconds, globally if `account` is None.
+    pub fn usage(&self, account: Option<PublicAddress>, window: u32) -> u64 {
+        let current = Self::current_window(window);
+        self.counters.get(&account)
+            .filter(|counter| counter.window == current)
+            .map_or(0, |counter| counter.count)
+    }
+
+    fn check_and_add_inner(&mut self, account: Option<PublicAddress>, amount: u64, limit: u64, window: u32) -> bool {
+        if self.paused {
+            return false
+        }
+        let current = Self::current_window(window);
+        let mut counter = self.counters.get(&account)
+            .filter(|counter| counter.window == current)
+            .unwrap_or(WindowCounter { window: current, count: 0 });
+
+        match counter.count.checked_add(amount) {
+            Some(count) if count <= limit => {
+                counter.count = count;
+                self.counters.insert(&account, counter);
+                true
+            },
+            _ => {
+                self.breaches = self.breaches.saturating_add(1);
+                if self.breach_threshold > 0 && self.breaches >= self.breach_threshold {
+                    self.paused = true;
+                }
+                false
+            }
+        }
+    }
+
+    fn current_window(window: u32) -> u64 {
+        if window == 0 {
+            panic!("Window of rate limiter must be positive");
+        }
+        (blockchain::timestamp() / window) as u64
+    }
+
+    /// Check if the rate limiter is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Panics if the rate limiter is paused, which aborts the call.
+    pub fn require_not_paused(&self) {
+        if self.paused {
+            panic!("Rate limiter is paused");
+        }
+    }
+
+    /// Pause the rate limiter manually. All checks fail until it is resumed.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume the rate limiter and reset the number of breaches.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.breaches = 0;
+    }
+
+    /// Number of breaches since the rate limiter was created or last resumed.
+    pub fn breaches(&self) -> u32 {
+        self.breaches
+    }
+
+    pub fn breach_threshold(&self) -> u32 {
+        self.breach_threshold
+    }
+
+    pub fn set_breach_threshold(&mut self, breach_threshold: u32) {
+        self.breach_threshold = breach_threshold;
+    }
+}
+
+impl Storable for RateLimiter {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            counters: Storable::__load_storage(&field.add(0)),
+            breach_threshold: Storable::__load_storage(&field.add(1)),
+            breaches: Storable::__load_storage(&field.add(2)),
+            paused: Storable::__load_storage(&field.add(3)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.counters.__save_storage(&field.add(0));
+        self.breach_threshold.__save_storage(&field.add(1));
+        self.breaches.__save_storage(&field.add(2));
+        self.paused.__save_storage(&field.add(3));
+    }
+}
@@ -0,0 +1,114 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines decoders for the results of contract calls found in Transaction Receipts, for off-chain tools written in Rust
+//! (e.g. backend services) that interpret Receipts using the same data types the contract used to produce them. 
+//! 
+//! This module is available with the feature `receipt` on non-WASM targets.
+
+use borsh::BorshDeserialize;
+use pchain_types::blockchain::{CommandReceipt, ExitStatus, Log};
+
+use crate::{ContractError, ContractMethodOutput};
+
+/// Error returned when a Command Receipt cannot be interpreted as the expected result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// The command did not succeed.
+    Unsuccessful(ExitStatus),
+    /// The contract method did not return a value.
+    NoReturnValue,
+    /// The return value or log value cannot be deserialized to the expected data type. All bytes of the value must be 
+    /// consumed.
+    Deserialization(String),
+}
+
+impl std::fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReceiptError::Unsuccessful(exit_status) => write!(f, "command is not successful: {:?}", exit_status),
+            ReceiptError::NoReturnValue => write!(f, "contract method did not return a value"),
+            ReceiptError::Deserialization(e) => write!(f, "deserialization failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
+
+impl ContractMethodOutput {
+    /// Reconstruct the output of the contract method from the Receipt of the Call Command.
+    pub fn from_receipt(receipt: &CommandReceipt) -> Result<Self, ReceiptError> {
+        if receipt.exit_status != ExitStatus::Success {
+            return Err(ReceiptError::Unsuccessful(receipt.exit_status.clone()))
+        }
+        if receipt.return_values.is_empty() {
+            Ok(Self::default())
+        } else {
+            Ok(Self::from_bytes(receipt.return_values.clone()))
+        }
+    }
+
+    /// Deserialize the return value into the data type returned by the contract method.
+    pub fn decode<T: BorshDeserialize>(self) -> Result<T, ReceiptError> {
+        let bytes = self.get().ok_or(ReceiptError::NoReturnValue)?;
+        T::try_from_slice(&bytes).map_err(|e| ReceiptError::Deserialization(e.to_string()))
+    }
+
+    /// Deserialize the error envelope returned by a contract method declared to return `Result<T, ContractError>`. 
+    /// The outer error is about the receipt, and the inner error is the error returned by the method.
+    pub fn decode_result<T: BorshDeserialize>(self) -> Result<Result<T, ContractError>, ReceiptError> {
+        self.decode()
+    }
+}
+
+/// Decode the return value of a contract method from the Receipt of the Call Command.
+/// 
+/// ### Example
+/// ```no_run
+/// let balance: u64 = pchain_sdk::receipt::decode_return_value(&receipt[0])?;
+/// ```
+pub fn decode_return_value<T: BorshDeserialize>(receipt: &CommandReceipt) -> Result<T, ReceiptError> {
+    ContractMethodOutput::from_receipt(receipt)?.decode()
+}
+
+/// Decode the error envelope returned by a contract method declared to return `Result<T, ContractError>` from the 
+/// Receipt of the Call Command. A method that returns `Err` does not revert the transaction, so the command succeeds 
+/// and the error is the inner `Err`.
+/// 
+/// ### Example
+/// ```no_run
+/// match pchain_sdk::receipt::decode_result::<()>(&receipt[0])? {
+///     Ok(()) => {},
+///     Err(e) if e.code == INSUFFICIENT_BALANCE => {},
+///     Err(e) => return Err(e.into()),
+/// }
+/// ```
+pub fn decode_result<T: BorshDeserialize>(receipt: &CommandReceipt) -> Result<Result<T, ContractError>, ReceiptError> {
+    ContractMethodOutput::from_receipt(receipt)?.decode_result()
+}
+
+/// Decode the errors returned by contract methods, i.e. the logs with the topic
+/// [METHOD_ERROR_TOPIC](crate::error::METHOD_ERROR_TOPIC), as the method names and the errors.
+/// 
+/// ### Example
+/// ```no_run
+/// let errors: Vec<(String, ContractError)> = pchain_sdk::receipt::decode_method_errors(&receipt[0])?;
+/// ```
+pub fn decode_method_errors<E: BorshDeserialize>(receipt: &CommandReceipt) -> Result<Vec<(String, E)>, ReceiptError> {
+    decode_logs(receipt, crate::error::METHOD_ERROR_TOPIC)
+}
+
+/// Decode the values of the logs with the given topic.
+pub fn decode_logs<T: BorshDeserialize>(receipt: &CommandReceipt, topic: &[u8]) -> Result<Vec<T>, ReceiptError> {
+    receipt.logs.iter()
+        .filter(|log| log.topic == topic)
+        .map(decode_log_value)
+        .collect()
+}
+
+/// Decode the value of a log.
+pub fn decode_log_value<T: BorshDeserialize>(log: &Log) -> Result<T, ReceiptError> {
+    T::try_from_slice(&log.value).map_err(|e| ReceiptError::Deserialization(e.to_string()))
+}
@@ -0,0 +1,83 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines functions for checking which host functions the runtime provides, so that a contract can fall back, or fail
+//! with a clear message, when it runs on a node that does not provide a host function it could use.
+//! 
+//! [supports] asks the current [Host](crate::host::Host), so the answer is based on the host functions that are 
+//! actually imported, not on a fixed list:
+//! - On WASM targets, a host function outside the base interface is imported only if the SDK feature of its 
+//!   [HostFeature] is enabled, e.g. `fee-context`. A WASM module that imports a function which the node does not 
+//!   provide cannot be instantiated at all, so a contract that runs with the feature enabled runs on a node that 
+//!   provides it, and [supports] returns true. Without the feature, the function is not imported and [supports] 
+//!   returns false, even if the node provides it.
+//! - On other targets, the host set by [set_host](crate::host::set_host), e.g. the `MockHost` of the feature `testing`, 
+//!   reports the features it implements.
+//! 
+//! The runtime has no host function that reports its version or the functions it provides, so a contract cannot find 
+//! out at run time whether a function it does not import would be available.
+//! 
+//! ### Example
+//! ```no_run
+//! // the tip is not known on a runtime without the host functions of the fee context
+//! let tip = if runtime::supports(HostFeature::FeeContext) {
+//!     transaction::tip()
+//! } else {
+//!     0
+//! };
+//! ```
+
+use crate::host;
+
+/// A group of host functions which may not be provided by every runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostFeature {
+    /// [crypto::sha256](crate::crypto::sha256)
+    Sha256,
+    /// [crypto::keccak256](crate::crypto::keccak256)
+    Keccak256,
+    /// [crypto::ripemd](crate::crypto::ripemd)
+    Ripemd160,
+    /// [crypto::verify_ed25519_signature](crate::crypto::verify_ed25519_signature)
+    VerifyEd25519Signature,
+    /// The functions in [network](crate::network) that defer network commands.
+    NetworkCommands,
+    /// Verification of secp256k1 signatures by [crypto::verify_secp256k1_signature](crate::crypto::verify_secp256k1_signature)
+    /// if the feature `secp256k1` of the SDK is enabled.
+    Secp256k1,
+    /// Emitting multiple logs with one host call, used by [log_batch](crate::log_batch) if the feature `log-batch`
+    /// of the SDK is enabled.
+    LogBatch,
+    /// The tip and gas price of the transaction, read by [transaction::tip](crate::transaction::tip) and 
+    /// [transaction::gas_price](crate::transaction::gas_price) if the feature `fee-context` of the SDK is enabled.
+    FeeContext,
+    /// The gas remaining in the transaction, read by [transaction::gas_remaining](crate::transaction::gas_remaining) 
+    /// if the feature `gas-remaining` of the SDK is enabled.
+    GasRemaining,
+    /// The random bytes of the block, read by [blockchain::random_bytes](crate::blockchain::random_bytes) if the 
+    /// feature `random-bytes` of the SDK is enabled.
+    RandomBytes,
+}
+
+impl HostFeature {
+    /// Check if the feature is part of the Contract Binary Interface this SDK is built for, i.e. its host functions are 
+    /// imported without enabling an SDK feature. It does not tell whether the current runtime provides the feature, 
+    /// which is checked by [supports].
+    pub fn is_in_base_interface(&self) -> bool {
+        !matches!(self, HostFeature::Secp256k1 | HostFeature::LogBatch | HostFeature::FeeContext | HostFeature::GasRemaining | HostFeature::RandomBytes)
+    }
+}
+
+/// Check if the runtime provides the host functions of the feature.
+pub fn supports(feature: HostFeature) -> bool {
+    host::current().supports(feature)
+}
+
+/// Panics with a message naming the feature if the runtime does not provide it, which aborts the call.
+pub fn require(feature: HostFeature) {
+    if !supports(feature) {
+        panic!("Host feature {:?} is not supported by this runtime", feature);
+    }
+}
@@ -0,0 +1,197 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [Scheduler], a contract field that stores jobs to be executed once a block height or block timestamp is 
+//! reached. Since contracts cannot run by themselves, due jobs are executed by any account (a 'keeper') that calls 
+//! a contract method wrapping [Scheduler::execute_due], and the keeper is paid a bounty from the contract balance.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{FastMap, Insertable}, internal, transaction, Storable, StoragePath};
+
+/// A job stored in [Scheduler]. It is a contract call to be made once it is due.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct Job {
+    /// Address of the contract to call. It can be the address of this contract.
+    pub target: PublicAddress,
+    /// Name of the method to call.
+    pub method: String,
+    /// Arguments of the call, e.g. constructed by `ContractMethodInputBuilder::to_call_arguments`.
+    pub arguments: Vec<u8>,
+    /// Amount to transfer to the target in the call.
+    pub value: u64,
+    /// The job is due when the block number is at least this value.
+    pub earliest_block: u64,
+    /// The job is due when the block timestamp is at least this value.
+    pub earliest_timestamp: u32,
+}
+
+impl Job {
+    /// Check if the job is due in the block of this transaction.
+    pub fn is_due(&self) -> bool {
+        blockchain::block_number() >= self.earliest_block && blockchain::timestamp() >= self.earliest_timestamp
+    }
+}
+
+impl Insertable for Job {}
+
+/// `Scheduler` is a contract field that stores pending jobs and executes the jobs that are due.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     scheduler: Scheduler,
+/// }
+/// 
+/// #[contract_methods]
+/// impl MyContract {
+///     #[call]
+///     fn execute_due(&mut self, limit: u32, skip: Vec<u64>) -> u32 {
+///         // permissionless: the caller receives the bounty for each executed job.
+///         self.scheduler.execute_due_except(limit, &skip)
+///     }
+/// }
+/// ```
+/// 
+/// ### Bounded Execution
+/// 
+/// Each call to [Scheduler::execute_due] reads at most `limit` jobs, continuing from where the previous call stopped,
+/// and starting over from the earliest pending job after the last one. Jobs are therefore visited in rounds, however
+/// many jobs are pending or not yet due.
+/// 
+/// The call of a job that fails aborts the whole transaction, as any failed internal call, so that nothing is executed
+/// or paid, and the next call would read the same job again. A keeper skips such a job by passing its ID to 
+/// [Scheduler::execute_due_except]. A skipped job stays pending, and is read again in the next round.
+/// 
+/// Executing a job that calls this contract itself is a re-entrant call. The fields of the contract struct are saved 
+/// after the outer call returns, so they may overwrite the changes made by the job.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Jobs|P, 0|`FastMap<u64, Job>`, keyed by ID|
+/// |Next Job ID|P, 1|u64|
+/// |Bounty|P, 2|u64|
+/// |First Pending Job ID|P, 3|u64|
+/// |Next Job ID to Read|P, 4|u64|
+/// 
+/// - P: parent key
+pub struct Scheduler {
+    jobs: FastMap<u64, Job>,
+    next_id: u64,
+    bounty: u64,
+    /// Jobs of lower IDs are executed or cancelled.
+    first_id: u64,
+    /// ID of the job where the next call of `execute_due` starts reading.
+    scan_id: u64,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self { jobs: FastMap::new(), next_id: 0, bounty: 0, first_id: 0, scan_id: 0 }
+    }
+
+    /// The amount paid to the caller of `execute_due` for each executed job.
+    pub fn bounty(&self) -> u64 {
+        self.bounty
+    }
+
+    pub fn set_bounty(&mut self, bounty: u64) {
+        self.bounty = bounty;
+    }
+
+    /// Store a job. Returns the ID of the job.
+    pub fn schedule(&mut self, job: Job) -> u64 {
+        let id = self.next_id;
+        self.jobs.insert(&id, job);
+        self.next_id += 1;
+        id
+    }
+
+    /// Get a pending job.
+    pub fn job(&self, id: u64) -> Option<Job> {
+        self.jobs.get(&id)
+    }
+
+    /// Remove a pending job without executing it.
+    pub fn cancel(&mut self, id: u64) {
+        self.jobs.remove(&id);
+    }
+
+    /// Read at most `limit` jobs, from where the previous call stopped, and execute the jobs that are due in order of 
+    /// their IDs. Executed jobs are removed. The calling account is paid `bounty` for each executed job, as far as the 
+    /// balance of this contract allows. Returns the number of executed jobs.
+    pub fn execute_due(&mut self, limit: u32) -> u32 {
+        self.execute_due_except(limit, &[])
+    }
+
+    /// Execute due jobs as [Scheduler::execute_due], except the jobs of the IDs in `skip`, e.g. jobs whose calls fail.
+    /// The skipped jobs are read, so they count towards `limit`, but stay pending.
+    pub fn execute_due_except(&mut self, limit: u32, skip: &[u64]) -> u32 {
+        let mut due_jobs: Vec<(u64, Job)> = vec![];
+        let start = self.scan_id;
+        let mut id = start;
+        let mut wrapped = false;
+        for _ in 0..limit {
+            if id >= self.next_id {
+                // start over from the earliest pending job, once per call
+                if wrapped || self.first_id >= start {
+                    break
+                }
+                wrapped = true;
+                id = self.first_id;
+            }
+            if wrapped && id >= start { // read in this call
+                break
+            }
+            match self.jobs.get(&id) {
+                Some(job) if job.is_due() && !skip.contains(&id) => due_jobs.push((id, job)),
+                Some(_) => {},
+                None if id == self.first_id => self.first_id += 1,
+                None => {},
+            }
+            id += 1;
+        }
+        self.scan_id = id;
+
+        let executed = due_jobs.len() as u32;
+        for (id, job) in due_jobs {
+            self.jobs.remove(&id);
+            if id == self.first_id {
+                self.first_id += 1;
+            }
+            internal::call_untyped(job.target, &job.method, job.arguments, job.value);
+        }
+
+        let payment = self.bounty.saturating_mul(executed as u64).min(blockchain::balance());
+        if payment > 0 {
+            internal::transfer(transaction::calling_account(), payment);
+        }
+        executed
+    }
+}
+
+impl Storable for Scheduler {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            jobs: Storable::__load_storage(&field.add(0)),
+            next_id: Storable::__load_storage(&field.add(1)),
+            bounty: Storable::__load_storage(&field.add(2)),
+            first_id: Storable::__load_storage(&field.add(3)),
+            scan_id: Storable::__load_storage(&field.add(4)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.jobs.__save_storage(&field.add(0));
+        self.next_id.__save_storage(&field.add(1));
+        self.bounty.__save_storage(&field.add(2));
+        self.first_id.__save_storage(&field.add(3));
+        self.scan_id.__save_storage(&field.add(4));
+    }
+}
@@ -0,0 +1,184 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines helpers for contracts that stake their balance to pools, built on top of the deferred network commands
+//! in [network](crate::network) and the Network Account's Storage.
+//! 
+//! [AutoCompounder] keeps track of the principal that a contract deposited to a pool, so that the rewards 
+//! accumulated in the deposit can be calculated and staked in a single call.
+
+use borsh::BorshDeserialize;
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{network, storage, transaction, Storable, StoragePath};
+
+// The keys below follow the layout of the Network Account's Storage defined by `NetworkAccount` in pchain-runtime 
+// 0.4 (`network/network_account.rs`): the prefixes of the account are the previous, current and next validator sets 
+// (0, 1, 2), Pools (3), Deposits (4) and the current epoch (5), and a Deposit keeps its balance (0) and its 
+// auto-stake-rewards setting (1). They are tested against these keys in `tests/staking.rs`.
+
+/// Key prefix of Deposits in the Network Account's Storage.
+const DEPOSITS_PREFIX: u8 = 4;
+/// Key suffix of the balance of a Deposit in the Network Account's Storage.
+const DEPOSIT_BALANCE_SUFFIX: u8 = 0;
+/// Key suffix of the auto-stake-rewards setting of a Deposit in the Network Account's Storage.
+const DEPOSIT_AUTO_STAKE_REWARDS_SUFFIX: u8 = 1;
+
+/// Get the balance of the Deposit owned by `owner` in the pool of `operator`. None if the Deposit does not exist.
+pub fn deposit_balance(operator: PublicAddress, owner: PublicAddress) -> Option<u64> {
+    let bytes = storage::get_network_state(&wskey_deposit(operator, owner, DEPOSIT_BALANCE_SUFFIX))?;
+    u64::deserialize(&mut bytes.as_slice()).ok()
+}
+
+/// Get the auto-stake-rewards setting of the Deposit owned by `owner` in the pool of `operator`. 
+/// None if the Deposit does not exist.
+pub fn deposit_auto_stake_rewards(operator: PublicAddress, owner: PublicAddress) -> Option<bool> {
+    let bytes = storage::get_network_state(&wskey_deposit(operator, owner, DEPOSIT_AUTO_STAKE_REWARDS_SUFFIX))?;
+    bool::deserialize(&mut bytes.as_slice()).ok()
+}
+
+/// Network Account Storage Key for a field of Deposit.
+/// 
+/// - Key: [4, O, A, F]
+/// 
+/// where
+///  - O: operator of the pool
+///  - A: owner of the deposit
+///  - F: suffix of the field
+fn wskey_deposit(operator: PublicAddress, owner: PublicAddress, suffix: u8) -> Vec<u8> {
+    [
+        [DEPOSITS_PREFIX].as_slice(),
+        operator.as_slice(),
+        owner.as_slice(),
+        [suffix].as_slice()
+    ].concat()
+}
+
+/// `AutoCompounder` is a contract field that compounds the staking rewards of the Deposit owned by this contract. 
+/// 
+/// Rewards are added to the balance of a Deposit. `AutoCompounder` records the principal deposited through it, 
+/// so that the rewards are the part of the Deposit balance that exceeds the principal and the rewards compounded before.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct StakingPool {
+///     compounder: AutoCompounder,
+/// }
+/// 
+/// #[contract_methods]
+/// impl StakingPool {
+///     #[call]
+///     fn deposit(&mut self) {
+///         self.compounder.deposit(pchain_sdk::transaction::amount());
+///     }
+/// 
+///     #[call]
+///     fn compound(&mut self) -> u64 {
+///         self.compounder.compound()
+///     }
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Operator|P, 0|PublicAddress|
+/// |Principal|P, 1|u64|
+/// |Compounded|P, 2|u64|
+/// 
+/// - P: parent key
+pub struct AutoCompounder {
+    operator: PublicAddress,
+    principal: u64,
+    compounded: u64,
+}
+
+impl AutoCompounder {
+    pub fn new(operator: PublicAddress) -> Self {
+        Self { operator, principal: 0, compounded: 0 }
+    }
+
+    /// The operator of the pool that the Deposit is in.
+    pub fn operator(&self) -> PublicAddress {
+        self.operator
+    }
+
+    /// Change the operator of the pool. It should only be called when this contract has no Deposit in the pool of current operator.
+    pub fn set_operator(&mut self, operator: PublicAddress) {
+        self.operator = operator;
+        self.principal = 0;
+        self.compounded = 0;
+    }
+
+    /// The amount deposited through `deposit`, minus the amount recorded by `record_withdrawal`.
+    pub fn principal(&self) -> u64 {
+        self.principal
+    }
+
+    /// The total amount of rewards compounded.
+    pub fn compounded(&self) -> u64 {
+        self.compounded
+    }
+
+    /// The balance of the Deposit owned by this contract. Zero if the Deposit does not exist.
+    pub fn deposit_balance(&self) -> u64 {
+        deposit_balance(self.operator, transaction::current_account()).unwrap_or(0)
+    }
+
+    /// The rewards accumulated in the Deposit that are not yet compounded.
+    pub fn pending_rewards(&self) -> u64 {
+        self.deposit_balance()
+            .saturating_sub(self.principal)
+            .saturating_sub(self.compounded)
+    }
+
+    /// Deposit `amount` from the balance of this contract to the pool. The Deposit is created if it does not exist. 
+    /// The execution is deferred to be executed after success of this contract call.
+    pub fn deposit(&mut self, amount: u64) {
+        if deposit_balance(self.operator, transaction::current_account()).is_some() {
+            network::defer_topup_deposit(self.operator, amount);
+        } else {
+            network::defer_create_deposit(self.operator, amount, false);
+        }
+        self.principal = self.principal.saturating_add(amount);
+    }
+
+    /// Record that `amount` is withdrawn from the Deposit (e.g. by `network::defer_withdraw_deposit`), which reduces 
+    /// the principal first and then the compounded rewards.
+    pub fn record_withdrawal(&mut self, amount: u64) {
+        let from_principal = amount.min(self.principal);
+        self.principal -= from_principal;
+        self.compounded = self.compounded.saturating_sub(amount - from_principal);
+    }
+
+    /// Stake the pending rewards of the Deposit. Returns the amount of rewards being staked. 
+    /// The execution is deferred to be executed after success of this contract call.
+    pub fn compound(&mut self) -> u64 {
+        let rewards = self.pending_rewards();
+        if rewards > 0 {
+            network::defer_stake_deposit(self.operator, rewards);
+            self.compounded = self.compounded.saturating_add(rewards);
+        }
+        rewards
+    }
+}
+
+impl Storable for AutoCompounder {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            operator: Storable::__load_storage(&field.add(0)),
+            principal: Storable::__load_storage(&field.add(1)),
+            compounded: Storable::__load_storage(&field.add(2)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.operator.__save_storage(&field.add(0));
+        self.principal.__save_storage(&field.add(1));
+        self.compounded.__save_storage(&field.add(2));
+    }
+}
@@ -0,0 +1,367 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [FungibleToken], the contract field of the fungible token standard, and the methods of the standard which
+//! `#[contract_methods]` dispatches to it for a contract struct with `#[derive(FungibleToken)]`:
+//! 
+//! |Method|Arguments|Return value|
+//! |:---|:---|:---|
+//! |name| |String|
+//! |symbol| |String|
+//! |decimals| |u8|
+//! |total_supply| |u64|
+//! |balance_of|`owner: PublicAddress`|u64|
+//! |allowance|`owner: PublicAddress, spender: PublicAddress`|u64|
+//! |transfer|`to: PublicAddress, amount: u64`|`Result<(), ContractError>`|
+//! |approve|`spender: PublicAddress, amount: u64`| |
+//! |transfer_from|`from: PublicAddress, to: PublicAddress, amount: u64`|`Result<(), ContractError>`|
+//! 
+//! The methods do not accept an attached amount. A transfer logs a [TransferEvent], and an approval logs an
+//! [ApprovalEvent]. A token created by [FungibleToken::with_holders] keeps its holders in a [HolderRegistry], which
+//! every change of the balances updates, including the transfers by the methods of the standard. Minting and burning are not methods of the standard, since who may mint is decided by the
+//! contract, which calls [FungibleToken::mint] and [FungibleToken::burn] from its own methods.
+//! 
+//! ```no_run
+//! #[contract]
+//! #[derive(FungibleToken)]
+//! pub struct MyToken {
+//!     owner: PublicAddress,
+//!     token: FungibleToken,
+//! }
+//! 
+//! #[contract_methods(fungible_token)]
+//! impl MyToken {
+//!     #[call]
+//!     fn init(&mut self) {
+//!         self.owner = transaction::calling_account();
+//!         self.token = FungibleToken::new("My Token", "MTK", 8);
+//!     }
+//! 
+//!     #[call]
+//!     fn mint(&mut self, to: PublicAddress, amount: u64) {
+//!         assert_eq!(self.owner, transaction::calling_account());
+//!         self.token.mint(to, amount).unwrap();
+//!     }
+//! }
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{
+    collections::FastMap, internal, method::MethodInfo, storage, token::{Allowances, HolderRegistry}, transaction,
+    Cacher, ContractError, ContractMethodInput, ContractMethodOutput, Storable, StoragePath
+};
+
+pub use pchain_sdk_macros::FungibleToken;
+
+pub use crate::token::{ApprovalEvent, APPROVAL_TOPIC};
+
+/// Topic of the log emitted when tokens are transferred, minted or burned.
+pub const TRANSFER_TOPIC: &[u8] = b"Transfer";
+
+/// Value of the log with topic [TRANSFER_TOPIC].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransferEvent {
+    /// None if the tokens are minted.
+    pub from: Option<PublicAddress>,
+    /// None if the tokens are burned.
+    pub to: Option<PublicAddress>,
+    pub amount: u64,
+}
+
+/// Code of the [ContractError] returned when the balance is less than the amount to transfer or burn.
+pub const INSUFFICIENT_BALANCE: u32 = 0xFFFF_0100;
+
+/// Code of the [ContractError] returned when the allowance of the spender is less than the amount to transfer.
+pub const INSUFFICIENT_ALLOWANCE: u32 = 0xFFFF_0101;
+
+/// Code of the [ContractError] returned when minting would overflow the total supply.
+pub const SUPPLY_OVERFLOW: u32 = 0xFFFF_0102;
+
+/// Error of moving tokens of a [FungibleToken].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FungibleTokenError {
+    InsufficientBalance { balance: u64 },
+    InsufficientAllowance { allowance: u64 },
+    SupplyOverflow,
+}
+
+impl std::fmt::Display for FungibleTokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FungibleTokenError::InsufficientBalance { balance } => write!(f, "insufficient balance, available {}", balance),
+            FungibleTokenError::InsufficientAllowance { allowance } => write!(f, "insufficient allowance, available {}", allowance),
+            FungibleTokenError::SupplyOverflow => write!(f, "total supply overflows"),
+        }
+    }
+}
+
+impl From<FungibleTokenError> for ContractError {
+    fn from(error: FungibleTokenError) -> Self {
+        let code = match error {
+            FungibleTokenError::InsufficientBalance { .. } => INSUFFICIENT_BALANCE,
+            FungibleTokenError::InsufficientAllowance { .. } => INSUFFICIENT_ALLOWANCE,
+            FungibleTokenError::SupplyOverflow => SUPPLY_OVERFLOW,
+        };
+        ContractError::new(code, error.to_string())
+    }
+}
+
+/// Name, symbol and decimals of a [FungibleToken].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl Storable for TokenMetadata {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
+
+/// `FungibleToken` is the contract field of the fungible token standard, which keeps the metadata, the total supply,
+/// the balances and the allowances of the token. The metadata and the total supply are read from world state only
+/// when they are accessed, so a transfer only reads and writes the balances of the two accounts.
+/// 
+/// The holders are enumerable if the token is created by [FungibleToken::with_holders], and the allowances support
+/// permits by [FungibleToken::allowances_mut].
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Metadata|P, 0|[TokenMetadata]|
+/// |Total Supply|P, 1|u64|
+/// |Balances|P, 2|`FastMap<PublicAddress, u64>`|
+/// |Allowances|P, 3|[Allowances]|
+/// |Holders|P, 4|[HolderRegistry]|
+/// 
+/// - P: parent key
+pub struct FungibleToken {
+    metadata: Cacher<TokenMetadata>,
+    total_supply: Cacher<u64>,
+    balances: FastMap<PublicAddress, u64>,
+    allowances: Allowances,
+    holders: HolderRegistry,
+}
+
+impl FungibleToken {
+    /// Instantiate a token without supply, which does not keep its holders.
+    pub fn new(name: &str, symbol: &str, decimals: u8) -> Self {
+        let mut metadata: Cacher<TokenMetadata> = Cacher::new();
+        metadata.set(TokenMetadata { name: name.to_string(), symbol: symbol.to_string(), decimals });
+        let mut total_supply: Cacher<u64> = Cacher::new();
+        total_supply.set(0);
+        Self { metadata, total_supply, balances: FastMap::new(), allowances: Allowances::new(), holders: HolderRegistry::new(false) }
+    }
+
+    /// Keep the holders of the token in a [HolderRegistry], which can only be enabled when the token is created.
+    /// 
+    /// ```no_run
+    /// self.token = FungibleToken::new("My Token", "MTK", 8).with_holders();
+    /// ```
+    pub fn with_holders(mut self) -> Self {
+        self.holders = HolderRegistry::new(true);
+        self
+    }
+
+    /// The registry of the holders, which is disabled unless the token is created by [FungibleToken::with_holders].
+    pub fn holders(&self) -> &HolderRegistry {
+        &self.holders
+    }
+
+    pub fn metadata(&self) -> &TokenMetadata {
+        self.metadata.get()
+    }
+
+    pub fn total_supply(&self) -> u64 {
+        *self.total_supply.get()
+    }
+
+    pub fn balance_of(&self, owner: PublicAddress) -> u64 {
+        self.balances.get(&owner).unwrap_or(0)
+    }
+
+    pub fn balances(&self) -> &FastMap<PublicAddress, u64> {
+        &self.balances
+    }
+
+    /// The amount that the spender may transfer on behalf of the owner.
+    pub fn allowance(&self, owner: PublicAddress, spender: PublicAddress) -> u64 {
+        self.allowances.allowance(owner, spender)
+    }
+
+    pub fn allowances_mut(&mut self) -> &mut Allowances {
+        &mut self.allowances
+    }
+
+    /// Transfer tokens from `from` to `to`, and log a [TransferEvent]. The caller must check that `from` is the
+    /// calling account.
+    pub fn transfer(&mut self, from: PublicAddress, to: PublicAddress, amount: u64) -> Result<(), FungibleTokenError> {
+        let from_balance = self.balance_of(from);
+        if from_balance < amount {
+            return Err(FungibleTokenError::InsufficientBalance { balance: from_balance })
+        }
+        if from != to {
+            // cannot overflow, since the sum of the balances is the total supply
+            let to_balance = self.balance_of(to);
+            self.balances.insert(&from, from_balance - amount);
+            self.balances.insert(&to, to_balance + amount);
+            self.holders.update(&self.balances, &[from, to]);
+        }
+        log_transfer(Some(from), Some(to), amount);
+        Ok(())
+    }
+
+    /// Set the allowance of the spender, and log an [ApprovalEvent]. The caller must check that the owner is the
+    /// calling account.
+    pub fn approve(&mut self, owner: PublicAddress, spender: PublicAddress, amount: u64) {
+        self.allowances.approve(owner, spender, amount)
+    }
+
+    /// Transfer tokens from `from` to `to` on behalf of `from` by the spender, decreasing the allowance of the spender
+    /// unless it is unlimited. Nothing changes if the allowance or the balance is insufficient.
+    pub fn transfer_from(&mut self, spender: PublicAddress, from: PublicAddress, to: PublicAddress, amount: u64) -> Result<(), FungibleTokenError> {
+        let balance = self.balance_of(from);
+        if balance < amount {
+            return Err(FungibleTokenError::InsufficientBalance { balance })
+        }
+        self.allowances.spend(from, spender, amount)
+            .map_err(|_| FungibleTokenError::InsufficientAllowance { allowance: self.allowance(from, spender) })?;
+        self.transfer(from, to, amount)
+    }
+
+    /// Create tokens in the balance of `to`, and log a [TransferEvent] without `from`.
+    pub fn mint(&mut self, to: PublicAddress, amount: u64) -> Result<(), FungibleTokenError> {
+        let total_supply = self.total_supply().checked_add(amount).ok_or(FungibleTokenError::SupplyOverflow)?;
+        *self.total_supply.get_mut() = total_supply;
+        self.balances.insert(&to, self.balance_of(to) + amount);
+        self.holders.update(&self.balances, &[to]);
+        log_transfer(None, Some(to), amount);
+        Ok(())
+    }
+
+    /// Destroy tokens in the balance of `from`, and log a [TransferEvent] without `to`.
+    pub fn burn(&mut self, from: PublicAddress, amount: u64) -> Result<(), FungibleTokenError> {
+        let balance = self.balance_of(from);
+        if balance < amount {
+            return Err(FungibleTokenError::InsufficientBalance { balance })
+        }
+        self.balances.insert(&from, balance - amount);
+        self.holders.update(&self.balances, &[from]);
+        *self.total_supply.get_mut() -= amount;
+        log_transfer(Some(from), None, amount);
+        Ok(())
+    }
+}
+
+fn log_transfer(from: Option<PublicAddress>, to: Option<PublicAddress>, amount: u64) {
+    let event = TransferEvent { from, to, amount };
+    internal::log(TRANSFER_TOPIC, &event.try_to_vec().unwrap());
+}
+
+impl Storable for FungibleToken {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            metadata: Storable::__load_storage(&field.add(0)),
+            total_supply: Storable::__load_storage(&field.add(1)),
+            balances: Storable::__load_storage(&field.add(2)),
+            allowances: Storable::__load_storage(&field.add(3)),
+            holders: Storable::__load_storage(&field.add(4)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.metadata.__save_storage(&field.add(0));
+        self.total_supply.__save_storage(&field.add(1));
+        self.balances.__save_storage(&field.add(2));
+        self.allowances.__save_storage(&field.add(3));
+        self.holders.__save_storage(&field.add(4));
+    }
+}
+
+/// Names and arities of the methods of the standard, in the order of the table in the module documentation.
+const METHODS: [(&str, u32, bool); 9] = [
+    ("name", 0, true),
+    ("symbol", 0, true),
+    ("decimals", 0, true),
+    ("total_supply", 0, true),
+    ("balance_of", 1, true),
+    ("allowance", 2, true),
+    ("transfer", 2, false),
+    ("approve", 2, false),
+    ("transfer_from", 3, false),
+];
+
+/// The methods of the standard as listed by the method `__methods` of the contract.
+#[doc(hidden)]
+pub fn __methods() -> Vec<MethodInfo> {
+    METHODS.iter().map(|(name, arity, view)| MethodInfo {
+        name: name.to_string(),
+        arity: *arity,
+        payable: false,
+        internal_only: false,
+        view: *view,
+    }).collect()
+}
+
+/// Call the method of the standard on the [FungibleToken] at the storage path `field`, or return None if the method
+/// is not a method of the standard. Called by the entrypoint of a contract with `#[derive(FungibleToken)]`.
+#[doc(hidden)]
+pub fn __dispatch(field: &StoragePath, ctx: &ContractMethodInput) -> Option<ContractMethodOutput> {
+    let method = ctx.method_name();
+    let (_, arity, _) = METHODS.iter().find(|(name, _, _)| *name == method)?;
+    if transaction::amount() > 0 {
+        panic!("Method {} accepts an attached amount of at most 0.", method);
+    }
+    // a call without arguments may have empty arguments
+    let args = if *arity > 0 { ctx.get_multiple_arguments() } else { vec![] };
+    let arg = |idx| ContractMethodInput::parse_multiple_arguments::<PublicAddress>(&args, idx);
+    let amount = |idx| ContractMethodInput::parse_multiple_arguments::<u64>(&args, idx);
+    // only the components changed by the method are saved
+    let mut token = FungibleToken::__load_storage(field);
+    let output = match method {
+        "name" => ContractMethodOutput::set(&token.metadata().name),
+        "symbol" => ContractMethodOutput::set(&token.metadata().symbol),
+        "decimals" => ContractMethodOutput::set(&token.metadata().decimals),
+        "total_supply" => ContractMethodOutput::set(&token.total_supply()),
+        "balance_of" => ContractMethodOutput::set(&token.balance_of(arg(0))),
+        "allowance" => ContractMethodOutput::set(&token.allowance(arg(0), arg(1))),
+        "transfer" => {
+            let result = token.transfer(transaction::calling_account(), arg(0), amount(1));
+            if result.is_ok() {
+                token.balances.__save_storage(&field.add(2));
+                token.holders.__save_storage(&field.add(4));
+            }
+            ContractMethodOutput::set_result(method, &result.map_err(ContractError::from))
+        },
+        "approve" => {
+            token.approve(transaction::calling_account(), arg(0), amount(1));
+            token.allowances.__save_storage(&field.add(3));
+            ContractMethodOutput::default()
+        },
+        "transfer_from" => {
+            let result = token.transfer_from(transaction::calling_account(), arg(0), arg(1), amount(2));
+            if result.is_ok() {
+                token.balances.__save_storage(&field.add(2));
+                token.allowances.__save_storage(&field.add(3));
+                token.holders.__save_storage(&field.add(4));
+            }
+            ContractMethodOutput::set_result(method, &result.map_err(ContractError::from))
+        },
+        _ => unreachable!()
+    };
+    Some(output)
+}
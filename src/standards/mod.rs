@@ -0,0 +1,69 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines token standards, which are implemented once by the SDK so that the tokens built with it have the same
+//! methods, events and storage layout.
+//! 
+//! A contract adopts a standard by keeping its contract field in the contract struct and deriving the macro of the
+//! standard, e.g. `#[derive(FungibleToken)]`, and declares it by the option of the name of its module, e.g.
+//! `#[contract_methods(fungible_token)]`. `#[contract_methods]` then dispatches the methods of the standard, unless
+//! the contract defines a method of the same name, which overrides it, and describes them in the ABI, the interface
+//! reference and the metrics of the contract.
+//! 
+//! Components:
+//! - [fungible_token]: balances, allowances and supply of a fungible token
+//! - [nft]: owners, approvals and metadata of non-fungible tokens
+//! 
+//! A contract struct adopts at most one standard, since the methods of the standards share names, e.g. `transfer`. It
+//! is a compile error to derive a standard for a contract struct with contract fields of both standards.
+
+pub mod fungible_token;
+
+pub mod nft;
+
+use crate::{method::MethodInfo, ContractMethodInput, ContractMethodOutput};
+
+/// Fallback of the standards of a contract that does not derive them. The derive macros of the standards generate
+/// inherent functions that take precedence over these ones.
+#[doc(hidden)]
+pub trait __NoStandards {
+    const __STANDARD: &'static str = "";
+
+    fn __fungible_token(_ctx: &ContractMethodInput) -> Option<ContractMethodOutput> {
+        None
+    }
+
+    fn __fungible_token_methods() -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn __nft(_ctx: &ContractMethodInput) -> Option<ContractMethodOutput> {
+        None
+    }
+
+    fn __nft_methods() -> Vec<MethodInfo> {
+        vec![]
+    }
+}
+
+impl<T> __NoStandards for T {}
+
+/// Check if the standard derived by the contract struct is the standard declared in `#[contract_methods]`, which
+/// is asserted at compile time.
+#[doc(hidden)]
+pub const fn __is_declared(derived: &str, declared: &str) -> bool {
+    let (derived, declared) = (derived.as_bytes(), declared.as_bytes());
+    if derived.len() != declared.len() {
+        return false
+    }
+    let mut i = 0;
+    while i < derived.len() {
+        if derived[i] != declared[i] {
+            return false
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,400 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines [NonFungibleToken], the contract field of the non-fungible token standard, and the methods of the standard
+//! which `#[contract_methods]` dispatches to it for a contract struct with `#[derive(NonFungibleToken)]`. Tokens are
+//! identified by [TokenId]s chosen by the contract when they are minted.
+//! 
+//! |Method|Arguments|Return value|
+//! |:---|:---|:---|
+//! |name| |String|
+//! |symbol| |String|
+//! |total_supply| |u64|
+//! |balance_of|`owner: PublicAddress`|u64|
+//! |owner_of|`token_id: u64`|`Option<PublicAddress>`|
+//! |get_approved|`token_id: u64`|`Option<PublicAddress>`|
+//! |token_uri|`token_id: u64`|`Option<String>`|
+//! |transfer|`to: PublicAddress, token_id: u64`|`Result<(), ContractError>`|
+//! |approve|`spender: PublicAddress, token_id: u64`|`Result<(), ContractError>`|
+//! |transfer_from|`from: PublicAddress, to: PublicAddress, token_id: u64`|`Result<(), ContractError>`|
+//! 
+//! The methods do not accept an attached amount. A transfer logs an [NftTransferEvent], and an approval logs an
+//! [NftApprovalEvent]. Their topics differ from the topics of the fungible token standard, whose logs have other
+//! values. Minting and burning are not methods of the standard, since who may mint is decided by the contract, which
+//! calls [NonFungibleToken::mint] and [NonFungibleToken::burn] from its own methods.
+//! 
+//! ```no_run
+//! #[contract]
+//! #[derive(NonFungibleToken)]
+//! pub struct MyCollection {
+//!     owner: PublicAddress,
+//!     nft: NonFungibleToken,
+//! }
+//! 
+//! #[contract_methods(nft)]
+//! impl MyCollection {
+//!     #[call]
+//!     fn init(&mut self) {
+//!         self.owner = transaction::calling_account();
+//!         self.nft = NonFungibleToken::new("My Collection", "MYC", "https://example.com/tokens/");
+//!     }
+//! 
+//!     #[call]
+//!     fn mint(&mut self, to: PublicAddress, token_id: u64) {
+//!         assert_eq!(self.owner, transaction::calling_account());
+//!         self.nft.mint(to, token_id, None).unwrap();
+//!     }
+//! }
+//! ```
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{
+    collections::FastMap, internal, method::MethodInfo, storage, transaction,
+    Cacher, ContractError, ContractMethodInput, ContractMethodOutput, Storable, StoragePath
+};
+
+pub use pchain_sdk_macros::NonFungibleToken;
+
+/// Identifier of a token in a [NonFungibleToken].
+pub type TokenId = u64;
+
+/// Topic of the log emitted when a token is transferred, minted or burned.
+pub const TRANSFER_TOPIC: &[u8] = b"NftTransfer";
+
+/// Topic of the log emitted when a spender is approved for a token.
+pub const APPROVAL_TOPIC: &[u8] = b"NftApproval";
+
+/// Value of the log with topic [TRANSFER_TOPIC].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NftTransferEvent {
+    /// None if the token is minted.
+    pub from: Option<PublicAddress>,
+    /// None if the token is burned.
+    pub to: Option<PublicAddress>,
+    pub token_id: TokenId,
+}
+
+/// Value of the log with topic [APPROVAL_TOPIC].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NftApprovalEvent {
+    pub owner: PublicAddress,
+    pub spender: PublicAddress,
+    pub token_id: TokenId,
+}
+
+/// Code of the [ContractError] returned when the token does not exist.
+pub const TOKEN_NOT_FOUND: u32 = 0xFFFF_0110;
+
+/// Code of the [ContractError] returned when the account is neither the owner of the token nor approved for it.
+pub const NOT_AUTHORIZED: u32 = 0xFFFF_0111;
+
+/// Code of the [ContractError] returned when minting a token that exists.
+pub const TOKEN_EXISTS: u32 = 0xFFFF_0112;
+
+/// Error of minting, moving or approving a token of a [NonFungibleToken].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NftError {
+    TokenNotFound,
+    /// The account is neither the owner of the token nor approved for it, or `from` of a transfer is not the owner.
+    NotAuthorized,
+    TokenExists,
+}
+
+impl std::fmt::Display for NftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NftError::TokenNotFound => write!(f, "token does not exist"),
+            NftError::NotAuthorized => write!(f, "not authorized for the token"),
+            NftError::TokenExists => write!(f, "token exists"),
+        }
+    }
+}
+
+impl From<NftError> for ContractError {
+    fn from(error: NftError) -> Self {
+        let code = match error {
+            NftError::TokenNotFound => TOKEN_NOT_FOUND,
+            NftError::NotAuthorized => NOT_AUTHORIZED,
+            NftError::TokenExists => TOKEN_EXISTS,
+        };
+        ContractError::new(code, error.to_string())
+    }
+}
+
+/// Name and symbol of a [NonFungibleToken], and the base of the URIs of its tokens.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CollectionMetadata {
+    pub name: String,
+    pub symbol: String,
+    /// The URI of a token without its own URI is the base URI followed by the token id, e.g. `https://example.com/tokens/7`.
+    pub base_uri: String,
+}
+
+impl Storable for CollectionMetadata {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
+
+/// `NonFungibleToken` is the contract field of the non-fungible token standard, which keeps the metadata of the
+/// collection, the owners, the approvals and the URIs of the tokens, and the number of tokens of each owner. The
+/// metadata and the total supply are read from world state only when they are accessed.
+/// 
+/// A token has at most one approved spender, which may transfer it on behalf of the owner. The approval is cleared
+/// when the token is transferred.
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Metadata|P, 0|[CollectionMetadata]|
+/// |Total Supply|P, 1|u64|
+/// |Owners|P, 2|`FastMap<TokenId, PublicAddress>`|
+/// |Balances|P, 3|`FastMap<PublicAddress, u64>`, number of tokens of each owner|
+/// |Approvals|P, 4|`FastMap<TokenId, PublicAddress>`|
+/// |Token URIs|P, 5|`FastMap<TokenId, String>`, URIs of the tokens minted with their own URIs|
+/// 
+/// - P: parent key
+pub struct NonFungibleToken {
+    metadata: Cacher<CollectionMetadata>,
+    total_supply: Cacher<u64>,
+    owners: FastMap<TokenId, PublicAddress>,
+    balances: FastMap<PublicAddress, u64>,
+    approvals: FastMap<TokenId, PublicAddress>,
+    token_uris: FastMap<TokenId, String>,
+}
+
+impl NonFungibleToken {
+    /// Instantiate a collection without tokens.
+    pub fn new(name: &str, symbol: &str, base_uri: &str) -> Self {
+        let mut metadata: Cacher<CollectionMetadata> = Cacher::new();
+        metadata.set(CollectionMetadata { name: name.to_string(), symbol: symbol.to_string(), base_uri: base_uri.to_string() });
+        let mut total_supply: Cacher<u64> = Cacher::new();
+        total_supply.set(0);
+        Self {
+            metadata,
+            total_supply,
+            owners: FastMap::new(),
+            balances: FastMap::new(),
+            approvals: FastMap::new(),
+            token_uris: FastMap::new(),
+        }
+    }
+
+    pub fn metadata(&self) -> &CollectionMetadata {
+        self.metadata.get()
+    }
+
+    /// Change the base URI, e.g. when the metadata of the tokens is moved. The caller must check that the calling
+    /// account may change it.
+    pub fn set_base_uri(&mut self, base_uri: &str) {
+        self.metadata.get_mut().base_uri = base_uri.to_string();
+    }
+
+    /// Number of tokens that exist.
+    pub fn total_supply(&self) -> u64 {
+        *self.total_supply.get()
+    }
+
+    /// Number of tokens owned by the owner.
+    pub fn balance_of(&self, owner: PublicAddress) -> u64 {
+        self.balances.get(&owner).unwrap_or(0)
+    }
+
+    /// The owner of the token, or None if it does not exist.
+    pub fn owner_of(&self, token_id: TokenId) -> Option<PublicAddress> {
+        self.owners.get(&token_id)
+    }
+
+    /// The spender approved for the token, if any.
+    pub fn get_approved(&self, token_id: TokenId) -> Option<PublicAddress> {
+        self.approvals.get(&token_id)
+    }
+
+    /// The URI of the metadata of the token, or None if it does not exist.
+    pub fn token_uri(&self, token_id: TokenId) -> Option<String> {
+        self.owner_of(token_id)?;
+        Some(match self.token_uris.get(&token_id) {
+            Some(uri) => uri,
+            None => format!("{}{}", self.metadata().base_uri, token_id)
+        })
+    }
+
+    /// Create the token owned by `to`, with its own URI or the URI derived from the base URI, and log an
+    /// [NftTransferEvent] without `from`.
+    pub fn mint(&mut self, to: PublicAddress, token_id: TokenId, uri: Option<String>) -> Result<(), NftError> {
+        if self.owner_of(token_id).is_some() {
+            return Err(NftError::TokenExists)
+        }
+        self.owners.insert(&token_id, to);
+        self.balances.insert(&to, self.balance_of(to) + 1);
+        if let Some(uri) = uri {
+            self.token_uris.insert(&token_id, uri);
+        }
+        *self.total_supply.get_mut() += 1;
+        log_transfer(None, Some(to), token_id);
+        Ok(())
+    }
+
+    /// Destroy the token, and log an [NftTransferEvent] without `to`. The caller must check that the calling account
+    /// may burn it.
+    pub fn burn(&mut self, token_id: TokenId) -> Result<(), NftError> {
+        let owner = self.owner_of(token_id).ok_or(NftError::TokenNotFound)?;
+        self.owners.remove(&token_id);
+        self.approvals.remove(&token_id);
+        self.token_uris.remove(&token_id);
+        self.balances.insert(&owner, self.balance_of(owner) - 1);
+        *self.total_supply.get_mut() -= 1;
+        log_transfer(Some(owner), None, token_id);
+        Ok(())
+    }
+
+    /// Transfer the token from `from` to `to` by the operator, which is the owner or the approved spender, clear its
+    /// approval, and log an [NftTransferEvent].
+    pub fn transfer(&mut self, operator: PublicAddress, from: PublicAddress, to: PublicAddress, token_id: TokenId) -> Result<(), NftError> {
+        let owner = self.owner_of(token_id).ok_or(NftError::TokenNotFound)?;
+        if owner != from || (operator != owner && self.get_approved(token_id) != Some(operator)) {
+            return Err(NftError::NotAuthorized)
+        }
+        self.approvals.remove(&token_id);
+        if from != to {
+            self.owners.insert(&token_id, to);
+            self.balances.insert(&from, self.balance_of(from) - 1);
+            self.balances.insert(&to, self.balance_of(to) + 1);
+        }
+        log_transfer(Some(from), Some(to), token_id);
+        Ok(())
+    }
+
+    /// Approve the spender to transfer the token on behalf of the owner, replacing the previous approval, and log an
+    /// [NftApprovalEvent]. Fails unless `owner` owns the token. The caller must check that the owner is the calling
+    /// account.
+    pub fn approve(&mut self, owner: PublicAddress, spender: PublicAddress, token_id: TokenId) -> Result<(), NftError> {
+        if self.owner_of(token_id).ok_or(NftError::TokenNotFound)? != owner {
+            return Err(NftError::NotAuthorized)
+        }
+        self.approvals.insert(&token_id, spender);
+        let event = NftApprovalEvent { owner, spender, token_id };
+        internal::log(APPROVAL_TOPIC, &event.try_to_vec().unwrap());
+        Ok(())
+    }
+}
+
+fn log_transfer(from: Option<PublicAddress>, to: Option<PublicAddress>, token_id: TokenId) {
+    let event = NftTransferEvent { from, to, token_id };
+    internal::log(TRANSFER_TOPIC, &event.try_to_vec().unwrap());
+}
+
+impl Storable for NonFungibleToken {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            metadata: Storable::__load_storage(&field.add(0)),
+            total_supply: Storable::__load_storage(&field.add(1)),
+            owners: Storable::__load_storage(&field.add(2)),
+            balances: Storable::__load_storage(&field.add(3)),
+            approvals: Storable::__load_storage(&field.add(4)),
+            token_uris: Storable::__load_storage(&field.add(5)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.metadata.__save_storage(&field.add(0));
+        self.total_supply.__save_storage(&field.add(1));
+        self.owners.__save_storage(&field.add(2));
+        self.balances.__save_storage(&field.add(3));
+        self.approvals.__save_storage(&field.add(4));
+        self.token_uris.__save_storage(&field.add(5));
+    }
+}
+
+/// Names and arities of the methods of the standard, and whether they are views, in the order of the table in the
+/// module documentation.
+const METHODS: [(&str, u32, bool); 10] = [
+    ("name", 0, true),
+    ("symbol", 0, true),
+    ("total_supply", 0, true),
+    ("balance_of", 1, true),
+    ("owner_of", 1, true),
+    ("get_approved", 1, true),
+    ("token_uri", 1, true),
+    ("transfer", 2, false),
+    ("approve", 2, false),
+    ("transfer_from", 3, false),
+];
+
+/// The methods of the standard as listed by the method `__methods` of the contract.
+#[doc(hidden)]
+pub fn __methods() -> Vec<MethodInfo> {
+    METHODS.iter().map(|(name, arity, view)| MethodInfo {
+        name: name.to_string(),
+        arity: *arity,
+        payable: false,
+        internal_only: false,
+        view: *view,
+    }).collect()
+}
+
+/// Call the method of the standard on the [NonFungibleToken] at the storage path `field`, or return None if the
+/// method is not a method of the standard. Called by the entrypoint of a contract with `#[derive(NonFungibleToken)]`.
+#[doc(hidden)]
+pub fn __dispatch(field: &StoragePath, ctx: &ContractMethodInput) -> Option<ContractMethodOutput> {
+    let method = ctx.method_name();
+    let (_, arity, _) = METHODS.iter().find(|(name, _, _)| *name == method)?;
+    if transaction::amount() > 0 {
+        panic!("Method {} accepts an attached amount of at most 0.", method);
+    }
+    // a call without arguments may have empty arguments
+    let args = if *arity > 0 { ctx.get_multiple_arguments() } else { vec![] };
+    let address = |idx| ContractMethodInput::parse_multiple_arguments::<PublicAddress>(&args, idx);
+    let token_id = |idx| ContractMethodInput::parse_multiple_arguments::<TokenId>(&args, idx);
+    // only the components changed by the method are saved
+    let mut nft = NonFungibleToken::__load_storage(field);
+    let output = match method {
+        "name" => ContractMethodOutput::set(&nft.metadata().name),
+        "symbol" => ContractMethodOutput::set(&nft.metadata().symbol),
+        "total_supply" => ContractMethodOutput::set(&nft.total_supply()),
+        "balance_of" => ContractMethodOutput::set(&nft.balance_of(address(0))),
+        "owner_of" => ContractMethodOutput::set(&nft.owner_of(token_id(0))),
+        "get_approved" => ContractMethodOutput::set(&nft.get_approved(token_id(0))),
+        "token_uri" => ContractMethodOutput::set(&nft.token_uri(token_id(0))),
+        "transfer" => {
+            let caller = transaction::calling_account();
+            let result = nft.transfer(caller, caller, address(0), token_id(1));
+            if result.is_ok() {
+                nft.owners.__save_storage(&field.add(2));
+                nft.balances.__save_storage(&field.add(3));
+                nft.approvals.__save_storage(&field.add(4));
+            }
+            ContractMethodOutput::set_result(method, &result.map_err(ContractError::from))
+        },
+        "approve" => {
+            let result = nft.approve(transaction::calling_account(), address(0), token_id(1));
+            if result.is_ok() {
+                nft.approvals.__save_storage(&field.add(4));
+            }
+            ContractMethodOutput::set_result(method, &result.map_err(ContractError::from))
+        },
+        "transfer_from" => {
+            let result = nft.transfer(transaction::calling_account(), address(0), address(1), token_id(2));
+            if result.is_ok() {
+                nft.owners.__save_storage(&field.add(2));
+                nft.balances.__save_storage(&field.add(3));
+                nft.approvals.__save_storage(&field.add(4));
+            }
+            ContractMethodOutput::set_result(method, &result.map_err(ContractError::from))
+        },
+        _ => unreachable!()
+    };
+    Some(output)
+}
@@ -6,51 +6,28 @@
 //! Defines functions for reading and writing into Contract Storage (a.k.a. 'World State'). It also defines two types:
 //! 'Storage' and 'StoragePath', that are used in macro-expanded code in a convoluted and hard-to-explain manner. These
 //! types will be moved out of this module, or removed entirely, in a future version of the SDK.
+//! 
+//! Reads and writes go through a [StorageBackend], which is the storage of this contract in world state unless 
+//! replaced by [set_backend].
+//! 
+//! Outside the contract struct, values can be read and written under a [TypedKey], which declares the type of its
+//! value.
 
 use std::ops::{Deref, DerefMut};
-use std::cell::UnsafeCell;
+use std::cell::{OnceCell, RefCell};
+use std::marker::PhantomData;
+use std::rc::Rc;
 use borsh::{BorshSerialize, BorshDeserialize};
-use crate::imports;
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{host, internal, method::ContractMethodInputBuilder};
 
 /// Gets the value, if any, associated with the provided key in this Contract Storage.
 ///
 /// If get fails, the smart contract terminates and the sets this invocation made
 /// are not committed.
 pub fn get(key: &[u8]) -> Option<Vec<u8>> {
-
-    let key_ptr = key.as_ptr();
-
-    // `get` needs to get two things:
-    //   * A WASM linear memory offset pointing to where the host process
-    //     wrote the UTF-8 encoded result of the DB get: `val_ptr`.
-    //   * The byte-wise length of the gotten value: `val_len`.
-    // 
-    // `val_len` is returned directly, but the problem is that WASM does not
-    // yet support multiple return values, so we can't return `val_ptr` alongside it.
-    // Instead, we allocate a space in the heap for the host to write `val_ptr` in,
-    // and then tell the host to write `val` there by passing it `val_ptr_ptr` through
-    // `raw_get`.
-    // `val_len` is negative if the key cannot be found in world-state.
-    //
-    // When val_ptr leaves this scope, it is deallocated (we have no further use
-    // for it).
-    let mut val_ptr: u32 = 0;
-    let val_ptr_ptr = &mut val_ptr;
-
-    unsafe {
-        match imports::get(key_ptr, key.len() as u32, val_ptr_ptr) {
-            val_len if val_len < 0 => {
-                None
-            },
-            val_len => {
-                // If module execution reaches this point, we can assume that the `get` has succeeded.
-                //
-                // This Vec<u8> takes ownership of the segment of memory, letting the Rust ownership
-                // system to Drop it later.
-                Some(Vec::<u8>::from_raw_parts(val_ptr as *mut u8, val_len as usize, val_len as usize))
-            }
-        }
-    }
+    with_backend(|backend| backend.get(key))
 } 
 
 /// Gets the value, if any, associated with the provided key in Network Account's Storage.
@@ -58,31 +35,117 @@ pub fn get(key: &[u8]) -> Option<Vec<u8>> {
 /// If get fails, the smart contract terminates and the sets this invocation made
 /// are not committed.
 pub fn get_network_state(key: &[u8]) -> Option<Vec<u8>> {
+    host::current().get_network_storage(key)
+} 
+
+/// Name of the method by which a contract exposes keys of its storage to other contracts, with argument 
+/// `key: Vec<u8>` and return value `Option<Vec<u8>>`. It is called by [get_external], and implemented by wrapping 
+/// [serve_external] in a `#[view]` method.
+pub const EXTERNAL_GET_METHOD: &str = "storage_get";
 
-    let key_ptr = key.as_ptr();
+/// Gets the value, if any, associated with the provided key in the storage of another contract. The runtime only 
+/// allows contracts to read the storage of the Network Account ([get_network_state]), so the value is read by calling 
+/// the method [EXTERNAL_GET_METHOD] of the contract, which returns the value if the key is readable by other contracts. 
+/// Returns None if the key has no value or is not readable. A call that fails, e.g. because the contract does not 
+/// implement the method, is not returned as None: it aborts this call as well, as any failed internal call does.
+/// 
+/// The value is the one saved in storage, so it does not include changes made by the contract in the current call 
+/// if it is called back before saving them.
+/// 
+/// ### Example
+/// ```no_run
+/// // the field at index 2 of the oracle contract
+/// let price: Option<u64> = pchain_sdk::storage::get_external_typed(oracle, StoragePath::new().add(2).get_path());
+/// ```
+pub fn get_external(address: PublicAddress, key: &[u8]) -> Option<Vec<u8>> {
+    let arguments = ContractMethodInputBuilder::new().add(key.to_vec()).to_call_arguments();
+    internal::call_view::<Option<Vec<u8>>>(address, EXTERNAL_GET_METHOD, arguments).flatten()
+}
 
-    let mut val_ptr: u32 = 0;
-    let val_ptr_ptr = &mut val_ptr;
+/// Gets the value associated with the provided key in the storage of another contract by [get_external], and 
+/// deserializes it. Returns None if the value cannot be deserialized into `T`.
+pub fn get_external_typed<T: BorshDeserialize>(address: PublicAddress, key: &[u8]) -> Option<T> {
+    get_external(address, key).and_then(|value| T::try_from_slice(&value).ok())
+}
 
-    unsafe {
-        match imports::get_network_storage(key_ptr, key.len() as u32, val_ptr_ptr) {
-            val_len if val_len < 0 => {
-                None
-            },
-            val_len => {
-                Some(Vec::<u8>::from_raw_parts(val_ptr as *mut u8, val_len as usize, val_len as usize))
-            }
-        }
+/// Gets the value associated with the provided key in this Contract Storage, if the key starts with one of the 
+/// readable prefixes. It implements the method [EXTERNAL_GET_METHOD] called by [get_external], so that a contract 
+/// chooses which fields other contracts can read, e.g. by their [StoragePath]s.
+/// 
+/// ### Example
+/// ```no_run
+/// #[view]
+/// fn storage_get(_contract: ReadOnly<'_, Self>, key: Vec<u8>) -> Option<Vec<u8>> {
+///     // fields at index 1 and 2, including their nested fields.
+///     pchain_sdk::storage::serve_external(&key, &[&[1], &[2]])
+/// }
+/// ```
+pub fn serve_external(key: &[u8], readable_prefixes: &[&[u8]]) -> Option<Vec<u8>> {
+    if !readable_prefixes.iter().any(|prefix| key.starts_with(prefix)) {
+        return None
     }
-} 
+    get(key)
+}
 
 /// Binds the provided key to the provided value in this Contract's Storage.
 pub fn set(key: &[u8], value: &[u8]) {
-    let key_ptr = key.as_ptr();
-    let val_ptr = value.as_ptr();
-    unsafe {
-        imports::set(key_ptr, key.len() as u32, val_ptr, value.len() as u32);
-    } 
+    with_backend(|backend| backend.set(key, value))
+}
+
+/// Removes the provided key from this Contract's Storage.
+pub fn delete(key: &[u8]) {
+    with_backend(|backend| backend.delete(key))
+}
+
+/// StorageBackend is where [get], [set] and [delete] read and write Contract Storage. By default, it is 
+/// [HostStorage], i.e. the storage of this contract in world state. Another backend, e.g. an in-memory
+/// world state for tests or a caching layer over the host, can be used by [set_backend]. Collections and 
+/// macro-expanded code always go through these functions, so they work unchanged on any backend.
+pub trait StorageBackend {
+    /// Gets the value, if any, associated with the key.
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+    /// Binds the value to the key.
+    fn set(&self, key: &[u8], value: &[u8]);
+    /// Removes the key. By default, it sets an empty value, which removes the key from world state.
+    fn delete(&self, key: &[u8]) {
+        self.set(key, &[])
+    }
+}
+
+/// The default [StorageBackend], which reads and writes through the [Host](crate::host::Host).
+pub struct HostStorage;
+
+impl StorageBackend for HostStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        host::current().get(key)
+    }
+
+    fn set(&self, key: &[u8], value: &[u8]) {
+        host::current().set(key, value)
+    }
+}
+
+thread_local! {
+    static BACKEND: RefCell<Option<Rc<dyn StorageBackend>>> = RefCell::new(None);
+}
+
+/// Uses the backend in subsequent storage operations in this thread. Returns the backend being replaced, if any.
+pub fn set_backend(backend: Rc<dyn StorageBackend>) -> Option<Rc<dyn StorageBackend>> {
+    BACKEND.with(|b| b.borrow_mut().replace(backend))
+}
+
+/// Removes the backend set by [set_backend], so that subsequent storage operations use [HostStorage] again. 
+/// Returns the backend being removed, if any.
+pub fn take_backend() -> Option<Rc<dyn StorageBackend>> {
+    BACKEND.with(|b| b.borrow_mut().take())
+}
+
+fn with_backend<R>(f: impl FnOnce(&dyn StorageBackend) -> R) -> R {
+    // The backend is cloned out of the cell so that it can itself perform storage operations.
+    match BACKEND.with(|b| b.borrow().clone()) {
+        Some(backend) => f(backend.as_ref()),
+        None => f(&HostStorage)
+    }
 }
 
 /// StoragePath defines the key format in canonical path for fields in contract storage
@@ -112,6 +175,89 @@ impl StoragePath {
     }
 }
 
+/// `TypedKey` is a key in Contract Storage with the type of its value, so that the value is read and written without
+/// handling bytes and Borsh, e.g. in functions outside the contract struct. The keys of the fields of the contract
+/// struct start with their index, so a typed key should start with a byte that is not the index of a field, e.g.
+/// `0xff`.
+/// 
+/// ### Example
+/// ```no_run
+/// const OWNER: TypedKey<PublicAddress> = TypedKey::new(b"\xffowner");
+/// const ORDERS: TypedKey<FastMap<u64, Order>> = TypedKey::new(b"\xfforders");
+/// 
+/// fn require_owner() {
+///     assert_eq!(OWNER.get(), Some(transaction::calling_account()));
+/// }
+/// 
+/// fn place(id: u64, order: Order) {
+///     // a Storable value, e.g. a collection, is loaded and saved under the key like a field
+///     let mut orders = ORDERS.load();
+///     orders.insert(&id, order);
+///     ORDERS.save(&mut orders);
+/// }
+/// ```
+pub struct TypedKey<T> {
+    key: &'static [u8],
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedKey<T> {
+    pub const fn new(key: &'static [u8]) -> Self {
+        Self { key, _value: PhantomData }
+    }
+
+    pub const fn key(&self) -> &'static [u8] {
+        self.key
+    }
+
+    /// Check if the key has a value in Contract Storage.
+    pub fn exists(&self) -> bool {
+        get(self.key).is_some()
+    }
+
+    /// Remove the key from Contract Storage.
+    pub fn delete(&self) {
+        delete(self.key)
+    }
+}
+
+impl<T> TypedKey<T> where T: BorshSerialize + BorshDeserialize {
+    /// Get the value of the key, if any. It panics if the value is not of type `T`.
+    pub fn get(&self) -> Option<T> {
+        get(self.key).map(|bytes| T::try_from_slice(&bytes).unwrap())
+    }
+
+    /// Bind the value to the key. It is written to Contract Storage immediately.
+    pub fn set(&self, value: &T) {
+        set(self.key, value.try_to_vec().unwrap().as_slice())
+    }
+}
+
+impl<T> TypedKey<T> where T: Storable {
+    /// Load the value under the key in the way of a field of the contract struct, e.g. a collection whose entries are
+    /// stored under keys prefixed by the key.
+    pub fn load(&self) -> T {
+        T::__load_storage(&self.path())
+    }
+
+    /// Save the value loaded by [TypedKey::load]. A collection only writes the changes since it was loaded.
+    pub fn save(&self, value: &mut T) {
+        value.__save_storage(&self.path())
+    }
+
+    fn path(&self) -> StoragePath {
+        StoragePath::new().append(self.key.to_vec())
+    }
+}
+
+impl<T> Clone for TypedKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TypedKey<T> {}
+
 macro_rules! define_primitives {
     ($($t:ty),*) => {
         $(
@@ -147,10 +293,22 @@ macro_rules! define_generics {
     };
 }
 
-define_primitives!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, usize, [u8;32]);
+define_primitives!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, usize);
 define_primitives!(String, bool);
 define_generics!(Vec<T>, Option<T>);
 
+impl<T, const N: usize> Storable for [T; N] where T: BorshSerialize + BorshDeserialize + Default {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => std::array::from_fn(|_| T::default())
+        }
+    }
+    fn __save_storage(&mut self, field: &StoragePath) {
+        set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
+
 /// Storable trait provides functions as wrapper to getter and setter to the key-value storage in world-state.
 /// Impl of this trait is generated by macro. To avoid conflict with user function, function names in this trait are prefix with two underscores.
 pub trait Storable {
@@ -160,6 +318,78 @@ pub trait Storable {
     fn __save_storage(&mut self, field: &StoragePath);
 }
 
+/// Save the contract to world state now, instead of after the method returns.
+/// 
+/// The fields of the contract struct, including collections, are written to world state in one place: after a method 
+/// with receiver `&mut self` returns. Until then, world state holds the values from before the method was called. 
+/// A contract called by [call](crate::call) inside the method therefore sees these old values, and if it calls back 
+/// into this contract, the callback loads the old values too, e.g. balances that have not been deducted yet. 
+/// 
+/// Flushing before the call closes this window. Collections are written once, so the contract can be flushed any 
+/// number of times, and the final save only writes what changed after the last flush. Changes made by a callback are 
+/// not seen by the contract struct of the outer method, whose cached fields overwrite them when it is saved. A method 
+/// that needs them should return after the call, or read them again through new collection handles.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn withdraw(&mut self, amount: u64) {
+///     let caller = transaction::calling_account();
+///     let balance = self.balances.get(&caller).unwrap_or(0);
+///     self.balances.insert(&caller, balance.checked_sub(amount).unwrap());
+///     // persist the deduction before the recipient can call back
+///     pchain_sdk::flush_storage(self);
+///     pchain_sdk::call_untyped(caller, "on_withdraw", args, amount);
+/// }
+/// ```
+pub fn flush_storage<C: Storable>(contract: &mut C) {
+    contract.__save_storage(&StoragePath::new());
+}
+
+/// Fallback of loading and saving only some fields of a contract, which loads and saves all fields. `#[contract]`
+/// generates inherent functions `__load_fields` and `__save_fields` that take precedence over these ones, so this is
+/// only used by a contract struct that implements [Storable] by itself.
+#[doc(hidden)]
+pub trait __AllFields: Storable + Sized {
+    fn __load_fields(field: &StoragePath, _names: &[&str]) -> Self {
+        Self::__load_storage(field)
+    }
+
+    fn __save_fields(&mut self, field: &StoragePath, _names: &[&str]) {
+        self.__save_storage(field)
+    }
+}
+
+impl<T: Storable> __AllFields for T {}
+
+/// Placeholder of a field that is not loaded by `__load_fields`. With both traits [__UnloadedDefault] and
+/// [__UnloadedStorable] in scope, `(&__Unloaded::<T>(PhantomData)).__unloaded(field)` is the default value of T
+/// without reading world state, or the value loaded from world state if T does not implement Default.
+#[doc(hidden)]
+pub struct __Unloaded<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait __UnloadedDefault<T> {
+    fn __unloaded(&self, field: &StoragePath) -> T;
+}
+
+impl<T: Default> __UnloadedDefault<T> for __Unloaded<T> {
+    fn __unloaded(&self, _field: &StoragePath) -> T {
+        T::default()
+    }
+}
+
+#[doc(hidden)]
+pub trait __UnloadedStorable<T> {
+    fn __unloaded(&self, field: &StoragePath) -> T;
+}
+
+impl<T: Storable> __UnloadedStorable<T> for &__Unloaded<T> {
+    fn __unloaded(&self, field: &StoragePath) -> T {
+        T::__load_storage(field)
+    }
+}
+
 /// `Cacher` is data wrapper to support Lazy Read and Lazy Write to Contract Storage.
 /// 
 /// ### Example
@@ -174,30 +404,21 @@ pub trait Storable {
 pub struct Cacher<T> where T: Storable {
     /// `scope` defines the key format to store data T into world state
     scope: StoragePath,
-    // None if Cacher is never Deref-ed into.
-    inner: UnsafeCell<Option<T>>,
+    // Empty if Cacher is never Deref-ed into.
+    inner: OnceCell<T>,
 }
 
 impl<T> Cacher<T> where T: Storable {
     pub fn new() -> Self {
         Self {
             scope: StoragePath::new(),
-            inner: UnsafeCell::new(None),
+            inner: OnceCell::new(),
         }
     }
 
     /// lazy read from world state
-    fn load(&self) {
-        let inner_ptr = self.inner.get() as *mut Option<T>;
-
-        unsafe {
-            let inner = &mut *inner_ptr;
-            if inner.is_none() {
-                *inner = Some(
-                    T::__load_storage(&self.scope)
-                );
-            }
-        }
+    fn load(&self) -> &T {
+        self.inner.get_or_init(|| T::__load_storage(&self.scope))
     } 
 
     pub fn get(&self) ->  &T {
@@ -208,8 +429,9 @@ impl<T> Cacher<T> where T: Storable {
         self.deref_mut()
     }
 
+    /// Replace the value without reading it from world state.
     pub fn set(&mut self, value: T) {
-        *self.deref_mut() = value;
+        self.inner = OnceCell::from(value);
     }
 }
 
@@ -217,25 +439,14 @@ impl<T> Deref for Cacher<T> where T: Storable {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.load();
-
-        let inner_ptr = self.inner.get();
-        unsafe { 
-            let inner = &*inner_ptr;
-            inner.as_ref().unwrap()
-        }
+        self.load()
     }
 }
 
 impl<T> DerefMut for Cacher<T> where T: Storable {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.load();
-
-        let inner_ptr = self.inner.get();
-        unsafe { 
-            let inner = &mut *inner_ptr;
-            inner.as_mut().unwrap()
-        }
+        self.inner.get_mut().unwrap()
     }
 }
 
@@ -243,16 +454,203 @@ impl<T> Storable for Cacher<T> where T: Storable{
     fn __load_storage(field: &StoragePath) -> Self {
         Cacher {
             scope: field.clone(),
-            inner: UnsafeCell::new(None),
+            inner: OnceCell::new(),
         }
     }
 
     fn __save_storage(&mut self, field: &StoragePath) {
-        let inner_ptr = self.inner.get();
-        unsafe {
-            if let Some(inner) = &mut *inner_ptr {
-                inner.__save_storage(field);
+        if let Some(inner) = self.inner.get_mut() {
+            inner.__save_storage(field);
+        }
+    }
+}
+
+/// `LazyOption` is a contract field of an optional value that is read from world state only when it is accessed, e.g.
+/// a large configuration or metadata that most methods do not use. Unlike `Option<T>`, which is read by every call
+/// that loads the contract, and unlike [Cacher], it also stores whether the value is present in a flag of one byte,
+/// so that [LazyOption::is_some] does not read the value.
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct MyContract {
+///     metadata: LazyOption<Metadata>,
+/// }
+/// 
+/// #[call]
+/// fn set_metadata(&mut self, metadata: Metadata) {
+///     // written without reading the previous metadata
+///     self.metadata.set(metadata);
+/// }
+/// 
+/// #[view]
+/// fn name(contract: ReadOnly<'_, Self>) -> Option<String> {
+///     contract.metadata().get().map(|metadata| metadata.name.clone())
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Presence|P, 0|true if the value is present (bool)|
+/// |Value|P, 1|user defined data (borsh-serialized), deleted if absent|
+/// - P: parent key
+/// 
+/// ### Lazy Write
+/// 
+/// The flag and the value are written to world state only if the value is set or accessed as mutable.
+pub struct LazyOption<T> where T: BorshSerialize + BorshDeserialize {
+    /// `scope` defines the key format to store data T into world state
+    scope: StoragePath,
+    /// Empty until the presence flag is read from world state or the value is set.
+    present: OnceCell<bool>,
+    /// Empty until the value is read from world state or set.
+    value: OnceCell<Option<T>>,
+    /// The value is set or accessed as mutable, so it is written to world state when the contract is saved.
+    changed: bool,
+}
+
+impl<T> LazyOption<T> where T: BorshSerialize + BorshDeserialize {
+    /// Instantiate with the value, without interacting with world state until it is assigned to a contract field.
+    pub fn new(value: Option<T>) -> Self {
+        Self {
+            scope: StoragePath::new(),
+            present: OnceCell::from(value.is_some()),
+            value: OnceCell::from(value),
+            changed: true,
+        }
+    }
+
+    /// Check if the value is present. It reads only the presence flag from world state.
+    pub fn is_some(&self) -> bool {
+        *self.present.get_or_init(|| {
+            get(&Self::wskey_present(self.scope.get_path())).is_some_and(|bytes| bool::try_from_slice(&bytes).unwrap())
+        })
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// The value, read from world state at the first access.
+    pub fn get(&self) -> Option<&T> {
+        self.load().as_ref()
+    }
+
+    /// The value as mutable, which is written back to world state when the contract is saved.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.load();
+        self.changed = true;
+        self.value.get_mut().unwrap().as_mut()
+    }
+
+    /// Set the value without reading the previous value from world state.
+    pub fn set(&mut self, value: T) {
+        self.put(Some(value));
+    }
+
+    /// Remove the value without reading it from world state.
+    pub fn clear(&mut self) {
+        self.put(None);
+    }
+
+    /// Set the value, and return the previous value, which is read from world state.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        let previous = self.take();
+        self.set(value);
+        previous
+    }
+
+    /// Remove the value, and return it.
+    pub fn take(&mut self) -> Option<T> {
+        self.load();
+        self.changed = true;
+        self.present = OnceCell::from(false);
+        self.value.get_mut().unwrap().take()
+    }
+
+    fn put(&mut self, value: Option<T>) {
+        self.present = OnceCell::from(value.is_some());
+        self.value = OnceCell::from(value);
+        self.changed = true;
+    }
+
+    /// lazy read from world state, which skips reading the value if the presence flag is false
+    fn load(&self) -> &Option<T> {
+        self.value.get_or_init(|| {
+            if !self.is_some() {
+                return None
+            }
+            let bytes = get(&Self::wskey_value(self.scope.get_path())).expect("Value of LazyOption is missing in world state");
+            Some(T::try_from_slice(&bytes).unwrap())
+        })
+    }
+
+    /// Account Storage State Key for saving the presence flag.
+    fn wskey_present(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[0u8]].concat()
+    }
+
+    /// Account Storage State Key for saving the value.
+    fn wskey_value(parent_key: &[u8]) -> Vec<u8> {
+        [parent_key, &[1u8]].concat()
+    }
+}
+
+impl<T> Storable for LazyOption<T> where T: BorshSerialize + BorshDeserialize {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            scope: field.clone(),
+            present: OnceCell::new(),
+            value: OnceCell::new(),
+            changed: false,
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        if !self.changed {
+            return
+        }
+        let parent_key = field.get_path();
+        match self.value.get() {
+            Some(Some(value)) => {
+                set(&Self::wskey_present(parent_key), &true.try_to_vec().unwrap());
+                set(&Self::wskey_value(parent_key), &value.try_to_vec().unwrap());
+            },
+            _ => {
+                set(&Self::wskey_present(parent_key), &false.try_to_vec().unwrap());
+                delete(&Self::wskey_value(parent_key));
             }
         }
+        self.changed = false;
     }
-}
\ No newline at end of file
+}
+
+/// `ReadOnly` is the contract passed to methods with attribute `view`. It only exposes getters of the fields of the 
+/// contract, which return shared references, so that an attempt to mutate the contract in a view is a compile error 
+/// rather than a write that is silently discarded. The getters are methods of the trait `<Contract>ReadOnly` generated 
+/// by the macro `contract`.
+/// 
+/// ### Example
+/// ```no_run
+/// #[view]
+/// fn balance_of(contract: ReadOnly<'_, Self>, address: PublicAddress) -> u64 {
+///     contract.balances().get(&address).unwrap_or(0)
+/// }
+/// ```
+pub struct ReadOnly<'a, T> {
+    contract: &'a T,
+}
+
+impl<'a, T> ReadOnly<'a, T> {
+    #[doc(hidden)]
+    pub fn __new(contract: &'a T) -> Self {
+        Self { contract }
+    }
+
+    #[doc(hidden)]
+    pub fn __inner(&self) -> &'a T {
+        self.contract
+    }
+}
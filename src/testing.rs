@@ -0,0 +1,742 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines an in-memory runtime for unit testing contracts with `cargo test` on native targets, without deploying
+//! them to a node. [MockWorldState] holds the storage and balances of accounts, and [TestEnv] runs calls against it
+//! with the call context of a transaction, e.g. the calling account and the attached amount. It requires the
+//! feature `testing` of the SDK.
+//! 
+//! ### Example
+//! ```no_run
+//! #[test]
+//! fn deposit() {
+//!     let env = TestEnv::new(CONTRACT).caller(ALICE).amount(100);
+//!     env.state().set_balance(ALICE, 1_000);
+//! 
+//!     let args = ContractMethodInputBuilder::new().add(7u64).to_call_arguments();
+//!     let outcome = env.call(__entrypoint, "deposit", args);
+//!     assert_eq!(outcome.decode::<u64>(), Some(100));
+//!     assert_eq!(env.state().balance(CONTRACT), 100);
+//!     // keys of the contract changed by the call
+//!     assert_eq!(outcome.diff.of(CONTRACT).count(), 1);
+//! 
+//!     // methods of the contract can also be called directly
+//!     let (total, _) = env.run(|| MyContract::load().total());
+//! }
+//! ```
+//! 
+//! Contracts are called through `__entrypoint`, which `#[contract_methods]` generates on native targets along with
+//! `entrypoint`, since a panic cannot unwind out of the `extern "C"` function. A panic in a call reverts all changes
+//! of the call, including those of its internal calls, like a failed transaction. Internal calls are made to the
+//! contracts registered by [MockWorldState::deploy].
+
+use std::{cell::RefCell, collections::{BTreeMap, BTreeSet}, panic::{self, AssertUnwindSafe}, rc::Rc};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::{blockchain::Command, cryptography::{self, PublicAddress, Verifier}, runtime::CallInput};
+use sha3::Digest;
+
+use crate::{host::{self, Host}, runtime::HostFeature};
+
+#[derive(Clone, Default)]
+struct Account {
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    balance: u64,
+}
+
+#[derive(Clone, Default)]
+struct WorldState {
+    accounts: BTreeMap<PublicAddress, Account>,
+    network_storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    contracts: BTreeMap<PublicAddress, fn()>,
+}
+
+/// `MockWorldState` is an in-memory world state of accounts, their storage and balances, the storage of the Network
+/// Account and the deployed contracts. Clones share the same state, so a test can inspect the state changed by calls.
+#[derive(Clone, Default)]
+pub struct MockWorldState(Rc<RefCell<WorldState>>);
+
+impl MockWorldState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value associated with the key in the storage of the account.
+    pub fn get(&self, address: PublicAddress, key: &[u8]) -> Option<Vec<u8>> {
+        self.0.borrow().accounts.get(&address).and_then(|account| account.storage.get(key).cloned())
+    }
+
+    /// Bind the value to the key in the storage of the account. An empty value removes the key.
+    pub fn set(&self, address: PublicAddress, key: &[u8], value: &[u8]) {
+        let mut world_state = self.0.borrow_mut();
+        let storage = &mut world_state.accounts.entry(address).or_default().storage;
+        if value.is_empty() {
+            storage.remove(key);
+        } else {
+            storage.insert(key.to_vec(), value.to_vec());
+        }
+    }
+
+    /// Get the value associated with the key in the storage of the Network Account.
+    pub fn get_network_storage(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.0.borrow().network_storage.get(key).cloned()
+    }
+
+    /// Bind the value to the key in the storage of the Network Account, e.g. the state of a pool for staking
+    /// contracts. An empty value removes the key.
+    pub fn set_network_storage(&self, key: &[u8], value: &[u8]) {
+        let mut world_state = self.0.borrow_mut();
+        if value.is_empty() {
+            world_state.network_storage.remove(key);
+        } else {
+            world_state.network_storage.insert(key.to_vec(), value.to_vec());
+        }
+    }
+
+    pub fn balance(&self, address: PublicAddress) -> u64 {
+        self.0.borrow().accounts.get(&address).map_or(0, |account| account.balance)
+    }
+
+    pub fn set_balance(&self, address: PublicAddress, balance: u64) {
+        self.0.borrow_mut().accounts.entry(address).or_default().balance = balance;
+    }
+
+    /// Deploy a contract at the address, so that internal calls to the address run its entrypoint, i.e. the function
+    /// `__entrypoint` generated by `#[contract_methods]`.
+    pub fn deploy(&self, address: PublicAddress, entrypoint: fn()) {
+        self.0.borrow_mut().contracts.insert(address, entrypoint);
+    }
+
+    fn contract(&self, address: PublicAddress) -> Option<fn()> {
+        self.0.borrow().contracts.get(&address).copied()
+    }
+
+    /// Move balance between accounts. It panics if the balance of `from` is insufficient, which fails the call.
+    fn transfer(&self, from: PublicAddress, to: PublicAddress, amount: u64) {
+        if amount == 0 {
+            return
+        }
+        let from_balance = self.balance(from);
+        if from_balance < amount {
+            panic!("Insufficient balance. Required: {}, Available: {}", amount, from_balance);
+        }
+        self.set_balance(from, from_balance - amount);
+        let to_balance = self.balance(to);
+        self.set_balance(to, to_balance.checked_add(amount).expect("Balance overflow"));
+    }
+
+    fn snapshot(&self) -> WorldState {
+        self.0.borrow().clone()
+    }
+
+    fn restore(&self, snapshot: WorldState) {
+        *self.0.borrow_mut() = snapshot;
+    }
+
+    /// The changes of storage from the snapshot to the current state, in order of address and key.
+    fn diff(&self, snapshot: &WorldState, schemas: &[Schema]) -> StateDiff {
+        fn storage_of<'a>(world_state: &'a WorldState, address: &PublicAddress, empty: &'a BTreeMap<Vec<u8>, Vec<u8>>) -> &'a BTreeMap<Vec<u8>, Vec<u8>> {
+            world_state.accounts.get(address).map_or(empty, |account| &account.storage)
+        }
+        let world_state = self.0.borrow();
+        let empty = BTreeMap::new();
+        let addresses: BTreeSet<&PublicAddress> = snapshot.accounts.keys().chain(world_state.accounts.keys()).collect();
+
+        let mut changes = vec![];
+        for address in addresses {
+            let (before, after) = (storage_of(snapshot, address, &empty), storage_of(&world_state, address, &empty));
+            let keys: BTreeSet<&Vec<u8>> = before.keys().chain(after.keys()).collect();
+            for key in keys {
+                let (before, after) = (before.get(key), after.get(key));
+                if before == after {
+                    continue
+                }
+                let schema = schemas.iter().find(|schema| schema.address == *address && &schema.key == key);
+                changes.push(StorageChange {
+                    address: *address,
+                    key: key.clone(),
+                    name: schema.map(|schema| schema.name.clone()),
+                    before: before.cloned(),
+                    after: after.cloned(),
+                    decoded_before: schema.zip(before).and_then(|(schema, bytes)| (schema.decode)(bytes)),
+                    decoded_after: schema.zip(after).and_then(|(schema, bytes)| (schema.decode)(bytes)),
+                });
+            }
+        }
+        StateDiff { changes }
+    }
+}
+
+/// Kind of a [StorageChange].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A key of the storage of an account whose value is changed by a call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageChange {
+    pub address: PublicAddress,
+    pub key: Vec<u8>,
+    /// Name of the key, if its schema is declared by [TestEnv::schema].
+    pub name: Option<String>,
+    /// The value before the call, None if the key is created.
+    pub before: Option<Vec<u8>>,
+    /// The value after the call, None if the key is deleted.
+    pub after: Option<Vec<u8>>,
+    /// The value before the call in the format of `Debug`, if its schema is declared and it can be deserialized.
+    pub decoded_before: Option<String>,
+    /// The value after the call in the format of `Debug`, if its schema is declared and it can be deserialized.
+    pub decoded_after: Option<String>,
+}
+
+impl StorageChange {
+    pub fn kind(&self) -> ChangeKind {
+        match (&self.before, &self.after) {
+            (None, _) => ChangeKind::Created,
+            (_, None) => ChangeKind::Deleted,
+            _ => ChangeKind::Updated,
+        }
+    }
+
+    /// Deserialize the value before the call. None if there is no value or it is not of type `T`.
+    pub fn before_as<T: BorshDeserialize>(&self) -> Option<T> {
+        self.before.as_ref().and_then(|bytes| T::try_from_slice(bytes).ok())
+    }
+
+    /// Deserialize the value after the call. None if there is no value or it is not of type `T`.
+    pub fn after_as<T: BorshDeserialize>(&self) -> Option<T> {
+        self.after.as_ref().and_then(|bytes| T::try_from_slice(bytes).ok())
+    }
+}
+
+impl std::fmt::Display for StorageChange {
+    /// One line of the change, e.g. `~ 0101..01 ff6f776e6572 (owner): 5 -> 6`, where `+`, `~` and `-` are created,
+    /// updated and deleted. Values are in hex unless decoded by their schema.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.kind() {
+            ChangeKind::Created => '+',
+            ChangeKind::Updated => '~',
+            ChangeKind::Deleted => '-',
+        };
+        let value = |bytes: &Option<Vec<u8>>, decoded: &Option<String>| match (bytes, decoded) {
+            (None, _) => "None".to_string(),
+            (_, Some(decoded)) => decoded.clone(),
+            (Some(bytes), None) => hex(bytes),
+        };
+        write!(f, "{} {} {}", sign, hex(&self.address), hex(&self.key))?;
+        if let Some(name) = &self.name {
+            write!(f, " ({})", name)?;
+        }
+        write!(f, ": {} -> {}", value(&self.before, &self.decoded_before), value(&self.after, &self.decoded_after))
+    }
+}
+
+/// The changes of storage by a call of [TestEnv], including its internal calls, in order of address and key. A key
+/// written with its previous value is not changed.
+/// 
+/// ```no_run
+/// let diff = env.call(__entrypoint, "transfer", args).diff;
+/// assert_eq!(diff.updated().count(), 2);
+/// assert!(diff.of(OTHER_CONTRACT).next().is_none());
+/// println!("{}", diff);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pub changes: Vec<StorageChange>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change of the key in the storage of the account, if it is changed.
+    pub fn get(&self, address: PublicAddress, key: &[u8]) -> Option<&StorageChange> {
+        self.changes.iter().find(|change| change.address == address && change.key == key)
+    }
+
+    /// The changes of the storage of the account.
+    pub fn of(&self, address: PublicAddress) -> impl Iterator<Item = &StorageChange> {
+        self.changes.iter().filter(move |change| change.address == address)
+    }
+
+    pub fn created(&self) -> impl Iterator<Item = &StorageChange> {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Created)
+    }
+
+    pub fn updated(&self) -> impl Iterator<Item = &StorageChange> {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Updated)
+    }
+
+    pub fn deleted(&self) -> impl Iterator<Item = &StorageChange> {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Deleted)
+    }
+}
+
+impl std::fmt::Display for StateDiff {
+    /// One line per change.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// The type and name of a key of the contract, declared by [TestEnv::schema].
+#[derive(Clone)]
+struct Schema {
+    address: PublicAddress,
+    key: Vec<u8>,
+    name: String,
+    decode: fn(&[u8]) -> Option<String>,
+}
+
+fn decode_debug<T: BorshDeserialize + std::fmt::Debug>(bytes: &[u8]) -> Option<String> {
+    T::try_from_slice(bytes).ok().map(|value| format!("{:?}", value))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The outcome of a call by [TestEnv], including its internal calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallOutcome {
+    /// The return value of the call, None if the method has no return value.
+    pub return_value: Option<Vec<u8>>,
+    /// Topics and values of the logs, in order of emission.
+    pub logs: Vec<(Vec<u8>, Vec<u8>)>,
+    /// Network commands deferred by the call.
+    pub commands: Vec<Command>,
+    /// The changes of storage by the call.
+    pub diff: StateDiff,
+}
+
+impl CallOutcome {
+    /// Deserialize the return value. None if there is no return value or it is not of type `T`. A contract with
+    /// `return_envelope` returns values in an `OutputEnvelope`.
+    pub fn decode<T: BorshDeserialize>(&self) -> Option<T> {
+        self.return_value.as_ref().and_then(|bytes| T::try_from_slice(bytes).ok())
+    }
+
+    /// Values of the logs with the topic.
+    pub fn logs_of(&self, topic: &[u8]) -> Vec<&[u8]> {
+        self.logs.iter().filter(|(t, _)| t == topic).map(|(_, value)| value.as_slice()).collect()
+    }
+}
+
+/// `TestEnv` is the context of calls to a contract in a [MockWorldState]: the address of the contract, the calling
+/// account, the attached amount and the block. It is built by chaining its setters from [TestEnv::new].
+#[derive(Clone)]
+pub struct TestEnv {
+    world_state: MockWorldState,
+    contract: PublicAddress,
+    caller: PublicAddress,
+    amount: u64,
+    block_height: u64,
+    block_timestamp: u32,
+    prev_block_hash: [u8; 32],
+    transaction_hash: [u8; 32],
+    random_bytes: Option<[u8; 32]>,
+    schemas: Vec<Schema>,
+}
+
+impl TestEnv {
+    /// Calls to the contract at the address, by the zero address, without attached amount, in block 0 at
+    /// timestamp 0, in an empty world state.
+    pub fn new(contract: PublicAddress) -> Self {
+        Self {
+            world_state: MockWorldState::new(),
+            contract,
+            caller: [0u8; 32],
+            amount: 0,
+            block_height: 0,
+            block_timestamp: 0,
+            prev_block_hash: [0u8; 32],
+            transaction_hash: [0u8; 32],
+            random_bytes: None,
+            schemas: vec![],
+        }
+    }
+
+    /// Run the calls in the world state, e.g. shared with another TestEnv.
+    pub fn world_state(mut self, world_state: MockWorldState) -> Self {
+        self.world_state = world_state;
+        self
+    }
+
+    pub fn caller(mut self, caller: PublicAddress) -> Self {
+        self.caller = caller;
+        self
+    }
+
+    /// The amount attached to the calls, which is transferred from the calling account to the contract. A call
+    /// panics if the balance of the calling account is insufficient.
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn block_height(mut self, block_height: u64) -> Self {
+        self.block_height = block_height;
+        self
+    }
+
+    pub fn block_timestamp(mut self, block_timestamp: u32) -> Self {
+        self.block_timestamp = block_timestamp;
+        self
+    }
+
+    pub fn prev_block_hash(mut self, prev_block_hash: [u8; 32]) -> Self {
+        self.prev_block_hash = prev_block_hash;
+        self
+    }
+
+    pub fn transaction_hash(mut self, transaction_hash: [u8; 32]) -> Self {
+        self.transaction_hash = transaction_hash;
+        self
+    }
+
+    /// Random bytes of the block, which enables the host feature [HostFeature::RandomBytes].
+    pub fn random_bytes(mut self, random_bytes: [u8; 32]) -> Self {
+        self.random_bytes = Some(random_bytes);
+        self
+    }
+
+    /// Declare the name and type of a key of the contract, e.g. the key of a field by its `StoragePath` or a
+    /// `TypedKey`, so that its changes in [StateDiff] are named and decoded.
+    pub fn schema<T: BorshDeserialize + std::fmt::Debug>(mut self, key: &[u8], name: &str) -> Self {
+        self.schemas.push(Schema { address: self.contract, key: key.to_vec(), name: name.to_string(), decode: decode_debug::<T> });
+        self
+    }
+
+    /// The world state of the calls.
+    pub fn state(&self) -> &MockWorldState {
+        &self.world_state
+    }
+
+    pub fn contract(&self) -> PublicAddress {
+        self.contract
+    }
+
+    /// Call the method of the contract through its entrypoint, i.e. the function `__entrypoint` generated by
+    /// `#[contract_methods]`. The arguments are encoded by `ContractMethodInputBuilder::to_call_arguments`. A panic of
+    /// the call is propagated after its changes are reverted.
+    pub fn call(&self, entrypoint: fn(), method: &str, arguments: Vec<u8>) -> CallOutcome {
+        self.execute(method, arguments, entrypoint).1
+    }
+
+    /// Call the method as [TestEnv::call], but return the panic message if the call panics.
+    pub fn try_call(&self, entrypoint: fn(), method: &str, arguments: Vec<u8>) -> Result<CallOutcome, String> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.call(entrypoint, method, arguments))).map_err(|payload| {
+            match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => String::new()
+            }
+        })
+    }
+
+    /// Run the closure in the context of a call with an empty method name, e.g. to call functions of the contract
+    /// directly. Returns the result of the closure and the outcome of the call.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> (R, CallOutcome) {
+        self.execute("", vec![], f)
+    }
+
+    fn execute<R>(&self, method: &str, arguments: Vec<u8>, f: impl FnOnce() -> R) -> (R, CallOutcome) {
+        let snapshot = self.world_state.snapshot();
+        let records = Rc::new(RefCell::new(CallOutcome::default()));
+        let mock_host = Rc::new(MockHost {
+            env: self.clone(),
+            calling_account: self.caller,
+            current_account: self.contract,
+            method: method.as_bytes().to_vec(),
+            arguments,
+            amount: self.amount,
+            is_internal_call: false,
+            return_value: RefCell::new(None),
+            records: records.clone(),
+        });
+        let previous_host = host::set_host(mock_host.clone());
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.world_state.transfer(self.caller, self.contract, self.amount);
+            f()
+        }));
+
+        match previous_host {
+            Some(previous_host) => { host::set_host(previous_host); },
+            None => { host::take_host(); }
+        }
+        match result {
+            Ok(result) => {
+                let mut outcome = records.take();
+                outcome.return_value = mock_host.return_value.take();
+                outcome.diff = self.world_state.diff(&snapshot, &self.schemas);
+                (result, outcome)
+            },
+            Err(payload) => {
+                self.world_state.restore(snapshot);
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Keys and values of the storage of an account, as recorded in [RecordedCall].
+pub type StorageEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// `RecordedCall` is a call exported from a network, e.g. a failed transaction, with the state it read, so that it
+/// can be replayed in a test against the local build of the contract. The exporter fetches the transaction, its
+/// block, and the values of the keys read by the call in the state before the transaction, e.g. by the client of a
+/// node, and saves the call in Borsh.
+/// 
+/// ### Example
+/// ```no_run
+/// #[test]
+/// fn withdraw_regression() {
+///     let call = RecordedCall::load("tests/data/withdraw_failed.bin").unwrap();
+///     assert!(call.replay(__entrypoint).is_ok());
+/// }
+/// ```
+/// 
+/// Contracts called by the recorded call are not recorded. They are deployed to the state of [RecordedCall::env]
+/// before replaying it by [TestEnv::try_call].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordedCall {
+    /// The address of the called contract.
+    pub contract: PublicAddress,
+    pub caller: PublicAddress,
+    pub method: String,
+    /// The arguments of the call as in the transaction.
+    pub arguments: Option<Vec<Vec<u8>>>,
+    pub amount: u64,
+    pub block_height: u64,
+    pub block_timestamp: u32,
+    pub prev_block_hash: [u8; 32],
+    pub transaction_hash: [u8; 32],
+    /// Random bytes of the block, if the runtime provides them.
+    pub random_bytes: Option<[u8; 32]>,
+    /// Keys and values of the storage of accounts before the call.
+    pub storage: Vec<(PublicAddress, StorageEntries)>,
+    /// Keys and values of the storage of the Network Account before the call.
+    pub network_storage: StorageEntries,
+    /// Balances of accounts before the call. The calling account must have the attached amount.
+    pub balances: Vec<(PublicAddress, u64)>,
+}
+
+impl RecordedCall {
+    /// Deserialize a recorded call from its Borsh serialization.
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        Self::try_from_slice(bytes)
+    }
+
+    /// Read a recorded call from a file of its Borsh serialization.
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Write the recorded call to a file, e.g. by an exporter.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.try_to_vec()?)
+    }
+
+    /// A test environment of the call, in a new world state with the recorded state.
+    pub fn env(&self) -> TestEnv {
+        let world_state = MockWorldState::new();
+        for (address, storage) in &self.storage {
+            for (key, value) in storage {
+                world_state.set(*address, key, value);
+            }
+        }
+        for (key, value) in &self.network_storage {
+            world_state.set_network_storage(key, value);
+        }
+        for (address, balance) in &self.balances {
+            world_state.set_balance(*address, *balance);
+        }
+
+        let env = TestEnv::new(self.contract)
+            .world_state(world_state)
+            .caller(self.caller)
+            .amount(self.amount)
+            .block_height(self.block_height)
+            .block_timestamp(self.block_timestamp)
+            .prev_block_hash(self.prev_block_hash)
+            .transaction_hash(self.transaction_hash);
+        match self.random_bytes {
+            Some(random_bytes) => env.random_bytes(random_bytes),
+            None => env
+        }
+    }
+
+    /// Replay the call against the entrypoint in [RecordedCall::env]. Returns the panic message if the call panics.
+    pub fn replay(&self, entrypoint: fn()) -> Result<CallOutcome, String> {
+        // the host passes the arguments to the contract without the Option, as for internal calls
+        let arguments = self.arguments.as_ref().map(|arguments| arguments.try_to_vec().unwrap()).unwrap_or_default();
+        self.env().try_call(entrypoint, &self.method, arguments)
+    }
+}
+
+/// The [Host] of a call by [TestEnv], or of one of its internal calls.
+struct MockHost {
+    env: TestEnv,
+    calling_account: PublicAddress,
+    current_account: PublicAddress,
+    method: Vec<u8>,
+    arguments: Vec<u8>,
+    amount: u64,
+    is_internal_call: bool,
+    return_value: RefCell<Option<Vec<u8>>>,
+    /// Logs and commands of the call and its internal calls.
+    records: Rc<RefCell<CallOutcome>>,
+}
+
+impl Host for MockHost {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.env.world_state.get(self.current_account, key)
+    }
+
+    fn get_network_storage(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.env.world_state.get_network_storage(key)
+    }
+
+    fn set(&self, key: &[u8], value: &[u8]) {
+        self.env.world_state.set(self.current_account, key, value)
+    }
+
+    fn balance(&self) -> u64 {
+        self.env.world_state.balance(self.current_account)
+    }
+
+    fn block_height(&self) -> u64 {
+        self.env.block_height
+    }
+
+    fn block_timestamp(&self) -> u32 {
+        self.env.block_timestamp
+    }
+
+    fn prev_block_hash(&self) -> [u8; 32] {
+        self.env.prev_block_hash
+    }
+
+    fn random_bytes(&self) -> [u8; 32] {
+        self.env.random_bytes
+            .unwrap_or_else(|| panic!("Host feature {:?} is not supported by this runtime", HostFeature::RandomBytes))
+    }
+
+    fn calling_account(&self) -> PublicAddress {
+        self.calling_account
+    }
+
+    fn current_account(&self) -> PublicAddress {
+        self.current_account
+    }
+
+    fn method(&self) -> Vec<u8> {
+        self.method.clone()
+    }
+
+    fn arguments(&self) -> Vec<u8> {
+        self.arguments.clone()
+    }
+
+    fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    fn is_internal_call(&self) -> bool {
+        self.is_internal_call
+    }
+
+    fn transaction_hash(&self) -> [u8; 32] {
+        self.env.transaction_hash
+    }
+
+    fn call(&self, input: CallInput) -> Vec<u8> {
+        let entrypoint = self.env.world_state.contract(input.target)
+            .unwrap_or_else(|| panic!("No contract is deployed at {:?}", input.target));
+        let amount = input.amount.unwrap_or(0);
+        self.env.world_state.transfer(self.current_account, input.target, amount);
+
+        let callee = Rc::new(MockHost {
+            env: self.env.clone(),
+            calling_account: self.current_account,
+            current_account: input.target,
+            method: input.method.into_bytes(),
+            arguments: input.arguments.map(|arguments| arguments.try_to_vec().unwrap()).unwrap_or_default(),
+            amount,
+            is_internal_call: true,
+            return_value: RefCell::new(None),
+            records: self.records.clone(),
+        });
+        // a panic fails the whole call, and the host is restored by TestEnv
+        let caller = host::set_host(callee.clone());
+        entrypoint();
+        if let Some(caller) = caller {
+            host::set_host(caller);
+        }
+        let return_value = callee.return_value.take();
+        return_value.unwrap_or_default()
+    }
+
+    fn return_value(&self, value: &[u8]) {
+        *self.return_value.borrow_mut() = Some(value.to_vec());
+    }
+
+    fn transfer(&self, recipient: PublicAddress, amount: u64) {
+        self.env.world_state.transfer(self.current_account, recipient, amount)
+    }
+
+    fn defer(&self, command: Command) {
+        self.records.borrow_mut().commands.push(command);
+    }
+
+    fn log(&self, topic: &[u8], value: &[u8]) {
+        self.records.borrow_mut().logs.push((topic.to_vec(), value.to_vec()));
+    }
+
+    fn sha256(&self, input: &[u8]) -> Vec<u8> {
+        cryptography::sha256(input).to_vec()
+    }
+
+    fn keccak256(&self, input: &[u8]) -> Vec<u8> {
+        sha3::Keccak256::digest(input).to_vec()
+    }
+
+    fn ripemd(&self, input: &[u8]) -> Vec<u8> {
+        ripemd::Ripemd160::digest(input).to_vec()
+    }
+
+    /// It panics if the address or the signature is malformed, as the runtime fails the call.
+    fn verify_ed25519_signature(&self, input: &[u8], signature: &[u8], address: &[u8]) -> bool {
+        let public_key = cryptography::PublicKey::from_bytes(address).expect("Invalid ed25519 public key");
+        let signature = cryptography::Signature::try_from(signature).expect("Invalid ed25519 signature");
+        public_key.verify(input, &signature).is_ok()
+    }
+
+    /// It panics if the public key or the signature is malformed, as the runtime fails the call, or if the feature
+    /// `secp256k1` of the SDK is not enabled, as the contract cannot import the host function.
+    fn verify_secp256k1_signature(&self, input: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+        if !cfg!(feature = "secp256k1") {
+            panic!("Host feature {:?} is not supported by this runtime", HostFeature::Secp256k1)
+        }
+        let public_key = libsecp256k1::PublicKey::parse_slice(public_key, None).expect("Invalid secp256k1 public key");
+        let signature = libsecp256k1::Signature::parse_standard_slice(signature).expect("Invalid secp256k1 signature");
+        let message = libsecp256k1::Message::parse(&cryptography::sha256(input));
+        libsecp256k1::verify(&message, &signature, &public_key)
+    }
+
+    fn supports(&self, feature: HostFeature) -> bool {
+        feature.is_in_base_interface() 
+            || (feature == HostFeature::RandomBytes && self.env.random_bytes.is_some())
+            || (cfg!(feature = "secp256k1") && feature == HostFeature::Secp256k1)
+    }
+}
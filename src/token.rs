@@ -0,0 +1,416 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the interfaces shared by token contracts. A contract that can hold tokens implements [TokenReceiver] and
+//! exposes it as the contract method `on_token_received`. A token contract moves tokens with [safe_transfer], which
+//! calls that method when the recipient is a contract, so that tokens are never stranded in a contract that is not
+//! aware of them.
+//! 
+//! The runtime cannot tell a contract from an external account, so the sender states it with [Recipient], and the 
+//! token contract trusts the statement. See [Recipient] for what a recipient contract can and cannot rely on.
+//! 
+//! A reference of a transfer, e.g. the deposit reference of an exchange, is attached by the caller as a
+//! [Memo](crate::memo::Memo) after the arguments of the transfer method, and read by [memo::read](crate::memo::read).
+//! 
+//! Token contracts keep approvals of spenders in [Allowances], which also implements gasless approval by [Permit]:
+//! the owner signs a permit off-chain, and anyone, e.g. a relayer paying the gas, submits it to the token contract.
+//! 
+//! Token contracts that have to enumerate their holders on-chain, e.g. for compliance, keep them in a [HolderRegistry],
+//! which can be disabled so that other token contracts do not pay for it.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use pchain_types::cryptography::PublicAddress;
+
+use crate::{blockchain, collections::{Cursor, FastMap, IterableSet, Page}, crypto, internal, method::ContractMethodInputBuilder, nonce::NonceManager, transaction, Storable, StoragePath};
+
+/// Name of the contract method that is called on the recipient contract by [safe_transfer].
+pub const ON_TOKEN_RECEIVED_METHOD: &str = "on_token_received";
+
+/// `TokenReceiver` is the receiver side of [safe_transfer]. A contract implements it and exposes it as a contract
+/// method named [ON_TOKEN_RECEIVED_METHOD]:
+/// 
+/// ```no_run
+/// #[contract_methods]
+/// impl Vault {
+///     #[call(internal_only)]
+///     fn on_token_received(&mut self, operator: PublicAddress, from: PublicAddress, amount: u64, data: Vec<u8>) -> bool {
+///         TokenReceiver::on_token_received(self, operator, from, amount, data)
+///     }
+/// }
+/// ```
+/// 
+/// The token contract is the calling account of the method.
+pub trait TokenReceiver {
+    /// Called after `amount` tokens are transferred from `from` to this contract by `operator`. Returns true to accept
+    /// the tokens. Returning false (or panicking) reverts the transfer.
+    fn on_token_received(&mut self, operator: PublicAddress, from: PublicAddress, amount: u64, data: Vec<u8>) -> bool;
+}
+
+/// Recipient of [safe_transfer].
+/// 
+/// ## Trust Assumption
+/// 
+/// Whether the recipient is a contract is declared by the sender, e.g. by choosing between a `transfer` and a 
+/// `transfer_to_contract` method of the token contract, and is not verified: the runtime has no host function to tell 
+/// whether an address has contract code. As a result:
+/// - A sender that declares a contract as [Recipient::Account] transfers the tokens without calling 
+///   `on_token_received`, so the tokens can be stranded in a contract that is not aware of them. Only the sender loses.
+/// - A sender that declares an external account as [Recipient::Contract] makes the call fail, which reverts the 
+///   transfer.
+/// 
+/// A recipient contract therefore cannot assume that `on_token_received` is called for every transfer to it, e.g. its 
+/// token balance can be larger than the sum of the amounts it was notified of. It should credit depositors only in 
+/// `on_token_received`, and must not derive their credit from its token balance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recipient {
+    /// An external account. Tokens are transferred without calling it.
+    Account(PublicAddress),
+    /// A contract, which must implement [TokenReceiver] to accept the tokens.
+    Contract(PublicAddress),
+}
+
+impl Recipient {
+    pub fn address(&self) -> PublicAddress {
+        match self {
+            Recipient::Account(address) | Recipient::Contract(address) => *address
+        }
+    }
+}
+
+/// Transfer `amount` tokens in `balances` from `from` to `to`. If the recipient is a contract, its method
+/// `on_token_received` is called with the calling account of this contract as the operator.
+/// 
+/// Panics if the balance of `from` is insufficient, or if the recipient contract does not accept the tokens, which
+/// reverts the transfer. A contract without the method panics when it is called, and so also reverts the transfer.
+/// 
+/// `balances` is saved after the call returns, so the recipient should not call back into the token contract to
+/// read the balances during `on_token_received`.
+pub fn safe_transfer(balances: &mut FastMap<PublicAddress, u64>, from: PublicAddress, to: Recipient, amount: u64, data: Vec<u8>) {
+    let recipient = to.address();
+    let from_balance = balances.get(&from).unwrap_or(0);
+    if from_balance < amount {
+        panic!("Insufficient token balance. Required: {}, Available: {}", amount, from_balance);
+    }
+    if from != recipient {
+        let to_balance = balances.get(&recipient).unwrap_or(0);
+        balances.insert(&from, from_balance - amount);
+        balances.insert(&recipient, to_balance.checked_add(amount).expect("Token balance overflow"));
+    }
+
+    if let Recipient::Contract(recipient) = to {
+        check_on_token_received(recipient, transaction::calling_account(), from, amount, data);
+    }
+}
+
+/// Call `on_token_received` of the recipient contract, and panic unless it accepts the tokens. Token contracts that
+/// keep balances in their own structure call this after moving the tokens.
+pub fn check_on_token_received(recipient: PublicAddress, operator: PublicAddress, from: PublicAddress, amount: u64, data: Vec<u8>) {
+    let mut args = ContractMethodInputBuilder::new();
+    args.add(operator).add(from).add(amount).add(data);
+    let accepted: Option<bool> = internal::call(recipient, ON_TOKEN_RECEIVED_METHOD, args.to_call_arguments(), 0);
+    if accepted != Some(true) {
+        panic!("Token transfer is declined by the recipient contract.");
+    }
+}
+
+/// Prefix of the signed bytes of a [Permit], which separates them from other messages signed by the same keys.
+pub const PERMIT_DOMAIN: &[u8] = b"pchain-token-permit";
+
+/// Topic of the log emitted when an allowance is set, by [Allowances::approve] or [Allowances::permit].
+pub const APPROVAL_TOPIC: &[u8] = b"Approval";
+
+/// Value of the log with topic [APPROVAL_TOPIC].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ApprovalEvent {
+    pub owner: PublicAddress,
+    pub spender: PublicAddress,
+    pub amount: u64,
+}
+
+/// An approval signed by the owner of tokens, which sets the allowance of `spender` to `amount` when it is submitted
+/// to the token contract by anyone before `deadline`. The signature is an ed25519 signature by the owner over
+/// [Permit::signing_bytes].
+/// 
+/// The permit is bound to the token contract and to the next nonce of the owner in that contract (see
+/// [Allowances::nonce]), so it is accepted once, by one contract. Submitting another permit or cancelling by
+/// [Allowances::cancel_permits] invalidates the permits signed with the same nonce.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Permit {
+    /// Address of the token contract.
+    pub token: PublicAddress,
+    pub owner: PublicAddress,
+    pub spender: PublicAddress,
+    pub amount: u64,
+    pub nonce: u64,
+    /// The permit is accepted if the block timestamp is at most this value.
+    pub deadline: u32,
+}
+
+impl Permit {
+    /// The bytes signed by the owner, i.e. [PERMIT_DOMAIN] followed by the borsh-serialized permit.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        [PERMIT_DOMAIN.to_vec(), self.try_to_vec().unwrap()].concat()
+    }
+}
+
+/// Error of spending an allowance or submitting a permit to [Allowances].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowanceError {
+    /// The allowance of the spender is less than the amount.
+    InsufficientAllowance { allowance: u64 },
+    /// The block timestamp is later than the deadline of the permit.
+    PermitExpired,
+    /// The signature is not the owner's signature over the permit with the next nonce of the owner.
+    InvalidSignature,
+}
+
+impl std::fmt::Display for AllowanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AllowanceError::InsufficientAllowance { allowance } => write!(f, "insufficient allowance, available {}", allowance),
+            AllowanceError::PermitExpired => write!(f, "permit expired"),
+            AllowanceError::InvalidSignature => write!(f, "invalid permit signature"),
+        }
+    }
+}
+
+/// `Allowances` is a contract field of token contracts that stores the amounts that spenders may transfer on behalf
+/// of owners, and the nonces of the owners' permits. An allowance of `u64::MAX` is unlimited, and is not decreased
+/// by spending.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn approve(&mut self, spender: PublicAddress, amount: u64) {
+///     self.allowances.approve(transaction::calling_account(), spender, amount);
+/// }
+/// 
+/// // submitted by a relayer on behalf of the owner
+/// #[call]
+/// fn permit(&mut self, owner: PublicAddress, spender: PublicAddress, amount: u64, deadline: u32, signature: Vec<u8>) {
+///     self.allowances.permit(owner, spender, amount, deadline, signature).unwrap();
+/// }
+/// 
+/// #[call]
+/// fn transfer_from(&mut self, from: PublicAddress, to: PublicAddress, amount: u64) {
+///     self.allowances.spend(from, transaction::calling_account(), amount).unwrap();
+///     token::safe_transfer(&mut self.balances, from, Recipient::Account(to), amount, vec![]);
+/// }
+/// ```
+/// 
+/// The owner signs the permit off-chain, with the address of the token contract and its nonce from a view method:
+/// 
+/// ```no_run
+/// let permit = Permit { token, owner, spender, amount, nonce, deadline };
+/// let signature = keypair.sign(&permit.signing_bytes());
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Allowances|P, 0|`FastMap<(PublicAddress, PublicAddress), u64>`, keyed by owner and spender|
+/// |Permit Nonces|P, 1|[NonceManager]|
+/// 
+/// - P: parent key
+pub struct Allowances {
+    allowances: FastMap<(PublicAddress, PublicAddress), u64>,
+    nonces: NonceManager,
+}
+
+impl Allowances {
+    pub fn new() -> Self {
+        Self { allowances: FastMap::new(), nonces: NonceManager::new() }
+    }
+
+    /// The amount that the spender may transfer on behalf of the owner.
+    pub fn allowance(&self, owner: PublicAddress, spender: PublicAddress) -> u64 {
+        self.allowances.get(&(owner, spender)).unwrap_or(0)
+    }
+
+    /// Set the allowance of the spender, and log an [ApprovalEvent]. The caller must check that the owner is the
+    /// calling account.
+    pub fn approve(&mut self, owner: PublicAddress, spender: PublicAddress, amount: u64) {
+        self.allowances.insert(&(owner, spender), amount);
+        let event = ApprovalEvent { owner, spender, amount };
+        internal::log(APPROVAL_TOPIC, &event.try_to_vec().unwrap());
+    }
+
+    /// Decrease the allowance of the spender by the amount to be transferred, unless it is unlimited.
+    pub fn spend(&mut self, owner: PublicAddress, spender: PublicAddress, amount: u64) -> Result<(), AllowanceError> {
+        let allowance = self.allowance(owner, spender);
+        if allowance < amount {
+            return Err(AllowanceError::InsufficientAllowance { allowance })
+        }
+        if allowance != u64::MAX {
+            self.allowances.insert(&(owner, spender), allowance - amount);
+        }
+        Ok(())
+    }
+
+    /// The nonce of the next permit of the owner.
+    pub fn nonce(&self, owner: PublicAddress) -> u64 {
+        self.nonces.next_nonce(owner)
+    }
+
+    /// Set the allowance of the spender by a [Permit] of this contract with the next nonce of the owner, signed by
+    /// the owner. The nonce is consumed, and an [ApprovalEvent] is logged. Anyone can submit the permit.
+    pub fn permit(&mut self, owner: PublicAddress, spender: PublicAddress, amount: u64, deadline: u32, signature: Vec<u8>) -> Result<(), AllowanceError> {
+        if blockchain::timestamp() > deadline {
+            return Err(AllowanceError::PermitExpired)
+        }
+        let nonce = self.nonce(owner);
+        let permit = Permit { token: transaction::current_account(), owner, spender, amount, nonce, deadline };
+        // the host traps on signatures of other lengths
+        if signature.len() != 64 || !crypto::verify_ed25519_signature(permit.signing_bytes(), signature, owner.to_vec()) {
+            return Err(AllowanceError::InvalidSignature)
+        }
+        self.nonces.consume(owner, nonce).unwrap();
+        self.approve(owner, spender, amount);
+        Ok(())
+    }
+
+    /// Invalidate the permits of the owner that are not yet submitted, by consuming the next nonce. The caller must
+    /// check that the owner is the calling account.
+    pub fn cancel_permits(&mut self, owner: PublicAddress) {
+        let nonce = self.nonce(owner);
+        self.nonces.consume(owner, nonce).unwrap();
+    }
+}
+
+impl Storable for Allowances {
+    fn __load_storage(field: &StoragePath) -> Self {
+        Self {
+            allowances: Storable::__load_storage(&field.add(0)),
+            nonces: Storable::__load_storage(&field.add(1)),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        self.allowances.__save_storage(&field.add(0));
+        self.nonces.__save_storage(&field.add(1));
+    }
+}
+
+/// `HolderRegistry` is a contract field of token contracts that keeps the set of accounts with a non-zero balance, so
+/// that the holders can be enumerated page by page. It is switched on or off when it is created: a disabled registry
+/// neither reads nor writes the set, so that token contracts that do not need it only pay for reading the switch.
+/// 
+/// The switch is saved with the registry, so upgrading the contract code does not change it. A registry cannot be
+/// enabled after it is created, since it would miss the holders whose balances did not change since. A
+/// [FungibleToken](crate::standards::fungible_token::FungibleToken) keeps its holders in a registry if it is created
+/// with [with_holders](crate::standards::fungible_token::FungibleToken::with_holders).
+/// 
+/// ### Example
+/// ```no_run
+/// #[contract]
+/// struct MyToken {
+///     balances: FastMap<PublicAddress, u64>,
+///     holders: HolderRegistry,
+/// }
+/// 
+/// #[call]
+/// fn init(&mut self) {
+///     // enable the registry to enumerate holders on-chain, or pass false to save gas otherwise
+///     self.holders = HolderRegistry::new(true);
+/// }
+/// 
+/// #[call]
+/// fn transfer(&mut self, to: PublicAddress, amount: u64) {
+///     let from = transaction::calling_account();
+///     token::safe_transfer(&mut self.balances, from, Recipient::Account(to), amount, vec![]);
+///     self.holders.update(&self.balances, &[from, to]);
+/// }
+/// 
+/// #[view]
+/// fn holders(contract: ReadOnly<'_, Self>, cursor: Cursor) -> Page<PublicAddress> {
+///     contract.holders().page(&cursor, 50)
+/// }
+/// ```
+/// 
+/// ### Storage Model
+/// 
+/// |Component|Key|Value (Data type) |
+/// |:---|:---|:---|
+/// |Enabled|P, 0|bool|
+/// |Holders|P, 1|[IterableSet] of the holders, if enabled|
+/// 
+/// - P: parent key
+pub struct HolderRegistry {
+    enabled: bool,
+    /// The switch is in world state, i.e. the registry is loaded or saved, so that it is not written again.
+    enabled_saved: bool,
+    /// None if the registry is disabled, so that the set is not loaded from world state.
+    holders: Option<IterableSet<PublicAddress>>,
+}
+
+impl HolderRegistry {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, enabled_saved: false, holders: enabled.then(IterableSet::new) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Add the accounts with a non-zero balance to the registry, and remove the others, after their balances
+    /// changed. It does nothing if the registry is disabled.
+    pub fn update(&mut self, balances: &FastMap<PublicAddress, u64>, accounts: &[PublicAddress]) {
+        if let Some(holders) = &mut self.holders {
+            for account in accounts {
+                if balances.get(account).unwrap_or(0) > 0 {
+                    holders.insert(account);
+                } else {
+                    holders.remove(account);
+                }
+            }
+        }
+    }
+
+    /// Number of holders. Panics if the registry is disabled.
+    pub fn len(&self) -> u64 {
+        self.enabled_holders().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the account is a holder. Panics if the registry is disabled.
+    pub fn contains(&self, account: &PublicAddress) -> bool {
+        self.enabled_holders().contains(account)
+    }
+
+    /// Read at most `limit` holders from the position of the cursor, in the order that they became holders, as in
+    /// [IterableSet::page]. Panics if the registry is disabled.
+    pub fn page(&self, cursor: &Cursor, limit: usize) -> Page<PublicAddress> {
+        self.enabled_holders().page(cursor, limit)
+    }
+
+    fn enabled_holders(&self) -> &IterableSet<PublicAddress> {
+        self.holders.as_ref().expect("Holder registry is disabled")
+    }
+}
+
+impl Storable for HolderRegistry {
+    fn __load_storage(field: &StoragePath) -> Self {
+        let enabled: bool = Storable::__load_storage(&field.add(0));
+        Self {
+            enabled,
+            enabled_saved: true,
+            holders: enabled.then(|| Storable::__load_storage(&field.add(1))),
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        if !self.enabled_saved {
+            self.enabled.__save_storage(&field.add(0));
+            self.enabled_saved = true;
+        }
+        if let Some(holders) = &mut self.holders {
+            holders.__save_storage(&field.add(1));
+        }
+    }
+}
@@ -0,0 +1,303 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines the trace of the calls to the [Host] made by contracts on native targets, e.g. in tests with
+//! [testing](crate::testing), for debugging which storage keys a method reads and writes, and which contracts it calls.
+//! 
+//! This module is available with the feature `trace` on non-WASM targets. With the feature, every call to the Host is
+//! counted by the name of the function. The calls that touch storage, other accounts or logs are also recorded with
+//! their keys, methods and topics after [log_calls] is enabled.
+//! 
+//! ```no_run
+//! pchain_sdk::trace::reset();
+//! pchain_sdk::trace::log_calls(true);
+//! let outcome = env.call(my_contract::__entrypoint, "deposit", args);
+//! println!("{}", pchain_sdk::trace::report());
+//! ```
+
+use std::{cell::{Cell, RefCell}, collections::BTreeMap, rc::Rc};
+
+use pchain_types::{blockchain::Command, cryptography::PublicAddress, runtime::CallInput};
+
+use crate::{host::Host, runtime::HostFeature};
+
+/// A call to the Host recorded by the trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TracedCall {
+    Get { key: Vec<u8> },
+    GetNetworkStorage { key: Vec<u8> },
+    /// A write to storage. An empty value deletes the key.
+    Set { key: Vec<u8>, value_len: usize },
+    Call { target: PublicAddress, method: String, amount: u64 },
+    Transfer { recipient: PublicAddress, amount: u64 },
+    Log { topic: Vec<u8>, value_len: usize },
+}
+
+impl std::fmt::Display for TracedCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TracedCall::Get { key } => write!(f, "get {}", hex(key)),
+            TracedCall::GetNetworkStorage { key } => write!(f, "get_network_storage {}", hex(key)),
+            TracedCall::Set { key, value_len: 0 } => write!(f, "set {} (deleted)", hex(key)),
+            TracedCall::Set { key, value_len } => write!(f, "set {} ({} bytes)", hex(key), value_len),
+            TracedCall::Call { target, method, amount } => write!(f, "call {}::{} (amount {})", hex(target), method, amount),
+            TracedCall::Transfer { recipient, amount } => write!(f, "transfer {} to {}", amount, hex(recipient)),
+            TracedCall::Log { topic, value_len } => write!(f, "log {} ({} bytes)", String::from_utf8_lossy(topic), value_len),
+        }
+    }
+}
+
+/// The calls to the Host since the trace was reset, returned by [report].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceReport {
+    /// Number of calls by the name of the function of [Host], e.g. `get`.
+    pub counts: BTreeMap<&'static str, u64>,
+    /// The calls in order, if [log_calls] is enabled.
+    pub calls: Vec<TracedCall>,
+}
+
+impl TraceReport {
+    /// Number of calls of the function of [Host].
+    pub fn count(&self, function: &str) -> u64 {
+        self.counts.get(function).copied().unwrap_or(0)
+    }
+
+    /// The keys of the storage of the contracts read by the recorded calls, in order of first read.
+    pub fn keys_read(&self) -> Vec<&[u8]> {
+        let mut keys: Vec<&[u8]> = vec![];
+        for call in &self.calls {
+            if let TracedCall::Get { key } = call {
+                if !keys.contains(&key.as_slice()) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// The keys of the storage of the contracts written by the recorded calls, in order of first write.
+    pub fn keys_written(&self) -> Vec<&[u8]> {
+        let mut keys: Vec<&[u8]> = vec![];
+        for call in &self.calls {
+            if let TracedCall::Set { key, .. } = call {
+                if !keys.contains(&key.as_slice()) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+}
+
+/// Formats the counts, one function per line, e.g. `get: 3`, followed by the recorded calls.
+impl std::fmt::Display for TraceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (function, count) in &self.counts {
+            writeln!(f, "{}: {}", function, count)?;
+        }
+        for call in &self.calls {
+            writeln!(f, "  {}", call)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static TRACE: RefCell<TraceReport> = RefCell::new(TraceReport::default());
+    static LOG_CALLS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The calls to the Host in this thread since the last [reset].
+pub fn report() -> TraceReport {
+    TRACE.with(|trace| trace.borrow().clone())
+}
+
+/// Clear the counts and the recorded calls in this thread.
+pub fn reset() {
+    TRACE.with(|trace| *trace.borrow_mut() = TraceReport::default());
+}
+
+/// Enable or disable recording the calls with their keys, methods and topics in this thread. It is disabled by
+/// default, in which case the calls are only counted.
+pub fn log_calls(enabled: bool) {
+    LOG_CALLS.with(|log_calls| log_calls.set(enabled));
+}
+
+/// Count the call of the function, and record the call returned by `call` if recording is enabled.
+fn record(function: &'static str, call: impl FnOnce() -> Option<TracedCall>) {
+    let log_calls = LOG_CALLS.with(Cell::get);
+    TRACE.with(|trace| {
+        let mut trace = trace.borrow_mut();
+        *trace.counts.entry(function).or_default() += 1;
+        if log_calls {
+            trace.calls.extend(call());
+        }
+    });
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The Host returned by `host::current` with the feature `trace`, which records each call before calling the Host set
+/// by [set_host](crate::host::set_host).
+pub(crate) struct TracedHost(pub(crate) Rc<dyn Host>);
+
+impl Host for TracedHost {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        record("get", || Some(TracedCall::Get { key: key.to_vec() }));
+        self.0.get(key)
+    }
+
+    fn get_network_storage(&self, key: &[u8]) -> Option<Vec<u8>> {
+        record("get_network_storage", || Some(TracedCall::GetNetworkStorage { key: key.to_vec() }));
+        self.0.get_network_storage(key)
+    }
+
+    fn set(&self, key: &[u8], value: &[u8]) {
+        record("set", || Some(TracedCall::Set { key: key.to_vec(), value_len: value.len() }));
+        self.0.set(key, value)
+    }
+
+    fn balance(&self) -> u64 {
+        record("balance", || None);
+        self.0.balance()
+    }
+
+    fn block_height(&self) -> u64 {
+        record("block_height", || None);
+        self.0.block_height()
+    }
+
+    fn block_timestamp(&self) -> u32 {
+        record("block_timestamp", || None);
+        self.0.block_timestamp()
+    }
+
+    fn prev_block_hash(&self) -> [u8; 32] {
+        record("prev_block_hash", || None);
+        self.0.prev_block_hash()
+    }
+
+    fn random_bytes(&self) -> [u8; 32] {
+        record("random_bytes", || None);
+        self.0.random_bytes()
+    }
+
+    fn calling_account(&self) -> PublicAddress {
+        record("calling_account", || None);
+        self.0.calling_account()
+    }
+
+    fn current_account(&self) -> PublicAddress {
+        record("current_account", || None);
+        self.0.current_account()
+    }
+
+    fn method(&self) -> Vec<u8> {
+        record("method", || None);
+        self.0.method()
+    }
+
+    fn arguments(&self) -> Vec<u8> {
+        record("arguments", || None);
+        self.0.arguments()
+    }
+
+    fn amount(&self) -> u64 {
+        record("amount", || None);
+        self.0.amount()
+    }
+
+    fn is_internal_call(&self) -> bool {
+        record("is_internal_call", || None);
+        self.0.is_internal_call()
+    }
+
+    fn transaction_hash(&self) -> [u8; 32] {
+        record("transaction_hash", || None);
+        self.0.transaction_hash()
+    }
+
+    fn priority_fee_per_gas(&self) -> u64 {
+        record("priority_fee_per_gas", || None);
+        self.0.priority_fee_per_gas()
+    }
+
+    fn gas_price(&self) -> u64 {
+        record("gas_price", || None);
+        self.0.gas_price()
+    }
+
+    fn gas_remaining(&self) -> u64 {
+        record("gas_remaining", || None);
+        self.0.gas_remaining()
+    }
+
+    fn call(&self, input: CallInput) -> Vec<u8> {
+        record("call", || Some(TracedCall::Call {
+            target: input.target,
+            method: input.method.clone(),
+            amount: input.amount.unwrap_or(0),
+        }));
+        self.0.call(input)
+    }
+
+    fn return_value(&self, value: &[u8]) {
+        record("return_value", || None);
+        self.0.return_value(value)
+    }
+
+    fn transfer(&self, recipient: PublicAddress, amount: u64) {
+        record("transfer", || Some(TracedCall::Transfer { recipient, amount }));
+        self.0.transfer(recipient, amount)
+    }
+
+    fn defer(&self, command: Command) {
+        record("defer", || None);
+        self.0.defer(command)
+    }
+
+    fn log(&self, topic: &[u8], value: &[u8]) {
+        record("log", || Some(TracedCall::Log { topic: topic.to_vec(), value_len: value.len() }));
+        self.0.log(topic, value)
+    }
+
+    fn log_batch(&self, logs: &[(&[u8], &[u8])]) {
+        for (topic, value) in logs {
+            record("log", || Some(TracedCall::Log { topic: topic.to_vec(), value_len: value.len() }));
+        }
+        self.0.log_batch(logs)
+    }
+
+    fn sha256(&self, input: &[u8]) -> Vec<u8> {
+        record("sha256", || None);
+        self.0.sha256(input)
+    }
+
+    fn keccak256(&self, input: &[u8]) -> Vec<u8> {
+        record("keccak256", || None);
+        self.0.keccak256(input)
+    }
+
+    fn ripemd(&self, input: &[u8]) -> Vec<u8> {
+        record("ripemd", || None);
+        self.0.ripemd(input)
+    }
+
+    fn verify_ed25519_signature(&self, input: &[u8], signature: &[u8], address: &[u8]) -> bool {
+        record("verify_ed25519_signature", || None);
+        self.0.verify_ed25519_signature(input, signature, address)
+    }
+
+    fn verify_secp256k1_signature(&self, input: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
+        record("verify_secp256k1_signature", || None);
+        self.0.verify_secp256k1_signature(input, signature, public_key)
+    }
+
+    fn supports(&self, feature: HostFeature) -> bool {
+        self.0.supports(feature)
+    }
+}
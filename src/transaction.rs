@@ -6,77 +6,126 @@
 //! Defines functions for getting information about the Transaction that triggered this call, e.g. the
 //! calling account (Transaction's `signer`) and `transaction_hash`, etc.
 
-use crate::imports;
+use crate::{host, internal};
 
 /// Get the address of this contract call
 pub fn calling_account() -> [u8;32] {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
-    
-    let arguments =
-    unsafe {
-        imports::calling_account(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8, 32, 32)
-    };
-    TryInto::<[u8;32]>::try_into(arguments).unwrap()
+    host::current().calling_account()
 }
 
 /// Get current address (equivalent to this contract address)
 pub fn current_account() -> [u8;32] {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
-
-    let arguments =
-    unsafe {
-        imports::current_account(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8, 32, 32)
-    };
-    TryInto::<[u8;32]>::try_into(arguments).unwrap()
+    host::current().current_account()
 }
 
 /// Get transferring amount in this contract call
 pub fn amount() -> u64 {
-    unsafe { imports::amount() }
+    host::current().amount()
 }
 
 /// Returns whether it is an internal call
 pub fn is_internal_call() -> bool {
-    unsafe { imports::is_internal_call() != 0 }
+    host::current().is_internal_call()
 }
 
 /// Get transaction hash of this contract call
 pub fn transaction_hash() -> [u8;32] {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
-
-    let arguments =
-    unsafe {
-        imports::transaction_hash(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8,32, 32)
-    };
-    TryInto::<[u8;32]>::try_into(arguments).unwrap()
+    host::current().transaction_hash()
 }
 
-/// Get method name of the invoking method in this contract call
+/// Get method name of the invoking method in this contract call. The method name is given by the caller, so it may 
+/// not be valid UTF-8 or may contain NUL. Such names are sanitized by [sanitize_method_name] instead of aborting the 
+/// call, so they never match a method defined in Rust and reach the method with attribute `fallback`, if any. 
+/// The bytes given by the caller are returned by [method_bytes].
 pub fn method() -> String {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
-
-    let arguments = 
-    unsafe {
-        let args_len = imports::method(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8,args_len as usize, args_len as usize)
-    };
-    String::from_utf8(arguments).unwrap()
+    sanitize_method_name(&host::current().method())
+}
+
+/// Get method name of the invoking method in this contract call as given by the caller, without sanitization.
+pub fn method_bytes() -> Vec<u8> {
+    host::current().method()
+}
+
+/// Convert a method name given by a caller into a String. Invalid UTF-8 sequences and NUL are replaced by 
+/// U+FFFD, which cannot appear in the name of a method defined in Rust.
+pub fn sanitize_method_name(method: &[u8]) -> String {
+    String::from_utf8_lossy(method).replace('\0', "\u{FFFD}")
 }
 
 /// Get method arguments of the invoking method in this contract call
 pub fn arguments() -> Vec<u8> {
-    let mut args_ptr: u32 = 0;
-    let args_ptr_ptr = &mut args_ptr;
+    host::current().arguments()
+}
+
+/// Get the priority fee per gas (the tip to the block proposer) of the transaction. It requires the host feature
+/// [FeeContext](crate::runtime::HostFeature::FeeContext), which is provided with the feature `fee-context` of the SDK.
+pub fn tip() -> u64 {
+    host::current().priority_fee_per_gas()
+}
+
+/// Get the price per gas paid by the transaction, i.e. the base fee of the block plus the tip, e.g. to reimburse
+/// a relayer for `gas_used * gas_price()`. It requires the host feature [FeeContext](crate::runtime::HostFeature::FeeContext).
+pub fn gas_price() -> u64 {
+    host::current().gas_price()
+}
+
+/// Get the gas remaining in the transaction, e.g. to stop a batch of work before the transaction runs out of gas.
+/// It requires the host feature [GasRemaining](crate::runtime::HostFeature::GasRemaining), which is provided with 
+/// the feature `gas-remaining` of the SDK.
+pub fn gas_remaining() -> u64 {
+    host::current().gas_remaining()
+}
+
+/// Get the amount attached to this contract call as an [Amount], for the bookkeeping of payable methods.
+pub fn attached_amount() -> Amount {
+    Amount(amount())
+}
+
+/// `Amount` is the amount of tokens attached to a contract call. E.g.
+/// 
+/// ```no_run
+/// #[call]
+/// fn buy_ticket(&mut self) {
+///     let paid = transaction::attached_amount();
+///     paid.assert_at_least(TICKET_PRICE);
+///     paid.refund_excess(TICKET_PRICE);
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Amount(pub u64);
+
+impl Amount {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if no tokens are attached.
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
 
-    unsafe {
-        let args_len = imports::arguments(args_ptr_ptr);
-        Vec::<u8>::from_raw_parts(args_ptr as *mut u8,args_len as usize, args_len as usize)
+    /// Panics if the amount is less than `required`, which aborts the call and returns the attached tokens.
+    pub fn assert_at_least(&self, required: u64) {
+        if self.0 < required {
+            panic!("Insufficient attached amount. Required: {}, Attached: {}", required, self.0);
+        }
     }
-}
\ No newline at end of file
+
+    /// Transfer the amount in excess of `required` back to the calling account, and returns the refunded amount.
+    /// Panics if the amount is less than `required`.
+    pub fn refund_excess(&self, required: u64) -> u64 {
+        self.assert_at_least(required);
+        let excess = self.0 - required;
+        if excess > 0 {
+            internal::transfer(calling_account(), excess);
+        }
+        excess
+    }
+}
+
+impl From<Amount> for u64 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
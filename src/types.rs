@@ -0,0 +1,54 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Re-exports the types of the crate `pchain-types` that appear in the API of the SDK, and defines conversions between 
+//! them and the types of the SDK.
+//! 
+//! Contract crates should import these types from `pchain_sdk::types` instead of depending on `pchain-types` directly. 
+//! The SDK pins the exact version of `pchain-types` it is built with, and the names in this module are kept stable
+//! across releases of the SDK with the same minor version, so upgrading the SDK does not break the contract crate with 
+//! mismatched versions of the same type.
+//! 
+//! ### Example
+//! ```no_run
+//! use pchain_sdk::types::{PublicAddress, CallInput};
+//! 
+//! let input: CallInput = (&job).into();
+//! ```
+
+pub use pchain_types::cryptography::{PublicAddress, Sha256Hash};
+pub use pchain_types::blockchain::{Command, Log, CommandReceipt, ExitStatus};
+pub use pchain_types::runtime::{
+    CallInput, 
+    CreateDepositInput, 
+    SetDepositSettingsInput, 
+    TopUpDepositInput, 
+    WithdrawDepositInput, 
+    StakeDepositInput, 
+    UnstakeDepositInput
+};
+pub use pchain_types::rpc::{Deposit, Stake};
+pub use pchain_types::serialization::{Serializable, Deserializable};
+
+use crate::{network::NetworkCommandsSummary, scheduler::Job};
+
+/// The contract call made when the job is executed.
+impl From<&Job> for CallInput {
+    fn from(job: &Job) -> Self {
+        CallInput {
+            target: job.target,
+            method: job.method.clone(),
+            arguments: <Vec<Vec<u8>>>::deserialize(&job.arguments).ok(),
+            amount: if job.value > 0 { Some(job.value) } else { None }
+        }
+    }
+}
+
+/// The network commands in the order they are deferred.
+impl From<NetworkCommandsSummary> for Vec<Command> {
+    fn from(summary: NetworkCommandsSummary) -> Self {
+        summary.commands
+    }
+}
@@ -0,0 +1,129 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Defines functions for validating strings, e.g. names in a registry, and [BoundedString], a string with a maximum
+//! length. They only scan the bytes of the string once, so that the gas cost is proportional to its length.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use crate::{storage, Storable, StoragePath};
+
+/// Check if the length of the string in bytes (UTF-8 encoded) is between `min` and `max` inclusively.
+pub fn utf8_len_between(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len())
+}
+
+/// Check if the string is not empty and consists of only ASCII letters and digits.
+pub fn is_ascii_alphanumeric(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+/// Error of creating a [BoundedString].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The string is longer than the maximum length in bytes.
+    TooLong { max: usize, len: usize },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::TooLong { max, len } => write!(f, "string of {} bytes exceeds the maximum length {}", len, max),
+        }
+    }
+}
+
+/// `BoundedString` is a string of at most `N` bytes (UTF-8 encoded). It is serialized in the same way as `String`,
+/// and deserializing a longer string fails, so a contract field of this type never holds a longer string.
+/// 
+/// ### Example
+/// ```no_run
+/// #[call]
+/// fn register(&mut self, name: String) {
+///     let name = BoundedString::<32>::new(name).unwrap();
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    pub fn new(s: String) -> Result<Self, ValidationError> {
+        if s.len() > N {
+            return Err(ValidationError::TooLong { max: N, len: s.len() })
+        }
+        Ok(Self(s))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> std::ops::Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = ValidationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = ValidationError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::new(s.to_string())
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(s: BoundedString<N>) -> Self {
+        s.0
+    }
+}
+
+impl<const N: usize> std::fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const N: usize> BorshSerialize for BoundedString<N> {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<const N: usize> BorshDeserialize for BoundedString<N> {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let s = String::deserialize_reader(reader)?;
+        Self::new(s).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl<const N: usize> Storable for BoundedString<N> {
+    fn __load_storage(field: &StoragePath) -> Self {
+        match storage::get(field.get_path()) {
+            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+            None => Self::default()
+        }
+    }
+
+    fn __save_storage(&mut self, field: &StoragePath) {
+        storage::set(field.get_path(), self.try_to_vec().unwrap().as_slice());
+    }
+}
@@ -0,0 +1,69 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of parsing, formatting and converting [TokenAmount].
+
+use pchain_sdk::{amount::AmountError, TokenAmount};
+
+type Xpll = TokenAmount<8>;
+type Bridged = TokenAmount<18>;
+
+#[test]
+fn amounts_are_parsed_in_tokens() {
+    assert_eq!("1.5".parse::<Xpll>().map(|a| a.base_units()), Ok(150_000_000));
+    assert_eq!("100".parse::<Xpll>().map(|a| a.base_units()), Ok(10_000_000_000));
+    assert_eq!("0.00000001".parse::<Xpll>().map(|a| a.base_units()), Ok(1));
+    assert_eq!("007".parse::<TokenAmount<0>>().map(|a| a.base_units()), Ok(7));
+}
+
+#[test]
+fn invalid_amounts_are_not_parsed() {
+    for s in ["", ".5", "1.", "1.2.3", "-1", "+1", "1e8", " 1"] {
+        assert_eq!(s.parse::<Xpll>(), Err(AmountError::Invalid), "{:?}", s);
+    }
+    assert_eq!("0.000000001".parse::<Xpll>(), Err(AmountError::TooManyDecimals { max: 8, found: 9 }));
+    assert_eq!("1000000000000000000000".parse::<Bridged>(), Err(AmountError::Overflow));
+}
+
+#[test]
+fn amounts_are_formatted_without_trailing_zeros() {
+    assert_eq!(Xpll::from_base_units(150_000_000).to_string(), "1.5");
+    assert_eq!(Xpll::from_base_units(10_000_000_000).to_string(), "100");
+    assert_eq!(Xpll::from_base_units(1).to_string(), "0.00000001");
+    assert_eq!(Xpll::ZERO.to_string(), "0");
+    assert_eq!(TokenAmount::<0>::from_base_units(42).to_string(), "42");
+    for s in ["1.5", "100", "0.00000001", "0"] {
+        assert_eq!(s.parse::<Xpll>().unwrap().to_string(), s);
+    }
+}
+
+#[test]
+fn conversion_to_fewer_decimals_does_not_round() {
+    let amount: Bridged = "1.5".parse().unwrap();
+    assert_eq!(amount.to_decimals::<8>().map(|a| a.base_units()), Ok(150_000_000));
+
+    let dust = Bridged::from_base_units(150_000_000_000_000_001);
+    assert_eq!(dust.to_decimals::<8>(), Err(AmountError::PrecisionLoss));
+    assert_eq!(dust.to_decimals_floor::<8>().map(|a| a.base_units()), Ok(15_000_000));
+}
+
+#[test]
+fn conversion_to_more_decimals_fails_on_overflow() {
+    let amount: Xpll = "1.5".parse().unwrap();
+    assert_eq!(amount.to_decimals::<18>().map(|a| a.base_units()), Ok(1_500_000_000_000_000_000));
+    assert_eq!(amount.to_decimals_floor::<18>(), amount.to_decimals::<18>());
+    assert_eq!(Xpll::MAX.to_decimals::<18>(), Err(AmountError::Overflow));
+}
+
+#[test]
+fn arithmetic_is_checked() {
+    let one = Xpll::ONE;
+    assert_eq!(one.checked_add(one), Some(Xpll::from_base_units(200_000_000)));
+    assert_eq!(Xpll::ZERO.checked_sub(one), None);
+    assert_eq!(Xpll::MAX.checked_add(one), None);
+    assert_eq!(one.checked_mul_div(25, 10_000).map(|a| a.base_units()), Some(250_000));
+    assert_eq!(one.checked_mul_div(1, 0), None);
+    assert_eq!(vec![one, one, one].into_iter().sum::<Xpll>(), Xpll::from_tokens(3).unwrap());
+}
@@ -0,0 +1,57 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the checks of [BridgeVerifier] before the signatures, with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{bridge::{BridgeError, BridgeSignature, BridgeVerifier, MessageEnvelope, SignatureScheme}, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const CHAIN_ID: u64 = 7;
+const SOURCE_CHAIN_ID: u64 = 2;
+
+/// Envelope of `b"payload"` from the source chain, built with the host of the runtime.
+fn envelope(destination_chain_id: u64, destination: [u8; 32]) -> MessageEnvelope {
+    TestEnv::new(CONTRACT).run(|| MessageEnvelope::new(SOURCE_CHAIN_ID, 0, destination_chain_id, destination, b"payload")).0
+}
+
+fn accept(envelope: &MessageEnvelope, payload: &[u8], signatures: &[BridgeSignature]) -> Result<(), BridgeError> {
+    let env = TestEnv::new(CONTRACT);
+    let (result, _) = env.run(|| {
+        let mut verifier = BridgeVerifier::new(CHAIN_ID, SignatureScheme::Ed25519, vec![[3u8; 32].to_vec()], 1);
+        verifier.accept(envelope, payload, signatures)
+    });
+    result
+}
+
+#[test]
+fn message_for_another_chain_is_rejected() {
+    let envelope = envelope(CHAIN_ID + 1, CONTRACT);
+    assert_eq!(accept(&envelope, b"payload", &[]), Err(BridgeError::WrongDestinationChain));
+}
+
+#[test]
+fn message_for_another_contract_is_rejected() {
+    let envelope = envelope(CHAIN_ID, [2u8; 32]);
+    assert_eq!(accept(&envelope, b"payload", &[]), Err(BridgeError::WrongDestination));
+}
+
+#[test]
+fn message_is_checked_against_payload_and_signers() {
+    let envelope = envelope(CHAIN_ID, CONTRACT);
+    assert_eq!(accept(&envelope, b"another payload", &[]), Err(BridgeError::PayloadMismatch));
+    assert_eq!(accept(&envelope, b"payload", &[]), Err(BridgeError::InsufficientSignatures { valid: 0, threshold: 1 }));
+
+    let signature = |signer_index| BridgeSignature { signer_index, signature: vec![0u8; 64] };
+    assert_eq!(accept(&envelope, b"payload", &[signature(1)]), Err(BridgeError::UnknownSigner(1)));
+}
+
+#[test]
+fn destination_chain_is_signed() {
+    let envelope = envelope(CHAIN_ID, CONTRACT);
+    let other_chain = MessageEnvelope { destination_chain_id: CHAIN_ID + 1, ..envelope.clone() };
+    assert_ne!(envelope.signing_bytes(), other_chain.signing_bytes());
+}
@@ -0,0 +1,56 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the limits `max_args_bytes` and `max_len` of methods with attribute `call`, with the in-memory runtime of 
+//! the feature `testing` and the trace of the feature `trace`.
+
+#![cfg(all(feature = "testing", feature = "trace"))]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*, trace};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+mod registry {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Registry {
+        total: u64,
+    }
+
+    #[contract_methods]
+    impl Registry {
+        #[call(max_args_bytes = 64, max_len = 3)]
+        fn register(&mut self, names: Vec<String>) {
+            self.total += names.len() as u64;
+        }
+    }
+}
+
+fn register(env: &TestEnv, names: &[&str]) -> Result<CallOutcome, String> {
+    let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    env.try_call(registry::__entrypoint, "register", ContractMethodInputBuilder::new().add(names).to_call_arguments())
+}
+
+#[test]
+fn arguments_within_limits_are_accepted() {
+    let env = TestEnv::new(CONTRACT);
+    register(&env, &["a", "b", "c"]).unwrap();
+    assert_eq!(env.state().get(CONTRACT, &[0]), Some(3u64.to_le_bytes().to_vec()));
+}
+
+#[test]
+fn oversized_arguments_are_rejected_before_the_contract_is_loaded() {
+    let env = TestEnv::new(CONTRACT);
+    trace::reset();
+    trace::log_calls(true);
+    let too_many = register(&env, &["a", "b", "c", "d"]).unwrap_err();
+    let too_long = register(&env, &["a".repeat(64).as_str()]).unwrap_err();
+    trace::log_calls(false);
+
+    assert!(too_many.contains("Argument 0 of method register has 4 items, but at most 3 are allowed."), "{}", too_many);
+    assert!(too_long.contains("Method register accepts arguments of at most 64 bytes."), "{}", too_long);
+    assert!(trace::report().keys_read().is_empty());
+}
@@ -0,0 +1,84 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of methods declared to return `Result` with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::{ContractMethodInputBuilder, EncodedCall}, testing::*, ContractError};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const CALLER: [u8; 32] = [9u8; 32];
+
+mod vault {
+    use pchain_sdk::{contract, contract_methods, call, ContractError};
+
+    #[contract]
+    pub struct Vault {
+        withdrawn: u64,
+    }
+
+    #[contract_methods(multicall)]
+    impl Vault {
+        /// Records the withdrawal, and pays out unless it exceeds the balance.
+        #[call]
+        fn withdraw(&mut self, amount: u64) -> Result<u64, ContractError> {
+            self.withdrawn += amount;
+            check_balance(amount)?;
+            pchain_sdk::transfer(pchain_sdk::transaction::calling_account(), amount);
+            Ok(self.withdrawn)
+        }
+
+        #[call]
+        fn record(&mut self, amount: u64) -> Result<u64, ContractError> {
+            self.withdrawn += amount;
+            Ok(self.withdrawn)
+        }
+
+        #[call]
+        fn withdrawn(&self) -> u64 {
+            self.withdrawn
+        }
+    }
+
+    fn check_balance(amount: u64) -> Result<(), ContractError> {
+        if amount > pchain_sdk::blockchain::balance() {
+            return Err(ContractError::new(1, "insufficient balance"))
+        }
+        Ok(())
+    }
+}
+
+fn withdraw(env: &TestEnv, amount: u64) -> Result<u64, ContractError> {
+    env.call(vault::__entrypoint, "withdraw", ContractMethodInputBuilder::new().add(amount).to_call_arguments()).decode().unwrap()
+}
+
+#[test]
+fn contract_is_not_saved_when_method_returns_error() {
+    let env = TestEnv::new(CONTRACT).caller(CALLER);
+    env.state().set_balance(CONTRACT, 100);
+    assert_eq!(withdraw(&env, 300).unwrap_err().code, 1);
+    // the record made before the early return is not saved
+    assert_eq!(env.state().balance(CALLER), 0);
+    assert_eq!(env.call(vault::__entrypoint, "withdrawn", vec![]).decode::<u64>(), Some(0));
+
+    assert_eq!(withdraw(&env, 30), Ok(30));
+    assert_eq!(env.state().balance(CALLER), 30);
+    assert_eq!(env.call(vault::__entrypoint, "withdrawn", vec![]).decode::<u64>(), Some(30));
+}
+
+#[test]
+fn error_in_multicall_aborts_all_calls() {
+    let env = TestEnv::new(CONTRACT).caller(CALLER);
+    env.state().set_balance(CONTRACT, 100);
+    let calls = vec![
+        EncodedCall::new("record", ContractMethodInputBuilder::new().add(5u64).to_call_arguments()),
+        EncodedCall::new("withdraw", ContractMethodInputBuilder::new().add(300u64).to_call_arguments()),
+    ];
+    let error = env.try_call(vault::__entrypoint, "multicall", ContractMethodInputBuilder::new().add(calls).to_call_arguments()).unwrap_err();
+    assert!(error.contains("Call 1 of multicall to method withdraw returned an error"));
+    assert_eq!(env.state().balance(CALLER), 0);
+    assert_eq!(env.call(vault::__entrypoint, "withdrawn", vec![]).decode::<u64>(), Some(0));
+}
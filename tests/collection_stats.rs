@@ -0,0 +1,83 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of `stats()` of the collections with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{collections::CollectionStats, method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+mod stats {
+    use pchain_sdk::{contract, contract_methods, call, collections::{CollectionStats, FastMap, Vector}};
+
+    #[contract]
+    pub struct Stats {
+        plain: FastMap<u64, u64>,
+        indexed: FastMap<u64, u64>,
+        nested: FastMap<u64, FastMap<u64, u64>>,
+        items: Vector<u64>,
+    }
+
+    #[contract_methods]
+    impl Stats {
+        #[call]
+        fn init(&mut self) {
+            self.indexed = FastMap::with_index();
+        }
+
+        #[call]
+        fn insert(&mut self, key: u64) {
+            self.plain.insert(&key, key);
+            self.indexed.insert(&key, key);
+            match self.nested.get_mut(&0) {
+                Some(inner) => inner.insert(&key, key),
+                None => {
+                    let mut inner = FastMap::new();
+                    inner.insert(&key, key);
+                    self.nested.insert(&0, inner);
+                }
+            }
+            self.items.push(&key);
+        }
+
+        #[call]
+        fn stats(&self) -> Vec<CollectionStats> {
+            vec![
+                self.plain.stats(),
+                self.indexed.stats(),
+                self.nested.get(&0).map(|inner| inner.stats()).unwrap_or_default(),
+                self.items.stats(),
+            ]
+        }
+    }
+}
+
+fn stats(env: &TestEnv) -> Vec<CollectionStats> {
+    env.call(stats::__entrypoint, "stats", vec![]).decode().unwrap()
+}
+
+#[test]
+fn entries_are_unknown_only_for_unlogged_fast_map() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().deploy(CONTRACT, stats::__entrypoint);
+    env.call(stats::__entrypoint, "init", vec![]);
+    for key in [1u64, 2, 3] {
+        env.call(stats::__entrypoint, "insert", ContractMethodInputBuilder::new().add(key).to_call_arguments());
+    }
+
+    let entries: Vec<Option<u64>> = stats(&env).into_iter().map(|stats| stats.entries).collect();
+    assert_eq!(entries, vec![None, Some(3), Some(3), Some(3)]);
+}
+
+#[test]
+fn entries_are_zero_before_the_first_save() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().deploy(CONTRACT, stats::__entrypoint);
+
+    let entries: Vec<Option<u64>> = stats(&env).into_iter().map(|stats| stats.entries).collect();
+    assert_eq!(entries[3], Some(0));
+}
@@ -0,0 +1,69 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the method `config` generated for `#[contract_config]`, with the in-memory runtime of the feature 
+//! `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::CONFIG_METHOD, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+mod configured {
+    use pchain_sdk::{contract, contract_methods, contract_config, call};
+
+    #[contract]
+    pub struct Configured {
+        orders: u32,
+    }
+
+    #[contract_config]
+    impl Configured {
+        const FEE: u64 = 1_000;
+        const ENABLED: bool = true;
+    }
+
+    #[contract_methods]
+    impl Configured {
+        #[call]
+        fn place_order(&mut self) {
+            if Self::ENABLED && u64::from(self.orders) < Self::FEE {
+                self.orders += 1;
+            }
+        }
+    }
+}
+
+mod unconfigured {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Unconfigured {
+        orders: u32,
+    }
+
+    #[contract_methods]
+    impl Unconfigured {
+        #[call]
+        fn place_order(&mut self) {
+            self.orders += 1;
+        }
+    }
+}
+
+#[test]
+fn config_returns_the_constants() {
+    let env = TestEnv::new(CONTRACT);
+    let config: Vec<(String, String)> = env.call(configured::__entrypoint, CONFIG_METHOD, vec![]).decode().unwrap();
+    assert_eq!(config, vec![("FEE".to_string(), "1000".to_string()), ("ENABLED".to_string(), "true".to_string())]);
+}
+
+#[test]
+fn config_is_not_defined_without_contract_config() {
+    let env = TestEnv::new(CONTRACT);
+    let error = env.try_call(unconfigured::__entrypoint, CONFIG_METHOD, vec![]).unwrap_err();
+    assert!(error.contains("Method \"config\" is not defined."), "{}", error);
+}
@@ -0,0 +1,41 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the cryptographic functions which are not part of the base interface, with the in-memory runtime of the 
+//! feature `testing`.
+
+#![cfg(all(feature = "testing", feature = "secp256k1"))]
+
+use pchain_sdk::{crypto, runtime::{self, HostFeature}, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+/// Signature of the SHA256 digest of `b"message"` by the secret key `[7u8; 32]`.
+const SIGNATURE: &str = "dd465f82e2786566068deebea09495a71fe6d3d904233d682594953184ed487b11ddca5c54ea0d98f36ca5070ccbeef704e20f55213ba44e5c27836d187be688";
+/// Compressed public key of the secret key `[7u8; 32]`.
+const PUBLIC_KEY: &str = "02989c0b76cb563971fdc9bef31ec06c3560f3249d6ee9e5d83c57625596e05f6f";
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn secp256k1_signature_is_verified() {
+    let env = TestEnv::new(CONTRACT);
+    let (signature, public_key) = (from_hex(SIGNATURE), from_hex(PUBLIC_KEY));
+    let (results, _) = env.run(|| (
+        runtime::supports(HostFeature::Secp256k1),
+        crypto::verify_secp256k1_signature(b"message".to_vec(), signature.clone(), public_key.clone()),
+        crypto::verify_secp256k1_signature(b"another message".to_vec(), signature.clone(), public_key.clone()),
+    ));
+    assert_eq!(results, (true, true, false));
+}
+
+#[test]
+#[should_panic(expected = "Invalid secp256k1 public key")]
+fn malformed_secp256k1_public_key_fails_the_call() {
+    let env = TestEnv::new(CONTRACT);
+    env.run(|| crypto::verify_secp256k1_signature(b"message".to_vec(), from_hex(SIGNATURE), vec![2u8; 5]));
+}
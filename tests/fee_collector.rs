@@ -0,0 +1,84 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [FeeCollector](pchain_sdk::fee_collector::FeeCollector) with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const MARKET: [u8; 32] = [1u8; 32];
+const TOKEN: [u8; 32] = [2u8; 32];
+const ADMIN: [u8; 32] = [9u8; 32];
+
+mod market {
+    use pchain_sdk::{contract, contract_methods, call, fee_collector::FeeCollector};
+
+    #[contract]
+    pub struct Market {
+        fees: FeeCollector,
+    }
+
+    #[contract_methods]
+    impl Market {
+        #[call]
+        fn init(&mut self) {
+            self.fees = FeeCollector::new(pchain_sdk::transaction::calling_account());
+        }
+
+        #[call]
+        fn charge(&mut self, token: Option<[u8; 32]>, amount: u64) {
+            self.fees.accrue(token, "charge", amount);
+        }
+
+        #[call]
+        fn sweep(&mut self, recipient: [u8; 32]) -> u64 {
+            self.fees.sweep(recipient).unwrap().iter().map(|(_, amount)| amount).sum()
+        }
+
+        #[call]
+        fn accrued(&self, token: Option<[u8; 32]>) -> u64 {
+            self.fees.total(token)
+        }
+    }
+}
+
+/// A token whose transfer charges a fee in the market once, by calling back into it.
+mod reentrant_token {
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder};
+
+    #[contract]
+    pub struct Token {
+        charged: bool,
+    }
+
+    #[contract_methods]
+    impl Token {
+        #[call]
+        fn transfer(&mut self, _recipient: [u8; 32], _amount: u64) {
+            if !self.charged {
+                self.charged = true;
+                pchain_sdk::flush_storage(self);
+                let arguments = ContractMethodInputBuilder::new().add(Some(super::TOKEN)).add(7u64).to_call_arguments();
+                pchain_sdk::call_untyped(super::MARKET, "charge", arguments, 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn fees_accrued_during_sweep_are_kept() {
+    let env = TestEnv::new(MARKET).caller(ADMIN);
+    env.state().deploy(MARKET, market::__entrypoint);
+    env.state().deploy(TOKEN, reentrant_token::__entrypoint);
+    env.call(market::__entrypoint, "init", vec![]);
+    env.call(market::__entrypoint, "charge", ContractMethodInputBuilder::new().add(Some(TOKEN)).add(100u64).to_call_arguments());
+
+    let outcome = env.call(market::__entrypoint, "sweep", ContractMethodInputBuilder::new().add(ADMIN).to_call_arguments());
+    assert_eq!(outcome.decode::<u64>(), Some(100));
+    // the fee charged by the token during the sweep is left for the next sweep
+    let accrued = env.call(market::__entrypoint, "accrued", ContractMethodInputBuilder::new().add(Some(TOKEN)).to_call_arguments());
+    assert_eq!(accrued.decode::<u64>(), Some(7));
+}
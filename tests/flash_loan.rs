@@ -0,0 +1,197 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [FlashLoan] with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{flash_loan::{FlashLoan, FlashLoanEvent, FLASH_LOAN_TOPIC, OPEN_LOAN_KEY}, method::ContractMethodInputBuilder, testing::*};
+
+const LENDER: [u8; 32] = [1u8; 32];
+const BORROWER: [u8; 32] = [2u8; 32];
+const UNGUARDED_LENDER: [u8; 32] = [3u8; 32];
+
+mod lender {
+    use pchain_sdk::{contract, contract_methods, call, collections::FastMap, flash_loan::FlashLoan};
+
+    #[contract]
+    pub struct Lender {
+        flash_loan: FlashLoan,
+        deposits: FastMap<[u8; 32], u64>,
+    }
+
+    #[contract_methods]
+    impl Lender {
+        fn __before_call(_method: &str) {
+            pchain_sdk::flash_loan::reject_during_loan();
+        }
+
+        #[call]
+        fn init(&mut self) {
+            self.flash_loan = FlashLoan::new(30);
+        }
+
+        #[call]
+        fn flash_loan(&mut self, amount: u64, data: Vec<u8>) -> u64 {
+            self.flash_loan.lend(pchain_sdk::transaction::calling_account(), amount, "on_flash_loan", data)
+        }
+
+        #[call]
+        fn collected_fees(&self) -> u64 {
+            self.flash_loan.collected_fees()
+        }
+
+        /// Credits the attached amount to the calling account.
+        #[call]
+        fn deposit(&mut self) {
+            let account = pchain_sdk::transaction::calling_account();
+            let balance = self.deposits.get(&account).unwrap_or(0);
+            self.deposits.insert(&account, balance + pchain_sdk::transaction::amount());
+        }
+
+        #[call]
+        fn deposit_of(&self, account: [u8; 32]) -> u64 {
+            self.deposits.get(&account).unwrap_or(0)
+        }
+    }
+}
+
+/// A lender without the hook that rejects the calls during a loan.
+mod unguarded_lender {
+    use pchain_sdk::{contract, contract_methods, call, flash_loan::FlashLoan};
+
+    #[contract]
+    pub struct UnguardedLender {
+        flash_loan: FlashLoan,
+    }
+
+    #[contract_methods]
+    impl UnguardedLender {
+        #[call]
+        fn flash_loan(&mut self, amount: u64, data: Vec<u8>) -> u64 {
+            self.flash_loan.lend(pchain_sdk::transaction::calling_account(), amount, "on_flash_loan", data)
+        }
+    }
+}
+
+/// A borrower that repays the amount plus the fee given in the data, which may be less than the fee due, by transfer
+/// or by depositing it to the lender.
+mod borrower {
+    use borsh::{BorshSerialize, BorshDeserialize};
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder};
+
+    #[contract]
+    pub struct Borrower {}
+
+    #[contract_methods]
+    impl Borrower {
+        #[call]
+        fn borrow(&self, lender: [u8; 32], amount: u64, fee_repaid: u64, by_deposit: bool) {
+            let data = (fee_repaid, by_deposit).try_to_vec().unwrap();
+            let arguments = ContractMethodInputBuilder::new().add(amount).add(data).to_call_arguments();
+            pchain_sdk::call_untyped(lender, "flash_loan", arguments, 0);
+        }
+
+        #[call]
+        fn on_flash_loan(&self, amount: u64, _fee: u64, data: Vec<u8>) {
+            let (fee_repaid, by_deposit) = <(u64, bool)>::try_from_slice(&data).unwrap();
+            let lender = pchain_sdk::transaction::calling_account();
+            if by_deposit {
+                pchain_sdk::call_untyped(lender, "deposit", vec![], amount + fee_repaid);
+            } else {
+                pchain_sdk::transfer(lender, amount + fee_repaid);
+            }
+        }
+    }
+}
+
+fn setup() -> TestEnv {
+    let env = TestEnv::new(LENDER);
+    env.state().deploy(LENDER, lender::__entrypoint);
+    env.state().deploy(BORROWER, borrower::__entrypoint);
+    env.state().set_balance(LENDER, 1_000_000);
+    env.state().set_balance(BORROWER, 1_000);
+    env.call(lender::__entrypoint, "init", vec![]);
+    env
+}
+
+fn borrow(env: &TestEnv, amount: u64, fee_repaid: u64) -> Result<CallOutcome, String> {
+    borrow_from(env, LENDER, amount, fee_repaid, false)
+}
+
+fn borrow_from(env: &TestEnv, lender: [u8; 32], amount: u64, fee_repaid: u64, by_deposit: bool) -> Result<CallOutcome, String> {
+    let arguments = ContractMethodInputBuilder::new().add(lender).add(amount).add(fee_repaid).add(by_deposit).to_call_arguments();
+    TestEnv::new(BORROWER).world_state(env.state().clone()).try_call(borrower::__entrypoint, "borrow", arguments)
+}
+
+#[test]
+fn fee_is_rounded_up() {
+    let flash_loan = FlashLoan::new(30);
+    assert_eq!(flash_loan.fee(0), 0);
+    assert_eq!(flash_loan.fee(1), 1);
+    assert_eq!(flash_loan.fee(10_000), 30);
+    assert_eq!(flash_loan.fee(10_001), 31);
+    assert_eq!(FlashLoan::new(0).fee(u64::MAX), 0);
+}
+
+#[test]
+fn repaid_loan_collects_fee() {
+    let env = setup();
+    let outcome = borrow(&env, 100_000, 300).unwrap();
+    let event: FlashLoanEvent = borsh::BorshDeserialize::try_from_slice(outcome.logs_of(FLASH_LOAN_TOPIC)[0]).unwrap();
+    assert_eq!(event, FlashLoanEvent { borrower: BORROWER, amount: 100_000, fee: 300 });
+    assert_eq!(env.state().balance(LENDER), 1_000_300);
+    assert_eq!(env.state().balance(BORROWER), 700);
+    assert_eq!(env.call(lender::__entrypoint, "collected_fees", vec![]).decode::<u64>(), Some(300));
+}
+
+#[test]
+fn loan_without_fee_is_reverted() {
+    let env = setup();
+    let error = borrow(&env, 100_000, 299).unwrap_err();
+    assert!(error.contains("Flash loan is not repaid"));
+    assert_eq!(env.state().balance(LENDER), 1_000_000);
+    assert_eq!(env.state().balance(BORROWER), 1_000);
+    assert_eq!(env.call(lender::__entrypoint, "collected_fees", vec![]).decode::<u64>(), Some(0));
+}
+
+#[test]
+fn loan_above_balance_is_rejected() {
+    let env = setup();
+    let error = borrow(&env, 2_000_000, 0).unwrap_err();
+    assert!(error.contains("Insufficient balance for flash loan"));
+}
+
+#[test]
+fn loan_repaid_by_deposit_is_reverted() {
+    let env = setup();
+    let error = borrow_from(&env, LENDER, 100_000, 300, true).unwrap_err();
+    assert!(error.contains("Calls are rejected while a flash loan is open"));
+    assert_eq!(env.state().balance(LENDER), 1_000_000);
+    assert_eq!(env.state().balance(BORROWER), 1_000);
+    let arguments = ContractMethodInputBuilder::new().add(BORROWER).to_call_arguments();
+    assert_eq!(env.call(lender::__entrypoint, "deposit_of", arguments).decode::<u64>(), Some(0));
+    assert_eq!(env.state().get(LENDER, OPEN_LOAN_KEY), None);
+}
+
+#[test]
+fn lender_without_hook_cannot_lend() {
+    let env = setup();
+    env.state().deploy(UNGUARDED_LENDER, unguarded_lender::__entrypoint);
+    env.state().set_balance(UNGUARDED_LENDER, 1_000_000);
+    let error = borrow_from(&env, UNGUARDED_LENDER, 100_000, 300, false).unwrap_err();
+    assert!(error.contains("reject_during_loan"));
+}
+
+#[test]
+fn lender_accepts_calls_after_the_loan() {
+    let env = setup();
+    borrow(&env, 100_000, 300).unwrap();
+    assert_eq!(env.state().get(LENDER, OPEN_LOAN_KEY), None);
+    TestEnv::new(LENDER).caller(BORROWER).amount(500).world_state(env.state().clone())
+        .call(lender::__entrypoint, "deposit", vec![]);
+    let arguments = ContractMethodInputBuilder::new().add(BORROWER).to_call_arguments();
+    assert_eq!(env.call(lender::__entrypoint, "deposit_of", arguments).decode::<u64>(), Some(500));
+}
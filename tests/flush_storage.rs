@@ -0,0 +1,124 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [flush_storage](pchain_sdk::flush_storage) with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const VAULT: [u8; 32] = [1u8; 32];
+const RECIPIENT: [u8; 32] = [2u8; 32];
+const CALLER: [u8; 32] = [9u8; 32];
+
+mod vault {
+    use pchain_sdk::{contract, contract_methods, call, collections::{FastMap, Vector}, method::ContractMethodInputBuilder};
+
+    #[contract]
+    pub struct Vault {
+        total: u64,
+        balances: FastMap<[u8; 32], u64>,
+        withdrawals: Vector<u64>,
+    }
+
+    #[contract_methods]
+    impl Vault {
+        #[call]
+        fn deposit(&mut self, account: [u8; 32], amount: u64) {
+            self.total += amount;
+            let balance = self.balances.get(&account).unwrap_or(0);
+            self.balances.insert(&account, balance + amount);
+        }
+
+        /// Deducts the amount and calls the recipient, which calls back `balance_of`.
+        #[call]
+        fn withdraw(&mut self, account: [u8; 32], amount: u64, flush: bool) {
+            self.total -= amount;
+            let balance = self.balances.get(&account).unwrap();
+            self.balances.insert(&account, balance - amount);
+            self.withdrawals.push(&amount);
+            if flush {
+                pchain_sdk::flush_storage(self);
+            }
+            let arguments = ContractMethodInputBuilder::new().add(account).to_call_arguments();
+            pchain_sdk::call_untyped(super::RECIPIENT, "on_withdraw", arguments, 0);
+        }
+
+        /// Flushes after each change, so that the final save follows the flushes.
+        #[call]
+        fn withdraw_twice(&mut self, account: [u8; 32], amount: u64) {
+            for _ in 0..2 {
+                self.total -= amount;
+                let balance = self.balances.get(&account).unwrap();
+                self.balances.insert(&account, balance - amount);
+                self.withdrawals.push(&amount);
+                pchain_sdk::flush_storage(self);
+                pchain_sdk::flush_storage(self);
+            }
+        }
+
+        #[call]
+        fn balance_of(&self, account: [u8; 32]) -> (u64, u64, u32) {
+            (self.total, self.balances.get(&account).unwrap_or(0), self.withdrawals.len() as u32)
+        }
+    }
+}
+
+/// A recipient that logs the state of the vault that it reads by calling back.
+mod recipient {
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder};
+
+    #[contract]
+    pub struct Recipient {}
+
+    #[contract_methods]
+    impl Recipient {
+        #[call]
+        fn on_withdraw(&self, account: [u8; 32]) {
+            let arguments = ContractMethodInputBuilder::new().add(account).to_call_arguments();
+            let state: (u64, u64, u32) = pchain_sdk::call(super::VAULT, "balance_of", arguments, 0).unwrap();
+            pchain_sdk::log(b"seen", &borsh::BorshSerialize::try_to_vec(&state).unwrap());
+        }
+    }
+}
+
+fn setup() -> TestEnv {
+    let env = TestEnv::new(VAULT).caller(CALLER);
+    env.state().deploy(VAULT, vault::__entrypoint);
+    env.state().deploy(RECIPIENT, recipient::__entrypoint);
+    env.call(vault::__entrypoint, "deposit", ContractMethodInputBuilder::new().add(CALLER).add(100u64).to_call_arguments());
+    env
+}
+
+fn withdraw(env: &TestEnv, flush: bool) -> (u64, u64, u32) {
+    let arguments = ContractMethodInputBuilder::new().add(CALLER).add(30u64).add(flush).to_call_arguments();
+    let outcome = env.call(vault::__entrypoint, "withdraw", arguments);
+    borsh::BorshDeserialize::try_from_slice(outcome.logs_of(b"seen")[0]).unwrap()
+}
+
+fn balance_of(env: &TestEnv) -> Option<(u64, u64, u32)> {
+    env.call(vault::__entrypoint, "balance_of", ContractMethodInputBuilder::new().add(CALLER).to_call_arguments()).decode()
+}
+
+#[test]
+fn reentrant_call_sees_flushed_state() {
+    let env = setup();
+    assert_eq!(withdraw(&env, true), (70, 70, 1));
+    assert_eq!(balance_of(&env), Some((70, 70, 1)));
+}
+
+#[test]
+fn reentrant_call_sees_old_state_without_flush() {
+    let env = setup();
+    assert_eq!(withdraw(&env, false), (100, 100, 0));
+    assert_eq!(balance_of(&env), Some((70, 70, 1)));
+}
+
+#[test]
+fn repeated_flushes_save_each_change_once() {
+    let env = setup();
+    env.call(vault::__entrypoint, "withdraw_twice", ContractMethodInputBuilder::new().add(CALLER).add(10u64).to_call_arguments());
+    assert_eq!(balance_of(&env), Some((80, 80, 2)));
+}
@@ -0,0 +1,66 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the fungible token standard with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const OWNER: [u8; 32] = [9u8; 32];
+const SPENDER: [u8; 32] = [8u8; 32];
+const RECIPIENT: [u8; 32] = [7u8; 32];
+
+mod token {
+    use pchain_sdk::{contract, contract_methods, call, view, ReadOnly, standards::fungible_token::FungibleToken};
+
+    #[contract]
+    #[derive(FungibleToken)]
+    pub struct MyToken {
+        token: FungibleToken,
+    }
+
+    #[contract_methods(fungible_token)]
+    impl MyToken {
+        #[call]
+        fn init(&mut self) {
+            self.token = FungibleToken::new("My Token", "MTK", 0).with_holders();
+            self.token.mint(pchain_sdk::transaction::calling_account(), 100).unwrap();
+        }
+
+        #[view]
+        fn holders(contract: ReadOnly<'_, Self>) -> u64 {
+            contract.token().holders().len()
+        }
+
+        #[view]
+        fn is_holder(contract: ReadOnly<'_, Self>, account: [u8; 32]) -> bool {
+            contract.token().holders().contains(&account)
+        }
+    }
+}
+
+fn is_holder(env: &TestEnv, account: [u8; 32]) -> bool {
+    let arguments = ContractMethodInputBuilder::new().add(account).to_call_arguments();
+    env.call(token::__entrypoint, "is_holder", arguments).decode::<bool>().unwrap()
+}
+
+#[test]
+fn transfers_of_standard_update_holders() {
+    let env = TestEnv::new(CONTRACT).caller(OWNER);
+    env.call(token::__entrypoint, "init", vec![]);
+    assert!(is_holder(&env, OWNER));
+
+    env.call(token::__entrypoint, "transfer", ContractMethodInputBuilder::new().add(RECIPIENT).add(60u64).to_call_arguments());
+    assert!(is_holder(&env, RECIPIENT));
+    assert_eq!(env.call(token::__entrypoint, "holders", vec![]).decode::<u64>(), Some(2));
+
+    env.call(token::__entrypoint, "approve", ContractMethodInputBuilder::new().add(SPENDER).add(40u64).to_call_arguments());
+    let spender = env.clone().caller(SPENDER);
+    spender.call(token::__entrypoint, "transfer_from", ContractMethodInputBuilder::new().add(OWNER).add(RECIPIENT).add(40u64).to_call_arguments());
+    assert!(!is_holder(&env, OWNER));
+    assert_eq!(env.call(token::__entrypoint, "holders", vec![]).decode::<u64>(), Some(1));
+}
@@ -0,0 +1,92 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the governance components with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const GOVERNOR: [u8; 32] = [1u8; 32];
+const TARGET: [u8; 32] = [2u8; 32];
+const VOTER: [u8; 32] = [9u8; 32];
+
+mod governor {
+    use pchain_sdk::{contract, contract_methods, call, governance::{Proposals, VoteKind}};
+
+    #[contract]
+    pub struct Governor {
+        proposals: Proposals,
+    }
+
+    #[contract_methods]
+    impl Governor {
+        #[call]
+        fn init(&mut self) {
+            self.proposals = Proposals::new(1, 1, 0);
+        }
+
+        #[call]
+        fn propose(&mut self, target: [u8; 32], method: String, arguments: Vec<u8>) -> u64 {
+            self.proposals.create(pchain_sdk::transaction::calling_account(), String::new(), target, method, arguments, 0)
+        }
+
+        #[call]
+        fn vote(&mut self, id: u64) {
+            self.proposals.vote(id, pchain_sdk::transaction::calling_account(), VoteKind::For, 1).unwrap();
+        }
+
+        #[call]
+        fn execute(&mut self, id: u64) -> bool {
+            self.proposals.execute(id).is_ok()
+        }
+    }
+}
+
+/// A target that executes the proposal again when it is called by the proposal.
+mod reentrant_target {
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder};
+
+    #[contract]
+    pub struct Target {
+        calls: u64,
+    }
+
+    #[contract_methods]
+    impl Target {
+        #[call]
+        fn reenter(&mut self, governor: [u8; 32], id: u64) {
+            self.calls += 1;
+            pchain_sdk::log(b"called", &[]);
+            // bounded, so that the test fails instead of overflowing the stack if re-entry succeeds
+            if self.calls < 3 {
+                pchain_sdk::flush_storage(self);
+                let arguments = ContractMethodInputBuilder::new().add(id).to_call_arguments();
+                pchain_sdk::call_untyped(governor, "execute", arguments, 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn proposal_cannot_be_executed_by_reentrant_call() {
+    let env = TestEnv::new(GOVERNOR).caller(VOTER).block_height(10);
+    env.state().deploy(GOVERNOR, governor::__entrypoint);
+    env.state().deploy(TARGET, reentrant_target::__entrypoint);
+    env.call(governor::__entrypoint, "init", vec![]);
+
+    let reenter = ContractMethodInputBuilder::new().add(GOVERNOR).add(0u64).to_call_arguments();
+    let arguments = ContractMethodInputBuilder::new().add(TARGET).add("reenter".to_string()).add(reenter).to_call_arguments();
+    assert_eq!(env.call(governor::__entrypoint, "propose", arguments).decode::<u64>(), Some(0));
+    env.call(governor::__entrypoint, "vote", ContractMethodInputBuilder::new().add(0u64).to_call_arguments());
+
+    let env = env.block_height(12);
+    let execute = ContractMethodInputBuilder::new().add(0u64).to_call_arguments();
+    let outcome = env.call(governor::__entrypoint, "execute", execute.clone());
+    assert_eq!(outcome.decode::<bool>(), Some(true));
+    // the target is called once, and its call back to `execute` fails
+    assert_eq!(outcome.logs_of(b"called").len(), 1);
+    assert_eq!(env.call(governor::__entrypoint, "execute", execute).decode::<bool>(), Some(false));
+}
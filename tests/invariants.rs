@@ -0,0 +1,85 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the invariants of a contract with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const OWNER: [u8; 32] = [9u8; 32];
+/// The account whose balance of the token is capped by the invariant.
+const CAPPED: [u8; 32] = [5u8; 32];
+
+mod capped_token {
+    use pchain_sdk::{contract, contract_methods, call, fallback, invariant, on_receive, standards::fungible_token::FungibleToken};
+
+    #[contract]
+    #[derive(FungibleToken)]
+    pub struct CappedToken {
+        token: FungibleToken,
+        received: u64,
+    }
+
+    #[contract_methods(fungible_token)]
+    impl CappedToken {
+        #[call]
+        fn init(&mut self) {
+            self.token = FungibleToken::new("Capped", "CAP", 0);
+            self.token.mint(pchain_sdk::transaction::calling_account(), 1000).unwrap();
+        }
+
+        #[on_receive]
+        fn receive(&mut self, amount: u64) {
+            self.received += amount;
+        }
+
+        /// Counts any other call as receiving 600.
+        #[fallback]
+        fn fallback(&mut self, _method: Vec<u8>, _arguments: Vec<u8>) {
+            self.received += 600;
+        }
+
+        #[invariant]
+        fn within_caps(&self) -> bool {
+            self.token.balance_of(super::CAPPED) <= 100 && self.received <= 1000
+        }
+    }
+}
+
+#[test]
+fn invariant_is_checked_after_method_of_standard() {
+    let env = TestEnv::new(CONTRACT).caller(OWNER);
+    env.call(capped_token::__entrypoint, "init", vec![]);
+    let transfer = |amount: u64| ContractMethodInputBuilder::new().add(CAPPED).add(amount).to_call_arguments();
+
+    env.call(capped_token::__entrypoint, "transfer", transfer(100));
+    let error = env.try_call(capped_token::__entrypoint, "transfer", transfer(1)).unwrap_err();
+    assert_eq!(error, "Invariant within_caps is violated by transfer.");
+
+    let outcome = env.call(capped_token::__entrypoint, "balance_of", ContractMethodInputBuilder::new().add(CAPPED).to_call_arguments());
+    assert_eq!(outcome.decode::<u64>(), Some(100));
+}
+
+#[test]
+fn invariant_is_checked_after_on_receive() {
+    let env = TestEnv::new(CONTRACT).caller(OWNER);
+    env.state().set_balance(OWNER, 2000);
+
+    env.clone().amount(1000).call(capped_token::__entrypoint, "", vec![]);
+    let error = env.clone().amount(1).try_call(capped_token::__entrypoint, "", vec![]).unwrap_err();
+    assert_eq!(error, "Invariant within_caps is violated by receive.");
+    assert_eq!(env.state().balance(OWNER), 1000);
+}
+
+#[test]
+fn invariant_is_checked_after_fallback() {
+    let env = TestEnv::new(CONTRACT).caller(OWNER);
+
+    env.call(capped_token::__entrypoint, "undefined", vec![]);
+    let error = env.try_call(capped_token::__entrypoint, "undefined", vec![]).unwrap_err();
+    assert_eq!(error, "Invariant within_caps is violated by fallback.");
+}
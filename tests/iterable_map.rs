@@ -0,0 +1,168 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [IterableMap](pchain_sdk::collections::IterableMap) with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+mod registry {
+    use pchain_sdk::{contract, contract_methods, call, collections::IterableMap};
+
+    #[contract]
+    pub struct Registry {
+        entries: IterableMap<String, u64>,
+    }
+
+    #[contract_methods]
+    impl Registry {
+        /// Inserts the keys, removes the keys to remove, and returns the keys in iteration order before saving.
+        #[call]
+        fn update(&mut self, insert: Vec<String>, remove: Vec<String>) -> Vec<String> {
+            for key in insert {
+                self.entries.insert(&key, 0);
+            }
+            for key in remove {
+                self.entries.remove(&key);
+            }
+            self.entries.keys().collect()
+        }
+
+        #[call]
+        fn keys(&self) -> Vec<String> {
+            self.entries.keys().collect()
+        }
+    }
+}
+
+/// A map of nested maps, whose removed nested maps are purged by `purge`.
+mod nested {
+    use pchain_sdk::{contract, contract_methods, call, collections::IterableMap};
+
+    #[contract]
+    pub struct Nested {
+        maps: IterableMap<u64, IterableMap<u64, u64>>,
+    }
+
+    #[contract_methods]
+    impl Nested {
+        /// Inserts a nested map of `len` entries at each key.
+        #[call]
+        fn add(&mut self, keys: Vec<u64>, len: u64) {
+            for key in keys {
+                let mut map = IterableMap::new();
+                for i in 0..len {
+                    map.insert(&i, i);
+                }
+                self.maps.insert(&key, map);
+            }
+        }
+
+        #[call]
+        fn remove(&mut self, keys: Vec<u64>) {
+            for key in keys {
+                self.maps.remove(&key);
+            }
+        }
+
+        #[call]
+        fn purge(&mut self, limit: u32) -> u32 {
+            self.maps.purge_removed(limit)
+        }
+    }
+}
+
+fn strings(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|key| key.to_string()).collect()
+}
+
+fn update(env: &TestEnv, insert: &[&str], remove: &[&str]) -> Vec<String> {
+    let arguments = ContractMethodInputBuilder::new().add(strings(insert)).add(strings(remove)).to_call_arguments();
+    env.call(registry::__entrypoint, "update", arguments).decode().unwrap()
+}
+
+fn keys(env: &TestEnv) -> Vec<String> {
+    env.call(registry::__entrypoint, "keys", vec![]).decode().unwrap()
+}
+
+#[test]
+fn keys_are_iterated_in_order_of_insertion() {
+    let env = TestEnv::new(CONTRACT);
+    assert_eq!(update(&env, &["c", "a", "b", "a"], &[]), strings(&["c", "a", "b"]));
+    assert_eq!(keys(&env), strings(&["c", "a", "b"]));
+
+    // keys inserted in a later call follow the saved keys
+    assert_eq!(update(&env, &["z", "d", "c"], &[]), strings(&["c", "a", "b", "z", "d"]));
+    assert_eq!(keys(&env), strings(&["c", "a", "b", "z", "d"]));
+}
+
+#[test]
+fn removed_keys_are_not_iterated() {
+    let env = TestEnv::new(CONTRACT);
+    update(&env, &["c", "a", "b"], &[]);
+    // a new key that is removed in the same call, and a saved key
+    assert_eq!(update(&env, &["x", "y"], &["x", "a"]), strings(&["c", "b", "y"]));
+    assert_eq!(keys(&env), strings(&["c", "b", "y"]));
+}
+
+#[test]
+fn key_inserted_again_after_removal_moves_to_the_end() {
+    let env = TestEnv::new(CONTRACT);
+    assert_eq!(update(&env, &["x", "y", "z"], &["x"]), strings(&["y", "z"]));
+    assert_eq!(update(&env, &["p", "q", "p"], &["p"]), strings(&["y", "z", "q"]));
+    assert_eq!(keys(&env), strings(&["y", "z", "q"]));
+}
+
+/// Keys of the purge queue of the field `maps` of `nested`.
+fn purge_queue_keys(env: &TestEnv) -> Vec<Vec<u8>> {
+    let parent_key = pchain_sdk::StoragePath::new().add(0).get_path().to_vec();
+    let bounds = [parent_key.as_slice(), &[4u8]].concat();
+    let tasks: Vec<Vec<u8>> = (0..10u64).map(|q| [parent_key.as_slice(), &[5u8], &q.to_le_bytes()].concat()).collect();
+    std::iter::once(bounds).chain(tasks).filter(|key| env.state().get(CONTRACT, key).is_some()).collect()
+}
+
+fn call_nested(env: &TestEnv, method: &str, keys: Vec<u64>, len: Option<u64>) -> CallOutcome {
+    let mut arguments = ContractMethodInputBuilder::new();
+    arguments.add(keys);
+    if let Some(len) = len {
+        arguments.add(len);
+    }
+    env.call(nested::__entrypoint, method, arguments.to_call_arguments())
+}
+
+fn purge(env: &TestEnv, limit: u32) -> u32 {
+    env.call(nested::__entrypoint, "purge", ContractMethodInputBuilder::new().add(limit).to_call_arguments()).decode().unwrap()
+}
+
+#[test]
+fn removed_nested_map_is_queued_without_rewriting_the_queue() {
+    let env = TestEnv::new(CONTRACT);
+    call_nested(&env, "add", vec![1, 2, 3, 4], Some(3));
+    call_nested(&env, "remove", vec![1, 2, 3], None);
+    assert_eq!(purge_queue_keys(&env).len(), 4);
+
+    // the task is added in its own key, and only the positions of the queue are updated
+    let outcome = call_nested(&env, "remove", vec![4], None);
+    let queue_changes: Vec<&StorageChange> = outcome.diff.of(CONTRACT).filter(|change| purge_queue_keys(&env).contains(&change.key)).collect();
+    assert_eq!(queue_changes.len(), 2);
+    assert_eq!(queue_changes.iter().filter(|change| change.kind() == ChangeKind::Created).count(), 1);
+    assert_eq!(purge_queue_keys(&env).len(), 5);
+}
+
+#[test]
+fn removed_nested_maps_are_purged_incrementally() {
+    let env = TestEnv::new(CONTRACT);
+    call_nested(&env, "add", vec![1, 2, 3], Some(3));
+    call_nested(&env, "remove", vec![1, 2, 3], None);
+
+    assert_eq!(purge(&env, 4), 4);
+    assert_eq!(purge_queue_keys(&env).len(), 3);
+    assert_eq!(purge(&env, 100), 5);
+    assert_eq!(purge(&env, 100), 0);
+    assert!(purge_queue_keys(&env).is_empty());
+}
@@ -0,0 +1,120 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [LazyOption](pchain_sdk::LazyOption) with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+
+/// Keys of the presence flag and the value of the first contract field.
+const PRESENT_KEY: [u8; 2] = [0, 0];
+const VALUE_KEY: [u8; 2] = [0, 1];
+
+mod config {
+    use pchain_sdk::{contract, contract_methods, call, LazyOption};
+
+    #[contract]
+    pub struct Config {
+        name: LazyOption<String>,
+    }
+
+    #[contract_methods]
+    impl Config {
+        #[call]
+        fn set(&mut self, name: String) {
+            self.name.set(name);
+        }
+
+        #[call]
+        fn clear(&mut self) {
+            self.name.clear();
+        }
+
+        #[call]
+        fn take(&mut self) -> Option<String> {
+            self.name.take()
+        }
+
+        #[call]
+        fn is_some(&self) -> bool {
+            self.name.is_some()
+        }
+
+        #[call]
+        fn get(&self) -> Option<String> {
+            self.name.get().cloned()
+        }
+    }
+}
+
+fn setup() -> TestEnv {
+    let env = TestEnv::new(CONTRACT);
+    env.state().deploy(CONTRACT, config::__entrypoint);
+    env
+}
+
+fn set(env: &TestEnv, name: &str) -> CallOutcome {
+    env.call(config::__entrypoint, "set", ContractMethodInputBuilder::new().add(name.to_string()).to_call_arguments())
+}
+
+fn is_some(env: &TestEnv) -> bool {
+    env.call(config::__entrypoint, "is_some", vec![]).decode().unwrap()
+}
+
+fn get(env: &TestEnv) -> Option<String> {
+    env.call(config::__entrypoint, "get", vec![]).decode().unwrap()
+}
+
+#[test]
+fn value_is_absent_before_it_is_set() {
+    let env = setup();
+    assert!(!is_some(&env));
+    assert_eq!(get(&env), None);
+}
+
+#[test]
+fn value_is_present_after_set_and_absent_after_clear() {
+    let env = setup();
+    set(&env, "alpha");
+    assert!(is_some(&env));
+    assert_eq!(get(&env), Some("alpha".to_string()));
+
+    let outcome = env.call(config::__entrypoint, "clear", vec![]);
+    assert_eq!(outcome.diff.get(CONTRACT, &VALUE_KEY).map(StorageChange::kind), Some(ChangeKind::Deleted));
+    assert!(!is_some(&env));
+    assert_eq!(get(&env), None);
+}
+
+#[test]
+fn take_returns_the_value_and_clears_it() {
+    let env = setup();
+    set(&env, "alpha");
+    let taken: Option<String> = env.call(config::__entrypoint, "take", vec![]).decode().unwrap();
+    assert_eq!(taken, Some("alpha".to_string()));
+    assert!(!is_some(&env));
+}
+
+#[test]
+fn presence_is_read_without_the_value() {
+    let env = setup();
+    set(&env, "alpha");
+    // a value that cannot be deserialized makes every read of the value fail
+    env.state().set(CONTRACT, &VALUE_KEY, &[0xff]);
+
+    assert!(is_some(&env));
+    assert!(env.try_call(config::__entrypoint, "get", vec![]).is_err());
+}
+
+#[test]
+fn accessing_without_change_writes_nothing() {
+    let env = setup();
+    set(&env, "alpha");
+    let outcome = env.call(config::__entrypoint, "get", vec![]);
+    assert!(outcome.diff.is_empty());
+    assert_eq!(env.state().get(CONTRACT, &PRESENT_KEY), Some(vec![1]));
+}
@@ -0,0 +1,53 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the encoding of the keys of map collections by [MapKey].
+
+use pchain_sdk::collections::MapKey;
+
+fn assert_ordered<K: MapKey + std::fmt::Debug + PartialEq>(keys: Vec<K>) {
+    let encoded: Vec<Vec<u8>> = keys.iter().map(MapKey::to_map_key).collect();
+    let mut sorted = encoded.clone();
+    sorted.sort();
+    assert_eq!(encoded, sorted, "encodings of {:?} are not ordered", keys);
+    for (key, bytes) in keys.iter().zip(encoded) {
+        assert_eq!(K::from_map_key(&bytes).as_ref(), Some(key));
+    }
+}
+
+#[test]
+fn integer_keys_are_ordered() {
+    assert_ordered(vec![0u64, 1, 255, 256, 65_536, u64::MAX]);
+    assert_ordered(vec![0u128, 1, 256, u128::MAX]);
+    assert_ordered(vec![i32::MIN, -256, -1, 0, 1, 256, i32::MAX]);
+    assert_ordered(vec![i8::MIN, -1, 0, i8::MAX]);
+    assert_ordered(vec![0usize, 1, 256]);
+    assert_eq!(258u32.to_map_key(), vec![0, 0, 1, 2]);
+}
+
+#[test]
+fn compound_keys_are_ordered_by_components() {
+    assert_ordered(vec![(1u32, 300u64), (2, 1), (2, 256), (256, 0)]);
+    assert_ordered(vec![None, Some(0u16), Some(1), Some(256)]);
+    assert_ordered(vec![[0u16, 300], [1, 0], [1, 2]]);
+    assert_ordered(vec![(String::from("a"), -1i64), (String::from("a"), 1)]);
+}
+
+#[test]
+fn byte_keys_keep_borsh_encoding() {
+    let address = [7u8; 32];
+    assert_eq!(address.to_map_key(), address.to_vec());
+    let bytes = vec![1u8, 2, 3];
+    assert_eq!(bytes.to_map_key(), borsh::BorshSerialize::try_to_vec(&bytes).unwrap());
+    assert_eq!(String::from("key").to_map_key(), borsh::BorshSerialize::try_to_vec("key").unwrap());
+}
+
+#[test]
+fn trailing_bytes_are_not_a_valid_key() {
+    assert_eq!(u32::from_map_key(&[0, 0, 0, 1, 0]), None);
+    assert_eq!(u32::from_map_key(&[0, 0, 1]), None);
+    assert_eq!(<(u8, u16)>::from_map_key(&[1, 0, 2]), Some((1, 2)));
+    assert_eq!(<Option<u8>>::from_map_key(&[2, 0]), None);
+}
@@ -0,0 +1,112 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [MerkleDistributor](pchain_sdk::merkle_distributor::MerkleDistributor) with the in-memory runtime of the
+//! feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{merkle_distributor::leaf_hash, method::ContractMethodInputBuilder, testing::*};
+
+const AIRDROP: [u8; 32] = [1u8; 32];
+const TOKEN: [u8; 32] = [2u8; 32];
+const ACCOUNT: [u8; 32] = [9u8; 32];
+
+mod airdrop {
+    use pchain_sdk::{contract, contract_methods, call, merkle_distributor::MerkleDistributor};
+
+    #[contract]
+    pub struct Airdrop {
+        distributor: MerkleDistributor,
+    }
+
+    #[contract_methods]
+    impl Airdrop {
+        #[call]
+        fn init(&mut self, merkle_root: [u8; 32], token: [u8; 32]) {
+            self.distributor = MerkleDistributor::with_token(merkle_root, token);
+        }
+
+        #[call]
+        fn claim(&mut self, index: u64, account: [u8; 32], amount: u64) -> bool {
+            self.distributor.claim(index, account, amount, &[]).is_ok()
+        }
+
+        #[call]
+        fn is_claimed(&self, index: u64) -> bool {
+            self.distributor.is_claimed(index)
+        }
+    }
+}
+
+/// A token that claims the entry again while it is paid, and fails the transfer if `fail` is set.
+mod token {
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder, ContractError};
+
+    #[contract]
+    pub struct Token {
+        transfers: u64,
+        fail: bool,
+    }
+
+    #[contract_methods]
+    impl Token {
+        #[call]
+        fn set_fail(&mut self, fail: bool) {
+            self.fail = fail;
+        }
+
+        #[call]
+        fn transfer(&mut self, to: [u8; 32], amount: u64) -> Result<(), ContractError> {
+            if self.fail {
+                return Err(ContractError::new(1, "transfer failed"))
+            }
+            self.transfers += 1;
+            pchain_sdk::log(b"transfer", &[]);
+            // bounded, so that the test fails instead of overflowing the stack if re-entry succeeds
+            if self.transfers < 3 {
+                pchain_sdk::flush_storage(self);
+                let arguments = ContractMethodInputBuilder::new().add(0u64).add(to).add(amount).to_call_arguments();
+                pchain_sdk::call_untyped(pchain_sdk::transaction::calling_account(), "claim", arguments, 0);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn setup() -> TestEnv {
+    let env = TestEnv::new(AIRDROP).caller(ACCOUNT);
+    env.state().deploy(AIRDROP, airdrop::__entrypoint);
+    env.state().deploy(TOKEN, token::__entrypoint);
+    // the single leaf is the root, proved by an empty proof
+    let (merkle_root, _) = env.run(|| leaf_hash(0, ACCOUNT, 100));
+    let arguments = ContractMethodInputBuilder::new().add(merkle_root).add(TOKEN).to_call_arguments();
+    env.call(airdrop::__entrypoint, "init", arguments);
+    env
+}
+
+fn claim_arguments() -> Vec<u8> {
+    ContractMethodInputBuilder::new().add(0u64).add(ACCOUNT).add(100u64).to_call_arguments()
+}
+
+#[test]
+fn entry_cannot_be_claimed_by_reentrant_call() {
+    let env = setup();
+    let outcome = env.call(airdrop::__entrypoint, "claim", claim_arguments());
+    assert_eq!(outcome.decode::<bool>(), Some(true));
+    assert_eq!(outcome.logs_of(b"transfer").len(), 1);
+    assert_eq!(env.call(airdrop::__entrypoint, "claim", claim_arguments()).decode::<bool>(), Some(false));
+}
+
+#[test]
+fn failed_transfer_leaves_entry_unclaimed() {
+    let env = setup();
+    let token = TestEnv::new(TOKEN).world_state(env.state().clone());
+    token.call(token::__entrypoint, "set_fail", ContractMethodInputBuilder::new().add(true).to_call_arguments());
+
+    assert_eq!(env.call(airdrop::__entrypoint, "claim", claim_arguments()).decode::<bool>(), Some(false));
+    let is_claimed = ContractMethodInputBuilder::new().add(0u64).to_call_arguments();
+    assert_eq!(env.call(airdrop::__entrypoint, "is_claimed", is_claimed).decode::<bool>(), Some(false));
+}
@@ -0,0 +1,44 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of calls to a contract with `return_envelope` with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{testing::*, CallError};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const ORACLE: [u8; 32] = [2u8; 32];
+
+mod oracle {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Oracle {}
+
+    #[contract_methods(return_envelope)]
+    impl Oracle {
+        #[call]
+        fn price(&self) -> u64 {
+            1_000
+        }
+    }
+}
+
+#[test]
+fn envelope_is_decoded_by_try_call_envelope() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().deploy(ORACLE, oracle::__entrypoint);
+    let (price, _) = env.run(|| pchain_sdk::try_call_envelope::<u64>(ORACLE, "price", vec![], 0));
+    assert_eq!(price, Ok(1_000));
+}
+
+#[test]
+fn envelope_is_not_read_as_plain_borsh() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().deploy(ORACLE, oracle::__entrypoint);
+    let (price, _) = env.run(|| pchain_sdk::try_call::<u64>(ORACLE, "price", vec![], 0));
+    assert!(matches!(price, Err(CallError::ReturnTypeMismatch { len: 10, .. })));
+}
@@ -0,0 +1,52 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the decoders of Transaction Receipts of the feature `receipt`.
+
+#![cfg(feature = "receipt")]
+
+use borsh::BorshSerialize;
+use pchain_sdk::{receipt::{self, ReceiptError}, ContractError};
+use pchain_types::blockchain::{CommandReceipt, ExitStatus, Log};
+
+fn receipt_of(return_values: Vec<u8>, logs: Vec<Log>) -> CommandReceipt {
+    CommandReceipt { exit_status: ExitStatus::Success, gas_used: 0, return_values, logs }
+}
+
+#[test]
+fn return_value_is_decoded() {
+    let receipt = receipt_of(7u64.try_to_vec().unwrap(), vec![]);
+    assert_eq!(receipt::decode_return_value::<u64>(&receipt), Ok(7));
+
+    let receipt = receipt_of(vec![], vec![]);
+    assert_eq!(receipt::decode_return_value::<u64>(&receipt), Err(ReceiptError::NoReturnValue));
+
+    let receipt = CommandReceipt { exit_status: ExitStatus::Failed, ..receipt_of(vec![], vec![]) };
+    assert_eq!(receipt::decode_return_value::<u64>(&receipt), Err(ReceiptError::Unsuccessful(ExitStatus::Failed)));
+}
+
+#[test]
+fn trailing_bytes_are_rejected() {
+    let receipt = receipt_of(7u64.try_to_vec().unwrap(), vec![]);
+    assert!(matches!(receipt::decode_return_value::<u32>(&receipt), Err(ReceiptError::Deserialization(_))));
+
+    let log = Log { topic: b"topic".to_vec(), value: 7u64.try_to_vec().unwrap() };
+    assert!(matches!(receipt::decode_log_value::<u32>(&log), Err(ReceiptError::Deserialization(_))));
+    assert_eq!(receipt::decode_log_value::<u64>(&log), Ok(7));
+}
+
+#[test]
+fn error_envelope_is_decoded() {
+    let ok: Result<u64, ContractError> = Ok(7);
+    let receipt = receipt_of(ok.try_to_vec().unwrap(), vec![]);
+    assert_eq!(receipt::decode_result::<u64>(&receipt), Ok(Ok(7)));
+
+    let err: Result<u64, ContractError> = Err(ContractError::new(1, "insufficient balance"));
+    let receipt = receipt_of(err.try_to_vec().unwrap(), vec![]);
+    assert_eq!(receipt::decode_result::<u64>(&receipt), Ok(Err(ContractError::new(1, "insufficient balance"))));
+
+    let receipt = receipt_of(7u64.try_to_vec().unwrap(), vec![]);
+    assert!(matches!(receipt::decode_result::<u64>(&receipt), Err(ReceiptError::Deserialization(_))));
+}
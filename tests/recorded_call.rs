@@ -0,0 +1,55 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of recording and replaying a call with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{borsh::BorshSerialize, method::ContractMethodInputBuilder, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const CALLER: [u8; 32] = [9u8; 32];
+
+mod counter {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Counter {
+        total: u64,
+    }
+
+    #[contract_methods]
+    impl Counter {
+        #[call]
+        fn add(&mut self, n: u64) -> u64 {
+            self.total += n;
+            self.total
+        }
+    }
+}
+
+#[test]
+fn recorded_call_is_replayed() {
+    // the state before the recorded call
+    let env = TestEnv::new(CONTRACT).caller(CALLER);
+    let outcome = env.call(counter::__entrypoint, "add", ContractMethodInputBuilder::new().add(5u64).to_call_arguments());
+    let storage = outcome.diff.of(CONTRACT).map(|change| (change.key.clone(), change.after.clone().unwrap())).collect();
+
+    let call = RecordedCall {
+        contract: CONTRACT,
+        caller: CALLER,
+        method: "add".to_string(),
+        arguments: Some(vec![3u64.try_to_vec().unwrap()]),
+        storage: vec![(CONTRACT, storage)],
+        ..Default::default()
+    };
+    let path = std::env::temp_dir().join("pchain_sdk_recorded_call.bin");
+    call.save(&path).unwrap();
+    let call = RecordedCall::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let outcome = call.replay(counter::__entrypoint).unwrap();
+    assert_eq!(outcome.decode::<u64>(), Some(8));
+}
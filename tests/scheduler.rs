@@ -0,0 +1,127 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of [Scheduler](pchain_sdk::scheduler::Scheduler) with the in-memory runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use pchain_sdk::{method::ContractMethodInputBuilder, testing::*};
+
+const KEEPER_CONTRACT: [u8; 32] = [1u8; 32];
+const TARGET: [u8; 32] = [2u8; 32];
+const KEEPER: [u8; 32] = [9u8; 32];
+
+mod keeper {
+    use pchain_sdk::{contract, contract_methods, call, method::ContractMethodInputBuilder, scheduler::{Job, Scheduler}};
+
+    #[contract]
+    pub struct Keeper {
+        scheduler: Scheduler,
+    }
+
+    #[contract_methods]
+    impl Keeper {
+        /// Schedules a call to `record(value)` of the target, due at the block.
+        #[call]
+        fn schedule(&mut self, value: u64, earliest_block: u64) -> u64 {
+            let arguments = ContractMethodInputBuilder::new().add(value).to_call_arguments();
+            self.scheduler.schedule(Job { target: super::TARGET, method: "record".to_string(), arguments, value: 0, earliest_block, earliest_timestamp: 0 })
+        }
+
+        #[call]
+        fn execute_due(&mut self, limit: u32, skip: Vec<u64>) -> u32 {
+            self.scheduler.execute_due_except(limit, &skip)
+        }
+
+        #[call]
+        fn is_pending(&self, id: u64) -> bool {
+            self.scheduler.job(id).is_some()
+        }
+    }
+}
+
+/// A target that records the values it is called with, and fails for the value 0.
+mod target {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Target {
+        values: Vec<u64>,
+    }
+
+    #[contract_methods]
+    impl Target {
+        #[call]
+        fn record(&mut self, value: u64) {
+            assert!(value != 0, "job fails");
+            self.values.push(value);
+        }
+
+        #[call]
+        fn values(&self) -> Vec<u64> {
+            self.values.clone()
+        }
+    }
+}
+
+fn setup() -> TestEnv {
+    let env = TestEnv::new(KEEPER_CONTRACT).caller(KEEPER).block_height(10);
+    env.state().deploy(KEEPER_CONTRACT, keeper::__entrypoint);
+    env.state().deploy(TARGET, target::__entrypoint);
+    env
+}
+
+fn schedule(env: &TestEnv, value: u64, earliest_block: u64) {
+    env.call(keeper::__entrypoint, "schedule", ContractMethodInputBuilder::new().add(value).add(earliest_block).to_call_arguments());
+}
+
+fn execute_due(env: &TestEnv, limit: u32, skip: Vec<u64>) -> Result<u32, String> {
+    let arguments = ContractMethodInputBuilder::new().add(limit).add(skip).to_call_arguments();
+    env.try_call(keeper::__entrypoint, "execute_due", arguments).map(|outcome| outcome.decode().unwrap())
+}
+
+fn values(env: &TestEnv) -> Vec<u64> {
+    TestEnv::new(TARGET).world_state(env.state().clone()).call(target::__entrypoint, "values", vec![]).decode().unwrap()
+}
+
+#[test]
+fn jobs_are_read_in_rounds_of_at_most_limit() {
+    let env = setup();
+    // jobs 0 and 1 are not due
+    schedule(&env, 1, 100);
+    schedule(&env, 2, 100);
+    schedule(&env, 3, 0);
+    schedule(&env, 4, 0);
+    schedule(&env, 5, 0);
+
+    // the jobs that are not due are read, and count towards the limit
+    assert_eq!(execute_due(&env, 3, vec![]), Ok(1));
+    assert_eq!(values(&env), vec![3]);
+    assert_eq!(execute_due(&env, 2, vec![]), Ok(2));
+    assert_eq!(values(&env), vec![3, 4, 5]);
+
+    // the next round starts over from the earliest pending job
+    let env = env.block_height(100);
+    assert_eq!(execute_due(&env, 1, vec![]), Ok(1));
+    assert_eq!(values(&env), vec![3, 4, 5, 1]);
+    assert_eq!(execute_due(&env, 10, vec![]), Ok(1));
+    assert_eq!(values(&env), vec![3, 4, 5, 1, 2]);
+    assert_eq!(execute_due(&env, 10, vec![]), Ok(0));
+}
+
+#[test]
+fn failing_job_is_skipped_and_stays_pending() {
+    let env = setup();
+    schedule(&env, 0, 0);
+    schedule(&env, 6, 0);
+
+    assert!(execute_due(&env, 10, vec![]).unwrap_err().contains("job fails"));
+    assert_eq!(values(&env), Vec::<u64>::new());
+
+    assert_eq!(execute_due(&env, 10, vec![0]), Ok(1));
+    assert_eq!(values(&env), vec![6]);
+    let is_pending = env.call(keeper::__entrypoint, "is_pending", ContractMethodInputBuilder::new().add(0u64).to_call_arguments());
+    assert_eq!(is_pending.decode::<bool>(), Some(true));
+}
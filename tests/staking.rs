@@ -0,0 +1,47 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the keys of the Network Account's Storage read by [staking](pchain_sdk::staking), with the in-memory
+//! runtime of the feature `testing`.
+
+#![cfg(feature = "testing")]
+
+use borsh::BorshSerialize;
+use pchain_sdk::{staking, testing::*};
+
+const CONTRACT: [u8; 32] = [1u8; 32];
+const OPERATOR: [u8; 32] = [2u8; 32];
+
+/// The key of a field of a Deposit as written by the runtime: prefix 4, operator, owner and the field.
+fn deposit_key(operator: [u8; 32], owner: [u8; 32], field: u8) -> Vec<u8> {
+    let mut key = vec![4u8];
+    key.extend_from_slice(&operator);
+    key.extend_from_slice(&owner);
+    key.push(field);
+    key
+}
+
+#[test]
+fn deposit_is_read_from_runtime_keys() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().set_network_storage(&deposit_key(OPERATOR, CONTRACT, 0), &500u64.try_to_vec().unwrap());
+    env.state().set_network_storage(&deposit_key(OPERATOR, CONTRACT, 1), &true.try_to_vec().unwrap());
+
+    let (deposit, _) = env.run(|| {
+        (staking::deposit_balance(OPERATOR, CONTRACT), staking::deposit_auto_stake_rewards(OPERATOR, CONTRACT))
+    });
+    assert_eq!(deposit, (Some(500), Some(true)));
+}
+
+#[test]
+fn deposit_of_another_owner_is_not_read() {
+    let env = TestEnv::new(CONTRACT);
+    env.state().set_network_storage(&deposit_key(OPERATOR, [3u8; 32], 0), &500u64.try_to_vec().unwrap());
+    // pools are stored under the prefix 3
+    env.state().set_network_storage(&[[3u8].as_slice(), &OPERATOR, &CONTRACT, &[0]].concat(), &700u64.try_to_vec().unwrap());
+
+    let (balance, _) = env.run(|| staking::deposit_balance(OPERATOR, CONTRACT));
+    assert_eq!(balance, None);
+}
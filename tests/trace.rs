@@ -0,0 +1,72 @@
+/*
+    Copyright © 2023, ParallelChain Lab 
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Tests of the trace of the calls to the Host of the feature `trace`, with the in-memory runtime of the feature
+//! `testing`.
+
+#![cfg(all(feature = "testing", feature = "trace"))]
+
+use pchain_sdk::{trace::{self, TracedCall}, testing::*};
+
+const COUNTER: [u8; 32] = [1u8; 32];
+
+mod counter {
+    use pchain_sdk::{contract, contract_methods, call};
+
+    #[contract]
+    pub struct Counter {
+        value: u64,
+    }
+
+    #[contract_methods]
+    impl Counter {
+        #[call]
+        fn increment(&mut self) {
+            self.value += 1;
+            pchain_sdk::log(b"incremented", &self.value.to_le_bytes());
+        }
+    }
+}
+
+fn increment(env: &TestEnv) {
+    env.call(counter::__entrypoint, "increment", vec![]);
+}
+
+#[test]
+fn calls_are_counted_and_recorded() {
+    let env = TestEnv::new(COUNTER);
+    env.state().deploy(COUNTER, counter::__entrypoint);
+    increment(&env);
+
+    trace::reset();
+    trace::log_calls(true);
+    increment(&env);
+    trace::log_calls(false);
+
+    let report = trace::report();
+    assert_eq!(report.count("log"), 1);
+    assert!(report.count("get") >= 1);
+    assert_eq!(report.keys_read(), vec![[0u8].as_slice()]);
+    assert_eq!(report.keys_written(), vec![[0u8].as_slice()]);
+    assert!(report.calls.contains(&TracedCall::Set { key: vec![0], value_len: 8 }));
+    assert!(report.calls.contains(&TracedCall::Log { topic: b"incremented".to_vec(), value_len: 8 }));
+    assert!(report.to_string().contains("log incremented (8 bytes)"));
+}
+
+#[test]
+fn calls_are_only_counted_unless_recording_is_enabled() {
+    let env = TestEnv::new(COUNTER);
+    env.state().deploy(COUNTER, counter::__entrypoint);
+
+    trace::reset();
+    increment(&env);
+    let report = trace::report();
+    assert_eq!(report.count("log"), 1);
+    assert!(report.count("set") >= 1);
+    assert!(report.calls.is_empty());
+
+    trace::reset();
+    assert_eq!(trace::report(), trace::TraceReport::default());
+}